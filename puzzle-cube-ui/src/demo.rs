@@ -1,4 +1,4 @@
-use crate::gui::start_gui;
+use crate::{gui::start_gui, logging};
 
 use std::time::Instant;
 
@@ -9,10 +9,9 @@ use rusty_puzzle_cube::{
 use tracing::error;
 
 pub fn run() {
-    #[cfg(not(target_arch = "wasm32"))]
-    tracing_subscriber::fmt::init();
+    let logging = logging::init();
 
-    if let Err(e) = start_gui() {
+    if let Err(e) = start_gui(logging) {
         error!("Could not start gui, defaulting to terminal demo: {}", e);
         demo_simple_turns();
         demo_simple_turns_big_cube();
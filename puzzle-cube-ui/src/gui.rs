@@ -1,51 +1,120 @@
+mod animation;
+mod camera_poses;
 mod colours;
 mod cube_ext;
 mod defaults;
 #[cfg(not(target_arch = "wasm32"))]
 mod file_io;
+mod i18n;
+mod keyboard_control;
+mod log_panel;
+mod memory;
 mod mouse_control;
+mod move_history;
+mod move_ticker;
+mod net_view;
+mod notation_input;
+mod persistence;
+mod picture;
+mod playback;
+mod rear_view;
+mod recovery;
 mod side_panel;
+mod stats;
+mod touch_control;
 mod transforms;
 
 use crate::gui::{
+    camera_poses::CameraPoses,
+    colours::{colour_scheme, sticker_colours, StickerColours},
     cube_ext::ToInstances,
-    defaults::{clear_state, initial_camera, initial_window},
+    defaults::{clear_state, frame_cube, initial_camera, initial_window},
+    keyboard_control::KeyboardControl,
     mouse_control::MouseControl,
+    move_history::MoveHistory,
+    persistence::{Autosave, Settings},
+    picture::Picture,
+    playback::Playback,
+    recovery::RendererRecovery,
+    touch_control::TouchControl,
 };
+use crate::logging::Logging;
 use mouse_control::MouseControlOutput;
-use rusty_puzzle_cube::{cube::Cube, known_transforms::cube_in_cube_in_cube};
+use rusty_puzzle_cube::{
+    cube::{colour_scheme::ColourScheme, Cube},
+    known_transforms::cube_in_cube_in_cube,
+};
+use stats::FrameStats;
 use three_d::{
     egui::ScrollArea, Axes, ColorMaterial, Context, CpuMesh, Cull, FrameOutput, Gm, InstancedMesh,
     Mesh, Object, RenderStates, Srgba, Viewport, GUI,
 };
-use tracing::{debug, error, info};
+use tracing::{debug, info};
 
-pub(super) fn start_gui() -> Result<(), three_d::WindowError> {
-    info!("Initialising default cube");
-    let mut side_length = 3;
-    let mut cube = Cube::create(side_length);
-    cube_in_cube_in_cube(&mut cube);
+pub(super) fn start_gui(logging: Logging) -> Result<(), three_d::WindowError> {
+    let mut autosave = Autosave::new();
+    let (saved_cube, mut settings) = autosave.restore();
+    let mut cube = saved_cube.unwrap_or_else(|| {
+        info!("Initialising default cube");
+        default_cube()
+    });
 
     info!("Initialising GUI");
     let window = initial_window()?;
     let mut camera = initial_camera(window.viewport());
+    let mut camera_poses = CameraPoses::new();
+    let mut camera_transition = None;
     let mut mouse_control = MouseControl::new(*camera.target(), 1.0, 80.0);
-    let mut unreasonable_mode = false;
+    let mut touch_control = TouchControl::new(*camera.target(), 1.0, 80.0);
+    let mut keyboard_control = KeyboardControl::new();
+    let mut sequence = String::new();
+    let mut shuffle_moves = 25;
+    let mut instant_shuffle = false;
+    let mut image_scale = 1;
+    let mut explode = 0.;
+    let mut picture = Picture::new();
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut picture_path = String::new();
+    let mut history = MoveHistory::new(&cube);
+    let mut playback = Playback::new();
+    let mut was_animating = false;
+    let mut shown_highlight = vec![];
 
     let ctx = window.gl();
     let mut gui = GUI::new(&ctx);
+    let mut scene = Scene::new(
+        &ctx,
+        &cube,
+        colour_scheme(settings.japanese_colours),
+        sticker_colours(settings.palette, &settings.custom_colours),
+        &picture,
+        explode,
+    );
+    let mut recovery = RendererRecovery::new();
+    #[cfg(target_arch = "wasm32")]
+    recovery::allow_context_restore();
+    #[cfg(target_arch = "wasm32")]
+    let touch_listener = touch_control::listener::TouchListener::attach();
 
-    let mut tiles = initial_instances(&ctx, &cube);
-
-    let inner_cube = inner_cube(&ctx);
-
-    let mut render_axes = false;
-    let axes = Axes::new(&ctx, 0.05, 2.);
+    let mut stats = FrameStats::new();
 
     window.render_loop(move |mut frame_input| {
-        let mut redraw = frame_input.first_frame;
+        let mut redraw = frame_input.first_frame
+            || settings.show_stats
+            || settings.show_log
+            || recovery.needs_redraw(frame_input.accumulated_time)
+            || playback.ticker.needs_redraw(frame_input.accumulated_time);
+        let mut updated_cube = false;
+        let mut repictured = false;
+        let previous_side_length = cube.side_length();
+        stats.record_frame(frame_input.elapsed_time);
 
         let mut panel_width = 0.;
+        playback.style = settings.animation_style;
+        if playback.tick(frame_input.accumulated_time, &mut cube, &mut history) {
+            updated_cube = true;
+            redraw = true;
+        }
         redraw |= gui.update(
             &mut frame_input.events,
             frame_input.accumulated_time,
@@ -55,78 +124,315 @@ pub(super) fn start_gui() -> Result<(), three_d::WindowError> {
                 use three_d::egui::SidePanel;
                 SidePanel::left("side_panel").show(gui_ctx, |ui| {
                     ScrollArea::vertical().show(ui, |ui| {
-                        side_panel::header(ui);
-                        side_panel::initialise_cube(
+                        side_panel::header(ui, &mut settings.language);
+                        let strings = settings.language.strings();
+                        updated_cube |= side_panel::initialise_cube(
+                            ui,
+                            strings,
+                            &mut settings.unreasonable_mode,
+                            &mut settings.japanese_colours,
+                            &mut settings.side_length,
+                            !picture.is_empty(),
+                            &mut cube,
+                            &mut history,
+                            &mut playback,
+                        );
+                        updated_cube |= side_panel::colours(
+                            ui,
+                            strings,
+                            &mut settings.palette,
+                            &mut settings.custom_colours,
+                        );
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if side_panel::picture(
+                            ui,
+                            strings,
+                            &mut picture_path,
+                            &mut picture,
+                            &mut cube,
+                            &mut history,
+                            &mut playback,
+                        ) {
+                            repictured = true;
+                            updated_cube = true;
+                        }
+                        updated_cube |= side_panel::control_cube(
+                            ui,
+                            strings,
+                            &mut cube,
+                            &mut history,
+                            &mut settings.animation_style,
+                        );
+                        side_panel::lock_faces(ui, strings, &mut cube);
+                        updated_cube |= side_panel::apply_sequence(
                             ui,
-                            &mut unreasonable_mode,
-                            &mut side_length,
+                            strings,
+                            &mut sequence,
                             &mut cube,
-                            &mut tiles,
+                            &mut history,
+                            &mut playback,
+                            frame_input.accumulated_time,
                         );
-                        side_panel::control_cube(ui, &mut cube, &mut tiles);
-                        side_panel::control_camera(
+                        updated_cube |=
+                            side_panel::known_transforms(ui, strings, &mut cube, &mut history);
+                        updated_cube |= side_panel::shuffle(
                             ui,
+                            strings,
+                            &mut shuffle_moves,
+                            &mut instant_shuffle,
+                            &mut cube,
+                            &mut history,
+                            &mut playback,
+                            frame_input.accumulated_time,
+                        );
+                        side_panel::solve(ui, strings, &cube, &history, &mut playback);
+                        updated_cube |= side_panel::playback(
+                            ui,
+                            strings,
+                            &mut cube,
+                            &mut history,
+                            &mut playback,
+                            frame_input.accumulated_time,
+                        );
+                        updated_cube |= side_panel::move_history(
+                            ui,
+                            strings,
+                            &mut cube,
+                            &mut history,
+                            &mut playback,
+                        );
+                        updated_cube |= side_panel::control_camera(
+                            ui,
+                            strings,
                             &mut camera,
                             frame_input.viewport,
-                            &mut render_axes,
+                            &mut camera_transition,
+                            frame_input.accumulated_time,
+                            &mut settings.render_axes,
+                            &mut settings.auto_frame,
+                            &mut settings.show_rear_view,
+                            &mut settings.show_net,
+                            &mut explode,
                         );
-                        #[cfg(not(target_arch = "wasm32"))]
-                        side_panel::debug(
+                        side_panel::diagnostics(
                             ui,
-                            &cube,
+                            strings,
+                            &mut settings.show_stats,
+                            &mut settings.show_log,
+                        );
+                        if autosave.is_active() && side_panel::saved_state(ui, strings, &cube) {
+                            info!("Resetting saved state");
+                            autosave.reset();
+                            settings = Settings::default();
+                            cube = default_cube();
+                            history.reset(&cube);
+                            playback.reset();
+                            picture = Picture::new();
+                            repictured = true;
+                            camera = initial_camera(frame_input.viewport);
+                            updated_cube = true;
+                        }
+                        side_panel::image(
+                            ui,
+                            strings,
+                            &mut image_scale,
                             &ctx,
                             frame_input.viewport,
                             &camera,
-                            &tiles,
-                            &inner_cube,
+                            &scene.tiles,
+                            &scene.inner_cube,
+                        );
+                        #[cfg(not(target_arch = "wasm32"))]
+                        side_panel::debug(
+                            ui,
+                            strings,
+                            &cube,
+                            colour_scheme(settings.japanese_colours),
                         );
                     })
                 });
                 panel_width = gui_ctx.used_rect().width();
+                if settings.show_stats {
+                    stats.overlay(
+                        gui_ctx,
+                        settings.language.strings(),
+                        6 * cube.side_length() * cube.side_length(),
+                    );
+                }
+                if settings.show_net {
+                    net_view::show(
+                        gui_ctx,
+                        settings.language.strings(),
+                        &cube,
+                        colour_scheme(settings.japanese_colours),
+                        sticker_colours(settings.palette, &settings.custom_colours),
+                        &mut settings.show_net,
+                    );
+                }
+                if settings.show_log {
+                    log_panel::show(
+                        gui_ctx,
+                        settings.language.strings(),
+                        &logging,
+                        &mut settings.show_log,
+                    );
+                }
+                recovery.toast(
+                    gui_ctx,
+                    settings.language.strings(),
+                    frame_input.accumulated_time,
+                );
+                playback.ticker.show(
+                    gui_ctx,
+                    |transform| side_panel::transform_name(settings.language.strings(), transform),
+                    frame_input.accumulated_time,
+                );
             },
         );
 
+        if repictured {
+            scene.tiles.material.texture = picture.texture(&ctx);
+        }
+
         let viewport = calc_viewport(
             panel_width,
             frame_input.viewport,
             frame_input.device_pixel_ratio,
         );
-        redraw |= camera.set_viewport(viewport);
+        let resized = camera.set_viewport(viewport);
+        redraw |= resized;
+
+        let resized_cube = cube.side_length() != previous_side_length;
+        if resized_cube {
+            camera_poses.switch_side_length(&mut camera, previous_side_length, cube.side_length());
+            redraw = true;
+        }
+        if settings.auto_frame && (resized || resized_cube) {
+            frame_cube(&mut camera);
+        }
+
+        if let Some(transition) = camera_transition {
+            transition.apply_to(&mut camera, frame_input.accumulated_time);
+            redraw = true;
+            if transition.is_finished(frame_input.accumulated_time) {
+                camera_transition = None;
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        let touch_inputs = touch_listener.drain();
+        #[cfg(not(target_arch = "wasm32"))]
+        let touch_inputs = vec![];
+        redraw |= touch_control.handle_inputs(touch_inputs, &mut camera);
+        if touch_control.is_gesturing() {
+            mouse_control.cancel_drag();
+            TouchControl::claim_mouse_events(&mut frame_input.events);
+        }
 
         let MouseControlOutput {
             redraw: needs_redraw,
-            updated_cube,
+            updated_cube: mouse_updated_cube,
+            highlight,
         } = mouse_control.handle_events(
             &ctx,
-            &inner_cube,
-            side_length,
+            &scene.inner_cube,
+            settings.side_length,
             &mut camera,
             &mut frame_input.events,
             &mut cube,
+            &mut history,
+            &mut playback,
+            frame_input.accumulated_time,
+        );
+        updated_cube |= keyboard_control.handle_events(
+            &mut frame_input.events,
+            &mut cube,
+            &mut history,
+            &mut playback,
+            frame_input.accumulated_time,
         );
-        if updated_cube {
-            tiles.set_instances(&cube.to_instances());
+        let animation = playback.animation(frame_input.accumulated_time);
+        let highlight_changed = highlight != shown_highlight;
+        shown_highlight = highlight;
+        if updated_cube
+            || mouse_updated_cube
+            || highlight_changed
+            || animation.is_some()
+            || was_animating
+        {
+            stats.time_instance_rebuild(|| {
+                let mut instances = cube.to_instances(
+                    colour_scheme(settings.japanese_colours),
+                    sticker_colours(settings.palette, &settings.custom_colours),
+                    &picture,
+                    &shown_highlight,
+                    explode,
+                );
+                if let Some(animation) = animation {
+                    animation.apply_to(
+                        &mut instances,
+                        cube.side_length(),
+                        explode,
+                        frame_input.accumulated_time,
+                    );
+                }
+                scene.tiles.set_instances(&instances);
+            });
         }
-        redraw |= needs_redraw;
+        redraw |= needs_redraw
+            || updated_cube
+            || highlight_changed
+            || animation.is_some()
+            || was_animating;
+        was_animating = animation.is_some();
 
         if redraw {
             debug!("Drawing cube");
             let screen = frame_input.screen();
-            let draw_res = screen
-                .clear(clear_state())
-                .render(&camera, tiles.into_iter().chain(&inner_cube), &[])
-                .write(|| {
-                    if render_axes {
-                        axes.render(&camera, &[]);
-                    }
+            screen.clear(clear_state()).render(
+                &camera,
+                scene.tiles.into_iter().chain(&scene.inner_cube),
+                &[],
+            );
+            if settings.show_rear_view {
+                let rear_viewport =
+                    rear_view::rear_viewport(viewport, frame_input.device_pixel_ratio);
+                screen
+                    .clear_partially(rear_viewport.into(), clear_state())
+                    .render_partially(
+                        rear_viewport.into(),
+                        &rear_view::rear_camera(&camera, rear_viewport),
+                        scene.tiles.into_iter().chain(&scene.inner_cube),
+                        &[],
+                    );
+            }
+            let draw_res = screen.write(|| {
+                if settings.render_axes {
+                    scene.axes.render(&camera, &[]);
+                }
 
-                    gui.render()
-                });
-            if let Err(e) = draw_res {
-                error!("Error drawing cube {}", e);
+                gui.render()
+            });
+            match draw_res {
+                Ok(_) => recovery.on_draw_success(),
+                Err(e) => {
+                    if recovery.on_draw_error(&e, frame_input.accumulated_time) {
+                        gui = GUI::new(&ctx);
+                        scene = Scene::new(
+                            &ctx,
+                            &cube,
+                            colour_scheme(settings.japanese_colours),
+                            sticker_colours(settings.palette, &settings.custom_colours),
+                            &picture,
+                            explode,
+                        );
+                    }
+                }
             }
         }
 
+        autosave.tick(frame_input.accumulated_time, &cube, &settings);
+
         FrameOutput {
             swap_buffers: redraw,
             ..Default::default()
@@ -135,10 +441,52 @@ pub(super) fn start_gui() -> Result<(), three_d::WindowError> {
     Ok(())
 }
 
-fn initial_instances(ctx: &Context, cube: &Cube) -> Gm<InstancedMesh, ColorMaterial> {
-    let instanced_square_mesh = InstancedMesh::new(ctx, &cube.to_instances(), &CpuMesh::cube());
+/// Every GPU resource drawn for the cube, so they can all be recreated from the cube when the graphics context is lost.
+struct Scene {
+    tiles: Gm<InstancedMesh, ColorMaterial>,
+    inner_cube: Gm<Mesh, ColorMaterial>,
+    axes: Axes,
+}
+
+impl Scene {
+    fn new(
+        ctx: &Context,
+        cube: &Cube,
+        scheme: &ColourScheme,
+        colours: &StickerColours,
+        picture: &Picture,
+        explode: f32,
+    ) -> Self {
+        Self {
+            tiles: initial_instances(ctx, cube, scheme, colours, picture, explode),
+            inner_cube: inner_cube(ctx),
+            axes: Axes::new(ctx, 0.05, 2.),
+        }
+    }
+}
+
+fn default_cube() -> Cube {
+    let mut cube = Cube::create(Settings::default().side_length);
+    cube_in_cube_in_cube(&mut cube);
+    cube
+}
+
+fn initial_instances(
+    ctx: &Context,
+    cube: &Cube,
+    scheme: &ColourScheme,
+    colours: &StickerColours,
+    picture: &Picture,
+    explode: f32,
+) -> Gm<InstancedMesh, ColorMaterial> {
+    let instanced_square_mesh = InstancedMesh::new(
+        ctx,
+        &cube.to_instances(scheme, colours, picture, &[], explode),
+        &CpuMesh::cube(),
+    );
     let material = ColorMaterial {
         color: Srgba::WHITE,
+        texture: picture.texture(ctx),
         render_states: RenderStates {
             cull: Cull::Back,
             ..Default::default()
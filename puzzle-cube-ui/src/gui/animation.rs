@@ -0,0 +1,502 @@
+use std::f32::consts::FRAC_PI_2;
+
+use rusty_puzzle_cube::{
+    cube::{direction::Direction, face::Face},
+    notation::parse_sequence,
+};
+use three_d::{vec3, vec4, InnerSpace, Instances, Mat4, Rad, Vector3};
+
+use super::colours::faded;
+use super::move_history::Move;
+
+/// How many layers in from the nearest face a layer may be and still turn when animating with [`AnimationStyle::InstantDeepLayers`].
+const SETBACK_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Easing {
+    Linear,
+    EaseInOut,
+    Bounce,
+}
+
+impl Easing {
+    pub(super) const ALL: [Easing; 3] = [Easing::Linear, Easing::EaseInOut, Easing::Bounce];
+
+    /// Maps how far through an animation the time is, from 0 to 1, onto how far through the animation the movement is.
+    pub(super) fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInOut => t * t * (3. - 2. * t),
+            Easing::Bounce => bounce_out(t),
+        }
+    }
+}
+
+/// How the layers turned by a move are shown getting into place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AnimationStyle {
+    /// Every layer turns into place.
+    Turn,
+    /// Every layer snaps into place straight away, and its stickers fade back in from dark.
+    SnapAndFade,
+    /// Layers near a face turn into place, while those set back further into big cubes snap into place straight away.
+    InstantDeepLayers,
+    /// Moves are shown without animating them.
+    Instant,
+}
+
+impl AnimationStyle {
+    pub(super) const ALL: [AnimationStyle; 4] = [
+        AnimationStyle::Turn,
+        AnimationStyle::SnapAndFade,
+        AnimationStyle::InstantDeepLayers,
+        AnimationStyle::Instant,
+    ];
+
+    pub(super) fn code(self) -> &'static str {
+        match self {
+            AnimationStyle::Turn => "turn",
+            AnimationStyle::SnapAndFade => "snap-and-fade",
+            AnimationStyle::InstantDeepLayers => "instant-deep-layers",
+            AnimationStyle::Instant => "instant",
+        }
+    }
+
+    pub(super) fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|style| style.code() == code)
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const STRENGTH: f32 = 7.5625;
+    const WIDTH: f32 = 2.75;
+    if t < 1. / WIDTH {
+        STRENGTH * t * t
+    } else if t < 2. / WIDTH {
+        let t = t - 1.5 / WIDTH;
+        STRENGTH * t * t + 0.75
+    } else if t < 2.5 / WIDTH {
+        let t = t - 2.25 / WIDTH;
+        STRENGTH * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / WIDTH;
+        STRENGTH * t * t + 0.984_375
+    }
+}
+
+/// Layers seen turning into place after a move has already been made to the cube, by drawing their stickers turned back by what is left of the move.
+///
+/// Every layer turns around the same axis at once, so a wide move or a group of parallel turns is seen as one movement.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct TurnAnimation {
+    face: Face,
+    turns: Vec<LayerTurn>,
+    started_at_ms: f64,
+    duration_ms: f64,
+    easing: Easing,
+    fade: bool,
+}
+
+/// How far one layer, counted back from the animation's face, turns clockwise as seen from that face.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LayerTurn {
+    layer: usize,
+    quarter_turns: i32,
+}
+
+impl TurnAnimation {
+    /// Returns `None` for moves that turn layers around more than one axis, and for moves the style shows without animating.
+    pub(super) fn for_move(
+        made: &Move,
+        side_length: usize,
+        started_at_ms: f64,
+        duration_ms: f64,
+        easing: Easing,
+        style: AnimationStyle,
+    ) -> Option<Self> {
+        if style == AnimationStyle::Instant {
+            return None;
+        }
+        let rotations = match made {
+            Move::Rotation(rotation) => vec![*rotation],
+            Move::Rotations(rotations) => rotations.clone(),
+            // Slices and wide moves are parsed for a 3x3 here, so only outer turns are sure to match the cube
+            Move::Step(step) => parse_sequence(step)
+                .ok()
+                .filter(|rotations| side_length == 3 || rotations.iter().all(|r| r.layer == 0))?,
+            Move::Transform(_) | Move::Group { .. } => return None,
+        };
+        let face = rotations.first()?.relative_to;
+        let mut turns: Vec<LayerTurn> = vec![];
+        for rotation in rotations {
+            if side_length <= rotation.layer {
+                return None;
+            }
+            let quarter_turns = match rotation.direction {
+                Direction::Clockwise => 1,
+                Direction::Anticlockwise => -1,
+            };
+            // A clockwise turn seen from the opposite face is anticlockwise seen from this one
+            let (layer, quarter_turns) = if rotation.relative_to == face {
+                (rotation.layer, quarter_turns)
+            } else if rotation.relative_to == face.opposite() {
+                (side_length - 1 - rotation.layer, -quarter_turns)
+            } else {
+                return None;
+            };
+            match turns.iter_mut().find(|turn| turn.layer == layer) {
+                Some(turn) => turn.quarter_turns += quarter_turns,
+                None => turns.push(LayerTurn {
+                    layer,
+                    quarter_turns,
+                }),
+            }
+        }
+        if style == AnimationStyle::InstantDeepLayers {
+            turns.retain(|turn| turn.layer.min(side_length - 1 - turn.layer) < SETBACK_DEPTH);
+        }
+        (!turns.is_empty()).then_some(Self {
+            face,
+            turns,
+            started_at_ms,
+            duration_ms,
+            easing,
+            fade: style == AnimationStyle::SnapAndFade,
+        })
+    }
+
+    /// The same animation for the move being undone.
+    pub(super) fn reversed(self) -> Self {
+        Self {
+            turns: self
+                .turns
+                .iter()
+                .map(|turn| LayerTurn {
+                    quarter_turns: -turn.quarter_turns,
+                    ..*turn
+                })
+                .collect(),
+            ..self
+        }
+    }
+
+    pub(super) fn is_finished(&self, now_ms: f64) -> bool {
+        self.started_at_ms + self.duration_ms <= now_ms
+    }
+
+    /// Returns how much of the move is left to show at the given time, from 1 at the start to 0 once finished.
+    #[allow(clippy::cast_possible_truncation)]
+    fn remaining(&self, now_ms: f64) -> f32 {
+        let t = ((now_ms - self.started_at_ms) / self.duration_ms) as f32;
+        1. - self.easing.apply(t)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn remaining_angle(&self, quarter_turns: i32, now_ms: f64) -> Rad<f32> {
+        // Turning clockwise as seen from outside the face is a negative turn around its outward normal, so undoing the rest of it is positive
+        Rad(quarter_turns as f32 * FRAC_PI_2 * self.remaining(now_ms))
+    }
+
+    /// Turn the stickers of each animated layer back by what is left of the move at the given time, or darken them by it when fading.
+    pub(super) fn apply_to(
+        &self,
+        instances: &mut Instances,
+        side_length: usize,
+        explode: f32,
+        now_ms: f64,
+    ) {
+        if self.fade {
+            let remaining = self.remaining(now_ms);
+            let colours = instances.colors.iter_mut().flatten();
+            for (colour, transformation) in colours.zip(&instances.transformations) {
+                if self.turns.iter().any(|turn| {
+                    is_in_layer(transformation, self.face, turn.layer, side_length, explode)
+                }) {
+                    *colour = faded(*colour, remaining);
+                }
+            }
+            return;
+        }
+        let turns = self
+            .turns
+            .iter()
+            .map(|turn| {
+                let angle = self.remaining_angle(turn.quarter_turns, now_ms);
+                (
+                    turn.layer,
+                    Mat4::from_axis_angle(outward_normal(self.face), angle),
+                )
+            })
+            .collect::<Vec<_>>();
+        for transformation in &mut instances.transformations {
+            if let Some((_, turn)) = turns.iter().find(|(layer, _)| {
+                is_in_layer(transformation, self.face, *layer, side_length, explode)
+            }) {
+                *transformation = turn * *transformation;
+            }
+        }
+    }
+}
+
+/// Whether the sticker placed by `transformation`, on a cube exploded by `explode`, belongs to the layer counted back from `face`.
+pub(super) fn is_in_layer(
+    transformation: &Mat4,
+    face: Face,
+    layer: usize,
+    side_length: usize,
+    explode: f32,
+) -> bool {
+    // Exploding moves each cubie out by a multiple of its distance from the centre, so scaling back down puts every sticker off the face back within its layer
+    let depth = (*transformation * vec4(0., 0., 0., 1.))
+        .truncate()
+        .dot(outward_normal(face))
+        / (1. + explode);
+    let (lower, upper) = layer_bounds(side_length, layer);
+    lower < depth && depth < upper
+}
+
+fn outward_normal(face: Face) -> Vector3<f32> {
+    match face {
+        Face::Up => vec3(0., 1., 0.),
+        Face::Down => vec3(0., -1., 0.),
+        Face::Front => vec3(0., 0., 1.),
+        Face::Back => vec3(0., 0., -1.),
+        Face::Right => vec3(1., 0., 0.),
+        Face::Left => vec3(-1., 0., 0.),
+    }
+}
+
+/// Returns the range of distances along the face's outward normal that sticker centres in the layer lie strictly within, which includes the face itself for the outer layers.
+#[allow(clippy::cast_precision_loss)]
+fn layer_bounds(side_length: usize, layer: usize) -> (f32, f32) {
+    let layer_width = 2. / side_length as f32;
+    let upper = if layer == 0 {
+        f32::INFINITY
+    } else {
+        1. - layer_width * layer as f32
+    };
+    let lower = if layer + 1 == side_length {
+        f32::NEG_INFINITY
+    } else {
+        1. - layer_width * (layer + 1) as f32
+    };
+    (lower, upper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::{colours::DEFAULT_COLOURS, cube_ext::ToInstances, picture::Picture};
+    use pretty_assertions::assert_eq;
+    use rusty_puzzle_cube::cube::{colour_scheme::WESTERN, rotation::Rotation, Cube};
+
+    fn animation(made: &Move, side_length: usize) -> Option<TurnAnimation> {
+        TurnAnimation::for_move(
+            made,
+            side_length,
+            0.,
+            100.,
+            Easing::Linear,
+            AnimationStyle::Turn,
+        )
+    }
+
+    #[test]
+    fn test_easings_start_and_end_in_place() {
+        for easing in Easing::ALL {
+            assert!(easing.apply(0.).abs() < 1e-5, "{easing:?}");
+            assert!((easing.apply(1.) - 1.).abs() < 1e-5, "{easing:?}");
+        }
+        assert_eq!(0.5, Easing::EaseInOut.apply(0.5));
+        assert!(Easing::Bounce.apply(0.73) > Easing::Bounce.apply(0.8));
+    }
+
+    fn turns(animation: &TurnAnimation) -> (Face, Vec<(usize, i32)>) {
+        let turns = animation
+            .turns
+            .iter()
+            .map(|turn| (turn.layer, turn.quarter_turns))
+            .collect();
+        (animation.face, turns)
+    }
+
+    #[test]
+    fn test_single_layer_moves_animate() {
+        let double = animation(&Move::Step("R2".to_string()), 3).unwrap();
+        assert_eq!((Face::Right, vec![(0, 2)]), turns(&double));
+
+        let slice = animation(&Move::Step("M'".to_string()), 3).unwrap();
+        assert_eq!((Face::Left, vec![(1, -1)]), turns(&slice));
+
+        let rotation = Rotation::anticlockwise(Face::Up).at_layer(3);
+        let inner = animation(&Move::Rotation(rotation), 5).unwrap();
+        assert_eq!((Face::Up, vec![(3, -1)]), turns(&inner));
+    }
+
+    #[test]
+    fn test_layers_around_one_axis_animate_together() {
+        let wide = animation(&Move::Step("Rw'".to_string()), 3).unwrap();
+        assert_eq!((Face::Right, vec![(0, -1), (1, -1)]), turns(&wide));
+
+        let parallel = animation(&Move::Step("(R, L')".to_string()), 3).unwrap();
+        assert_eq!((Face::Right, vec![(0, 1), (2, 1)]), turns(&parallel));
+
+        let up = Rotation::clockwise(Face::Up);
+        let opposite = Move::Rotations(vec![up, Rotation::clockwise(Face::Down).at_layer(1)]);
+        assert_eq!(
+            (Face::Up, vec![(0, 1), (2, -1)]),
+            turns(&animation(&opposite, 4).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_other_moves_do_not_animate() {
+        assert_eq!(None, animation(&Move::Step("(R U)".to_string()), 3));
+        assert_eq!(None, animation(&Move::Step("M".to_string()), 5));
+        assert_eq!(
+            None,
+            animation(
+                &Move::Rotation(Rotation::clockwise(Face::Up).at_layer(3)),
+                3
+            )
+        );
+        assert_eq!(
+            None,
+            animation(
+                &Move::Transform(rusty_puzzle_cube::known_transforms::KnownTransform::Checkerboard),
+                3
+            )
+        );
+    }
+
+    #[test]
+    fn test_only_the_turning_layer_moves() {
+        let side_length = 3;
+        let cube = Cube::create(side_length);
+        let before = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let mut instances = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let rotation = Rotation::clockwise(Face::Front).at_layer(1);
+        let turn = animation(&Move::Rotation(rotation), side_length).unwrap();
+
+        turn.apply_to(&mut instances, side_length, 0., 50.);
+
+        let moved = before
+            .transformations
+            .iter()
+            .zip(&instances.transformations)
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(4 * side_length, moved);
+    }
+
+    #[test]
+    fn test_styles_choose_which_layers_turn() {
+        let right = Rotation::clockwise(Face::Right);
+        let wide = Move::Rotations((0..4).map(|layer| right.at_layer(layer)).collect());
+        let styled = |style| TurnAnimation::for_move(&wide, 9, 0., 100., Easing::Linear, style);
+
+        assert_eq!(None, styled(AnimationStyle::Instant));
+        assert_eq!(
+            (Face::Right, vec![(0, 1), (1, 1), (2, 1)]),
+            turns(&styled(AnimationStyle::InstantDeepLayers).unwrap())
+        );
+        assert_eq!(
+            (Face::Right, vec![(0, 1), (1, 1), (2, 1), (3, 1)]),
+            turns(&styled(AnimationStyle::SnapAndFade).unwrap())
+        );
+        let deep = Move::Rotation(Rotation::clockwise(Face::Up).at_layer(4));
+        assert_eq!(
+            None,
+            TurnAnimation::for_move(
+                &deep,
+                9,
+                0.,
+                100.,
+                Easing::Linear,
+                AnimationStyle::InstantDeepLayers
+            )
+        );
+    }
+
+    #[test]
+    fn test_style_codes_round_trip() {
+        for style in AnimationStyle::ALL {
+            assert_eq!(Some(style), AnimationStyle::from_code(style.code()));
+        }
+        assert_eq!(None, AnimationStyle::from_code("spin"));
+    }
+
+    #[test]
+    fn test_snap_and_fade_darkens_the_layer_in_place() {
+        let cube = Cube::create(3);
+        let before = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let mut instances = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let turn = TurnAnimation::for_move(
+            &Move::Rotation(Rotation::clockwise(Face::Front)),
+            3,
+            0.,
+            100.,
+            Easing::Linear,
+            AnimationStyle::SnapAndFade,
+        )
+        .unwrap();
+
+        turn.apply_to(&mut instances, 3, 0., 50.);
+
+        assert_eq!(before.transformations, instances.transformations);
+        let faded = before
+            .colors
+            .unwrap()
+            .iter()
+            .zip(instances.colors.unwrap())
+            .filter(|(a, b)| **a != *b)
+            .count();
+        assert_eq!(9 + 4 * 3, faded);
+    }
+
+    #[test]
+    fn test_parallel_layers_move_and_the_middle_stays() {
+        let cube = Cube::create(3);
+        let before = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let mut instances = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let turn = animation(&Move::Step("(U, D)".to_string()), 3).unwrap();
+
+        turn.apply_to(&mut instances, 3, 0., 50.);
+
+        let moved = before
+            .transformations
+            .iter()
+            .zip(&instances.transformations)
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(2 * (9 + 4 * 3), moved);
+    }
+
+    #[test]
+    fn test_layer_is_back_in_place_when_finished() {
+        let cube = Cube::create(2);
+        let before = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let mut instances = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let turn = animation(&Move::Rotation(Rotation::clockwise(Face::Back)), 2).unwrap();
+
+        turn.apply_to(&mut instances, 2, 0., 100.);
+
+        for (a, b) in before
+            .transformations
+            .iter()
+            .zip(&instances.transformations)
+        {
+            assert!((a.w - b.w).magnitude() < 1e-5);
+        }
+        assert!(turn.is_finished(100.));
+        assert!(!turn.is_finished(99.));
+    }
+
+    #[test]
+    fn test_clockwise_turns_back_anticlockwise_around_the_normal() {
+        let turn = animation(&Move::Rotation(Rotation::clockwise(Face::Up)), 3).unwrap();
+        assert_eq!(Rad(FRAC_PI_2), turn.remaining_angle(1, 0.));
+        assert_eq!((Face::Up, vec![(0, -1)]), turns(&turn.reversed()));
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use three_d::Camera;
+
+use super::defaults::initial_camera;
+
+pub(super) struct CameraPoses {
+    saved: HashMap<usize, Camera>,
+}
+
+impl CameraPoses {
+    pub(super) fn new() -> Self {
+        Self {
+            saved: HashMap::new(),
+        }
+    }
+
+    pub(super) fn switch_side_length(
+        &mut self,
+        camera: &mut Camera,
+        from_side_length: usize,
+        to_side_length: usize,
+    ) {
+        let viewport = camera.viewport();
+        self.saved.insert(from_side_length, camera.clone());
+        *camera = self
+            .saved
+            .get(&to_side_length)
+            .cloned()
+            .unwrap_or_else(|| initial_camera(viewport));
+        camera.set_viewport(viewport);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use three_d::{vec3, Viewport};
+
+    fn viewport() -> Viewport {
+        Viewport {
+            x: 0,
+            y: 0,
+            width: 75,
+            height: 50,
+        }
+    }
+
+    fn moved_camera() -> Camera {
+        let mut camera = initial_camera(viewport());
+        camera.set_view(vec3(0., 0., 9.), vec3(0., 0., 0.), vec3(0., 1., 0.));
+        camera
+    }
+
+    #[test]
+    fn test_unseen_side_length_gets_initial_camera() {
+        let mut poses = CameraPoses::new();
+        let mut camera = moved_camera();
+
+        poses.switch_side_length(&mut camera, 3, 4);
+
+        assert_eq!(initial_camera(viewport()).position(), camera.position());
+    }
+
+    #[test]
+    fn test_returning_to_side_length_restores_camera() {
+        let mut poses = CameraPoses::new();
+        let mut camera = moved_camera();
+
+        poses.switch_side_length(&mut camera, 3, 4);
+        poses.switch_side_length(&mut camera, 4, 3);
+
+        assert_eq!(&vec3(0., 0., 9.), camera.position());
+    }
+}
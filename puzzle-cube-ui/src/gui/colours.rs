@@ -1,3 +1,4 @@
+use rusty_puzzle_cube::cube::colour_scheme::{ColourScheme, JAPANESE, WESTERN};
 use three_d::Srgba;
 
 pub(super) const RED: Srgba = Srgba::new_opaque(204, 0, 0);
@@ -6,3 +7,224 @@ pub(super) const BLUE: Srgba = Srgba::new_opaque(0, 0, 204);
 pub(super) const ORANGE: Srgba = Srgba::new_opaque(224, 112, 0);
 pub(super) const WHITE: Srgba = Srgba::new_opaque(255, 255, 255);
 pub(super) const YELLOW: Srgba = Srgba::new_opaque(224, 224, 0);
+
+const HIGHLIGHT_TINT: Srgba = Srgba::new_opaque(255, 0, 255);
+const HIGHLIGHT_STRENGTH: f32 = 0.4;
+
+pub(super) const DEFAULT_COLOURS: StickerColours = StickerColours {
+    blue: BLUE,
+    green: GREEN,
+    orange: ORANGE,
+    red: RED,
+    white: WHITE,
+    yellow: YELLOW,
+};
+
+/// Taken from the Okabe-Ito palette, with red swapped for reddish purple so that it cannot be confused with orange.
+pub(super) const COLOUR_BLIND_COLOURS: StickerColours = StickerColours {
+    blue: Srgba::new_opaque(0, 114, 178),
+    green: Srgba::new_opaque(0, 158, 115),
+    orange: Srgba::new_opaque(230, 159, 0),
+    red: Srgba::new_opaque(204, 121, 167),
+    white: WHITE,
+    yellow: Srgba::new_opaque(240, 228, 66),
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Palette {
+    Default,
+    ColourBlind,
+    Custom,
+}
+
+impl Palette {
+    pub(super) const ALL: [Palette; 3] = [Palette::Default, Palette::ColourBlind, Palette::Custom];
+
+    pub(super) fn code(self) -> &'static str {
+        match self {
+            Palette::Default => "default",
+            Palette::ColourBlind => "colour-blind",
+            Palette::Custom => "custom",
+        }
+    }
+
+    pub(super) fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|palette| palette.code() == code)
+    }
+}
+
+/// The colour each sticker is drawn in, named after the sticker it replaces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct StickerColours {
+    pub(super) blue: Srgba,
+    pub(super) green: Srgba,
+    pub(super) orange: Srgba,
+    pub(super) red: Srgba,
+    pub(super) white: Srgba,
+    pub(super) yellow: Srgba,
+}
+
+impl StickerColours {
+    /// Writes the colours as comma separated hex codes, in the order of the fields.
+    pub(super) fn encode(&self) -> String {
+        self.all()
+            .map(|c| format!("#{:02x}{:02x}{:02x}", c.r, c.g, c.b))
+            .join(",")
+    }
+
+    pub(super) fn decode(encoded: &str) -> Option<Self> {
+        let mut colours = encoded.split(',').map(decode_hex);
+        let decoded = Self {
+            blue: colours.next()??,
+            green: colours.next()??,
+            orange: colours.next()??,
+            red: colours.next()??,
+            white: colours.next()??,
+            yellow: colours.next()??,
+        };
+        colours.next().is_none().then_some(decoded)
+    }
+
+    fn all(&self) -> [Srgba; 6] {
+        [
+            self.blue,
+            self.green,
+            self.orange,
+            self.red,
+            self.white,
+            self.yellow,
+        ]
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Srgba> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some(Srgba::new_opaque(channel(0)?, channel(2)?, channel(4)?))
+}
+
+pub(super) fn colour_scheme(japanese_colours: bool) -> &'static ColourScheme {
+    if japanese_colours {
+        &JAPANESE
+    } else {
+        &WESTERN
+    }
+}
+
+/// Mixes some of [`HIGHLIGHT_TINT`] into the colour, so highlighted stickers stand out whatever colour they are.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub(super) fn highlighted(colour: Srgba) -> Srgba {
+    let mix = |channel: u8, tint: u8| {
+        (f32::from(channel) * (1. - HIGHLIGHT_STRENGTH) + f32::from(tint) * HIGHLIGHT_STRENGTH)
+            .round() as u8
+    };
+    Srgba::new_opaque(
+        mix(colour.r, HIGHLIGHT_TINT.r),
+        mix(colour.g, HIGHLIGHT_TINT.g),
+        mix(colour.b, HIGHLIGHT_TINT.b),
+    )
+}
+
+/// Darkens the colour towards black by `amount`, from 0 leaving it as it is to 1 making it black.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub(super) fn faded(colour: Srgba, amount: f32) -> Srgba {
+    let fade = |channel: u8| (f32::from(channel) * (1. - amount.clamp(0., 1.))).round() as u8;
+    Srgba::new_opaque(fade(colour.r), fade(colour.g), fade(colour.b))
+}
+
+pub(super) fn sticker_colours(palette: Palette, custom: &StickerColours) -> &StickerColours {
+    match palette {
+        Palette::Default => &DEFAULT_COLOURS,
+        Palette::ColourBlind => &COLOUR_BLIND_COLOURS,
+        Palette::Custom => custom,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_palette_codes_round_trip() {
+        for palette in Palette::ALL {
+            assert_eq!(Some(palette), Palette::from_code(palette.code()));
+        }
+        assert_eq!(None, Palette::from_code("sepia"));
+    }
+
+    #[test]
+    fn test_sticker_colours_round_trip() {
+        assert_eq!(
+            "#0000cc,#00cc00,#e07000,#cc0000,#ffffff,#e0e000",
+            DEFAULT_COLOURS.encode()
+        );
+        for colours in [DEFAULT_COLOURS, COLOUR_BLIND_COLOURS] {
+            assert_eq!(Some(colours), StickerColours::decode(&colours.encode()));
+        }
+    }
+
+    #[test]
+    fn test_malformed_sticker_colours_are_rejected() {
+        assert_eq!(None, StickerColours::decode(""));
+        assert_eq!(
+            None,
+            StickerColours::decode("#0000cc,#00cc00,#e07000,#cc0000,#ffffff")
+        );
+        assert_eq!(
+            None,
+            StickerColours::decode("#0000cc,#00cc00,#e07000,#cc0000,#ffffff,#e0e000,#000000")
+        );
+        assert_eq!(
+            None,
+            StickerColours::decode("#0000cc,#00cc00,#e07000,#cc0000,#ffffff,e0e000")
+        );
+        assert_eq!(
+            None,
+            StickerColours::decode("#0000cc,#00cc00,#e07000,#cc0000,#ffffff,#e0e0zz")
+        );
+        assert_eq!(
+            None,
+            StickerColours::decode("#0000cc,#00cc00,#e07000,#cc0000,#ffffff,#0é0e0")
+        );
+    }
+
+    #[test]
+    fn test_faded_darkens_towards_black() {
+        assert_eq!(WHITE, faded(WHITE, 0.));
+        assert_eq!(Srgba::new_opaque(128, 128, 128), faded(WHITE, 0.5));
+        assert_eq!(Srgba::new_opaque(0, 0, 0), faded(RED, 1.));
+    }
+
+    #[test]
+    fn test_highlighted_mixes_in_tint() {
+        assert_eq!(Srgba::new_opaque(255, 153, 255), highlighted(WHITE));
+        assert_eq!(Srgba::new_opaque(224, 0, 102), highlighted(RED));
+    }
+
+    #[test]
+    fn test_custom_palette_uses_custom_colours() {
+        let custom = StickerColours {
+            white: Srgba::new_opaque(1, 2, 3),
+            ..DEFAULT_COLOURS
+        };
+
+        assert_eq!(&DEFAULT_COLOURS, sticker_colours(Palette::Default, &custom));
+        assert_eq!(
+            &COLOUR_BLIND_COLOURS,
+            sticker_colours(Palette::ColourBlind, &custom)
+        );
+        assert_eq!(&custom, sticker_colours(Palette::Custom, &custom));
+    }
+}
@@ -1,108 +1,265 @@
-use rusty_puzzle_cube::cube::{cubie_face::CubieFace, face::Face, Cube};
+use rusty_puzzle_cube::cube::{
+    colour_scheme::{recolour, ColourScheme},
+    cubie_face::CubieFace,
+    face::Face,
+    rotation::Rotation,
+    Cube,
+};
 use three_d::{Instances, Matrix4, Srgba};
 
 use super::{
-    colours::{BLUE, GREEN, ORANGE, RED, WHITE, YELLOW},
+    animation::is_in_layer,
+    colours::{highlighted, StickerColours},
+    picture::{blank_slice, Picture},
     transforms::cubie_face_to_transformation,
 };
 
 pub(crate) trait ToInstances {
-    fn to_instances(&self) -> Instances;
+    /// Places a tile for every sticker, with the layers turned by the `highlight` rotations tinted and the cubies spread apart by `explode`.
+    ///
+    /// Stickers that started on a face of the `picture` show their slice of its image instead of their colour.
+    fn to_instances(
+        &self,
+        scheme: &ColourScheme,
+        colours: &StickerColours,
+        picture: &Picture,
+        highlight: &[Rotation],
+        explode: f32,
+    ) -> Instances;
 }
 
-macro_rules! all_faces_to_instances {
-    ($side_map:ident, $side_length:ident) => {{
-        let (iter_transformations, iter_colours) = all_faces_to_instances!(
-            $side_map,
-            $side_length,
-            Face::Front,
-            Face::Back,
-            Face::Left,
-            Face::Right,
-            Face::Up,
-            Face::Down,
-        );
-
-        let required_capacity = 6 * $side_length * $side_length;
-        let mut transformations = Vec::with_capacity(required_capacity);
-        transformations.extend(iter_transformations);
-        let mut colours = Vec::with_capacity(required_capacity);
-        colours.extend(iter_colours);
-
-        (transformations, colours)
-    }};
-    ($side_map:ident, $side_length:ident, $this_face:expr) => {
-        face_to_instances($this_face, &$side_map[$this_face], $side_length)
-    };
-    ($side_map:ident, $side_length:ident, $this_face:expr, $($tail:expr),+ $(,)?) => {{
-        let (transforms, colours) = all_faces_to_instances!($side_map, $side_length, $this_face);
-        let (tail_transforms, tail_colours) = all_faces_to_instances!($side_map, $side_length, $($tail),*);
-        (
-            transforms.chain(tail_transforms),
-            colours.chain(tail_colours),
-        )
-    }};
-}
+pub(super) const FACE_ORDER: [Face; 6] = [
+    Face::Front,
+    Face::Back,
+    Face::Left,
+    Face::Right,
+    Face::Up,
+    Face::Down,
+];
 
 impl ToInstances for Cube {
-    fn to_instances(&self) -> Instances {
+    fn to_instances(
+        &self,
+        scheme: &ColourScheme,
+        colours: &StickerColours,
+        picture: &Picture,
+        highlight: &[Rotation],
+        explode: f32,
+    ) -> Instances {
         let side_length = self.side_length();
-        let side_map = self.side_map();
-        let (transformations, colours) = all_faces_to_instances!(side_map, side_length);
+        let mut transformations = Vec::with_capacity(6 * side_length * side_length);
+        for face in FACE_ORDER {
+            transformations.extend(face_to_transformations(face, side_length, explode));
+        }
+        let mut tile_colours = tile_colours(self, scheme, colours);
+        let texture_transformations = (!picture.is_empty()).then(|| {
+            picture
+                .slices(self)
+                .into_iter()
+                .zip(tile_colours.iter_mut())
+                .map(|(slice, colour)| {
+                    slice.map_or_else(blank_slice, |slice| {
+                        *colour = Srgba::WHITE;
+                        slice
+                    })
+                })
+                .collect()
+        });
+        for (colour, transformation) in tile_colours.iter_mut().zip(&transformations) {
+            if highlight.iter().any(|rotation| {
+                is_in_layer(
+                    transformation,
+                    rotation.relative_to,
+                    rotation.layer,
+                    side_length,
+                    explode,
+                )
+            }) {
+                *colour = highlighted(*colour);
+            }
+        }
         Instances {
             transformations,
-            colors: Some(colours),
-            ..Default::default()
+            texture_transformations,
+            colors: Some(tile_colours),
         }
     }
 }
 
-fn face_to_instances(
+fn face_to_transformations(
     face: Face,
-    side: &[Vec<CubieFace>],
     side_length: usize,
-) -> (
-    impl Iterator<Item = Matrix4<f32>> + '_,
-    impl Iterator<Item = Srgba> + '_,
-) {
-    let transformations = side
-        .iter()
-        .flatten()
-        .enumerate()
-        .map(move |(i, _cubie_face)| {
-            let y = i / side_length;
-            let x = i % side_length;
-            cubie_face_to_transformation(side_length, face, x, y)
-        });
+    explode: f32,
+) -> impl Iterator<Item = Matrix4<f32>> {
+    (0..side_length * side_length).map(move |i| {
+        let y = i / side_length;
+        let x = i % side_length;
+        cubie_face_to_transformation(side_length, face, x, y, explode)
+    })
+}
 
-    let colours = side
+#[cfg(not(feature = "rayon"))]
+fn tile_colours(cube: &Cube, scheme: &ColourScheme, colours: &StickerColours) -> Vec<Srgba> {
+    FACE_ORDER
         .iter()
-        .flatten()
-        .map(move |cubie_face| cubie_face_to_colour(*cubie_face));
+        .flat_map(|&face| cube.oriented_side(face).cells())
+        .map(|cubie_face| cubie_face_to_colour(recolour(*cubie_face, scheme), colours))
+        .collect()
+}
+
+#[cfg(feature = "rayon")]
+fn tile_colours(cube: &Cube, scheme: &ColourScheme, colours: &StickerColours) -> Vec<Srgba> {
+    use rayon::prelude::*;
 
-    (transformations, colours)
+    FACE_ORDER
+        .par_iter()
+        .flat_map(|&face| cube.oriented_side(face).cells().par_iter())
+        .map(|cubie_face| cubie_face_to_colour(recolour(*cubie_face, scheme), colours))
+        .collect()
 }
 
-fn cubie_face_to_colour(cubie_face: CubieFace) -> Srgba {
+pub(super) fn cubie_face_to_colour(cubie_face: CubieFace, colours: &StickerColours) -> Srgba {
     match cubie_face {
-        CubieFace::Blue(_) => BLUE,
-        CubieFace::Green(_) => GREEN,
-        CubieFace::Orange(_) => ORANGE,
-        CubieFace::Red(_) => RED,
-        CubieFace::White(_) => WHITE,
-        CubieFace::Yellow(_) => YELLOW,
+        CubieFace::Blue(_) => colours.blue,
+        CubieFace::Green(_) => colours.green,
+        CubieFace::Orange(_) => colours.orange,
+        CubieFace::Red(_) => colours.red,
+        CubieFace::White(_) => colours.white,
+        CubieFace::Yellow(_) => colours.yellow,
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::colours::{
+        BLUE, COLOUR_BLIND_COLOURS, DEFAULT_COLOURS, GREEN, ORANGE, RED, WHITE, YELLOW,
+    };
+    use super::super::transforms::MAX_EXPLODE;
     use super::*;
-    use pretty_assertions::assert_eq;
+    use pretty_assertions::{assert_eq, assert_ne};
+    use rusty_puzzle_cube::cube::{
+        colour_scheme::{JAPANESE, WESTERN},
+        rotation::Rotation,
+    };
+    use three_d::{CpuTexture, TextureData};
+
+    #[test]
+    fn test_colours_follow_face_order() {
+        let mut cube = Cube::create(2);
+        cube.rotate(Rotation::clockwise(Face::Up));
+
+        let colours = tile_colours(&cube, &WESTERN, &DEFAULT_COLOURS);
+
+        assert_eq!(24, colours.len());
+        assert_eq!(&[ORANGE, ORANGE, BLUE, BLUE], &colours[..4]);
+        assert_eq!(&[YELLOW; 4], &colours[20..]);
+    }
+
+    #[test]
+    fn test_colours_use_scheme() {
+        let colours = tile_colours(&Cube::create(1), &JAPANESE, &DEFAULT_COLOURS);
+
+        assert_eq!(&[YELLOW, GREEN, RED, ORANGE, WHITE, BLUE], &colours[..]);
+    }
+
+    #[test]
+    fn test_colours_use_sticker_colours() {
+        let colours = tile_colours(&Cube::create(1), &WESTERN, &COLOUR_BLIND_COLOURS);
+
+        assert_eq!(
+            &[
+                COLOUR_BLIND_COLOURS.blue,
+                COLOUR_BLIND_COLOURS.green,
+                COLOUR_BLIND_COLOURS.red,
+                COLOUR_BLIND_COLOURS.orange,
+                COLOUR_BLIND_COLOURS.white,
+                COLOUR_BLIND_COLOURS.yellow,
+            ],
+            &colours[..]
+        );
+    }
+
+    #[test]
+    fn test_highlight_tints_only_the_turning_layer() {
+        let cube = Cube::create(3);
+        let plain = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        let highlighted_layer = cube.to_instances(
+            &WESTERN,
+            &DEFAULT_COLOURS,
+            &Picture::new(),
+            &[Rotation::clockwise(Face::Up).at_layer(1)],
+            0.,
+        );
+
+        let changed = plain
+            .colors
+            .unwrap()
+            .iter()
+            .zip(highlighted_layer.colors.unwrap())
+            .filter(|(before, after)| **before != *after)
+            .count();
+        assert_eq!(12, changed);
+    }
+
+    #[test]
+    fn test_highlight_finds_layer_of_exploded_cube() {
+        let cube = Cube::create(5);
+        let plain = cube.to_instances(
+            &WESTERN,
+            &DEFAULT_COLOURS,
+            &Picture::new(),
+            &[],
+            MAX_EXPLODE,
+        );
+        let highlighted_layer = cube.to_instances(
+            &WESTERN,
+            &DEFAULT_COLOURS,
+            &Picture::new(),
+            &[Rotation::clockwise(Face::Right).at_layer(3)],
+            MAX_EXPLODE,
+        );
+
+        let changed = plain
+            .colors
+            .unwrap()
+            .iter()
+            .zip(highlighted_layer.colors.unwrap())
+            .filter(|(before, after)| **before != *after)
+            .count();
+        assert_eq!(4 * 5, changed);
+    }
+
+    #[test]
+    fn test_picture_replaces_colours_of_its_stickers() {
+        let cube = Cube::create_supercube(2);
+        let plain = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &Picture::new(), &[], 0.);
+        assert_eq!(None, plain.texture_transformations);
+
+        let mut picture = Picture::new();
+        picture
+            .set_face(
+                Face::Up,
+                &CpuTexture {
+                    data: TextureData::RgbU8(vec![[0, 0, 0]]),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let pictured = cube.to_instances(&WESTERN, &DEFAULT_COLOURS, &picture, &[], 0.);
+
+        let colours = pictured.colors.unwrap();
+        assert_eq!(&[Srgba::WHITE; 4], &colours[16..20]);
+        assert_eq!(&[BLUE; 4], &colours[..4]);
+        let texture_transformations = pictured.texture_transformations.unwrap();
+        assert_eq!(24, texture_transformations.len());
+        assert_eq!(blank_slice(), texture_transformations[0]);
+        assert_ne!(blank_slice(), texture_transformations[16]);
+    }
 
     #[test]
     fn test_cubie_face_to_colour_blue() {
         assert_eq!(
-            cubie_face_to_colour(CubieFace::Blue(None)),
+            cubie_face_to_colour(CubieFace::Blue(None), &DEFAULT_COLOURS),
             Srgba {
                 r: 0,
                 g: 0,
@@ -115,7 +272,7 @@ mod tests {
     #[test]
     fn test_cubie_face_to_colour_green() {
         assert_eq!(
-            cubie_face_to_colour(CubieFace::Green(None)),
+            cubie_face_to_colour(CubieFace::Green(None), &DEFAULT_COLOURS),
             Srgba {
                 r: 0,
                 g: 204,
@@ -128,7 +285,7 @@ mod tests {
     #[test]
     fn test_cubie_face_to_colour_orange() {
         assert_eq!(
-            cubie_face_to_colour(CubieFace::Orange(None)),
+            cubie_face_to_colour(CubieFace::Orange(None), &DEFAULT_COLOURS),
             Srgba {
                 r: 224,
                 g: 112,
@@ -141,7 +298,7 @@ mod tests {
     #[test]
     fn test_cubie_face_to_colour_red() {
         assert_eq!(
-            cubie_face_to_colour(CubieFace::Red(None)),
+            cubie_face_to_colour(CubieFace::Red(None), &DEFAULT_COLOURS),
             Srgba {
                 r: 204,
                 g: 0,
@@ -154,7 +311,7 @@ mod tests {
     #[test]
     fn test_cubie_face_to_colour_white() {
         assert_eq!(
-            cubie_face_to_colour(CubieFace::White(None)),
+            cubie_face_to_colour(CubieFace::White(None), &DEFAULT_COLOURS),
             Srgba {
                 r: 255,
                 g: 255,
@@ -167,7 +324,7 @@ mod tests {
     #[test]
     fn test_cubie_face_to_colour_yellow() {
         assert_eq!(
-            cubie_face_to_colour(CubieFace::Yellow(None)),
+            cubie_face_to_colour(CubieFace::Yellow(None), &DEFAULT_COLOURS),
             Srgba {
                 r: 224,
                 g: 224,
@@ -1,4 +1,17 @@
-use three_d::{degrees, vec3, Camera, ClearState, Viewport, Window, WindowSettings};
+use three_d::{
+    degrees, vec3, Angle as _, Camera, ClearState, InnerSpace as _, One as _, Quat, Rad,
+    Rotation as _, Vec3, Viewport, Window, WindowSettings,
+};
+
+use super::animation::Easing;
+
+const FIELD_OF_VIEW_Y_DEGREES: f32 = 45.0;
+const CAMERA_TRANSITION_MS: f64 = 600.;
+const EPSILON: f32 = 0.0001;
+const INITIAL_VIEW_DIRECTION: Vec3 = vec3(-3.0, -3.0, -6.0);
+// tiles sit just outside the inner cube, which spans -1.0 to 1.0 on each axis
+const CUBE_BOUNDING_RADIUS: f32 = 1.05 * 1.732_050_8;
+const FRAMING_PADDING: f32 = 1.5;
 
 pub(super) fn initial_window() -> Result<Window, three_d::WindowError> {
     Window::new(WindowSettings {
@@ -10,17 +23,154 @@ pub(super) fn initial_window() -> Result<Window, three_d::WindowError> {
 }
 
 pub(super) fn initial_camera(viewport: Viewport) -> Camera {
+    let position = -INITIAL_VIEW_DIRECTION.normalize() * framing_distance(viewport);
     Camera::new_perspective(
         viewport,
-        vec3(3.0, 3.0, 6.0),
+        position,
         vec3(0.0, 0.0, 0.0),
         vec3(0.0, 1.0, 0.0),
-        degrees(45.0),
+        degrees(FIELD_OF_VIEW_Y_DEGREES),
         0.1,
         100.0,
     )
 }
 
+pub(super) fn frame_cube(camera: &mut Camera) {
+    let target = *camera.target();
+    let up = *camera.up();
+    let position =
+        target - camera.view_direction().normalize() * framing_distance(camera.viewport());
+    camera.set_view(position, target, up);
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn framing_distance(viewport: Viewport) -> f32 {
+    let half_fov_y: Rad<f32> = degrees(FIELD_OF_VIEW_Y_DEGREES / 2.).into();
+    let limiting_half_fov = if viewport.width == 0 || viewport.height == 0 {
+        half_fov_y
+    } else {
+        let aspect = viewport.width as f32 / viewport.height as f32;
+        let half_fov_x = Rad::atan(half_fov_y.tan() * aspect);
+        if half_fov_x < half_fov_y {
+            half_fov_x
+        } else {
+            half_fov_y
+        }
+    };
+    CUBE_BOUNDING_RADIUS * FRAMING_PADDING / limiting_half_fov.sin()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CameraPreset {
+    FrontRightUp,
+    BackLeftDown,
+    Isometric,
+    Front,
+    Back,
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl CameraPreset {
+    pub(super) const ALL: [CameraPreset; 9] = [
+        CameraPreset::FrontRightUp,
+        CameraPreset::BackLeftDown,
+        CameraPreset::Isometric,
+        CameraPreset::Front,
+        CameraPreset::Back,
+        CameraPreset::Left,
+        CameraPreset::Right,
+        CameraPreset::Up,
+        CameraPreset::Down,
+    ];
+
+    /// Returns the direction from the cube to the camera, and the direction that is up on screen.
+    fn view(self) -> (Vec3, Vec3) {
+        let up = vec3(0.0, 1.0, 0.0);
+        match self {
+            CameraPreset::FrontRightUp => (-INITIAL_VIEW_DIRECTION, up),
+            CameraPreset::BackLeftDown => (INITIAL_VIEW_DIRECTION, up),
+            CameraPreset::Isometric => (vec3(1.0, 1.0, 1.0), up),
+            CameraPreset::Front => (vec3(0.0, 0.0, 1.0), up),
+            CameraPreset::Back => (vec3(0.0, 0.0, -1.0), up),
+            CameraPreset::Left => (vec3(-1.0, 0.0, 0.0), up),
+            CameraPreset::Right => (vec3(1.0, 0.0, 0.0), up),
+            CameraPreset::Up => (vec3(0.0, 1.0, 0.0), vec3(0.0, 0.0, -1.0)),
+            CameraPreset::Down => (vec3(0.0, -1.0, 0.0), vec3(0.0, 0.0, 1.0)),
+        }
+    }
+}
+
+/// A smooth move of the camera to a preset view, swinging around its target while moving to the distance that frames the cube.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct CameraTransition {
+    target: Vec3,
+    from_offset: Vec3,
+    from_up: Vec3,
+    to_offset: Vec3,
+    to_up: Vec3,
+    started_at_ms: f64,
+}
+
+impl CameraTransition {
+    pub(super) fn to_preset(camera: &Camera, preset: CameraPreset, now_ms: f64) -> Self {
+        let (direction, up) = preset.view();
+        Self {
+            target: *camera.target(),
+            from_offset: *camera.position() - *camera.target(),
+            from_up: *camera.up(),
+            to_offset: direction.normalize() * framing_distance(camera.viewport()),
+            to_up: up,
+            started_at_ms: now_ms,
+        }
+    }
+
+    pub(super) fn is_finished(&self, now_ms: f64) -> bool {
+        self.started_at_ms + CAMERA_TRANSITION_MS <= now_ms
+    }
+
+    pub(super) fn apply_to(&self, camera: &mut Camera, now_ms: f64) {
+        let (offset, up) = self.view_at(now_ms);
+        camera.set_view(self.target + offset, self.target, up);
+    }
+
+    /// Returns the camera's offset from its target and its up direction at the given time.
+    #[allow(clippy::cast_possible_truncation)]
+    fn view_at(&self, now_ms: f64) -> (Vec3, Vec3) {
+        let t =
+            Easing::EaseInOut.apply(((now_ms - self.started_at_ms) / CAMERA_TRANSITION_MS) as f32);
+        let from_direction = self.from_offset.normalize();
+        let to_direction = self.to_offset.normalize();
+        let distance = self.from_offset.magnitude() * (1. - t) + self.to_offset.magnitude() * t;
+        let direction = turn_part_way(
+            from_direction,
+            to_direction,
+            perpendicular(from_direction, self.from_up),
+            t,
+        );
+        let up = turn_part_way(self.from_up.normalize(), self.to_up, direction, t);
+        (direction * distance, up)
+    }
+}
+
+/// Turns the unit vector `from` by the fraction `t` of the shortest turn onto `to`, turning around `fallback_axis` when they point in opposite directions.
+fn turn_part_way(from: Vec3, to: Vec3, fallback_axis: Vec3, t: f32) -> Vec3 {
+    let turn = Quat::from_arc(from, to, Some(fallback_axis));
+    Quat::one().slerp(turn, t).rotate_vector(from)
+}
+
+/// A unit vector at right angles to `direction`, as close to `hint` as possible.
+fn perpendicular(direction: Vec3, hint: Vec3) -> Vec3 {
+    let perpendicular = hint - direction * hint.dot(direction);
+    if perpendicular.magnitude() < EPSILON {
+        direction.cross(vec3(1.0, 0.0, 0.0)).normalize()
+    } else {
+        perpendicular.normalize()
+    }
+}
+
 pub(super) fn clear_state() -> ClearState {
     ClearState::color_and_depth(0.13, 0.13, 0.13, 1.0, 1.0)
 }
@@ -43,6 +193,103 @@ mod tests {
         assert_eq!(camera.target(), &Vector3::new(0., 0., 0.));
     }
 
+    fn viewport(width: u32, height: u32) -> Viewport {
+        Viewport {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_wide_viewport_framed_by_vertical_fov() {
+        let square = framing_distance(viewport(100, 100));
+        let wide = framing_distance(viewport(300, 100));
+
+        assert!((square - wide).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_narrow_viewport_needs_more_distance() {
+        let square = framing_distance(viewport(100, 100));
+        let narrow = framing_distance(viewport(50, 100));
+
+        assert!(narrow > square);
+    }
+
+    #[test]
+    fn test_minimized_viewport_has_finite_framing_distance() {
+        assert!(framing_distance(viewport(0, 0)).is_finite());
+    }
+
+    #[test]
+    fn test_frame_cube_keeps_view_direction() {
+        let mut camera = initial_camera(viewport(100, 100));
+        camera.set_view(vec3(0., 0., 40.), vec3(0., 0., 0.), vec3(0., 1., 0.));
+
+        frame_cube(&mut camera);
+
+        let expected_distance = framing_distance(viewport(100, 100));
+        assert!((camera.position().z - expected_distance).abs() < 0.0001);
+        assert!(camera.position().x.abs() < 0.0001);
+        assert!(camera.position().y.abs() < 0.0001);
+    }
+
+    fn transition(from_offset: Vec3, preset: CameraPreset) -> CameraTransition {
+        let (direction, up) = preset.view();
+        CameraTransition {
+            target: vec3(0., 0., 0.),
+            from_offset,
+            from_up: vec3(0., 1., 0.),
+            to_offset: direction.normalize() * 10.,
+            to_up: up,
+            started_at_ms: 0.,
+        }
+    }
+
+    fn assert_vec3_near(expected: Vec3, actual: Vec3) {
+        assert!(
+            (expected - actual).magnitude() < 0.001,
+            "{expected:?} != {actual:?}"
+        );
+    }
+
+    #[test]
+    fn test_transition_runs_from_current_view_to_preset() {
+        let transition = transition(vec3(0., 0., 5.), CameraPreset::Right);
+
+        let (offset, up) = transition.view_at(0.);
+        assert_vec3_near(vec3(0., 0., 5.), offset);
+        assert_vec3_near(vec3(0., 1., 0.), up);
+        assert!(!transition.is_finished(CAMERA_TRANSITION_MS / 2.));
+
+        let (offset, up) = transition.view_at(CAMERA_TRANSITION_MS);
+        assert_vec3_near(vec3(10., 0., 0.), offset);
+        assert_vec3_near(vec3(0., 1., 0.), up);
+        assert!(transition.is_finished(CAMERA_TRANSITION_MS));
+    }
+
+    #[test]
+    fn test_transition_swings_around_rather_than_through_target() {
+        let transition = transition(vec3(0., 0., 10.), CameraPreset::Back);
+
+        let (offset, _) = transition.view_at(CAMERA_TRANSITION_MS / 2.);
+
+        assert!((offset.magnitude() - 10.).abs() < 0.001);
+        assert!(offset.z.abs() < 0.001);
+    }
+
+    #[test]
+    fn test_transition_to_top_turns_up_direction() {
+        let transition = transition(vec3(0., 0., 10.), CameraPreset::Up);
+
+        let (offset, up) = transition.view_at(CAMERA_TRANSITION_MS);
+
+        assert_vec3_near(vec3(0., 10., 0.), offset);
+        assert_vec3_near(vec3(0., 0., -1.), up);
+    }
+
     #[test]
     fn test_clear_state_is_monochrome() {
         let clear_state = clear_state();
@@ -1,24 +1,104 @@
-use std::time::{SystemTime, UNIX_EPOCH};
-
 use three_d::{
     Camera, ColorMaterial, Context, CpuTexture, DepthTexture2D, Gm, InstancedMesh, Interpolation,
     Mesh, RenderTarget, Texture2D, TextureData, Viewport, Wrapping,
 };
-use three_d_asset::{io::Serialize as _, Error};
+use three_d_asset::io::Serialize as _;
+use web_time::{SystemTime, UNIX_EPOCH};
 
 use super::defaults::clear_state;
 
+pub(super) const MIN_IMAGE_SCALE: u32 = 1;
+pub(super) const MAX_IMAGE_SCALE: u32 = 4;
+const MAX_IMAGE_DIMENSION: u32 = 8192;
+
 pub(super) fn save_as_image(
     ctx: &Context,
     viewport: Viewport,
     camera: &Camera,
     tiles: &Gm<InstancedMesh, ColorMaterial>,
     inner_cube: &Gm<Mesh, ColorMaterial>,
-) -> Result<(), Error> {
+    scale: u32,
+) -> Result<(), String> {
+    let (width, height) = scaled_size(viewport, scale);
+    let pixels = render(ctx, width, height, camera, tiles, inner_cube);
+    let file_name = format!(
+        "rusty-puzzle-cube-{}.png",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis()
+    );
+    let image = CpuTexture {
+        data: TextureData::RgbaU8(pixels),
+        width,
+        height,
+        ..Default::default()
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    three_d_asset::io::save(
+        &image
+            .serialize(format!("img/{file_name}"))
+            .map_err(|e| e.to_string())?,
+    )
+    .map_err(|e| e.to_string())?;
+    #[cfg(target_arch = "wasm32")]
+    download::png(
+        &file_name,
+        &image
+            .serialize(&file_name)
+            .and_then(|mut raw_assets| raw_assets.remove(&file_name))
+            .map_err(|e| e.to_string())?,
+    )?;
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn copy_image(
+    ctx: &Context,
+    viewport: Viewport,
+    camera: &Camera,
+    tiles: &Gm<InstancedMesh, ColorMaterial>,
+    inner_cube: &Gm<Mesh, ColorMaterial>,
+    scale: u32,
+) -> Result<(), String> {
+    let (width, height) = scaled_size(viewport, scale);
+    let pixels = render(ctx, width, height, camera, tiles, inner_cube);
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| {
+            clipboard.set_image(arboard::ImageData {
+                width: width as usize,
+                height: height as usize,
+                bytes: pixels.into_iter().flatten().collect::<Vec<_>>().into(),
+            })
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Scales the viewport size up by `scale`, lowered as needed to keep both sides within [`MAX_IMAGE_DIMENSION`].
+fn scaled_size(viewport: Viewport, scale: u32) -> (u32, u32) {
+    let largest_side = viewport.width.max(viewport.height).max(1);
+    let scale = scale
+        .clamp(MIN_IMAGE_SCALE, MAX_IMAGE_SCALE)
+        .min(MAX_IMAGE_DIMENSION / largest_side)
+        .max(MIN_IMAGE_SCALE);
+    (viewport.width * scale, viewport.height * scale)
+}
+
+fn render(
+    ctx: &Context,
+    width: u32,
+    height: u32,
+    camera: &Camera,
+    tiles: &Gm<InstancedMesh, ColorMaterial>,
+    inner_cube: &Gm<Mesh, ColorMaterial>,
+) -> Vec<[u8; 4]> {
+    let mut camera = camera.clone();
+    camera.set_viewport(Viewport::new_at_origo(width, height));
     let mut texture = Texture2D::new_empty::<[u8; 4]>(
         ctx,
-        viewport.width,
-        viewport.height,
+        width,
+        height,
         Interpolation::Linear,
         Interpolation::Linear,
         None,
@@ -27,8 +107,8 @@ pub(super) fn save_as_image(
     );
     let mut depth_texture = DepthTexture2D::new::<f32>(
         ctx,
-        viewport.width,
-        viewport.height,
+        width,
+        height,
         Wrapping::ClampToEdge,
         Wrapping::ClampToEdge,
     );
@@ -37,23 +117,68 @@ pub(super) fn save_as_image(
         depth_texture.as_depth_target(),
     )
     .clear(clear_state())
-    .render(camera, tiles.into_iter().chain(inner_cube), &[])
+    .render(&camera, tiles.into_iter().chain(inner_cube), &[])
     .read_color();
+    pixels
+}
 
-    three_d_asset::io::save(
-        &CpuTexture {
-            data: TextureData::RgbaU8(pixels),
-            width: texture.width(),
-            height: texture.height(),
-            ..Default::default()
+#[cfg(target_arch = "wasm32")]
+mod download {
+    use wasm_bindgen::{JsCast as _, JsValue};
+    use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+    /// Hands the image to the browser as a download, as there is no file system to save it to.
+    pub(super) fn png(file_name: &str, bytes: &[u8]) -> Result<(), String> {
+        let error = |e: JsValue| format!("{e:?}");
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+        let mut options = BlobPropertyBag::new();
+        options.type_("image/png");
+        let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(error)?;
+        let url = Url::create_object_url_with_blob(&blob).map_err(error)?;
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or("No document to download the image from")?;
+        let link: HtmlAnchorElement = document
+            .create_element("a")
+            .map_err(error)?
+            .unchecked_into();
+        link.set_href(&url);
+        link.set_download(file_name);
+        link.click();
+        Url::revoke_object_url(&url).map_err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn viewport(width: u32, height: u32) -> Viewport {
+        Viewport {
+            x: 0,
+            y: 0,
+            width,
+            height,
         }
-        .serialize(format!(
-            "img/rusty-puzzle-cube-{}.png",
-            SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("Time went backwards")
-                .as_millis()
-        ))?,
-    )?;
-    Ok(())
+    }
+
+    #[test]
+    fn test_scaled_size_multiplies_viewport() {
+        assert_eq!((800, 600), scaled_size(viewport(800, 600), 1));
+        assert_eq!((2400, 1800), scaled_size(viewport(800, 600), 3));
+    }
+
+    #[test]
+    fn test_scaled_size_is_clamped_to_allowed_scales() {
+        assert_eq!((800, 600), scaled_size(viewport(800, 600), 0));
+        assert_eq!((3200, 2400), scaled_size(viewport(800, 600), 10));
+    }
+
+    #[test]
+    fn test_scaled_size_stays_within_max_dimension() {
+        assert_eq!((7680, 4320), scaled_size(viewport(3840, 2160), 4));
+        assert_eq!((10000, 100), scaled_size(viewport(10000, 100), 2));
+    }
 }
@@ -0,0 +1,568 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Language {
+    English,
+    French,
+}
+
+impl Language {
+    pub(super) const ALL: [Language; 2] = [Language::English, Language::French];
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::French => "Français",
+        }
+    }
+
+    pub(super) fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::French => "fr",
+        }
+    }
+
+    pub(super) fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+
+    pub(super) fn strings(self) -> &'static Strings {
+        match self {
+            Language::English => &ENGLISH,
+            Language::French => &FRENCH,
+        }
+    }
+}
+
+pub(super) struct Strings {
+    pub(super) by_author: &'static str,
+    pub(super) on_github: &'static str,
+    pub(super) language: &'static str,
+
+    pub(super) initialise_cube: &'static str,
+    pub(super) cube_size: fn(usize) -> String,
+    pub(super) unreasonable_mode: &'static str,
+    pub(super) japanese_colours: &'static str,
+    pub(super) approx_memory: fn(usize) -> String,
+    pub(super) memory_warning: &'static str,
+    pub(super) apply: &'static str,
+
+    pub(super) colours: &'static str,
+    pub(super) palette: &'static str,
+    pub(super) default_palette: &'static str,
+    pub(super) colour_blind_palette: &'static str,
+    pub(super) custom_palette: &'static str,
+    pub(super) blue_stickers: &'static str,
+    pub(super) green_stickers: &'static str,
+    pub(super) orange_stickers: &'static str,
+    pub(super) red_stickers: &'static str,
+    pub(super) white_stickers: &'static str,
+    pub(super) yellow_stickers: &'static str,
+    pub(super) reset_colours: &'static str,
+
+    pub(super) picture_cube: &'static str,
+    pub(super) picture_cube_hint: &'static str,
+    pub(super) picture_path: &'static str,
+    pub(super) load_picture: &'static str,
+    pub(super) clear_picture: &'static str,
+
+    pub(super) control_cube: &'static str,
+    pub(super) drag_to_rotate: &'static str,
+    pub(super) drag_one_face: &'static str,
+    pub(super) drag_cancelled: &'static str,
+    pub(super) drag_modifiers_hint: &'static str,
+    pub(super) use_buttons: &'static str,
+    pub(super) keyboard_hint: &'static str,
+    pub(super) face_keys_hint: &'static str,
+    pub(super) animation_style: &'static str,
+    pub(super) turn_style: &'static str,
+    pub(super) snap_and_fade: &'static str,
+    pub(super) instant_deep_layers: &'static str,
+    pub(super) instant: &'static str,
+
+    pub(super) lock_faces: &'static str,
+    pub(super) lock_faces_hint: &'static str,
+
+    pub(super) apply_sequence_heading: &'static str,
+    pub(super) sequence_hint: &'static str,
+    pub(super) apply_sequence: &'static str,
+    pub(super) run_sequence: &'static str,
+    pub(super) stop_sequence: &'static str,
+    pub(super) step_error: fn(usize, &str) -> String,
+    pub(super) moves: fn(usize) -> String,
+    pub(super) invalid_tokens: fn(usize) -> String,
+
+    pub(super) known_transforms: &'static str,
+    pub(super) transforms_applied_in_full: &'static str,
+    pub(super) checkerboard: &'static str,
+    pub(super) checkerboard_corners: &'static str,
+    pub(super) cube_in_cube_in_cube: &'static str,
+    pub(super) frames: &'static str,
+    pub(super) side_stripes: &'static str,
+    pub(super) stripes: &'static str,
+    pub(super) no_known_transforms: &'static str,
+    pub(super) unlock_to_apply: &'static str,
+
+    pub(super) shuffle_heading: &'static str,
+    pub(super) shuffle_moves: &'static str,
+    pub(super) instant_shuffle: &'static str,
+    pub(super) shuffle: &'static str,
+    pub(super) shuffled: fn(usize) -> String,
+
+    pub(super) solve_heading: &'static str,
+    pub(super) solve_hint: &'static str,
+    pub(super) solve: &'static str,
+    pub(super) only_3x3_solvable: &'static str,
+
+    pub(super) playback_heading: &'static str,
+    pub(super) playback_progress: fn(usize, usize) -> String,
+    pub(super) step_back: &'static str,
+    pub(super) play: &'static str,
+    pub(super) pause: &'static str,
+    pub(super) step_forward: &'static str,
+    pub(super) jump_to_end: &'static str,
+    pub(super) moves_per_second: &'static str,
+    pub(super) easing: &'static str,
+    pub(super) linear: &'static str,
+    pub(super) ease_in_out: &'static str,
+    pub(super) bounce: &'static str,
+
+    pub(super) move_history: &'static str,
+    pub(super) move_history_hint: &'static str,
+    pub(super) history_start: &'static str,
+    pub(super) undo: &'static str,
+    pub(super) redo: &'static str,
+
+    pub(super) control_camera: &'static str,
+    pub(super) camera_help: &'static str,
+    pub(super) touch_help: &'static str,
+    pub(super) reset_camera: &'static str,
+    pub(super) frame_cube: &'static str,
+    pub(super) camera_presets: &'static str,
+    pub(super) front_right_up: &'static str,
+    pub(super) back_left_down: &'static str,
+    pub(super) isometric: &'static str,
+    pub(super) front_view: &'static str,
+    pub(super) back_view: &'static str,
+    pub(super) left_view: &'static str,
+    pub(super) right_view: &'static str,
+    pub(super) top_view: &'static str,
+    pub(super) bottom_view: &'static str,
+    pub(super) auto_frame: &'static str,
+    pub(super) show_axes: &'static str,
+    pub(super) show_rear_view: &'static str,
+    pub(super) show_net: &'static str,
+    pub(super) net_view: &'static str,
+    pub(super) exploded_view: &'static str,
+    pub(super) exploded_view_hint: &'static str,
+    pub(super) f_axis: &'static str,
+    pub(super) r_axis: &'static str,
+    pub(super) u_axis: &'static str,
+
+    pub(super) diagnostics: &'static str,
+    pub(super) show_stats: &'static str,
+    pub(super) show_log: &'static str,
+
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(super) debug: &'static str,
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(super) print_cube: &'static str,
+
+    pub(super) image: &'static str,
+    pub(super) image_scale: &'static str,
+    pub(super) save_as_image: &'static str,
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(super) copy_image: &'static str,
+
+    pub(super) statistics: &'static str,
+    pub(super) fps: &'static str,
+    pub(super) frame_time_mean: &'static str,
+    pub(super) frame_time_max: &'static str,
+    pub(super) instances: &'static str,
+    pub(super) last_instance_rebuild: &'static str,
+
+    pub(super) saved_state: &'static str,
+    pub(super) saved_state_hint: &'static str,
+    pub(super) reset_everything: &'static str,
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub(super) copy_share_link: &'static str,
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub(super) share_link_hint: &'static str,
+
+    pub(super) log: &'static str,
+    pub(super) verbosity: &'static str,
+    pub(super) clear_log: &'static str,
+
+    pub(super) renderer_restarted: &'static str,
+}
+
+const ENGLISH: Strings = Strings {
+    by_author: "By Mike Croall",
+    on_github: "on GitHub",
+    language: "Language",
+
+    initialise_cube: "Initialise Cube",
+    cube_size: |n| format!("{n}x{n} Cube"),
+    unreasonable_mode: "Unreasonable mode",
+    japanese_colours: "Use the Japanese colour scheme",
+    approx_memory: |mib| format!("Needs roughly {mib} MiB of memory"),
+    memory_warning: "Cubes this large may take a long time to create and could run out of memory",
+    apply: "Apply",
+
+    colours: "Colours",
+    palette: "Palette",
+    default_palette: "Default",
+    colour_blind_palette: "Colour-blind friendly",
+    custom_palette: "Custom",
+    blue_stickers: "Blue stickers",
+    green_stickers: "Green stickers",
+    orange_stickers: "Orange stickers",
+    red_stickers: "Red stickers",
+    white_stickers: "White stickers",
+    yellow_stickers: "Yellow stickers",
+    reset_colours: "Reset colours",
+
+    picture_cube: "Picture Cube",
+    picture_cube_hint: "Load an image onto a face to print it across the stickers that start there. The cube starts again as a super cube so every sticker remembers where it belongs",
+    picture_path: "Path to a PNG image",
+    load_picture: "Load",
+    clear_picture: "Clear",
+
+    control_cube: "Control Cube",
+    drag_to_rotate: "Click and drag directly on the cube to make a rotation",
+    drag_one_face: "You must only drag across one face of the cube",
+    drag_cancelled:
+        "Dragging to another face, diagonally, or for a very small distance will be cancelled",
+    drag_modifiers_hint: "Hold Shift while dragging to turn twice, or Ctrl to also turn every layer between the nearest face and the one dragged",
+    use_buttons: "Alternatively, use the buttons below",
+    keyboard_hint: "Or hover over a sticker and press the arrow keys to turn its row or column, which is easier than dragging on large cubes",
+    face_keys_hint: "Or press F, R, U, L, B or D to turn that face, holding Shift to turn it anticlockwise, or type a layer number first to turn that layer, such as 2 then R",
+    animation_style: "Animation style",
+    turn_style: "Turn",
+    snap_and_fade: "Snap and fade",
+    instant_deep_layers: "Instant for deep layers",
+    instant: "Instant",
+
+    lock_faces: "Lock Faces",
+    lock_faces_hint: "Locked faces cannot be turned, which helps when practising moves that must leave them alone",
+
+    apply_sequence_heading: "Apply Sequence",
+    sequence_hint: "Enter moves in standard notation, such as F R U' L2",
+    apply_sequence: "Apply sequence",
+    run_sequence: "Run step by step",
+    stop_sequence: "Stop",
+    step_error: |step, message| format!("Step {step}: {message}"),
+    moves: |n| match n {
+        1 => "1 move".to_string(),
+        _ => format!("{n} moves"),
+    },
+    invalid_tokens: |n| match n {
+        1 => "1 invalid token".to_string(),
+        _ => format!("{n} invalid tokens"),
+    },
+
+    known_transforms: "Known Transforms",
+    transforms_applied_in_full: "Each transform is applied in full before the cube is redrawn",
+    checkerboard: "Checkerboard",
+    checkerboard_corners: "Checkerboard corners",
+    cube_in_cube_in_cube: "Cube in cube in cube",
+    frames: "Frames",
+    side_stripes: "Side stripes",
+    stripes: "Stripes",
+    no_known_transforms: "There are no known transforms for a cube of this size",
+    unlock_to_apply: "Unlock every face to apply these",
+
+    shuffle_heading: "Shuffle",
+    shuffle_moves: "Random moves",
+    instant_shuffle: "Shuffle instantly instead of playing each move",
+    shuffle: "Shuffle",
+    shuffled: |n| format!("Shuffle of {n} moves"),
+
+    solve_heading: "Solve",
+    solve_hint: "Find a solution with the layer by layer method, then play it or step through it one move at a time",
+    solve: "Solve",
+    only_3x3_solvable: "Only 3x3 cubes can be solved",
+
+    playback_heading: "Playback",
+    playback_progress: |applied, total| format!("Move {applied} of {total}"),
+    step_back: "Step back",
+    play: "Play",
+    pause: "Pause",
+    step_forward: "Step forward",
+    jump_to_end: "Jump to end",
+    moves_per_second: "Moves per second",
+    easing: "Easing",
+    linear: "Linear",
+    ease_in_out: "Ease in and out",
+    bounce: "Bounce",
+
+    move_history: "Move History",
+    move_history_hint: "Click a move to rewind or fast forward the cube to just after it",
+    history_start: "Start",
+    undo: "Undo (Ctrl+Z)",
+    redo: "Redo (Ctrl+Y)",
+
+    control_camera: "Control Camera etc.",
+    camera_help: "The camera can be moved with a click and drag starting from the blank space around the cube, or by dragging from one face to any other face or empty space",
+    touch_help: "On a touch screen, drag with one finger to turn the cube, move two fingers together to move the camera, and pinch to zoom",
+    reset_camera: "Reset camera",
+    frame_cube: "Frame cube",
+    camera_presets: "Move the camera to a preset view",
+    front_right_up: "Front right up",
+    back_left_down: "Back left down",
+    isometric: "Isometric",
+    front_view: "Front",
+    back_view: "Back",
+    left_view: "Left",
+    right_view: "Right",
+    top_view: "Top",
+    bottom_view: "Bottom",
+    auto_frame: "Frame cube after resizing the window or cube",
+    show_axes: "Show axes",
+    show_rear_view: "Show the back of the cube in the corner",
+    show_net: "Show every face unfolded flat",
+    net_view: "Net",
+    exploded_view: "Exploded view",
+    exploded_view_hint: "Spread the pieces apart to see the stickers between them",
+    f_axis: "F is the blue axis",
+    r_axis: "R is the red axis",
+    u_axis: "U is the green axis",
+
+    diagnostics: "Diagnostics",
+    show_stats: "Show statistics",
+    show_log: "Show log",
+
+    debug: "Debug",
+    print_cube: "Print cube to terminal",
+
+    image: "Image",
+    image_scale: "Resolution multiplier",
+    save_as_image: "Save as image",
+    copy_image: "Copy to clipboard",
+
+    statistics: "Statistics",
+    fps: "FPS",
+    frame_time_mean: "Frame time (mean)",
+    frame_time_max: "Frame time (max)",
+    instances: "Instances",
+    last_instance_rebuild: "Last instance rebuild",
+
+    saved_state: "Saved State",
+    saved_state_hint: "The cube and settings are saved in this browser every few seconds",
+    reset_everything: "Reset everything",
+    copy_share_link: "Copy share link",
+    share_link_hint: "Puts this cube in the page's address and copies it, so opening the link shows the same cube",
+
+    log: "Log",
+    verbosity: "Verbosity",
+    clear_log: "Clear",
+
+    renderer_restarted: "Renderer restarted after a graphics error",
+};
+
+const FRENCH: Strings = Strings {
+    by_author: "Par Mike Croall",
+    on_github: "sur GitHub",
+    language: "Langue",
+
+    initialise_cube: "Initialiser le cube",
+    cube_size: |n| format!("Cube {n}x{n}"),
+    unreasonable_mode: "Mode déraisonnable",
+    japanese_colours: "Utiliser le schéma de couleurs japonais",
+    approx_memory: |mib| format!("Nécessite environ {mib} Mio de mémoire"),
+    memory_warning: "Un cube aussi grand peut être long à créer et risque d'épuiser la mémoire",
+    apply: "Appliquer",
+
+    colours: "Couleurs",
+    palette: "Palette",
+    default_palette: "Par défaut",
+    colour_blind_palette: "Adaptée au daltonisme",
+    custom_palette: "Personnalisée",
+    blue_stickers: "Autocollants bleus",
+    green_stickers: "Autocollants verts",
+    orange_stickers: "Autocollants orange",
+    red_stickers: "Autocollants rouges",
+    white_stickers: "Autocollants blancs",
+    yellow_stickers: "Autocollants jaunes",
+    reset_colours: "Réinitialiser les couleurs",
+
+    picture_cube: "Cube à images",
+    picture_cube_hint: "Chargez une image sur une face pour l'imprimer sur les autocollants qui y commencent. Le cube recommence en super cube afin que chaque autocollant se souvienne de sa place",
+    picture_path: "Chemin vers une image PNG",
+    load_picture: "Charger",
+    clear_picture: "Effacer",
+
+    control_cube: "Contrôler le cube",
+    drag_to_rotate: "Cliquez et faites glisser directement sur le cube pour effectuer une rotation",
+    drag_one_face: "Ne faites glisser que sur une seule face du cube",
+    drag_cancelled:
+        "Un glissement vers une autre face, en diagonale ou sur une très courte distance sera annulé",
+    drag_modifiers_hint: "Maintenez Maj en faisant glisser pour tourner deux fois, ou Ctrl pour tourner aussi toutes les couches entre la face la plus proche et celle glissée",
+    use_buttons: "Vous pouvez aussi utiliser les boutons ci-dessous",
+    keyboard_hint:
+        "Ou survolez une vignette et appuyez sur les flèches pour tourner sa rangée ou sa colonne, ce qui est plus simple que de glisser sur les grands cubes",
+    face_keys_hint:
+        "Ou appuyez sur F, R, U, L, B ou D pour tourner cette face, en maintenant Maj pour le sens inverse, ou saisissez d'abord un numéro de couche pour tourner cette couche, par exemple 2 puis R",
+    animation_style: "Style d'animation",
+    turn_style: "Rotation",
+    snap_and_fade: "Saut et fondu",
+    instant_deep_layers: "Instantané pour les couches profondes",
+    instant: "Instantané",
+
+    lock_faces: "Verrouiller des faces",
+    lock_faces_hint: "Les faces verrouillées ne peuvent pas être tournées, ce qui aide à s'entraîner aux mouvements qui doivent les laisser intactes",
+
+    apply_sequence_heading: "Appliquer une séquence",
+    sequence_hint: "Saisissez des mouvements en notation standard, par exemple F R U' L2",
+    apply_sequence: "Appliquer la séquence",
+    run_sequence: "Exécuter pas à pas",
+    stop_sequence: "Arrêter",
+    step_error: |step, message| format!("Étape {step} : {message}"),
+    moves: |n| match n {
+        0 | 1 => format!("{n} mouvement"),
+        _ => format!("{n} mouvements"),
+    },
+    invalid_tokens: |n| match n {
+        0 | 1 => format!("{n} symbole invalide"),
+        _ => format!("{n} symboles invalides"),
+    },
+
+    known_transforms: "Transformations connues",
+    transforms_applied_in_full:
+        "Chaque transformation est appliquée entièrement avant que le cube ne soit redessiné",
+    checkerboard: "Damier",
+    checkerboard_corners: "Coins en damier",
+    cube_in_cube_in_cube: "Cube dans un cube dans un cube",
+    frames: "Cadres",
+    side_stripes: "Rayures sur les côtés",
+    stripes: "Rayures",
+    no_known_transforms: "Aucune transformation connue pour un cube de cette taille",
+    unlock_to_apply: "Déverrouillez toutes les faces pour les appliquer",
+
+    shuffle_heading: "Mélanger",
+    shuffle_moves: "Mouvements aléatoires",
+    instant_shuffle: "Mélanger instantanément au lieu de jouer chaque mouvement",
+    shuffle: "Mélanger",
+    shuffled: |n| format!("Mélange de {n} mouvements"),
+
+    solve_heading: "Résoudre",
+    solve_hint: "Trouvez une solution avec la méthode couche par couche, puis lisez-la ou parcourez-la un mouvement à la fois",
+    solve: "Résoudre",
+    only_3x3_solvable: "Seuls les cubes 3x3 peuvent être résolus",
+
+    playback_heading: "Lecture",
+    playback_progress: |applied, total| format!("Mouvement {applied} sur {total}"),
+    step_back: "Reculer",
+    play: "Lire",
+    pause: "Pause",
+    step_forward: "Avancer",
+    jump_to_end: "Aller à la fin",
+    moves_per_second: "Mouvements par seconde",
+    easing: "Accélération",
+    linear: "Linéaire",
+    ease_in_out: "Douce",
+    bounce: "Rebond",
+
+    move_history: "Historique des mouvements",
+    move_history_hint: "Cliquez sur un mouvement pour ramener le cube juste après celui-ci",
+    history_start: "Début",
+    undo: "Annuler (Ctrl+Z)",
+    redo: "Rétablir (Ctrl+Y)",
+
+    control_camera: "Contrôler la caméra, etc.",
+    camera_help: "La caméra se déplace en cliquant et en faisant glisser depuis l'espace vide autour du cube, ou en faisant glisser d'une face vers une autre face ou vers l'espace vide",
+    touch_help: "Sur un écran tactile, faites glisser un doigt pour tourner le cube, déplacez deux doigts ensemble pour déplacer la caméra, et pincez pour zoomer",
+    reset_camera: "Réinitialiser la caméra",
+    frame_cube: "Cadrer le cube",
+    camera_presets: "Déplacer la caméra vers une vue prédéfinie",
+    front_right_up: "Avant droite haut",
+    back_left_down: "Arrière gauche bas",
+    isometric: "Isométrique",
+    front_view: "Avant",
+    back_view: "Arrière",
+    left_view: "Gauche",
+    right_view: "Droite",
+    top_view: "Dessus",
+    bottom_view: "Dessous",
+    auto_frame: "Cadrer le cube après avoir redimensionné la fenêtre ou le cube",
+    show_axes: "Afficher les axes",
+    show_rear_view: "Afficher l'arrière du cube dans le coin",
+    show_net: "Afficher toutes les faces dépliées à plat",
+    net_view: "Patron",
+    exploded_view: "Vue éclatée",
+    exploded_view_hint: "Écarter les pièces pour voir les autocollants entre elles",
+    f_axis: "F est l'axe bleu",
+    r_axis: "R est l'axe rouge",
+    u_axis: "U est l'axe vert",
+
+    diagnostics: "Diagnostics",
+    show_stats: "Afficher les statistiques",
+    show_log: "Afficher le journal",
+
+    debug: "Débogage",
+    print_cube: "Afficher le cube dans le terminal",
+
+    image: "Image",
+    image_scale: "Multiplicateur de résolution",
+    save_as_image: "Enregistrer comme image",
+    copy_image: "Copier dans le presse-papiers",
+
+    statistics: "Statistiques",
+    fps: "IPS",
+    frame_time_mean: "Temps par image (moyen)",
+    frame_time_max: "Temps par image (max)",
+    instances: "Instances",
+    last_instance_rebuild: "Dernière reconstruction des instances",
+
+    saved_state: "Sauvegarde",
+    saved_state_hint: "Le cube et les réglages sont enregistrés dans ce navigateur toutes les quelques secondes",
+    reset_everything: "Tout réinitialiser",
+    copy_share_link: "Copier le lien de partage",
+    share_link_hint: "Place ce cube dans l'adresse de la page et la copie, afin que le lien ouvre le même cube",
+
+    log: "Journal",
+    verbosity: "Verbosité",
+    clear_log: "Effacer",
+
+    renderer_restarted: "Rendu redémarré après une erreur graphique",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_every_language_has_a_distinct_name() {
+        let names = Language::ALL
+            .map(Language::name)
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(Language::ALL.len(), names.len());
+    }
+
+    #[test]
+    fn test_language_codes_round_trip() {
+        for language in Language::ALL {
+            assert_eq!(Some(language), Language::from_code(language.code()));
+        }
+        assert_eq!(None, Language::from_code("xx"));
+    }
+
+    #[test]
+    fn test_formatted_strings() {
+        let english = Language::English.strings();
+        let french = Language::French.strings();
+
+        assert_eq!("4x4 Cube", (english.cube_size)(4));
+        assert_eq!("Cube 4x4", (french.cube_size)(4));
+        assert_eq!("1 move", (english.moves)(1));
+        assert_eq!("0 moves", (english.moves)(0));
+        assert_eq!("0 mouvement", (french.moves)(0));
+        assert_eq!("2 symboles invalides", (french.invalid_tokens)(2));
+        assert_eq!("Step 2: oops", (english.step_error)(2, "oops"));
+        assert_eq!("Mouvement 3 sur 20", (french.playback_progress)(3, 20));
+    }
+}
@@ -0,0 +1,252 @@
+use rusty_puzzle_cube::cube::{face::Face, rotation::Rotation, Cube};
+use three_d::{Event, Key, Modifiers};
+use tracing::warn;
+
+use crate::gui::{
+    move_history::{Move, MoveHistory},
+    playback::Playback,
+};
+
+const MAX_LAYER_PREFIX: usize = 10_000;
+
+/// Turns faces from the keyboard, with F, R, U, L, B and D turning that face clockwise, or anticlockwise while Shift is held.
+///
+/// Typing a number first turns that layer instead of the outermost one, so `2` then `R` turns the layer just inside the Right face.
+///
+/// Turns are made through the playback, so they are animated, and queued while an earlier turn is still animating.
+///
+/// Ctrl+Z undoes the latest move from the move history, and Ctrl+Y or Ctrl+Shift+Z makes it again, once any queued turns have been made.
+pub(super) struct KeyboardControl {
+    layer_prefix: Option<usize>,
+}
+
+impl KeyboardControl {
+    pub(super) fn new() -> Self {
+        Self { layer_prefix: None }
+    }
+
+    /// Returns whether the cube was updated.
+    pub(super) fn handle_events(
+        &mut self,
+        events: &mut [Event],
+        cube: &mut Cube,
+        history: &mut MoveHistory,
+        playback: &mut Playback,
+        now_ms: f64,
+    ) -> bool {
+        let mut updated_cube = false;
+        for event in events.iter_mut() {
+            let Event::KeyPress {
+                kind,
+                modifiers,
+                handled,
+            } = event
+            else {
+                continue;
+            };
+            if *handled || modifiers.alt {
+                continue;
+            }
+            if modifiers.ctrl || modifiers.command {
+                let undo = match (*kind, modifiers.shift) {
+                    (Key::Z, false) => true,
+                    (Key::Z, true) | (Key::Y, _) => false,
+                    _ => continue,
+                };
+                updated_cube |= if undo {
+                    playback.undo(cube, history)
+                } else {
+                    playback.redo(cube, history)
+                };
+                *handled = true;
+                continue;
+            }
+            if let Some(digit) = key_digit(*kind) {
+                self.layer_prefix = Some(push_digit(self.layer_prefix, digit));
+                *handled = true;
+            } else if *kind == Key::Escape {
+                self.layer_prefix = None;
+            } else if let Some(rotation) = key_rotation(*kind, *modifiers) {
+                let layer = self.layer_prefix.take().unwrap_or(1);
+                *handled = true;
+                if layer == 0 || cube.side_length() < layer {
+                    warn!(
+                        "Layer {layer} is not within a {0}x{0} cube",
+                        cube.side_length()
+                    );
+                    continue;
+                }
+                updated_cube |= playback.make_user_move(
+                    Move::Rotation(rotation.at_layer(layer - 1)),
+                    cube,
+                    history,
+                    now_ms,
+                );
+            }
+        }
+        updated_cube
+    }
+}
+
+fn key_rotation(key: Key, modifiers: Modifiers) -> Option<Rotation> {
+    let face = match key {
+        Key::F => Face::Front,
+        Key::R => Face::Right,
+        Key::U => Face::Up,
+        Key::L => Face::Left,
+        Key::B => Face::Back,
+        Key::D => Face::Down,
+        _ => return None,
+    };
+    Some(if modifiers.shift {
+        Rotation::anticlockwise(face)
+    } else {
+        Rotation::clockwise(face)
+    })
+}
+
+fn key_digit(key: Key) -> Option<usize> {
+    [
+        Key::Num0,
+        Key::Num1,
+        Key::Num2,
+        Key::Num3,
+        Key::Num4,
+        Key::Num5,
+        Key::Num6,
+        Key::Num7,
+        Key::Num8,
+        Key::Num9,
+    ]
+    .iter()
+    .position(|&digit| digit == key)
+}
+
+fn push_digit(prefix: Option<usize>, digit: usize) -> usize {
+    (prefix.unwrap_or(0) * 10 + digit).min(MAX_LAYER_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn press(kind: Key, shift: bool) -> Event {
+        Event::KeyPress {
+            kind,
+            modifiers: Modifiers {
+                shift,
+                ..Modifiers::default()
+            },
+            handled: false,
+        }
+    }
+
+    #[test]
+    fn test_shift_turns_anticlockwise() {
+        assert_eq!(
+            Some(Rotation::clockwise(Face::Back)),
+            key_rotation(Key::B, Modifiers::default())
+        );
+        assert_eq!(
+            Some(Rotation::anticlockwise(Face::Down)),
+            key_rotation(
+                Key::D,
+                Modifiers {
+                    shift: true,
+                    ..Modifiers::default()
+                }
+            )
+        );
+        assert_eq!(None, key_rotation(Key::X, Modifiers::default()));
+    }
+
+    #[test]
+    fn test_digits_build_a_layer_prefix() {
+        assert_eq!(Some(7), key_digit(Key::Num7));
+        assert_eq!(None, key_digit(Key::A));
+        assert_eq!(12, push_digit(Some(1), 2));
+        assert_eq!(MAX_LAYER_PREFIX, push_digit(Some(MAX_LAYER_PREFIX), 9));
+    }
+
+    #[test]
+    fn test_number_prefix_turns_inner_layer() {
+        let mut cube = Cube::create(4);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        let mut control = KeyboardControl::new();
+        let mut events = [
+            press(Key::R, false),
+            press(Key::Num2, false),
+            press(Key::U, true),
+        ];
+
+        assert!(control.handle_events(&mut events, &mut cube, &mut history, &mut playback, 0.));
+        assert!(playback.finish_pending_moves(&mut cube, &mut history));
+
+        let mut expected = Cube::create(4);
+        expected.rotate(Rotation::clockwise(Face::Right));
+        expected.rotate(Rotation::anticlockwise(Face::Up).at_layer(1));
+        assert_eq!(expected, cube);
+        assert_eq!(None, control.layer_prefix);
+    }
+
+    #[test]
+    fn test_ctrl_z_undoes_and_ctrl_y_redoes() {
+        let ctrl = |kind, shift| Event::KeyPress {
+            kind,
+            modifiers: Modifiers {
+                ctrl: true,
+                shift,
+                ..Modifiers::default()
+            },
+            handled: false,
+        };
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        let mut control = KeyboardControl::new();
+
+        let mut events = [
+            press(Key::F, false),
+            press(Key::U, false),
+            ctrl(Key::Z, false),
+        ];
+        assert!(control.handle_events(&mut events, &mut cube, &mut history, &mut playback, 0.));
+        let mut expected = Cube::create(3);
+        expected.rotate(Rotation::clockwise(Face::Front));
+        assert_eq!(expected, cube);
+
+        let mut events = [ctrl(Key::Z, false), ctrl(Key::Y, false), ctrl(Key::Z, true)];
+        assert!(control.handle_events(&mut events, &mut cube, &mut history, &mut playback, 0.));
+        expected.rotate(Rotation::clockwise(Face::Up));
+        assert_eq!(expected, cube);
+        assert!(!control.handle_events(
+            &mut [ctrl(Key::Y, false)],
+            &mut cube,
+            &mut history,
+            &mut playback,
+            0.
+        ));
+    }
+
+    #[test]
+    fn test_handled_and_out_of_range_presses_do_not_turn() {
+        let mut cube = Cube::create(2);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        let mut control = KeyboardControl::new();
+        let mut events = [
+            Event::KeyPress {
+                kind: Key::F,
+                modifiers: Modifiers::default(),
+                handled: true,
+            },
+            press(Key::Num3, false),
+            press(Key::F, false),
+        ];
+
+        assert!(!control.handle_events(&mut events, &mut cube, &mut history, &mut playback, 0.));
+        assert_eq!(Cube::create(2), cube);
+    }
+}
@@ -0,0 +1,66 @@
+use three_d::egui::{Color32, ComboBox, Context, Id, RichText, ScrollArea, Window};
+use tracing::{level_filters::LevelFilter, Level};
+
+use super::i18n::Strings;
+use crate::logging::Logging;
+
+const LEVEL_FILTERS: [LevelFilter; 6] = [
+    LevelFilter::OFF,
+    LevelFilter::ERROR,
+    LevelFilter::WARN,
+    LevelFilter::INFO,
+    LevelFilter::DEBUG,
+    LevelFilter::TRACE,
+];
+
+pub(super) fn show(gui_ctx: &Context, strings: &Strings, logging: &Logging, open: &mut bool) {
+    Window::new(strings.log)
+        .id(Id::new("log"))
+        .open(open)
+        .default_size([500., 300.])
+        .show(gui_ctx, |ui| {
+            ui.horizontal(|ui| {
+                let current = logging.level();
+                ComboBox::from_label(strings.verbosity)
+                    .selected_text(current.to_string())
+                    .show_ui(ui, |ui| {
+                        for level in LEVEL_FILTERS {
+                            if ui
+                                .selectable_label(level == current, level.to_string())
+                                .clicked()
+                            {
+                                logging.set_level(level);
+                            }
+                        }
+                    });
+                if ui.button(strings.clear_log).clicked() {
+                    logging.buffer().clear();
+                }
+            });
+            ui.separator();
+            ScrollArea::vertical()
+                .auto_shrink(false)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for line in logging.buffer().lines() {
+                        ui.label(
+                            RichText::new(format!(
+                                "{:>5} {}: {}",
+                                line.level, line.target, line.message
+                            ))
+                            .monospace()
+                            .color(level_colour(line.level)),
+                        );
+                    }
+                });
+        });
+}
+
+fn level_colour(level: Level) -> Color32 {
+    match level {
+        Level::ERROR => Color32::from_rgb(255, 80, 80),
+        Level::WARN => Color32::from_rgb(255, 180, 60),
+        Level::INFO => Color32::LIGHT_GRAY,
+        Level::DEBUG | Level::TRACE => Color32::GRAY,
+    }
+}
@@ -0,0 +1,79 @@
+use std::mem::size_of;
+
+use rusty_puzzle_cube::cube::Cube;
+use three_d::{Mat4, Srgba};
+
+const BYTES_PER_MEBIBYTE: usize = 1024 * 1024;
+
+pub(super) fn approx_bytes_for(side_length: usize) -> usize {
+    let stickers = side_length.saturating_mul(side_length).saturating_mul(6);
+    let instance_bytes = stickers.saturating_mul(size_of::<Mat4>() + size_of::<Srgba>());
+    Cube::approx_memory_bytes_for(side_length).saturating_add(instance_bytes)
+}
+
+pub(super) fn mebibytes(bytes: usize) -> usize {
+    bytes.div_ceil(BYTES_PER_MEBIBYTE)
+}
+
+pub(super) fn max_side_length_within(budget_bytes: usize, min: usize, max: usize) -> usize {
+    let (mut low, mut high) = (min, max);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if approx_bytes_for(mid) <= budget_bytes {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn available_bytes() -> Option<usize> {
+    use std::sync::OnceLock;
+
+    static AVAILABLE: OnceLock<usize> = OnceLock::new();
+    let available = *AVAILABLE.get_or_init(|| {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+        usize::try_from(system.available_memory()).unwrap_or(usize::MAX)
+    });
+    (available > 0).then_some(available)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) fn available_bytes() -> Option<usize> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_approx_bytes_grows_with_side_length() {
+        assert!(approx_bytes_for(3) < approx_bytes_for(4));
+        assert!(approx_bytes_for(100) < approx_bytes_for(2000));
+        assert!(BYTES_PER_MEBIBYTE * 1000 < approx_bytes_for(2000));
+    }
+
+    #[test]
+    fn test_mebibytes_rounds_up() {
+        assert_eq!(0, mebibytes(0));
+        assert_eq!(1, mebibytes(1));
+        assert_eq!(1, mebibytes(BYTES_PER_MEBIBYTE));
+        assert_eq!(2, mebibytes(BYTES_PER_MEBIBYTE + 1));
+    }
+
+    #[test]
+    fn test_max_side_length_within() {
+        let budget = approx_bytes_for(500);
+
+        assert_eq!(500, max_side_length_within(budget, 100, 2000));
+        assert_eq!(500, max_side_length_within(budget + 1, 100, 2000));
+        assert_eq!(499, max_side_length_within(budget - 1, 100, 2000));
+        assert_eq!(100, max_side_length_within(0, 100, 2000));
+        assert_eq!(2000, max_side_length_within(usize::MAX, 100, 2000));
+    }
+}
@@ -1,26 +1,36 @@
 use std::f32::consts::PI;
 
-use rusty_puzzle_cube::cube::{face::Face, Cube};
+use rusty_puzzle_cube::cube::{face::Face, rotation::Rotation, Cube};
 use three_d::{
-    pick, radians, Camera, ColorMaterial, Context, Event, Gm, InnerSpace, Mesh, MouseButton,
-    OrbitControl, Rad, Transform, Vec3, Vector3,
+    pick, radians, vec2, Camera, ColorMaterial, Context, Event, Gm, InnerSpace, Key, Mesh,
+    Modifiers, MouseButton, OrbitControl, PhysicalPoint, Rad, Transform, Vec2, Vec3, Vector3,
 };
 use tracing::{error, warn};
 
-use crate::gui::transforms::move_face_into_place;
+use crate::gui::{
+    move_history::{Move, MoveHistory},
+    playback::Playback,
+    transforms::move_face_into_place,
+};
 
 const MOVE_TOO_SMALL_THRESHOLD: f32 = 0.3;
 const DIAGONAL_MOVE_THRESHOLD: Rad<f32> = radians(0.125 * PI);
 const EPSILON: f32 = 0.0001;
+const KEY_MOVE_DISTANCE: f32 = 0.5;
+const PROJECTION_STEP: f32 = 0.1;
 
 pub(super) struct MouseControl {
     orbit: OrbitControl,
     drag: Option<FaceDrag>,
+    cursor: Option<PhysicalPoint>,
+    highlight: Vec<Rotation>,
 }
 
 pub(super) struct MouseControlOutput {
     pub(super) redraw: bool,
     pub(super) updated_cube: bool,
+    /// The rotations the drag in progress would make if released where the cursor is now.
+    pub(super) highlight: Vec<Rotation>,
 }
 
 struct FaceDrag {
@@ -28,37 +38,11 @@ struct FaceDrag {
     face: Face,
 }
 
-#[allow(dead_code)]
-enum DecidedMove {
-    WholeFace {
-        face: Face,
-        clockwise: bool,
-    },
-    InnerRow {
-        face: Face,
-        row: usize,
-        toward_positive: bool,
-    },
-    InnerCol {
-        face: Face,
-        col: usize,
-        toward_positive: bool,
-    },
-}
-
-impl DecidedMove {
-    fn apply(self, cube: &mut Cube) {
-        match self {
-            DecidedMove::WholeFace {
-                face,
-                clockwise: true,
-            } => cube.rotate_face_90_degrees_clockwise(face),
-            DecidedMove::WholeFace {
-                face,
-                clockwise: false,
-            } => cube.rotate_face_90_degrees_anticlockwise(face),
-            _ => warn!("Moves that rotate only inner rows/cols are not yet supported"),
-        }
+fn to_move(mut rotations: Vec<Rotation>) -> Move {
+    if rotations.len() == 1 {
+        Move::Rotation(rotations.remove(0))
+    } else {
+        Move::Rotations(rotations)
     }
 }
 
@@ -67,9 +51,18 @@ impl MouseControl {
         Self {
             orbit: OrbitControl::new(target, min_distance, max_distance),
             drag: None,
+            cursor: None,
+            highlight: vec![],
         }
     }
 
+    /// Forget the drag in progress, so releasing it makes no move.
+    pub(super) fn cancel_drag(&mut self) {
+        self.drag = None;
+        self.highlight.clear();
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn handle_events(
         &mut self,
         ctx: &Context,
@@ -78,9 +71,17 @@ impl MouseControl {
         camera: &mut Camera,
         events: &mut [Event],
         cube: &mut Cube,
+        history: &mut MoveHistory,
+        playback: &mut Playback,
+        now_ms: f64,
     ) -> MouseControlOutput {
         let mut updated_cube = false;
         for event in events.iter_mut() {
+            match event {
+                Event::MouseMotion { position, .. } => self.cursor = Some(*position),
+                Event::MouseLeave => self.cursor = None,
+                _ => {}
+            }
             match event {
                 Event::MousePress {
                     button: MouseButton::Left,
@@ -95,15 +96,17 @@ impl MouseControl {
                         continue;
                     };
                     self.drag = Some(FaceDrag { start_pick, face });
+                    self.highlight.clear();
                     *handled = true;
                 }
                 Event::MouseMotion {
                     button: Some(MouseButton::Left),
                     position,
+                    modifiers,
                     handled,
                     ..
                 } => {
-                    let Some(FaceDrag { face, .. }) = self.drag else {
+                    let Some(FaceDrag { start_pick, face }) = self.drag else {
                         continue;
                     };
                     let Some(pick) = pick(ctx, camera, *position, inner_cube) else {
@@ -112,8 +115,13 @@ impl MouseControl {
                     let Some(new_face) = pick_to_face(pick) else {
                         continue;
                     };
-                    if face != new_face {
+                    if face == new_face {
+                        self.highlight = drag_to_move(side_length, start_pick, pick, face)
+                            .map(|rotation| with_modifiers(rotation, side_length, *modifiers))
+                            .unwrap_or_default();
+                    } else {
                         self.drag = None;
+                        self.highlight.clear();
                         warn!("Dragged from face {face:?} to {new_face:?}, skipping...");
                     }
                     *handled = true;
@@ -121,20 +129,51 @@ impl MouseControl {
                 Event::MouseRelease {
                     button: MouseButton::Left,
                     position,
+                    modifiers,
                     handled,
                     ..
                 } => {
+                    self.highlight.clear();
                     let Some(FaceDrag { start_pick, face }) = &self.drag else {
                         continue;
                     };
                     let Some(end_pick) = pick(ctx, camera, *position, inner_cube) else {
                         continue;
                     };
-                    if let Some(decided_move) =
-                        picks_to_move(side_length, *start_pick, end_pick, *face)
+                    if let Some(rotation) = picks_to_move(side_length, *start_pick, end_pick, *face)
                     {
-                        decided_move.apply(cube);
-                        updated_cube = true;
+                        let rotations = with_modifiers(rotation, side_length, *modifiers);
+                        updated_cube |=
+                            playback.make_user_move(to_move(rotations), cube, history, now_ms);
+                        *handled = true;
+                    };
+                }
+                Event::KeyPress { kind, handled, .. } => {
+                    if *handled {
+                        continue;
+                    }
+                    let Some(screen_direction) = arrow_key_direction(*kind) else {
+                        continue;
+                    };
+                    let Some(position) = self.cursor else {
+                        continue;
+                    };
+                    let Some(start_pick) = pick(ctx, camera, position, inner_cube) else {
+                        continue;
+                    };
+                    let Some(face) = pick_to_face(start_pick) else {
+                        continue;
+                    };
+                    let end_pick = start_pick
+                        + direction_on_face(camera, start_pick, face, screen_direction)
+                            * KEY_MOVE_DISTANCE;
+                    if let Some(rotation) = picks_to_move(side_length, start_pick, end_pick, face) {
+                        updated_cube |= playback.make_user_move(
+                            Move::Rotation(rotation),
+                            cube,
+                            history,
+                            now_ms,
+                        );
                         *handled = true;
                     };
                 }
@@ -144,6 +183,7 @@ impl MouseControl {
 
         MouseControlOutput {
             updated_cube,
+            highlight: self.highlight.clone(),
             redraw: updated_cube || self.orbit.handle_events(camera, events),
         }
     }
@@ -168,44 +208,133 @@ fn pick_to_face(pick: Vector3<f32>) -> Option<Face> {
     }
 }
 
+fn picks_to_move(
+    side_length: usize,
+    start_pick: Vector3<f32>,
+    end_pick: Vector3<f32>,
+    dragged_face: Face,
+) -> Option<Rotation> {
+    drag_to_move(side_length, start_pick, end_pick, dragged_face)
+        .map_err(|e| warn!("{}, skipping...", e))
+        .ok()
+}
+
+/// Works out the move for a drag without logging why a drag makes no move, as this is also checked throughout every drag.
 #[allow(
     clippy::cast_precision_loss,
     clippy::cast_possible_truncation,
     clippy::cast_sign_loss
 )]
-fn picks_to_move(
+fn drag_to_move(
     side_length: usize,
     start_pick: Vector3<f32>,
     end_pick: Vector3<f32>,
     dragged_face: Face,
-) -> Option<DecidedMove> {
+) -> Result<Rotation, &'static str> {
     let (start_pick, end_pick) = unrotate_picks(start_pick, end_pick, dragged_face);
     let (move_along_x, toward_positive) = validate_straight_dir(start_pick, end_pick)?;
 
-    let (face, clockwise) = if move_along_x {
-        let row_0_to_1 = (start_pick.y + 1.) / 2.;
-        let row = (row_0_to_1 * side_length as f32) as usize;
-        if row != 0 && row != side_length - 1 {
-            return Some(DecidedMove::InnerRow {
-                face: dragged_face,
-                row,
-                toward_positive,
-            });
-        }
-        translate_horizontal_drag(row, dragged_face, toward_positive)
+    let (face, clockwise, index) = if move_along_x {
+        let row = pick_to_index(start_pick.y, side_length);
+        let (face, clockwise) = translate_horizontal_drag(row, dragged_face, toward_positive);
+        (face, clockwise, row)
+    } else {
+        let col = pick_to_index(start_pick.x, side_length);
+        let (face, clockwise) = translate_vertical_drag(col, dragged_face, toward_positive);
+        (face, clockwise, col)
+    };
+    // Index 0 turns the face on that side, and every other index counts back from the face on the opposite side
+    let layer = if index == 0 {
+        0
     } else {
-        let col_0_to_1 = (start_pick.x + 1.) / 2.;
-        let col = (col_0_to_1 * side_length as f32) as usize;
-        if col != 0 && col != side_length - 1 {
-            return Some(DecidedMove::InnerCol {
-                face: dragged_face,
-                col,
-                toward_positive,
-            });
+        side_length - 1 - index
+    };
+    let rotation = Rotation::clockwise(face).at_layer(layer);
+    Ok(if clockwise {
+        rotation
+    } else {
+        rotation.inverse()
+    })
+}
+
+/// Holding Shift while dragging turns twice, and holding Ctrl (or Command) turns every layer from the nearest face up to the dragged one together, as a wide move.
+fn with_modifiers(rotation: Rotation, side_length: usize, modifiers: Modifiers) -> Vec<Rotation> {
+    let turned = if modifiers.ctrl || modifiers.command {
+        wide_turn(rotation, side_length)
+    } else {
+        vec![rotation]
+    };
+    if modifiers.shift {
+        turned.repeat(2)
+    } else {
+        turned
+    }
+}
+
+fn wide_turn(rotation: Rotation, side_length: usize) -> Vec<Rotation> {
+    let layer_from_opposite = side_length - 1 - rotation.layer;
+    let rotation = if layer_from_opposite < rotation.layer {
+        Rotation {
+            relative_to: rotation.relative_to.opposite(),
+            direction: rotation.direction.opposite(),
+            layer: layer_from_opposite,
         }
-        translate_vertical_drag(col, dragged_face, toward_positive)
+    } else {
+        rotation
+    };
+    (0..=rotation.layer)
+        .map(|layer| rotation.at_layer(layer))
+        .collect()
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn pick_to_index(unrotated_coordinate: f32, side_length: usize) -> usize {
+    let coordinate_0_to_1 = (unrotated_coordinate + 1.) / 2.;
+    ((coordinate_0_to_1 * side_length as f32) as usize).min(side_length - 1)
+}
+
+fn arrow_key_direction(key: Key) -> Option<Vec2> {
+    match key {
+        Key::ArrowUp => Some(vec2(0., 1.)),
+        Key::ArrowDown => Some(vec2(0., -1.)),
+        Key::ArrowRight => Some(vec2(1., 0.)),
+        Key::ArrowLeft => Some(vec2(-1., 0.)),
+        _ => None,
+    }
+}
+
+/// The direction along the face, out of its rows and columns, that appears on screen closest to `screen_direction`.
+fn direction_on_face(
+    camera: &Camera,
+    pick: Vector3<f32>,
+    face: Face,
+    screen_direction: Vec2,
+) -> Vector3<f32> {
+    let pick_on_screen = camera.pixel_at_position(pick);
+    let alignment = |direction: Vector3<f32>| {
+        let moved_on_screen = camera.pixel_at_position(pick + direction * PROJECTION_STEP);
+        vec2(
+            moved_on_screen.x - pick_on_screen.x,
+            moved_on_screen.y - pick_on_screen.y,
+        )
+        .normalize()
+        .dot(screen_direction)
     };
-    Some(DecidedMove::WholeFace { face, clockwise })
+    let face_to_world = move_face_into_place(face);
+    [
+        Vector3::unit_x(),
+        -Vector3::unit_x(),
+        Vector3::unit_y(),
+        -Vector3::unit_y(),
+    ]
+    .map(|direction| face_to_world.transform_vector(direction))
+    .into_iter()
+    .max_by(|a, b| alignment(*a).total_cmp(&alignment(*b)))
+    .expect("There are always four directions along a face")
 }
 
 fn unrotate_picks(
@@ -224,11 +353,10 @@ fn unrotate_picks(
 fn validate_straight_dir(
     unrotated_start_pick: Vector3<f32>,
     unrotated_end_pick: Vector3<f32>,
-) -> Option<(bool, bool)> {
+) -> Result<(bool, bool), &'static str> {
     let displacement = unrotated_end_pick - unrotated_start_pick;
     if displacement.magnitude() < MOVE_TOO_SMALL_THRESHOLD {
-        warn!("Move was too small, skipping...");
-        return None;
+        return Err("Move was too small");
     }
 
     let angle_to_x = displacement.angle(Vector3::unit_x()).0.abs();
@@ -240,8 +368,7 @@ fn validate_straight_dir(
     angles.sort_by(|a, b| a.partial_cmp(b).expect("No NaNs here"));
 
     if (angles[0] - angles[1]).abs() < DIAGONAL_MOVE_THRESHOLD.0 {
-        warn!("Move was diagonal, skipping...");
-        return None;
+        return Err("Move was diagonal");
     }
 
     let smallest = angles[0];
@@ -250,7 +377,7 @@ fn validate_straight_dir(
     let positive_vertical = (smallest - angle_to_y).abs() < EPSILON;
     let move_along_x = positive_horizontal || negative_horizontal;
     let toward_positive = positive_horizontal || positive_vertical;
-    Some((move_along_x, toward_positive))
+    Ok((move_along_x, toward_positive))
 }
 
 fn translate_vertical_drag(col: usize, dragged_face: Face, toward_positive: bool) -> (Face, bool) {
@@ -301,5 +428,95 @@ fn translate_horizontal_drag(
 
 #[cfg(test)]
 mod tests {
-    // todo write tests to keep it working!
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use three_d::vec3;
+
+    #[test]
+    fn test_drag_along_outer_row_turns_face() {
+        assert_eq!(
+            Some(Rotation::clockwise(Face::Down)),
+            picks_to_move(5, vec3(-0.5, -0.9, 1.), vec3(0.5, -0.9, 1.), Face::Front)
+        );
+    }
+
+    #[test]
+    fn test_drag_along_inner_row_turns_layer() {
+        assert_eq!(
+            Some(Rotation::anticlockwise(Face::Up).at_layer(3)),
+            picks_to_move(5, vec3(-0.5, -0.4, 1.), vec3(0.5, -0.4, 1.), Face::Front)
+        );
+        assert_eq!(
+            Some(Rotation::clockwise(Face::Right).at_layer(2)),
+            picks_to_move(5, vec3(0., -0.5, 1.), vec3(0., 0.5, 1.), Face::Front)
+        );
+    }
+
+    #[test]
+    fn test_short_or_diagonal_drags_make_no_move() {
+        assert_eq!(
+            Err("Move was too small"),
+            drag_to_move(3, vec3(0., 0., 1.), vec3(0.1, 0., 1.), Face::Front)
+        );
+        assert_eq!(
+            Err("Move was diagonal"),
+            drag_to_move(3, vec3(-0.5, -0.5, 1.), vec3(0.5, 0.5, 1.), Face::Front)
+        );
+    }
+
+    #[test]
+    fn test_shift_drag_turns_twice() {
+        let shift = Modifiers {
+            shift: true,
+            ..Modifiers::default()
+        };
+
+        assert_eq!(
+            vec![Rotation::clockwise(Face::Down); 2],
+            with_modifiers(Rotation::clockwise(Face::Down), 3, shift)
+        );
+    }
+
+    #[test]
+    fn test_ctrl_drag_turns_from_nearest_face() {
+        let ctrl = Modifiers {
+            ctrl: true,
+            ..Modifiers::default()
+        };
+        let right = Rotation::clockwise(Face::Right);
+
+        assert_eq!(vec![right], with_modifiers(right, 5, ctrl));
+        assert_eq!(
+            vec![right, right.at_layer(1), right.at_layer(2)],
+            with_modifiers(right.at_layer(2), 5, ctrl)
+        );
+        assert_eq!(
+            vec![
+                Rotation::anticlockwise(Face::Left),
+                Rotation::anticlockwise(Face::Left).at_layer(1)
+            ],
+            with_modifiers(right.at_layer(3), 5, ctrl)
+        );
+    }
+
+    #[test]
+    fn test_shift_and_ctrl_drag_makes_wide_double_turn() {
+        let both = Modifiers {
+            shift: true,
+            command: true,
+            ..Modifiers::default()
+        };
+        let up = Rotation::anticlockwise(Face::Up);
+
+        assert_eq!(
+            vec![up, up.at_layer(1), up, up.at_layer(1)],
+            with_modifiers(up.at_layer(1), 4, both)
+        );
+    }
+
+    #[test]
+    fn test_pick_on_far_edge_is_last_index() {
+        assert_eq!(4, pick_to_index(1., 5));
+        assert_eq!(0, pick_to_index(-1., 5));
+    }
 }
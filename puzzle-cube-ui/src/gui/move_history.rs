@@ -0,0 +1,387 @@
+use rusty_puzzle_cube::{
+    cube::{rotation::Rotation, Cube},
+    known_transforms::KnownTransform,
+    notation::{format_sequence, perform_3x3_sequence},
+};
+use tracing::error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Move {
+    Rotation(Rotation),
+    /// Several rotations made by one gesture, such as a double or wide turn, which are undone together.
+    Rotations(Vec<Rotation>),
+    Step(String),
+    Transform(KnownTransform),
+    /// Moves made by one larger operation, such as a shuffle or a pasted sequence, which are undone together and shown as one entry with the given label.
+    Group {
+        label: String,
+        moves: Vec<Move>,
+    },
+}
+
+impl Move {
+    fn apply_to(&self, cube: &mut Cube) -> Result<(), String> {
+        match self {
+            Move::Rotation(rotation) => {
+                check_turnable(cube, *rotation)?;
+                cube.try_rotate(*rotation)
+            }
+            Move::Rotations(rotations) => {
+                for rotation in rotations {
+                    check_turnable(cube, *rotation)?;
+                }
+                for rotation in rotations {
+                    cube.rotate(*rotation);
+                }
+                Ok(())
+            }
+            Move::Step(step) => perform_3x3_sequence(step, cube),
+            Move::Transform(transform) => transform.apply_to(cube),
+            Move::Group { moves, .. } => {
+                let mut grouped = cube.clone();
+                for made in moves {
+                    made.apply_to(&mut grouped)?;
+                }
+                *cube = grouped;
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the notation for a rotation or step, the given name for a known transform, and the label of a group.
+    pub(super) fn label(&self, transform_name: impl Fn(KnownTransform) -> &'static str) -> String {
+        match self {
+            Move::Rotation(rotation) => format_sequence(&[*rotation]),
+            Move::Rotations(rotations) => format_sequence(rotations),
+            Move::Step(step) => step.clone(),
+            Move::Transform(transform) => transform_name(*transform).to_string(),
+            Move::Group { label, .. } => label.clone(),
+        }
+    }
+}
+
+/// Rotations are kept as they were made, so one made on a cube of another size may name a layer this cube does not have.
+fn check_turnable(cube: &Cube, rotation: Rotation) -> Result<(), String> {
+    let side_length = cube.side_length();
+    if side_length <= rotation.layer {
+        return Err(format!(
+            "Layer {} is not within a {side_length}x{side_length} cube",
+            rotation.layer + 1
+        ));
+    }
+    cube.check_unlocked(rotation)
+}
+
+/// Every move made since the cube was created, so the cube can be rewound to any earlier point by replaying from the start.
+pub(super) struct MoveHistory {
+    start: Cube,
+    moves: Vec<Move>,
+    position: usize,
+}
+
+impl MoveHistory {
+    pub(super) fn new(cube: &Cube) -> Self {
+        Self {
+            start: cube.clone(),
+            moves: vec![],
+            position: 0,
+        }
+    }
+
+    /// Forget every move, starting again from the cube as it is now.
+    pub(super) fn reset(&mut self, cube: &Cube) {
+        *self = Self::new(cube);
+    }
+
+    pub(super) fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Returns how many of the moves have been made to reach the cube as it is now, the rest having been rewound.
+    pub(super) fn position(&self) -> usize {
+        self.position
+    }
+
+    pub(super) fn rotate(&mut self, cube: &mut Cube, rotation: Rotation) -> Result<(), String> {
+        self.make(cube, Move::Rotation(rotation))
+    }
+
+    pub(super) fn perform_step(&mut self, cube: &mut Cube, step: &str) -> Result<(), String> {
+        self.make(cube, Move::Step(step.trim().to_string()))
+    }
+
+    pub(super) fn apply_transform(
+        &mut self,
+        cube: &mut Cube,
+        transform: KnownTransform,
+    ) -> Result<(), String> {
+        self.make(cube, Move::Transform(transform))
+    }
+
+    /// Returns whether there was a move to undo.
+    pub(super) fn undo(&mut self, cube: &mut Cube) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        self.jump_to(self.position - 1, cube);
+        true
+    }
+
+    /// Returns whether there was an undone move to make again.
+    pub(super) fn redo(&mut self, cube: &mut Cube) -> bool {
+        if self.position == self.moves.len() {
+            return false;
+        }
+        self.jump_to(self.position + 1, cube);
+        true
+    }
+
+    /// Rebuild the cube as it was after the first `position` moves, keeping the later moves so they can be jumped forward to again.
+    pub(super) fn jump_to(&mut self, position: usize, cube: &mut Cube) {
+        let mut replayed = self.start.clone();
+        replayed.unlock_all();
+        for made in &self.moves[..position] {
+            if let Err(e) = made.apply_to(&mut replayed) {
+                error!("Could not replay move history: {}", e);
+                return;
+            }
+        }
+        for &(face, layer) in cube.locked_layers() {
+            replayed.lock_layer(face, layer);
+        }
+        *cube = replayed;
+        self.position = position;
+    }
+
+    pub(super) fn make(&mut self, cube: &mut Cube, made: Move) -> Result<(), String> {
+        made.apply_to(cube)?;
+        self.record(made);
+        Ok(())
+    }
+
+    /// Add a move that has already been made to the cube.
+    pub(super) fn record(&mut self, made: Move) {
+        self.moves.truncate(self.position);
+        self.moves.push(made);
+        self.position += 1;
+    }
+
+    /// Replace the last `count` moves made with one group of them, so they are undone together.
+    pub(super) fn group_last(&mut self, count: usize, label: String) {
+        let start = self.position.saturating_sub(count);
+        let moves = self.moves.drain(start..self.position).collect();
+        self.moves.insert(start, Move::Group { label, moves });
+        self.position = start + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rusty_puzzle_cube::cube::face::Face;
+
+    #[test]
+    fn test_jump_rewinds_and_returns() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        history
+            .rotate(&mut cube, Rotation::clockwise(Face::Right))
+            .unwrap();
+        history.perform_step(&mut cube, " (U F)2 ").unwrap();
+        let end = cube.clone();
+
+        history.jump_to(1, &mut cube);
+        let mut expected = Cube::create(3);
+        expected.rotate(Rotation::clockwise(Face::Right));
+        assert_eq!(expected, cube);
+        assert_eq!(1, history.position());
+
+        history.jump_to(2, &mut cube);
+        assert_eq!(end, cube);
+        assert_eq!(
+            vec!["R", "(U F)2"],
+            history
+                .moves()
+                .iter()
+                .map(|made| made.label(|_| "transform"))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_move_after_rewinding_forgets_later_moves() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        history.perform_step(&mut cube, "R").unwrap();
+        history.perform_step(&mut cube, "U").unwrap();
+
+        history.jump_to(0, &mut cube);
+        history.perform_step(&mut cube, "F").unwrap();
+
+        assert_eq!(vec![Move::Step("F".to_string())], history.moves().to_vec());
+        assert_eq!(1, history.position());
+    }
+
+    #[test]
+    fn test_recorded_moves_can_be_rewound() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let shuffle = cube.shuffle(10);
+        shuffle
+            .into_iter()
+            .for_each(|rotation| history.record(Move::Rotation(rotation)));
+
+        history.jump_to(0, &mut cube);
+
+        assert_eq!(Cube::create(3), cube);
+        assert_eq!(10, history.moves().len());
+    }
+
+    #[test]
+    fn test_undo_and_redo_cover_transforms_and_shuffles() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        history
+            .apply_transform(&mut cube, KnownTransform::Checkerboard)
+            .unwrap();
+        cube.shuffle(5)
+            .into_iter()
+            .for_each(|rotation| history.record(Move::Rotation(rotation)));
+        let end = cube.clone();
+
+        while history.undo(&mut cube) {}
+        assert_eq!(Cube::create(3), cube);
+        assert!(!history.undo(&mut cube));
+
+        while history.redo(&mut cube) {}
+        assert_eq!(end, cube);
+        assert_eq!(6, history.position());
+    }
+
+    #[test]
+    fn test_group_is_undone_in_one_step() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        history.perform_step(&mut cube, "F").unwrap();
+        let moves = cube.shuffle(25).into_iter().map(Move::Rotation).collect();
+        history.record(Move::Group {
+            label: "Shuffle".to_string(),
+            moves,
+        });
+        let end = cube.clone();
+
+        assert!(history.undo(&mut cube));
+        let mut expected = Cube::create(3);
+        expected.rotate(Rotation::clockwise(Face::Front));
+        assert_eq!(expected, cube);
+
+        assert!(history.redo(&mut cube));
+        assert_eq!(end, cube);
+        assert_eq!("Shuffle", history.moves()[1].label(|_| "transform"));
+    }
+
+    #[test]
+    fn test_group_last_collects_the_latest_moves() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        for step in ["F", "R", "U"] {
+            history.perform_step(&mut cube, step).unwrap();
+        }
+        let end = cube.clone();
+
+        history.group_last(2, "R U".to_string());
+
+        assert_eq!(2, history.moves().len());
+        assert_eq!(2, history.position());
+        assert!(history.undo(&mut cube));
+        assert_eq!(1, history.position());
+        assert!(history.redo(&mut cube));
+        assert_eq!(end, cube);
+    }
+
+    #[test]
+    fn test_group_with_a_locked_layer_makes_no_turn() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(Face::Up, 0);
+        let before = cube.clone();
+        let mut history = MoveHistory::new(&cube);
+        let moves = ["R", "U"].map(|step| Move::Step(step.to_string())).to_vec();
+
+        assert!(history
+            .make(
+                &mut cube,
+                Move::Group {
+                    label: "R U".to_string(),
+                    moves,
+                },
+            )
+            .is_err());
+        assert_eq!(before, cube);
+        assert!(history.moves().is_empty());
+    }
+
+    #[test]
+    fn test_failed_move_is_not_recorded() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(Face::Up, 0);
+        let mut history = MoveHistory::new(&cube);
+
+        assert!(history.perform_step(&mut cube, "U").is_err());
+        assert!(history
+            .rotate(&mut cube, Rotation::clockwise(Face::Up))
+            .is_err());
+        assert!(history.moves().is_empty());
+    }
+
+    #[test]
+    fn test_rotations_with_a_locked_layer_make_no_turn() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(Face::Up, 1);
+        let before = cube.clone();
+        let mut history = MoveHistory::new(&cube);
+        let up = Rotation::clockwise(Face::Up);
+
+        assert!(history
+            .make(&mut cube, Move::Rotations(vec![up, up.at_layer(1)]))
+            .is_err());
+        assert_eq!(before, cube);
+        assert!(history.moves().is_empty());
+
+        history
+            .make(&mut cube, Move::Rotations(vec![up, up]))
+            .unwrap();
+        assert_eq!("U2", history.moves()[0].label(|_| "transform"));
+    }
+
+    #[test]
+    fn test_rotations_outside_the_cube_are_not_made() {
+        let mut cube = Cube::create(2);
+        let mut history = MoveHistory::new(&cube);
+        let inner = Rotation::clockwise(Face::Right).at_layer(2);
+
+        assert_eq!(
+            Err("Layer 3 is not within a 2x2 cube".to_string()),
+            history.rotate(&mut cube, inner)
+        );
+        assert!(history
+            .make(&mut cube, Move::Rotations(vec![inner.at_layer(0), inner]))
+            .is_err());
+        assert_eq!(Cube::create(2), cube);
+        assert!(history.moves().is_empty());
+    }
+
+    #[test]
+    fn test_jump_ignores_locks_taken_since_and_keeps_them() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        history.perform_step(&mut cube, "U").unwrap();
+        cube.lock_layer(Face::Up, 0);
+
+        history.jump_to(0, &mut cube);
+        history.jump_to(1, &mut cube);
+
+        assert_eq!(1, history.position());
+        assert!(cube.is_layer_locked(Face::Up, 0));
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use rusty_puzzle_cube::known_transforms::KnownTransform;
+use three_d::egui::{Align2, Area, Color32, Context, Id, RichText};
+
+use super::move_history::Move;
+
+const TICKER_FADE_MS: f64 = 1500.;
+const TICKER_MARGIN: f32 = 60.;
+const TICKER_FONT_SIZE: f32 = 40.;
+const RECENT_FONT_SIZE: f32 = 18.;
+const MAX_RECENT_MOVES: usize = 12;
+
+/// Shows the notation of each move as it is made, large and fading away near the cube, above a strip of the moves made just before it, so viewers can follow along with a sequence.
+pub(super) struct MoveTicker {
+    recent: VecDeque<Move>,
+    latest_at_ms: Option<f64>,
+    shown: bool,
+}
+
+impl MoveTicker {
+    pub(super) fn new() -> Self {
+        Self {
+            recent: VecDeque::with_capacity(MAX_RECENT_MOVES),
+            latest_at_ms: None,
+            shown: false,
+        }
+    }
+
+    pub(super) fn push(&mut self, made: Move, now_ms: f64) {
+        if self.recent.len() == MAX_RECENT_MOVES {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(made);
+        self.latest_at_ms = Some(now_ms);
+    }
+
+    pub(super) fn needs_redraw(&self, now_ms: f64) -> bool {
+        self.opacity(now_ms).is_some() || self.shown
+    }
+
+    /// Returns how opaque the ticker is at the given time, fading from 1 as a move is made to nothing, or `None` once it has faded away.
+    #[allow(clippy::cast_possible_truncation)]
+    fn opacity(&self, now_ms: f64) -> Option<f32> {
+        let elapsed = now_ms - self.latest_at_ms?;
+        (elapsed < TICKER_FADE_MS).then(|| (1. - elapsed / TICKER_FADE_MS) as f32)
+    }
+
+    pub(super) fn show(
+        &mut self,
+        gui_ctx: &Context,
+        transform_name: impl Fn(KnownTransform) -> &'static str,
+        now_ms: f64,
+    ) {
+        let opacity = self.opacity(now_ms);
+        self.shown = opacity.is_some();
+        let (Some(opacity), Some((latest, earlier))) =
+            (opacity, self.recent.make_contiguous().split_last())
+        else {
+            return;
+        };
+        let colour = |base: Color32| base.gamma_multiply(opacity);
+        Area::new(Id::new("move_ticker"))
+            .anchor(Align2::CENTER_BOTTOM, [0., -TICKER_MARGIN])
+            .interactable(false)
+            .show(gui_ctx, |ui| {
+                let text_colour = ui.visuals().strong_text_color();
+                ui.vertical_centered(|ui| {
+                    ui.label(
+                        RichText::new(latest.label(&transform_name))
+                            .monospace()
+                            .size(TICKER_FONT_SIZE)
+                            .color(colour(text_colour)),
+                    );
+                    let strip = earlier
+                        .iter()
+                        .map(|made| made.label(&transform_name))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    ui.label(
+                        RichText::new(strip)
+                            .monospace()
+                            .size(RECENT_FONT_SIZE)
+                            .color(colour(ui.visuals().weak_text_color())),
+                    );
+                });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_fades_after_the_latest_move() {
+        let mut ticker = MoveTicker::new();
+        assert_eq!(None, ticker.opacity(0.));
+
+        ticker.push(Move::Step("R".to_string()), 100.);
+
+        assert_eq!(Some(1.), ticker.opacity(100.));
+        assert_eq!(Some(0.5), ticker.opacity(100. + TICKER_FADE_MS / 2.));
+        assert_eq!(None, ticker.opacity(100. + TICKER_FADE_MS));
+        assert!(!ticker.needs_redraw(100. + TICKER_FADE_MS));
+    }
+
+    #[test]
+    fn test_keeps_only_the_most_recent_moves() {
+        let mut ticker = MoveTicker::new();
+        for i in 0..MAX_RECENT_MOVES + 3 {
+            ticker.push(Move::Step(format!("{}R", i + 1)), 0.);
+        }
+
+        assert_eq!(MAX_RECENT_MOVES, ticker.recent.len());
+        assert_eq!(Some(&Move::Step("4R".to_string())), ticker.recent.front());
+    }
+}
@@ -0,0 +1,139 @@
+use rusty_puzzle_cube::cube::{
+    colour_scheme::{recolour, ColourScheme},
+    face::Face,
+    Cube,
+};
+use three_d::egui::{pos2, vec2, Color32, Context, Id, Pos2, Rect, Sense, Window};
+
+use super::colours::StickerColours;
+use super::cube_ext::{cubie_face_to_colour, FACE_ORDER};
+use super::i18n::Strings;
+
+const FACE_SIZE: f32 = 60.;
+const FACE_GAP: f32 = 4.;
+const STICKER_GAP: f32 = 1.;
+// Stickers smaller than this are drawn touching, so big cubes don't turn into a grid of gaps
+const MIN_STICKER_SIZE_FOR_GAP: f32 = 4.;
+const BACKGROUND: Color32 = Color32::from_gray(20);
+
+/// Draws every face of the cube unfolded flat, with the up and down faces folded out from the front.
+pub(super) fn show(
+    gui_ctx: &Context,
+    strings: &Strings,
+    cube: &Cube,
+    scheme: &ColourScheme,
+    colours: &StickerColours,
+    open: &mut bool,
+) {
+    Window::new(strings.net_view)
+        .id(Id::new("net_view"))
+        .open(open)
+        .resizable(false)
+        .show(gui_ctx, |ui| {
+            let (response, painter) = ui.allocate_painter(
+                vec2(
+                    4. * FACE_SIZE + 3. * FACE_GAP,
+                    3. * FACE_SIZE + 2. * FACE_GAP,
+                ),
+                Sense::hover(),
+            );
+            let side_length = cube.side_length();
+            for face in FACE_ORDER {
+                painter.rect_filled(face_rect(response.rect.min, face), 0., BACKGROUND);
+                for (i, cubie_face) in cube.oriented_side(face).cells().iter().enumerate() {
+                    let colour = cubie_face_to_colour(recolour(*cubie_face, scheme), colours);
+                    painter.rect_filled(
+                        sticker_rect(
+                            response.rect.min,
+                            face,
+                            side_length,
+                            i / side_length,
+                            i % side_length,
+                        ),
+                        0.,
+                        Color32::from_rgb(colour.r, colour.g, colour.b),
+                    );
+                }
+            }
+        });
+}
+
+/// Where each face sits in the net, counted in faces from the top left.
+fn net_position(face: Face) -> (f32, f32) {
+    match face {
+        Face::Up => (1., 0.),
+        Face::Left => (0., 1.),
+        Face::Front => (1., 1.),
+        Face::Right => (2., 1.),
+        Face::Back => (3., 1.),
+        Face::Down => (1., 2.),
+    }
+}
+
+fn face_rect(origin: Pos2, face: Face) -> Rect {
+    let (x, y) = net_position(face);
+    Rect::from_min_size(
+        origin + vec2(x * (FACE_SIZE + FACE_GAP), y * (FACE_SIZE + FACE_GAP)),
+        vec2(FACE_SIZE, FACE_SIZE),
+    )
+}
+
+/// Rows and columns run as they are drawn on the cube, as seen when looking at the face from outside the cube.
+#[allow(clippy::cast_precision_loss)]
+fn sticker_rect(origin: Pos2, face: Face, side_length: usize, row: usize, col: usize) -> Rect {
+    let size = FACE_SIZE / side_length as f32;
+    let gap = if size < MIN_STICKER_SIZE_FOR_GAP {
+        0.
+    } else {
+        STICKER_GAP
+    };
+    let face_min = face_rect(origin, face).min;
+    Rect::from_min_size(
+        pos2(
+            face_min.x + col as f32 * size + gap / 2.,
+            face_min.y + row as f32 * size + gap / 2.,
+        ),
+        vec2(size - gap, size - gap),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_faces_do_not_overlap() {
+        for face in FACE_ORDER {
+            for other in FACE_ORDER.into_iter().filter(|other| *other != face) {
+                assert!(!face_rect(Pos2::ZERO, face).intersects(face_rect(Pos2::ZERO, other)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_up_face_folds_onto_top_edge_of_front() {
+        let up = face_rect(Pos2::ZERO, Face::Up);
+        let front = face_rect(Pos2::ZERO, Face::Front);
+
+        assert_eq!(up.left(), front.left());
+        assert_eq!(up.bottom() + FACE_GAP, front.top());
+    }
+
+    #[test]
+    fn test_stickers_fill_face() {
+        let face = face_rect(Pos2::ZERO, Face::Right);
+        let first = sticker_rect(Pos2::ZERO, Face::Right, 3, 0, 0);
+        let last = sticker_rect(Pos2::ZERO, Face::Right, 3, 2, 2);
+
+        assert_eq!(face.min + vec2(STICKER_GAP, STICKER_GAP) / 2., first.min);
+        assert_eq!(face.max - vec2(STICKER_GAP, STICKER_GAP) / 2., last.max);
+    }
+
+    #[test]
+    fn test_tiny_stickers_have_no_gap() {
+        let sticker = sticker_rect(Pos2::ZERO, Face::Front, 30, 0, 0);
+
+        assert_eq!(vec2(2., 2.), sticker.size());
+    }
+}
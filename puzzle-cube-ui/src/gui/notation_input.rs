@@ -0,0 +1,132 @@
+use super::i18n::Strings;
+use rusty_puzzle_cube::notation::{tokenise, TokenSpan};
+use three_d::egui::{text::LayoutJob, Color32, FontId, TextFormat};
+
+const VALID_COLOUR: Color32 = Color32::from_rgb(80, 200, 80);
+pub(super) const INVALID_COLOUR: Color32 = Color32::from_rgb(255, 80, 80);
+
+pub(super) fn validate(text: &str) -> Vec<TokenSpan> {
+    if text.trim().is_empty() {
+        vec![]
+    } else {
+        tokenise(text)
+    }
+}
+
+pub(super) fn highlight(
+    text: &str,
+    spans: &[TokenSpan],
+    font_id: &FontId,
+    default_colour: Color32,
+) -> LayoutJob {
+    let format = |color| TextFormat {
+        font_id: font_id.clone(),
+        color,
+        ..Default::default()
+    };
+
+    let mut job = LayoutJob::default();
+    let mut end_of_last_span = 0;
+    for span in spans {
+        job.append(
+            &text[end_of_last_span..span.range.start],
+            0.,
+            format(default_colour),
+        );
+        let colour = if span.error.is_some() {
+            INVALID_COLOUR
+        } else {
+            VALID_COLOUR
+        };
+        job.append(&text[span.range.clone()], 0., format(colour));
+        end_of_last_span = span.range.end;
+    }
+    job.append(&text[end_of_last_span..], 0., format(default_colour));
+    job
+}
+
+pub(super) fn summary(strings: &Strings, spans: &[TokenSpan]) -> String {
+    let invalid = spans.iter().filter(|span| span.error.is_some()).count();
+    let moves = (strings.moves)(spans.len() - invalid);
+    if invalid == 0 {
+        moves
+    } else {
+        format!("{moves}, {}", (strings.invalid_tokens)(invalid))
+    }
+}
+
+pub(super) fn errors(spans: &[TokenSpan]) -> Option<String> {
+    let errors = spans
+        .iter()
+        .filter_map(|span| span.error.as_deref())
+        .collect::<Vec<_>>();
+    (!errors.is_empty()).then(|| errors.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gui::i18n::Language;
+    use pretty_assertions::assert_eq;
+
+    fn english() -> &'static Strings {
+        Language::English.strings()
+    }
+
+    #[test]
+    fn test_highlight_colours_each_token() {
+        let text = " R2  x ";
+        let job = highlight(text, &tokenise(text), &FontId::default(), Color32::WHITE);
+
+        let sections = job
+            .sections
+            .iter()
+            .map(|section| (&job.text[section.byte_range.clone()], section.format.color))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (" ", Color32::WHITE),
+                ("R2", VALID_COLOUR),
+                (" ", Color32::WHITE),
+                ("", INVALID_COLOUR),
+                (" ", Color32::WHITE),
+                ("x", INVALID_COLOUR),
+                (" ", Color32::WHITE),
+            ],
+            sections
+        );
+        assert_eq!(text, job.text);
+    }
+
+    #[test]
+    fn test_blank_text_has_no_tokens() {
+        assert_eq!(Vec::<TokenSpan>::new(), validate("  "));
+        assert_eq!("0 moves", summary(english(), &validate("")));
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!("1 move", summary(english(), &tokenise("F")));
+        assert_eq!("3 moves", summary(english(), &tokenise("F R' U2")));
+        assert_eq!(
+            "2 moves, 1 invalid token",
+            summary(english(), &tokenise("F x U2"))
+        );
+        assert_eq!(
+            "0 moves, 2 invalid tokens",
+            summary(english(), &tokenise("x y"))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(None, errors(&tokenise("F R")));
+        assert_eq!(
+            Some(
+                "Unsupported token in notation string: [x]\nUnsupported token in notation string: [y]"
+                    .to_string()
+            ),
+            errors(&tokenise("x F y"))
+        );
+    }
+}
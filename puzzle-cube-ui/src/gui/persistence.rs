@@ -0,0 +1,480 @@
+use std::str::FromStr;
+
+use rusty_puzzle_cube::cube::Cube;
+
+use super::animation::AnimationStyle;
+use super::colours::{Palette, StickerColours, DEFAULT_COLOURS};
+use super::i18n::Language;
+use super::side_panel::UNREASONABLE_MAX_CUBE_SIZE;
+
+const AUTOSAVE_INTERVAL_MS: f64 = 3000.;
+const KEY_PREFIX: &str = "rusty-puzzle-cube.";
+const CUBE_KEY: &str = "cube";
+const SHARE_PREFIX: &str = "#cube=";
+const SETTINGS_KEYS: [&str; 13] = [
+    "side_length",
+    "unreasonable_mode",
+    "japanese_colours",
+    "palette",
+    "custom_colours",
+    "render_axes",
+    "auto_frame",
+    "show_rear_view",
+    "show_net",
+    "show_stats",
+    "show_log",
+    "language",
+    "animation_style",
+];
+
+pub(super) trait Store {
+    fn get(&self, key: &str) -> Option<String>;
+    fn set(&mut self, key: &str, value: &str);
+    fn remove(&mut self, key: &str);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Settings {
+    pub(super) side_length: usize,
+    pub(super) unreasonable_mode: bool,
+    pub(super) japanese_colours: bool,
+    pub(super) palette: Palette,
+    pub(super) custom_colours: StickerColours,
+    pub(super) render_axes: bool,
+    pub(super) auto_frame: bool,
+    pub(super) show_rear_view: bool,
+    pub(super) show_net: bool,
+    pub(super) show_stats: bool,
+    pub(super) show_log: bool,
+    pub(super) language: Language,
+    pub(super) animation_style: AnimationStyle,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            side_length: 3,
+            unreasonable_mode: false,
+            japanese_colours: false,
+            palette: Palette::Default,
+            custom_colours: DEFAULT_COLOURS,
+            render_axes: false,
+            auto_frame: true,
+            show_rear_view: false,
+            show_net: false,
+            show_stats: false,
+            show_log: false,
+            language: Language::English,
+            animation_style: AnimationStyle::Turn,
+        }
+    }
+}
+
+fn get(store: &dyn Store, key: &str) -> Option<String> {
+    store.get(&format!("{KEY_PREFIX}{key}"))
+}
+
+fn get_parsed<T: FromStr>(store: &dyn Store, key: &str) -> Option<T> {
+    get(store, key).and_then(|value| value.parse().ok())
+}
+
+impl Settings {
+    fn to_values(&self) -> [String; 13] {
+        [
+            self.side_length.to_string(),
+            self.unreasonable_mode.to_string(),
+            self.japanese_colours.to_string(),
+            self.palette.code().to_string(),
+            self.custom_colours.encode(),
+            self.render_axes.to_string(),
+            self.auto_frame.to_string(),
+            self.show_rear_view.to_string(),
+            self.show_net.to_string(),
+            self.show_stats.to_string(),
+            self.show_log.to_string(),
+            self.language.code().to_string(),
+            self.animation_style.code().to_string(),
+        ]
+    }
+
+    fn from_store(store: &dyn Store) -> Self {
+        let defaults = Self::default();
+        Self {
+            side_length: get_parsed(store, "side_length").unwrap_or(defaults.side_length),
+            unreasonable_mode: get_parsed(store, "unreasonable_mode")
+                .unwrap_or(defaults.unreasonable_mode),
+            japanese_colours: get_parsed(store, "japanese_colours")
+                .unwrap_or(defaults.japanese_colours),
+            palette: get(store, "palette")
+                .and_then(|code| Palette::from_code(&code))
+                .unwrap_or(defaults.palette),
+            custom_colours: get(store, "custom_colours")
+                .and_then(|encoded| StickerColours::decode(&encoded))
+                .unwrap_or(defaults.custom_colours),
+            render_axes: get_parsed(store, "render_axes").unwrap_or(defaults.render_axes),
+            auto_frame: get_parsed(store, "auto_frame").unwrap_or(defaults.auto_frame),
+            show_rear_view: get_parsed(store, "show_rear_view").unwrap_or(defaults.show_rear_view),
+            show_net: get_parsed(store, "show_net").unwrap_or(defaults.show_net),
+            show_stats: get_parsed(store, "show_stats").unwrap_or(defaults.show_stats),
+            show_log: get_parsed(store, "show_log").unwrap_or(defaults.show_log),
+            language: get(store, "language")
+                .and_then(|code| Language::from_code(&code))
+                .unwrap_or(defaults.language),
+            animation_style: get(store, "animation_style")
+                .and_then(|code| AnimationStyle::from_code(&code))
+                .unwrap_or(defaults.animation_style),
+        }
+    }
+}
+
+pub(super) struct Autosave {
+    store: Option<Box<dyn Store>>,
+    last_saved: Option<(String, [String; 13])>,
+    next_save_ms: f64,
+}
+
+impl Autosave {
+    pub(super) fn new() -> Self {
+        #[cfg(target_arch = "wasm32")]
+        let store =
+            local_storage::LocalStorage::open().map(|store| Box::new(store) as Box<dyn Store>);
+        #[cfg(not(target_arch = "wasm32"))]
+        let store = None;
+        Self::with_store(store)
+    }
+
+    fn with_store(store: Option<Box<dyn Store>>) -> Self {
+        Self {
+            store,
+            last_saved: None,
+            next_save_ms: AUTOSAVE_INTERVAL_MS,
+        }
+    }
+
+    pub(super) fn is_active(&self) -> bool {
+        self.store.is_some()
+    }
+
+    /// Returns the saved cube and settings, except that a cube shared in the page's URL takes the place of the saved cube.
+    pub(super) fn restore(&self) -> (Option<Cube>, Settings) {
+        #[cfg(target_arch = "wasm32")]
+        let fragment = location::fragment();
+        #[cfg(not(target_arch = "wasm32"))]
+        let fragment: Option<String> = None;
+        self.restore_with(fragment.as_deref())
+    }
+
+    fn restore_with(&self, fragment: Option<&str>) -> (Option<Cube>, Settings) {
+        let (saved_cube, mut settings) = self.restore_saved();
+        match fragment.and_then(decode_fragment) {
+            Some(Ok(shared_cube)) => {
+                settings.side_length = shared_cube.side_length();
+                (Some(shared_cube), settings)
+            }
+            Some(Err(e)) => {
+                tracing::warn!("Ignoring shared cube: {}", e);
+                (saved_cube, settings)
+            }
+            None => (saved_cube, settings),
+        }
+    }
+
+    fn restore_saved(&self) -> (Option<Cube>, Settings) {
+        let Some(store) = self.store.as_deref() else {
+            return (None, Settings::default());
+        };
+        let cube = store
+            .get(&format!("{KEY_PREFIX}{CUBE_KEY}"))
+            .and_then(|encoded| match decode_cube(&encoded) {
+                Ok(cube) => Some(cube),
+                Err(e) => {
+                    tracing::warn!("Discarding saved cube: {}", e);
+                    None
+                }
+            });
+        (cube, Settings::from_store(store))
+    }
+
+    pub(super) fn tick(&mut self, accumulated_time_ms: f64, cube: &Cube, settings: &Settings) {
+        if accumulated_time_ms < self.next_save_ms {
+            return;
+        }
+        self.next_save_ms = accumulated_time_ms + AUTOSAVE_INTERVAL_MS;
+        let Some(store) = self.store.as_deref_mut() else {
+            return;
+        };
+
+        let state = (encode_cube(cube), settings.to_values());
+        if self.last_saved.as_ref() == Some(&state) {
+            return;
+        }
+        store.set(&format!("{KEY_PREFIX}{CUBE_KEY}"), &state.0);
+        for (key, value) in SETTINGS_KEYS.iter().zip(&state.1) {
+            store.set(&format!("{KEY_PREFIX}{key}"), value);
+        }
+        self.last_saved = Some(state);
+    }
+
+    pub(super) fn reset(&mut self) {
+        if let Some(store) = self.store.as_deref_mut() {
+            for key in SETTINGS_KEYS.iter().chain(&[CUBE_KEY]) {
+                store.remove(&format!("{KEY_PREFIX}{key}"));
+            }
+        }
+        self.last_saved = None;
+    }
+}
+
+/// Returns a URL fragment that opens the page with the given cube.
+#[cfg_attr(not(any(test, target_arch = "wasm32")), allow(dead_code))]
+pub(super) fn share_fragment(cube: &Cube) -> String {
+    format!("{SHARE_PREFIX}{}", encode_cube(cube))
+}
+
+fn decode_fragment(fragment: &str) -> Option<Result<Cube, String>> {
+    fragment.strip_prefix(SHARE_PREFIX).map(decode_cube)
+}
+
+/// Writes the cube in the format of [`Cube::to_bytes`], as two hex digits per byte so it can be kept in local storage or a URL.
+fn encode_cube(cube: &Cube) -> String {
+    cube.to_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn decode_cube(encoded: &str) -> Result<Cube, String> {
+    let bytes = (0..encoded.len())
+        .step_by(2)
+        .map(|i| {
+            encoded
+                .get(i..i + 2)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or("Saved cube is not valid hex")
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if let Some(&[low, high]) = bytes.first_chunk() {
+        let side_length = usize::from(u16::from_le_bytes([low, high]));
+        if side_length > UNREASONABLE_MAX_CUBE_SIZE {
+            return Err(format!(
+                "Saved cube has side length {side_length}, larger than the largest allowed ({UNREASONABLE_MAX_CUBE_SIZE})"
+            ));
+        }
+    }
+    Cube::from_bytes(&bytes).map_err(|e| format!("Saved cube is invalid: {e}"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) mod location {
+    use rusty_puzzle_cube::cube::Cube;
+
+    pub(super) fn fragment() -> Option<String> {
+        web_sys::window()?.location().hash().ok()
+    }
+
+    /// Puts the cube into the page's URL, returning the whole URL so it can be shared.
+    pub(super) fn share(cube: &Cube) -> Option<String> {
+        let location = web_sys::window()?.location();
+        location.set_hash(&super::share_fragment(cube)).ok()?;
+        location.href().ok()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod local_storage {
+    use tracing::error;
+    use web_sys::Storage;
+
+    use super::Store;
+
+    pub(super) struct LocalStorage(Storage);
+
+    impl LocalStorage {
+        pub(super) fn open() -> Option<Self> {
+            let storage = web_sys::window()?.local_storage().ok().flatten();
+            if storage.is_none() {
+                error!("Local storage is unavailable, progress will not be saved");
+            }
+            storage.map(Self)
+        }
+    }
+
+    impl Store for LocalStorage {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.get_item(key).ok().flatten()
+        }
+
+        fn set(&mut self, key: &str, value: &str) {
+            if let Err(e) = self.0.set_item(key, value) {
+                error!("Could not save {}: {:?}", key, e);
+            }
+        }
+
+        fn remove(&mut self, key: &str) {
+            if let Err(e) = self.0.remove_item(key) {
+                error!("Could not remove {}: {:?}", key, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rusty_puzzle_cube::known_transforms::checkerboard_corners;
+
+    #[derive(Clone, Default)]
+    struct MemoryStore(Rc<RefCell<HashMap<String, String>>>);
+
+    impl Store for MemoryStore {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.borrow().get(key).cloned()
+        }
+
+        fn set(&mut self, key: &str, value: &str) {
+            self.0
+                .borrow_mut()
+                .insert(key.to_string(), value.to_string());
+        }
+
+        fn remove(&mut self, key: &str) {
+            self.0.borrow_mut().remove(key);
+        }
+    }
+
+    fn autosave() -> (Autosave, MemoryStore) {
+        let store = MemoryStore::default();
+        (Autosave::with_store(Some(Box::new(store.clone()))), store)
+    }
+
+    #[test]
+    fn test_cube_round_trip() {
+        let mut cube = Cube::create(3);
+        checkerboard_corners(&mut cube);
+
+        assert_eq!(Ok(cube.clone()), decode_cube(&encode_cube(&cube)));
+    }
+
+    #[test]
+    fn test_encode_solved_1x1() {
+        assert_eq!("010088c602", encode_cube(&Cube::create(1)));
+    }
+
+    #[test]
+    fn test_decode_invalid_cube() {
+        assert!(decode_cube("nonsense").is_err());
+        assert!(decode_cube("01008").is_err());
+        assert!(decode_cube("0100").is_err());
+        assert!(decode_cube("0000").is_err());
+        assert!(decode_cube("01008bc602").is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_cubes_too_large_to_make() {
+        assert_eq!(
+            Err(format!("Saved cube has side length 65535, larger than the largest allowed ({UNREASONABLE_MAX_CUBE_SIZE})")),
+            decode_cube("ffff00")
+        );
+    }
+
+    #[test]
+    fn test_tick_waits_for_interval() {
+        let (mut autosave, store) = autosave();
+
+        autosave.tick(
+            AUTOSAVE_INTERVAL_MS / 2.,
+            &Cube::create(2),
+            &Settings::default(),
+        );
+        assert!(store.0.borrow().is_empty());
+
+        autosave.tick(AUTOSAVE_INTERVAL_MS, &Cube::create(2), &Settings::default());
+        assert!(!store.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_restore_after_save() {
+        let (mut autosave, _) = autosave();
+        let mut cube = Cube::create(4);
+        checkerboard_corners(&mut cube);
+        let settings = Settings {
+            side_length: 4,
+            japanese_colours: true,
+            palette: Palette::Custom,
+            custom_colours: StickerColours {
+                white: three_d::Srgba::new_opaque(10, 20, 30),
+                ..DEFAULT_COLOURS
+            },
+            render_axes: true,
+            show_rear_view: true,
+            show_net: true,
+            language: Language::French,
+            animation_style: AnimationStyle::SnapAndFade,
+            ..Settings::default()
+        };
+
+        autosave.tick(AUTOSAVE_INTERVAL_MS, &cube, &settings);
+
+        assert_eq!((Some(cube), settings), autosave.restore());
+    }
+
+    #[test]
+    fn test_reset_clears_saved_state() {
+        let (mut autosave, store) = autosave();
+        autosave.tick(AUTOSAVE_INTERVAL_MS, &Cube::create(2), &Settings::default());
+
+        autosave.reset();
+
+        assert!(store.0.borrow().is_empty());
+        assert_eq!((None, Settings::default()), autosave.restore());
+    }
+
+    #[test]
+    fn test_shared_cube_replaces_saved_cube() {
+        let (mut autosave, _) = autosave();
+        autosave.tick(AUTOSAVE_INTERVAL_MS, &Cube::create(4), &Settings::default());
+        let mut shared = Cube::create(2);
+        checkerboard_corners(&mut shared);
+
+        let (cube, settings) = autosave.restore_with(Some(&share_fragment(&shared)));
+
+        assert_eq!(Some(shared), cube);
+        assert_eq!(2, settings.side_length);
+    }
+
+    #[test]
+    fn test_shared_cube_works_without_store() {
+        let autosave = Autosave::with_store(None);
+
+        let (cube, _) = autosave.restore_with(Some("#cube=010088c602"));
+
+        assert_eq!(Some(Cube::create(1)), cube);
+    }
+
+    #[test]
+    fn test_other_fragments_are_ignored() {
+        let (mut autosave, _) = autosave();
+        autosave.tick(AUTOSAVE_INTERVAL_MS, &Cube::create(2), &Settings::default());
+
+        assert_eq!(
+            (Some(Cube::create(2)), Settings::default()),
+            autosave.restore_with(Some("#cube=0200ff"))
+        );
+        assert_eq!(
+            (Some(Cube::create(2)), Settings::default()),
+            autosave.restore_with(Some("#somewhere"))
+        );
+    }
+
+    #[test]
+    fn test_without_store_nothing_is_restored() {
+        let mut autosave = Autosave::with_store(None);
+        autosave.tick(AUTOSAVE_INTERVAL_MS, &Cube::create(2), &Settings::default());
+
+        assert!(!autosave.is_active());
+        assert_eq!((None, Settings::default()), autosave.restore());
+    }
+}
@@ -0,0 +1,324 @@
+use rusty_puzzle_cube::cube::{face::Face, sticker_origin::StickerOrigin, Cube};
+use three_d::{
+    Context, CpuTexture, Interpolation, Mat3, SquareMatrix, Texture2DRef, TextureData, Wrapping,
+};
+
+use super::cube_ext::FACE_ORDER;
+
+const FACE_IMAGE_SIZE: usize = 256;
+const BLANK_CELL: usize = FACE_ORDER.len();
+const ATLAS_CELLS: usize = FACE_ORDER.len() + 1;
+
+/// An image for any of the faces of the cube, sliced between the stickers that start on that face so the picture scrambles along with them.
+///
+/// Every image is stretched to a square and kept side by side with the others in one texture, in [`FACE_ORDER`], followed by a blank white cell for stickers without a picture.
+pub(super) struct Picture {
+    faces: [Option<Vec<[u8; 4]>>; FACE_ORDER.len()],
+}
+
+impl Picture {
+    pub(super) fn new() -> Self {
+        Self {
+            faces: Default::default(),
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.faces.iter().all(Option::is_none)
+    }
+
+    pub(super) fn has_face(&self, face: Face) -> bool {
+        self.faces[cell(face)].is_some()
+    }
+
+    /// Stretches the image over the face, to be shown by the stickers that start there.
+    /// # Errors
+    /// Will return an Err variant when the image has no pixels, or its channels are not 8 bit
+    pub(super) fn set_face(&mut self, face: Face, image: &CpuTexture) -> Result<(), String> {
+        let pixels = rgba_pixels(&image.data)?;
+        let width = image.width as usize;
+        let height = image.height as usize;
+        if width == 0 || height == 0 || pixels.len() != width * height {
+            return Err("Picture images must have pixels to show".to_string());
+        }
+        self.faces[cell(face)] = Some(
+            (0..FACE_IMAGE_SIZE * FACE_IMAGE_SIZE)
+                .map(|i| {
+                    let y = i / FACE_IMAGE_SIZE * height / FACE_IMAGE_SIZE;
+                    let x = i % FACE_IMAGE_SIZE * width / FACE_IMAGE_SIZE;
+                    pixels[y * width + x]
+                })
+                .collect(),
+        );
+        Ok(())
+    }
+
+    pub(super) fn clear_face(&mut self, face: Face) {
+        self.faces[cell(face)] = None;
+    }
+
+    /// Returns the texture to draw the tiles with, or `None` when no face has a picture.
+    pub(super) fn texture(&self, ctx: &Context) -> Option<Texture2DRef> {
+        (!self.is_empty()).then(|| Texture2DRef::from_cpu_texture(ctx, &self.atlas()))
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn atlas(&self) -> CpuTexture {
+        let width = FACE_IMAGE_SIZE * ATLAS_CELLS;
+        let mut pixels = vec![[255; 4]; width * FACE_IMAGE_SIZE];
+        for (i, face) in self.faces.iter().enumerate() {
+            let Some(face) = face else {
+                continue;
+            };
+            for (y, row) in face.chunks_exact(FACE_IMAGE_SIZE).enumerate() {
+                let start = y * width + i * FACE_IMAGE_SIZE;
+                pixels[start..start + FACE_IMAGE_SIZE].copy_from_slice(row);
+            }
+        }
+        CpuTexture {
+            name: "picture".to_string(),
+            data: TextureData::RgbaU8(pixels),
+            width: width as u32,
+            height: FACE_IMAGE_SIZE as u32,
+            min_filter: Interpolation::Linear,
+            mag_filter: Interpolation::Linear,
+            mip_map_filter: None,
+            wrap_s: Wrapping::ClampToEdge,
+            wrap_t: Wrapping::ClampToEdge,
+        }
+    }
+
+    /// Returns the part of the picture each tile shows, in the order tiles are placed by [`ToInstances`], or `None` for tiles whose sticker did not start on a face with a picture.
+    ///
+    /// Stickers are only told apart when the cube tracks where they started, and are only turned with their cubie when it is a super cube.
+    ///
+    /// [`ToInstances`]: super::cube_ext::ToInstances
+    pub(super) fn slices(&self, cube: &Cube) -> Vec<Option<Mat3>> {
+        let side_length = cube.side_length();
+        let Some(origins) = cube.sticker_origin_map() else {
+            return vec![None; FACE_ORDER.len() * side_length * side_length];
+        };
+        let turns = cube.sticker_rotation_map();
+        FACE_ORDER
+            .iter()
+            .flat_map(|&face| {
+                let face_turns = turns.map(|turns| turns[face].cells());
+                origins[face]
+                    .cells()
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, &origin)| {
+                        self.has_face(origin.face()).then(|| {
+                            slice(origin, face_turns.map_or(0, |turns| turns[i]), side_length)
+                        })
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Maps the outward face of a tile to the blank cell of the atlas.
+#[allow(clippy::cast_precision_loss)]
+pub(super) fn blank_slice() -> Mat3 {
+    into_atlas(BLANK_CELL as f32, 0., 1.) * from_outward_face()
+}
+
+/// Maps the outward face of a tile to the part of the atlas its sticker started on, turned by the quarter turns the sticker has made since.
+#[allow(clippy::cast_precision_loss)]
+fn slice(origin: StickerOrigin, quarter_turns: u8, side_length: usize) -> Mat3 {
+    let column = cell(origin.face()) * side_length + origin.col();
+    let turned = (0..quarter_turns % 4).fold(Mat3::identity(), |turned, _| {
+        turned * quarter_turn_anticlockwise()
+    });
+    into_atlas(column as f32, origin.row() as f32, side_length as f32)
+        * turned
+        * from_outward_face()
+}
+
+/// Maps a square with corners `(0, 0)` top left and `(1, 1)` bottom right onto the square at `column` and `row` of an atlas cut into squares `cells_per_face` to a side for each cell.
+#[allow(clippy::cast_precision_loss)]
+fn into_atlas(column: f32, row: f32, cells_per_face: f32) -> Mat3 {
+    let width = ATLAS_CELLS as f32 * cells_per_face;
+    Mat3::new(
+        1. / width,
+        0.,
+        0.,
+        0.,
+        1. / cells_per_face,
+        0.,
+        column / width,
+        row / cells_per_face,
+        1.,
+    )
+}
+
+/// Finds the point of a sticker's image to show at a point of its tile once the sticker has turned a quarter clockwise.
+fn quarter_turn_anticlockwise() -> Mat3 {
+    Mat3::new(0., -1., 0., 1., 0., 0., 0., 1., 1.)
+}
+
+/// Maps the texture coordinates of the outward face of a tile, which is the Front face of the three-d cube mesh, onto a square with corners `(0, 0)` top left and `(1, 1)` bottom right.
+fn from_outward_face() -> Mat3 {
+    Mat3::new(4., 0., 0., 0., 3., 0., -2., -1., 1.)
+}
+
+fn cell(face: Face) -> usize {
+    FACE_ORDER
+        .iter()
+        .position(|&f| f == face)
+        .expect("Every face is in the face order")
+}
+
+fn rgba_pixels(data: &TextureData) -> Result<Vec<[u8; 4]>, String> {
+    match data {
+        TextureData::RU8(values) => Ok(values.iter().map(|&v| [v, v, v, 255]).collect()),
+        TextureData::RgU8(values) => Ok(values.iter().map(|&[v, a]| [v, v, v, a]).collect()),
+        TextureData::RgbU8(values) => Ok(values.iter().map(|&[r, g, b]| [r, g, b, 255]).collect()),
+        TextureData::RgbaU8(values) => Ok(values.clone()),
+        _ => Err("Picture images must have 8 bit channels".to_string()),
+    }
+}
+
+/// Reads an image file, such as a PNG, to give to [`Picture::set_face`].
+/// # Errors
+/// Will return an Err variant when the file cannot be read or is not an image
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn load(path: &str) -> Result<CpuTexture, String> {
+    three_d_asset::io::load_and_deserialize(path).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rusty_puzzle_cube::cube::rotation::Rotation;
+    use three_d::{vec2, vec3, Vec2};
+
+    const TOP_LEFT: Vec2 = vec2(0.5, 1. / 3.);
+    const TOP_RIGHT: Vec2 = vec2(0.75, 1. / 3.);
+    const BOTTOM_RIGHT: Vec2 = vec2(0.75, 2. / 3.);
+
+    fn rounded(point: Vec2) -> Vec2 {
+        vec2(
+            (point.x * 1000.).round() / 1000.,
+            (point.y * 1000.).round() / 1000.,
+        )
+    }
+
+    fn map(slice: Mat3, uv: Vec2) -> Vec2 {
+        rounded((slice * vec3(uv.x, uv.y, 1.)).truncate())
+    }
+
+    fn image(width: u32, height: u32, pixels: Vec<[u8; 3]>) -> CpuTexture {
+        CpuTexture {
+            data: TextureData::RgbU8(pixels),
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn test_slice_picks_the_starting_square() {
+        let origin = Cube::create_with_sticker_origins(2)
+            .sticker_origin_map()
+            .unwrap()[Face::Back][1][0];
+        let slice = slice(origin, 0, 2);
+
+        let cell_width = 1. / (ATLAS_CELLS as f32 * 2.);
+        assert_eq!(rounded(vec2(2. * cell_width, 0.5)), map(slice, TOP_LEFT));
+        assert_eq!(rounded(vec2(3. * cell_width, 1.)), map(slice, BOTTOM_RIGHT));
+    }
+
+    #[test]
+    fn test_slice_turns_with_the_sticker() {
+        let origin = Cube::create_with_sticker_origins(1)
+            .sticker_origin_map()
+            .unwrap()[Face::Front][0][0];
+
+        let turned = slice(origin, 1, 1);
+
+        assert_eq!(
+            map(slice(origin, 0, 1), TOP_LEFT),
+            map(turned, TOP_RIGHT),
+            "the top left of the image has turned to the top right"
+        );
+        assert_eq!(
+            map(slice(origin, 0, 1), TOP_LEFT),
+            map(slice(origin, 4, 1), TOP_LEFT)
+        );
+    }
+
+    #[test]
+    fn test_slices_follow_sticker_origins() {
+        let mut picture = Picture::new();
+        picture
+            .set_face(Face::Front, &image(1, 1, vec![[255, 0, 0]]))
+            .unwrap();
+        let mut cube = Cube::create_supercube(2);
+        cube.rotate(Rotation::clockwise(Face::Up));
+
+        let slices = picture.slices(&cube);
+
+        assert_eq!(24, slices.len());
+        let pictured = slices.iter().map(Option::is_some).collect::<Vec<_>>();
+        assert_eq!(&[false, false, true, true], &pictured[..4]);
+        assert_eq!(&[true, true, false, false], &pictured[8..12]);
+        assert_eq!(4, pictured.iter().filter(|&&pictured| pictured).count());
+    }
+
+    #[test]
+    fn test_slices_need_sticker_origins() {
+        let mut picture = Picture::new();
+        picture
+            .set_face(Face::Front, &image(1, 1, vec![[255, 0, 0]]))
+            .unwrap();
+
+        assert_eq!(vec![None; 24], picture.slices(&Cube::create(2)));
+    }
+
+    #[test]
+    fn test_atlas_places_faces_side_by_side() {
+        let mut picture = Picture::new();
+        assert!(picture.is_empty());
+        picture
+            .set_face(Face::Back, &image(2, 1, vec![[255, 0, 0], [0, 0, 255]]))
+            .unwrap();
+        assert!(!picture.is_empty());
+
+        let atlas = picture.atlas();
+        let TextureData::RgbaU8(pixels) = atlas.data else {
+            panic!("Atlas should be RGBA");
+        };
+        let width = atlas.width as usize;
+        assert_eq!(ATLAS_CELLS * FACE_IMAGE_SIZE, width);
+        assert_eq!([255; 4], pixels[0]);
+        assert_eq!([255, 0, 0, 255], pixels[FACE_IMAGE_SIZE]);
+        assert_eq!([0, 0, 255, 255], pixels[width + 2 * FACE_IMAGE_SIZE - 1]);
+
+        picture.clear_face(Face::Back);
+        assert!(picture.is_empty());
+    }
+
+    #[test]
+    fn test_images_need_pixels() {
+        let mut picture = Picture::new();
+
+        assert_eq!(
+            Err("Picture images must have pixels to show".to_string()),
+            picture.set_face(Face::Up, &image(2, 2, vec![[0, 0, 0]]))
+        );
+        assert_eq!(
+            Err("Picture images must have 8 bit channels".to_string()),
+            picture.set_face(
+                Face::Up,
+                &CpuTexture {
+                    data: TextureData::RF32(vec![0.]),
+                    ..Default::default()
+                }
+            )
+        );
+        assert!(picture.is_empty());
+    }
+}
@@ -0,0 +1,593 @@
+use std::collections::VecDeque;
+
+use rusty_puzzle_cube::{cube::Cube, notation::tokenise};
+use tracing::{debug, error, warn};
+
+use super::animation::{AnimationStyle, Easing, TurnAnimation};
+use super::move_history::{Move, MoveHistory};
+use super::move_ticker::MoveTicker;
+
+pub(super) const MIN_MOVES_PER_SECOND: f64 = 0.5;
+pub(super) const MAX_MOVES_PER_SECOND: f64 = 10.;
+const DEFAULT_MOVES_PER_SECOND: f64 = 4.;
+const ANIMATED_FRACTION_OF_STEP: f64 = 0.8;
+const USER_MOVE_ANIMATION_MS: f64 = 150.;
+const MAX_PENDING_MOVES: usize = 8;
+
+/// Steps through a list of moves, either one per interval while playing or one at a time on request, so each move can be seen happening rather than only the end result.
+///
+/// Every step is made through the move history, so stepping back rewinds it. Playback stops if the cube is turned in any other way, as the remaining steps no longer follow on.
+///
+/// Moves the user makes with the mouse or keyboard are animated too. Any made while a turn is still animating are queued, and made one after another in order as each animation finishes. Only a few are queued, so holding a key down cannot build up a backlog that keeps turning the cube long after it is let go.
+pub(super) struct Playback {
+    steps: Vec<Move>,
+    group_label: Option<String>,
+    pending: VecDeque<Move>,
+    applied: usize,
+    history_start: usize,
+    playing: bool,
+    next_step_at_ms: f64,
+    animation: Option<TurnAnimation>,
+    fast: bool,
+    pub(super) moves_per_second: f64,
+    pub(super) easing: Easing,
+    pub(super) style: AnimationStyle,
+    pub(super) ticker: MoveTicker,
+}
+
+impl Playback {
+    pub(super) fn new() -> Self {
+        Self {
+            steps: vec![],
+            group_label: None,
+            pending: VecDeque::new(),
+            applied: 0,
+            history_start: 0,
+            playing: false,
+            next_step_at_ms: 0.,
+            animation: None,
+            fast: false,
+            moves_per_second: DEFAULT_MOVES_PER_SECOND,
+            easing: Easing::EaseInOut,
+            style: AnimationStyle::Turn,
+            ticker: MoveTicker::new(),
+        }
+    }
+
+    /// Get ready to step through the given moves, starting from the cube as it is now.
+    pub(super) fn load(&mut self, steps: Vec<Move>, history: &MoveHistory) {
+        self.steps = steps;
+        self.group_label = None;
+        self.applied = 0;
+        self.history_start = history.position();
+        self.playing = false;
+        self.fast = false;
+    }
+
+    /// Start playing the given moves at the fastest speed, whatever speed is chosen for other playback.
+    ///
+    /// Once every move has been made they are grouped in the history under `label`, so they can be undone together, and playback finishes.
+    pub(super) fn start_fast(
+        &mut self,
+        label: String,
+        steps: Vec<Move>,
+        history: &MoveHistory,
+        now_ms: f64,
+    ) {
+        self.load(steps, history);
+        self.group_label = Some(label);
+        self.fast = true;
+        self.play(now_ms);
+    }
+
+    pub(super) fn start(&mut self, sequence: &str, history: &MoveHistory, now_ms: f64) {
+        let steps = tokenise(sequence)
+            .into_iter()
+            .map(|span| Move::Step(sequence[span.range].to_string()))
+            .collect();
+        self.load(steps, history);
+        self.play(now_ms);
+    }
+
+    pub(super) fn play(&mut self, now_ms: f64) {
+        self.playing = self.applied < self.steps.len();
+        self.next_step_at_ms = now_ms;
+    }
+
+    pub(super) fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub(super) fn stop(&mut self) {
+        self.steps.clear();
+        self.group_label = None;
+        self.applied = 0;
+        self.playing = false;
+        self.animation = None;
+    }
+
+    /// Stop playback and forget any queued user moves, as they no longer apply once the cube has been replaced.
+    pub(super) fn reset(&mut self) {
+        self.stop();
+        self.pending.clear();
+        self.ticker = MoveTicker::new();
+    }
+
+    pub(super) fn is_loaded(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    pub(super) fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Returns how many steps have been made, and how many there are in total.
+    pub(super) fn progress(&self) -> (usize, usize) {
+        (self.applied, self.steps.len())
+    }
+
+    /// Returns whether a step was applied to the cube, stopping playback if it cannot be applied.
+    pub(super) fn step_forward(
+        &mut self,
+        cube: &mut Cube,
+        history: &mut MoveHistory,
+        now_ms: f64,
+    ) -> bool {
+        if !self.follows(history) {
+            return false;
+        }
+        let Some(step) = self.steps.get(self.applied).cloned() else {
+            return false;
+        };
+        match history.make(cube, step.clone()) {
+            Ok(()) => {
+                self.applied += 1;
+                self.animation = self.animate(&step, cube, now_ms);
+                self.ticker.push(step, now_ms);
+                if self.applied == self.steps.len() {
+                    if let Some(label) = self.group_label.take() {
+                        history.group_last(self.applied, label);
+                        self.steps.clear();
+                        self.applied = 0;
+                        self.playing = false;
+                    }
+                }
+                true
+            }
+            Err(e) => {
+                error!("Could not apply step: {e}");
+                self.stop();
+                false
+            }
+        }
+    }
+
+    /// Returns whether the most recent step was undone.
+    pub(super) fn step_back(
+        &mut self,
+        cube: &mut Cube,
+        history: &mut MoveHistory,
+        now_ms: f64,
+    ) -> bool {
+        if !self.follows(history) || self.applied == 0 {
+            return false;
+        }
+        history.jump_to(history.position() - 1, cube);
+        self.applied -= 1;
+        self.animation = self
+            .animate(&self.steps[self.applied], cube, now_ms)
+            .map(TurnAnimation::reversed);
+        true
+    }
+
+    /// Returns whether any steps were applied to the cube, making every remaining step at once.
+    pub(super) fn jump_to_end(&mut self, cube: &mut Cube, history: &mut MoveHistory) -> bool {
+        self.pause();
+        let mut stepped = false;
+        while self.step_forward(cube, history, 0.) {
+            stepped = true;
+        }
+        self.animation = None;
+        stepped
+    }
+
+    /// Returns whether the user's move was made straight away, rather than queued behind the turn still animating or dropped because the queue is full.
+    pub(super) fn make_user_move(
+        &mut self,
+        made: Move,
+        cube: &mut Cube,
+        history: &mut MoveHistory,
+        now_ms: f64,
+    ) -> bool {
+        if self.pending.len() == MAX_PENDING_MOVES {
+            debug!("Dropped a move as {MAX_PENDING_MOVES} are already waiting to be made");
+            return false;
+        }
+        self.pending.push_back(made);
+        self.animation(now_ms).is_none() && self.make_pending_move(cube, history, now_ms)
+    }
+
+    /// Returns whether any queued user moves were made, making them all at once without animating them.
+    pub(super) fn finish_pending_moves(
+        &mut self,
+        cube: &mut Cube,
+        history: &mut MoveHistory,
+    ) -> bool {
+        let mut made = false;
+        while !self.pending.is_empty() {
+            made |= self.make_pending_move(cube, history, 0.);
+        }
+        self.animation = None;
+        made
+    }
+
+    /// Returns whether the cube changed, making any queued user moves before undoing the latest one, so the move undone is the one the user made last.
+    pub(super) fn undo(&mut self, cube: &mut Cube, history: &mut MoveHistory) -> bool {
+        self.finish_pending_moves(cube, history) | history.undo(cube)
+    }
+
+    /// Returns whether the cube changed, making any queued user moves before redoing, which leaves nothing to redo if any were queued.
+    pub(super) fn redo(&mut self, cube: &mut Cube, history: &mut MoveHistory) -> bool {
+        self.finish_pending_moves(cube, history) | history.redo(cube)
+    }
+
+    /// Rewind or fast forward the history to the given position, once any queued user moves have been made.
+    pub(super) fn jump_to(&mut self, position: usize, cube: &mut Cube, history: &mut MoveHistory) {
+        self.finish_pending_moves(cube, history);
+        history.jump_to(position, cube);
+    }
+
+    fn make_pending_move(
+        &mut self,
+        cube: &mut Cube,
+        history: &mut MoveHistory,
+        now_ms: f64,
+    ) -> bool {
+        let Some(made) = self.pending.pop_front() else {
+            return false;
+        };
+        match history.make(cube, made.clone()) {
+            Ok(()) => {
+                self.stop();
+                self.animation = TurnAnimation::for_move(
+                    &made,
+                    cube.side_length(),
+                    now_ms,
+                    USER_MOVE_ANIMATION_MS,
+                    self.easing,
+                    self.style,
+                );
+                self.ticker.push(made, now_ms);
+                true
+            }
+            Err(e) => {
+                warn!("Could not make move: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Returns whether a move was applied to the cube, which happens for the next queued user move once the latest turn has finished animating, or otherwise once per interval while playing.
+    pub(super) fn tick(&mut self, now_ms: f64, cube: &mut Cube, history: &mut MoveHistory) -> bool {
+        if !self.pending.is_empty() {
+            return self.animation(now_ms).is_none()
+                && self.make_pending_move(cube, history, now_ms);
+        }
+        if !self.playing || now_ms < self.next_step_at_ms {
+            return false;
+        }
+        self.next_step_at_ms = now_ms + self.step_interval_ms();
+        let stepped = self.step_forward(cube, history, now_ms);
+        if self.applied == self.steps.len() {
+            self.playing = false;
+        }
+        stepped
+    }
+
+    /// Returns the turn to draw part way through at the given time, if the latest step is still animating.
+    pub(super) fn animation(&mut self, now_ms: f64) -> Option<&TurnAnimation> {
+        if self
+            .animation
+            .as_ref()
+            .is_some_and(|animation| animation.is_finished(now_ms))
+        {
+            self.animation = None;
+        }
+        self.animation.as_ref()
+    }
+
+    fn animate(&self, step: &Move, cube: &Cube, now_ms: f64) -> Option<TurnAnimation> {
+        TurnAnimation::for_move(
+            step,
+            cube.side_length(),
+            now_ms,
+            self.step_interval_ms() * ANIMATED_FRACTION_OF_STEP,
+            self.easing,
+            self.style,
+        )
+    }
+
+    fn step_interval_ms(&self) -> f64 {
+        if self.fast {
+            1000. / MAX_MOVES_PER_SECOND
+        } else {
+            1000. / self.moves_per_second
+        }
+    }
+
+    fn follows(&mut self, history: &MoveHistory) -> bool {
+        let follows = history.position() == self.history_start + self.applied;
+        if !follows {
+            self.stop();
+        }
+        follows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rusty_puzzle_cube::notation::perform_3x3_sequence;
+
+    #[test]
+    fn test_applies_one_step_per_interval() {
+        let mut cube = Cube::create(3);
+        let mut expected = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        let interval = playback.step_interval_ms();
+        playback.start("F (R U)2", &history, 0.);
+
+        assert!(playback.tick(0., &mut cube, &mut history));
+        perform_3x3_sequence("F", &mut expected).unwrap();
+        assert_eq!(expected, cube);
+
+        assert!(!playback.tick(interval / 2., &mut cube, &mut history));
+        assert!(playback.tick(interval, &mut cube, &mut history));
+        perform_3x3_sequence("(R U)2", &mut expected).unwrap();
+        assert_eq!(expected, cube);
+        assert!(!playback.is_playing());
+        assert!(!playback.tick(interval * 2., &mut cube, &mut history));
+        assert_eq!(2, history.moves().len());
+    }
+
+    #[test]
+    fn test_stops_at_a_step_that_cannot_be_applied() {
+        let mut cube = Cube::create(2);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        let interval = playback.step_interval_ms();
+        playback.start("F M U", &history, 0.);
+
+        assert!(playback.tick(0., &mut cube, &mut history));
+        assert!(!playback.tick(interval, &mut cube, &mut history));
+        assert!(!playback.is_loaded());
+        assert_eq!(1, history.moves().len());
+    }
+
+    #[test]
+    fn test_steps_back_and_forward() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        playback.load(
+            vec![Move::Step("R".to_string()), Move::Step("U".to_string())],
+            &history,
+        );
+
+        assert!(!playback.tick(0., &mut cube, &mut history));
+        assert!(!playback.step_back(&mut cube, &mut history, 0.));
+        assert!(playback.step_forward(&mut cube, &mut history, 0.));
+        assert!(playback.step_forward(&mut cube, &mut history, 0.));
+        assert!(!playback.step_forward(&mut cube, &mut history, 0.));
+        assert_eq!((2, 2), playback.progress());
+
+        assert!(playback.step_back(&mut cube, &mut history, 0.));
+        let mut expected = Cube::create(3);
+        perform_3x3_sequence("R", &mut expected).unwrap();
+        assert_eq!(expected, cube);
+        assert_eq!((1, 2), playback.progress());
+    }
+
+    #[test]
+    fn test_jump_to_end_makes_every_remaining_step() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        playback.start("R U F", &history, 0.);
+        assert!(playback.tick(0., &mut cube, &mut history));
+
+        assert!(playback.jump_to_end(&mut cube, &mut history));
+
+        let mut expected = Cube::create(3);
+        perform_3x3_sequence("R U F", &mut expected).unwrap();
+        assert_eq!(expected, cube);
+        assert_eq!((3, 3), playback.progress());
+        assert!(!playback.is_playing());
+        assert!(!playback.jump_to_end(&mut cube, &mut history));
+    }
+
+    #[test]
+    fn test_animates_each_step_for_part_of_the_interval() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        playback.moves_per_second = 2.;
+        playback.start("R (R U)", &history, 0.);
+
+        assert!(playback.tick(0., &mut cube, &mut history));
+        assert!(playback.animation(399.).is_some());
+        assert_eq!(None, playback.animation(400.));
+
+        assert!(playback.tick(500., &mut cube, &mut history));
+        assert_eq!(None, playback.animation(500.));
+
+        assert!(playback.step_back(&mut cube, &mut history, 1000.));
+        assert_eq!(None, playback.animation(1000.));
+        assert!(playback.step_back(&mut cube, &mut history, 1000.));
+        assert!(playback.animation(1000.).is_some());
+    }
+
+    #[test]
+    fn test_fast_playback_ignores_chosen_speed() {
+        let cube = Cube::create(3);
+        let history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        playback.moves_per_second = MIN_MOVES_PER_SECOND;
+
+        playback.start_fast(
+            "R".to_string(),
+            vec![Move::Step("R".to_string())],
+            &history,
+            0.,
+        );
+        assert_eq!(1000. / MAX_MOVES_PER_SECOND, playback.step_interval_ms());
+
+        playback.load(vec![Move::Step("R".to_string())], &history);
+        assert_eq!(1000. / MIN_MOVES_PER_SECOND, playback.step_interval_ms());
+    }
+
+    #[test]
+    fn test_fast_playback_groups_its_moves_once_finished() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        let interval = playback.step_interval_ms();
+        let steps = ["R", "U", "F"]
+            .map(|step| Move::Step(step.to_string()))
+            .to_vec();
+        playback.start_fast("Shuffle".to_string(), steps, &history, 0.);
+
+        assert!(playback.tick(0., &mut cube, &mut history));
+        assert!(playback.tick(interval, &mut cube, &mut history));
+        assert_eq!(2, history.moves().len());
+        assert!(playback.tick(interval * 2., &mut cube, &mut history));
+
+        assert_eq!(1, history.moves().len());
+        assert_eq!("Shuffle", history.moves()[0].label(|_| "transform"));
+        assert!(!playback.is_loaded());
+        assert!(playback.animation(interval * 2.).is_some());
+        assert!(history.undo(&mut cube));
+        assert_eq!(Cube::create(3), cube);
+    }
+
+    #[test]
+    fn test_user_moves_during_an_animation_are_made_in_order() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        let [r, u, f] = ["R", "U", "F"].map(|step| Move::Step(step.to_string()));
+
+        assert!(playback.make_user_move(r, &mut cube, &mut history, 0.));
+        assert!(!playback.make_user_move(u, &mut cube, &mut history, 10.));
+        assert!(!playback.make_user_move(f, &mut cube, &mut history, 20.));
+        assert_eq!(1, history.moves().len());
+
+        assert!(!playback.tick(USER_MOVE_ANIMATION_MS / 2., &mut cube, &mut history));
+        assert!(playback.tick(USER_MOVE_ANIMATION_MS, &mut cube, &mut history));
+        assert!(playback.animation(USER_MOVE_ANIMATION_MS).is_some());
+        assert!(playback.tick(USER_MOVE_ANIMATION_MS * 2., &mut cube, &mut history));
+        assert!(!playback.tick(USER_MOVE_ANIMATION_MS * 3., &mut cube, &mut history));
+
+        let mut expected = Cube::create(3);
+        perform_3x3_sequence("R U F", &mut expected).unwrap();
+        assert_eq!(expected, cube);
+        assert_eq!(3, history.moves().len());
+    }
+
+    #[test]
+    fn test_moves_beyond_the_queue_limit_are_dropped() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        let r = Move::Step("R".to_string());
+        playback.make_user_move(r.clone(), &mut cube, &mut history, 0.);
+        for _ in 0..MAX_PENDING_MOVES * 2 {
+            assert!(!playback.make_user_move(r.clone(), &mut cube, &mut history, 0.));
+        }
+
+        assert!(playback.finish_pending_moves(&mut cube, &mut history));
+
+        assert_eq!(1 + MAX_PENDING_MOVES, history.moves().len());
+    }
+
+    #[test]
+    fn test_reset_forgets_queued_moves() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        playback.make_user_move(Move::Step("R".to_string()), &mut cube, &mut history, 0.);
+        playback.make_user_move(Move::Step("U".to_string()), &mut cube, &mut history, 0.);
+
+        playback.reset();
+
+        assert_eq!(None, playback.animation(0.));
+        assert!(!playback.finish_pending_moves(&mut cube, &mut history));
+        assert_eq!(1, history.moves().len());
+    }
+
+    #[test]
+    fn test_finish_pending_moves_makes_every_queued_move() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        playback.make_user_move(Move::Step("R".to_string()), &mut cube, &mut history, 0.);
+        playback.make_user_move(Move::Step("U".to_string()), &mut cube, &mut history, 0.);
+
+        assert!(playback.finish_pending_moves(&mut cube, &mut history));
+
+        let mut expected = Cube::create(3);
+        perform_3x3_sequence("R U", &mut expected).unwrap();
+        assert_eq!(expected, cube);
+        assert_eq!(None, playback.animation(0.));
+        assert!(!playback.finish_pending_moves(&mut cube, &mut history));
+    }
+
+    #[test]
+    fn test_undo_makes_queued_moves_first() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        for step in ["R", "U", "F"] {
+            playback.make_user_move(Move::Step(step.to_string()), &mut cube, &mut history, 0.);
+        }
+
+        assert!(playback.undo(&mut cube, &mut history));
+
+        let mut expected = Cube::create(3);
+        perform_3x3_sequence("R U", &mut expected).unwrap();
+        assert_eq!(expected, cube);
+        assert_eq!(None, playback.animation(0.));
+        assert!(!playback.tick(USER_MOVE_ANIMATION_MS, &mut cube, &mut history));
+        assert!(playback.redo(&mut cube, &mut history));
+        assert_eq!(3, history.position());
+    }
+
+    #[test]
+    fn test_user_move_stops_playback() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        playback.start("R U", &history, 0.);
+
+        assert!(playback.make_user_move(Move::Step("F".to_string()), &mut cube, &mut history, 0.));
+
+        assert!(!playback.is_loaded());
+        assert!(playback.animation(0.).is_some());
+    }
+
+    #[test]
+    fn test_stops_when_the_cube_is_turned_elsewhere() {
+        let mut cube = Cube::create(3);
+        let mut history = MoveHistory::new(&cube);
+        let mut playback = Playback::new();
+        playback.start("R U", &history, 0.);
+
+        history.perform_step(&mut cube, "F").unwrap();
+
+        assert!(!playback.tick(0., &mut cube, &mut history));
+        assert!(!playback.is_loaded());
+        assert_eq!(1, history.moves().len());
+    }
+}
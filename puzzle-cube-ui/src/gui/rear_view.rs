@@ -0,0 +1,74 @@
+use three_d::{Camera, Viewport};
+
+const REAR_VIEW_FRACTION: f32 = 0.3;
+const REAR_VIEW_MARGIN: f32 = 10.;
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_wrap
+)]
+pub(super) fn rear_viewport(main_viewport: Viewport, device_pixel_ratio: f32) -> Viewport {
+    let size = (main_viewport.width.min(main_viewport.height) as f32 * REAR_VIEW_FRACTION) as u32;
+    let margin = (REAR_VIEW_MARGIN * device_pixel_ratio) as u32;
+    if size == 0 || main_viewport.width < size + margin || main_viewport.height < size + margin {
+        return Viewport {
+            width: 0,
+            height: 0,
+            ..main_viewport
+        };
+    }
+    Viewport {
+        x: main_viewport.x + (main_viewport.width - size - margin) as i32,
+        y: main_viewport.y + (main_viewport.height - size - margin) as i32,
+        width: size,
+        height: size,
+    }
+}
+
+pub(super) fn rear_camera(camera: &Camera, viewport: Viewport) -> Camera {
+    let mut rear = camera.clone();
+    let target = *camera.target();
+    rear.set_viewport(viewport);
+    rear.set_view(target - (camera.position() - target), target, *camera.up());
+    rear
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_rear_viewport_is_in_top_right_corner() {
+        let main_viewport = Viewport {
+            x: 200,
+            y: 0,
+            width: 1000,
+            height: 600,
+        };
+
+        assert_eq!(
+            Viewport {
+                x: 200 + 1000 - 180 - 20,
+                y: 600 - 180 - 20,
+                width: 180,
+                height: 180,
+            },
+            rear_viewport(main_viewport, 2.)
+        );
+    }
+
+    #[test]
+    fn test_rear_viewport_is_empty_when_window_minimized() {
+        let minimized_viewport = Viewport {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+        };
+
+        assert_eq!(0, rear_viewport(minimized_viewport, 1.).width);
+    }
+}
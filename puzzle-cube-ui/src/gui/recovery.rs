@@ -0,0 +1,134 @@
+use three_d::egui::{Align2, Area, Context, Frame, Id};
+use tracing::{error, warn};
+
+use super::i18n::Strings;
+
+const TOAST_DURATION_MS: f64 = 4000.;
+const TOAST_MARGIN: f32 = 10.;
+const MAX_CONSECUTIVE_RESTARTS: u32 = 3;
+
+pub(super) struct RendererRecovery {
+    consecutive_failures: u32,
+    restarted_at_ms: Option<f64>,
+    toast_shown: bool,
+}
+
+impl RendererRecovery {
+    pub(super) fn new() -> Self {
+        Self {
+            consecutive_failures: 0,
+            restarted_at_ms: None,
+            toast_shown: false,
+        }
+    }
+
+    /// Returns whether every GPU resource should be recreated, which stops being tried after a few restarts in a row fail, as the context is then gone for good.
+    pub(super) fn on_draw_error(&mut self, error: &impl std::fmt::Display, now_ms: f64) -> bool {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures > MAX_CONSECUTIVE_RESTARTS {
+            if self.consecutive_failures == MAX_CONSECUTIVE_RESTARTS + 1 {
+                error!(
+                    "Error drawing cube {error}, giving up on restarting the renderer after {MAX_CONSECUTIVE_RESTARTS} attempts"
+                );
+            }
+            return false;
+        }
+        warn!("Error drawing cube {error}, restarting the renderer");
+        self.restarted_at_ms = Some(now_ms);
+        true
+    }
+
+    pub(super) fn on_draw_success(&mut self) {
+        self.consecutive_failures = 0;
+    }
+
+    pub(super) fn needs_redraw(&self, now_ms: f64) -> bool {
+        self.is_toast_visible(now_ms) || self.toast_shown
+    }
+
+    pub(super) fn toast(&mut self, gui_ctx: &Context, strings: &Strings, now_ms: f64) {
+        self.toast_shown = self.is_toast_visible(now_ms);
+        if self.toast_shown {
+            Area::new(Id::new("renderer_restarted"))
+                .anchor(Align2::CENTER_BOTTOM, [0., -TOAST_MARGIN])
+                .interactable(false)
+                .show(gui_ctx, |ui| {
+                    Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(strings.renderer_restarted);
+                    });
+                });
+        }
+    }
+
+    fn is_toast_visible(&self, now_ms: f64) -> bool {
+        self.restarted_at_ms
+            .is_some_and(|restarted_at_ms| now_ms - restarted_at_ms < TOAST_DURATION_MS)
+    }
+}
+
+/// Browsers only restore a lost WebGL context when the event reporting the loss is cancelled.
+#[cfg(target_arch = "wasm32")]
+pub(super) fn allow_context_restore() {
+    use wasm_bindgen::{closure::Closure, JsCast as _};
+
+    let Some(canvas) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.query_selector("canvas").ok().flatten())
+    else {
+        warn!("Could not find the canvas, so a lost WebGL context will not be restored");
+        return;
+    };
+    let on_context_lost =
+        Closure::<dyn FnMut(web_sys::Event)>::new(|event: web_sys::Event| event.prevent_default());
+    if canvas
+        .add_event_listener_with_callback(
+            "webglcontextlost",
+            on_context_lost.as_ref().unchecked_ref(),
+        )
+        .is_err()
+    {
+        warn!("Could not listen for WebGL context loss, so a lost context will not be restored");
+    }
+    on_context_lost.forget();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_gives_up_after_repeated_failures() {
+        let mut recovery = RendererRecovery::new();
+
+        let restarts = (0..5)
+            .map(|frame| recovery.on_draw_error(&"context lost", f64::from(frame)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![true, true, true, false, false], restarts);
+    }
+
+    #[test]
+    fn test_success_allows_restarting_again() {
+        let mut recovery = RendererRecovery::new();
+        for frame in 0..3 {
+            recovery.on_draw_error(&"context lost", f64::from(frame));
+        }
+        recovery.on_draw_success();
+
+        assert!(recovery.on_draw_error(&"context lost", 10.));
+    }
+
+    #[test]
+    fn test_toast_redraws_until_hidden() {
+        let mut recovery = RendererRecovery::new();
+        assert!(!recovery.needs_redraw(0.));
+
+        recovery.on_draw_error(&"context lost", 0.);
+        assert!(recovery.needs_redraw(TOAST_DURATION_MS - 1.));
+        recovery.toast_shown = true;
+        assert!(recovery.needs_redraw(TOAST_DURATION_MS));
+        recovery.toast_shown = false;
+        assert!(!recovery.needs_redraw(TOAST_DURATION_MS));
+    }
+}
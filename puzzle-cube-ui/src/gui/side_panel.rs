@@ -1,140 +1,789 @@
-use rusty_puzzle_cube::cube::{face::Face, Cube};
+use rusty_puzzle_cube::{
+    cube::{colour_scheme::ColourScheme, face::Face, rotation::Rotation, Cube},
+    known_transforms::KnownTransform,
+    notation::parse_sequence,
+    solver::layer_by_layer,
+};
 use three_d::{
-    egui::{epaint, special_emojis::GITHUB, Checkbox, FontId, Rgba, Slider, TextStyle, Ui},
-    Camera, ColorMaterial, Context, Gm, InstancedMesh, Mesh, Viewport,
+    egui::{
+        epaint, special_emojis::GITHUB, Button, Checkbox, Color32, ComboBox, FontId, ProgressBar,
+        Rgba, RichText, ScrollArea, Slider, TextEdit, TextStyle, Ui,
+    },
+    Camera, ColorMaterial, Context, Gm, InstancedMesh, Mesh, Srgba, Viewport,
 };
 use tracing::{error, info};
 
+use super::animation::{AnimationStyle, Easing};
+use super::colours::{Palette, StickerColours, DEFAULT_COLOURS};
+use super::defaults::{frame_cube, initial_camera, CameraPreset, CameraTransition};
+#[cfg(not(target_arch = "wasm32"))]
+use super::file_io::copy_image;
+use super::file_io::{save_as_image, MAX_IMAGE_SCALE, MIN_IMAGE_SCALE};
+use super::i18n::{Language, Strings};
+use super::memory;
+use super::move_history::{Move, MoveHistory};
+use super::notation_input::{errors, highlight, summary, validate, INVALID_COLOUR};
 #[cfg(not(target_arch = "wasm32"))]
-use super::file_io::save_as_image;
-use super::{cube_ext::ToInstances, defaults::initial_camera};
+use super::picture::{load, Picture};
+use super::playback::{Playback, MAX_MOVES_PER_SECOND, MIN_MOVES_PER_SECOND};
+use super::transforms::MAX_EXPLODE;
 
 const MIN_CUBE_SIZE: usize = 1;
 const MAX_CUBE_SIZE: usize = 100;
-const UNREASONABLE_MAX_CUBE_SIZE: usize = 2000;
+pub(super) const UNREASONABLE_MAX_CUBE_SIZE: usize = 2000;
 const EXTRA_SPACING: f32 = 10.;
+const AVAILABLE_MEMORY_DIVISOR: usize = 2;
+const MEMORY_WARNING_BYTES: usize = 1024 * 1024 * 1024;
+const MIN_SHUFFLE_MOVES: usize = 1;
+const MAX_SHUFFLE_MOVES: usize = 100;
+const SOLVABLE_SIDE_LENGTH: usize = 3;
+const MOVE_HISTORY_HEIGHT: f32 = 200.;
+const MEMORY_WARNING_COLOUR: Color32 = Color32::from_rgb(255, 180, 60);
 
 macro_rules! rotate_buttons {
-    ($ui:ident, $cube:ident, $instanced_square:ident) => {
-        rotate_buttons!($ui, $cube, $instanced_square, "F", Front);
-        rotate_buttons!($ui, $cube, $instanced_square, "R", Right);
-        rotate_buttons!($ui, $cube, $instanced_square, "U", Up);
-        rotate_buttons!($ui, $cube, $instanced_square, "B", Back);
-        rotate_buttons!($ui, $cube, $instanced_square, "L", Left);
-        rotate_buttons!($ui, $cube, $instanced_square, "D", Down);
+    ($ui:ident, $cube:ident, $history:ident, $updated_cube:ident) => {
+        rotate_buttons!($ui, $cube, $history, $updated_cube, "F", Front);
+        rotate_buttons!($ui, $cube, $history, $updated_cube, "R", Right);
+        rotate_buttons!($ui, $cube, $history, $updated_cube, "U", Up);
+        rotate_buttons!($ui, $cube, $history, $updated_cube, "B", Back);
+        rotate_buttons!($ui, $cube, $history, $updated_cube, "L", Left);
+        rotate_buttons!($ui, $cube, $history, $updated_cube, "D", Down);
     };
-    ($ui:ident, $cube:ident, $instanced_square:ident, $text:literal, $face:ident) => {
+    ($ui:ident, $cube:ident, $history:ident, $updated_cube:ident, $text:literal, $face:ident) => {
         $ui.horizontal(|ui| {
             ui.style_mut().text_styles.insert(
                 TextStyle::Button,
                 FontId::new(24.0, epaint::FontFamily::Proportional),
             );
             if ui.button($text).clicked() {
-                $cube.rotate_face_90_degrees_clockwise(Face::$face);
-                $instanced_square.set_instances(&$cube.to_instances());
+                $updated_cube |= try_rotate($cube, $history, Rotation::clockwise(Face::$face));
             }
             if ui.button(format!("{}'", $text)).clicked() {
-                $cube.rotate_face_90_degrees_anticlockwise(Face::$face);
-                $instanced_square.set_instances(&$cube.to_instances());
+                $updated_cube |= try_rotate($cube, $history, Rotation::anticlockwise(Face::$face));
             }
         });
     };
 }
 
-pub(super) fn header(ui: &mut Ui) {
+macro_rules! lock_checkboxes {
+    ($ui:ident, $cube:ident, $($text:literal, $face:ident),+ $(,)?) => {
+        $ui.horizontal(|ui| {
+            $(
+                let mut locked = $cube.is_layer_locked(Face::$face, 0);
+                if ui.checkbox(&mut locked, $text).changed() {
+                    if locked {
+                        $cube.lock_layer(Face::$face, 0);
+                    } else {
+                        $cube.unlock_layer(Face::$face, 0);
+                    }
+                }
+            )+
+        });
+    };
+}
+
+fn try_rotate(cube: &mut Cube, history: &mut MoveHistory, rotation: Rotation) -> bool {
+    match history.rotate(cube, rotation) {
+        Ok(()) => true,
+        Err(e) => {
+            error!("Could not make rotation: {}", e);
+            false
+        }
+    }
+}
+
+pub(super) fn header(ui: &mut Ui, language: &mut Language) {
+    let strings = language.strings();
     ui.heading("Rusty Puzzle Cube");
-    ui.label("By Mike Croall");
+    ui.label(strings.by_author);
     ui.hyperlink_to(
-        format!("{GITHUB} on GitHub"),
+        format!("{GITHUB} {}", strings.on_github),
         "https://github.com/MikeCroall/rusty-puzzle-cube/",
     );
+    ComboBox::from_label(strings.language)
+        .selected_text(language.name())
+        .show_ui(ui, |ui| {
+            for option in Language::ALL {
+                ui.selectable_value(language, option, option.name());
+            }
+        });
     ui.add_space(EXTRA_SPACING);
     ui.separator();
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) fn initialise_cube(
     ui: &mut Ui,
+    strings: &Strings,
     unreasonable_mode: &mut bool,
+    japanese_colours: &mut bool,
     side_length: &mut usize,
+    supercube: bool,
     cube: &mut Cube,
-    instanced_square: &mut Gm<InstancedMesh, ColorMaterial>,
-) {
+    history: &mut MoveHistory,
+    playback: &mut Playback,
+) -> bool {
     ui.add_space(EXTRA_SPACING);
-    ui.heading("Initialise Cube");
+    ui.heading(strings.initialise_cube);
     let slider_max_value = if *unreasonable_mode {
-        UNREASONABLE_MAX_CUBE_SIZE
+        memory::available_bytes().map_or(UNREASONABLE_MAX_CUBE_SIZE, |available| {
+            memory::max_side_length_within(
+                available / AVAILABLE_MEMORY_DIVISOR,
+                MAX_CUBE_SIZE,
+                UNREASONABLE_MAX_CUBE_SIZE,
+            )
+        })
     } else {
         MAX_CUBE_SIZE
     };
     let prev_side_length = *side_length;
     ui.add(
         Slider::new(side_length, MIN_CUBE_SIZE..=slider_max_value)
-            .text(format!("{prev_side_length}x{prev_side_length} Cube")),
+            .text((strings.cube_size)(prev_side_length)),
     );
     if ui
-        .checkbox(unreasonable_mode, "Unreasonable mode")
+        .checkbox(unreasonable_mode, strings.unreasonable_mode)
         .changed()
         && !*unreasonable_mode
         && MAX_CUBE_SIZE < *side_length
     {
         *side_length = MAX_CUBE_SIZE;
     };
-    if ui.button("Apply").clicked() {
-        *cube = Cube::create(*side_length);
-        instanced_square.set_instances(&cube.to_instances());
+    if *unreasonable_mode {
+        let approx_bytes = memory::approx_bytes_for(*side_length);
+        ui.label((strings.approx_memory)(memory::mebibytes(approx_bytes)));
+        if MEMORY_WARNING_BYTES < approx_bytes {
+            ui.colored_label(MEMORY_WARNING_COLOUR, strings.memory_warning);
+        }
+    }
+    let recoloured = ui
+        .checkbox(japanese_colours, strings.japanese_colours)
+        .changed();
+    let updated_cube = ui.button(strings.apply).clicked();
+    if updated_cube {
+        *cube = if supercube {
+            Cube::create_supercube(*side_length)
+        } else {
+            Cube::create(*side_length)
+        };
+        history.reset(cube);
+        playback.reset();
     }
     ui.add_space(EXTRA_SPACING);
     ui.separator();
+    updated_cube || recoloured
+}
+
+pub(super) fn colours(
+    ui: &mut Ui,
+    strings: &Strings,
+    palette: &mut Palette,
+    custom_colours: &mut StickerColours,
+) -> bool {
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.colours);
+    let mut recoloured = false;
+    ComboBox::from_label(strings.palette)
+        .selected_text(palette_name(strings, *palette))
+        .show_ui(ui, |ui| {
+            for option in Palette::ALL {
+                recoloured |= ui
+                    .selectable_value(palette, option, palette_name(strings, option))
+                    .changed();
+            }
+        });
+    if *palette == Palette::Custom {
+        for (name, colour) in [
+            (strings.blue_stickers, &mut custom_colours.blue),
+            (strings.green_stickers, &mut custom_colours.green),
+            (strings.orange_stickers, &mut custom_colours.orange),
+            (strings.red_stickers, &mut custom_colours.red),
+            (strings.white_stickers, &mut custom_colours.white),
+            (strings.yellow_stickers, &mut custom_colours.yellow),
+        ] {
+            ui.horizontal(|ui| {
+                let mut rgb = [colour.r, colour.g, colour.b];
+                if ui.color_edit_button_srgb(&mut rgb).changed() {
+                    *colour = Srgba::new_opaque(rgb[0], rgb[1], rgb[2]);
+                    recoloured = true;
+                }
+                ui.label(name);
+            });
+        }
+        if ui.button(strings.reset_colours).clicked() {
+            *custom_colours = DEFAULT_COLOURS;
+            recoloured = true;
+        }
+    }
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+    recoloured
+}
+
+/// Loads images onto faces of the cube, starting a super cube when the cube does not already track where its stickers started, as the picture needs that to follow them.
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn picture(
+    ui: &mut Ui,
+    strings: &Strings,
+    path: &mut String,
+    picture: &mut Picture,
+    cube: &mut Cube,
+    history: &mut MoveHistory,
+    playback: &mut Playback,
+) -> bool {
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.picture_cube);
+    ui.label(strings.picture_cube_hint);
+    ui.add(TextEdit::singleline(path).hint_text(strings.picture_path));
+    let mut repictured = false;
+    for (text, face) in [
+        ("F", Face::Front),
+        ("R", Face::Right),
+        ("U", Face::Up),
+        ("B", Face::Back),
+        ("L", Face::Left),
+        ("D", Face::Down),
+    ] {
+        ui.horizontal(|ui| {
+            ui.label(text);
+            if ui.button(strings.load_picture).clicked() {
+                match load(path.trim()).and_then(|image| picture.set_face(face, &image)) {
+                    Ok(()) => {
+                        info!("Loaded picture for face {}", text);
+                        repictured = true;
+                    }
+                    Err(e) => error!("Could not load picture: {}", e),
+                }
+            }
+            if ui
+                .add_enabled(picture.has_face(face), Button::new(strings.clear_picture))
+                .clicked()
+            {
+                picture.clear_face(face);
+                repictured = true;
+            }
+        });
+    }
+    if repictured && !picture.is_empty() && cube.sticker_origin_map().is_none() {
+        info!("Starting a super cube to show the picture");
+        *cube = Cube::create_supercube(cube.side_length());
+        history.reset(cube);
+        playback.reset();
+    }
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+    repictured
 }
 
 pub(super) fn control_cube(
     ui: &mut Ui,
+    strings: &Strings,
+    cube: &mut Cube,
+    history: &mut MoveHistory,
+    animation_style: &mut AnimationStyle,
+) -> bool {
+    let mut updated_cube = false;
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.control_cube);
+    ui.label(strings.drag_to_rotate);
+    ui.label(strings.drag_one_face);
+    ui.label(strings.drag_cancelled);
+    ui.label(strings.drag_modifiers_hint);
+    ui.add_space(EXTRA_SPACING);
+    ui.label(strings.use_buttons);
+    rotate_buttons!(ui, cube, history, updated_cube);
+    ui.add_space(EXTRA_SPACING);
+    ui.label(strings.keyboard_hint);
+    ui.label(strings.face_keys_hint);
+    ui.add_space(EXTRA_SPACING);
+    ComboBox::from_label(strings.animation_style)
+        .selected_text(animation_style_name(strings, *animation_style))
+        .show_ui(ui, |ui| {
+            for option in AnimationStyle::ALL {
+                ui.selectable_value(
+                    animation_style,
+                    option,
+                    animation_style_name(strings, option),
+                );
+            }
+        });
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+    updated_cube
+}
+
+pub(super) fn lock_faces(ui: &mut Ui, strings: &Strings, cube: &mut Cube) {
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.lock_faces);
+    ui.label(strings.lock_faces_hint);
+    lock_checkboxes!(ui, cube, "F", Front, "R", Right, "U", Up, "B", Back, "L", Left, "D", Down);
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+}
+
+pub(super) fn apply_sequence(
+    ui: &mut Ui,
+    strings: &Strings,
+    sequence: &mut String,
     cube: &mut Cube,
-    instanced_square: &mut Gm<InstancedMesh, ColorMaterial>,
+    history: &mut MoveHistory,
+    playback: &mut Playback,
+    now_ms: f64,
+) -> bool {
+    let mut updated_cube = false;
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.apply_sequence_heading);
+    ui.label(strings.sequence_hint);
+    let spans = validate(sequence);
+    let mut layouter = |ui: &Ui, text: &str, wrap_width: f32| {
+        let font_id = TextStyle::Monospace.resolve(ui.style());
+        let mut job = highlight(text, &validate(text), &font_id, ui.visuals().text_color());
+        job.wrap.max_width = wrap_width;
+        ui.fonts(|fonts| fonts.layout_job(job))
+    };
+    let response = ui.add(TextEdit::singleline(sequence).layouter(&mut layouter));
+    if let Some(errors) = errors(&spans) {
+        response.on_hover_text(errors);
+    }
+    ui.label(summary(strings, &spans));
+    let parse_error = (!spans.is_empty())
+        .then(|| parse_sequence(sequence).err())
+        .flatten();
+    if let Some(e) = &parse_error {
+        ui.colored_label(
+            INVALID_COLOUR,
+            (strings.step_error)(e.token_index + 1, &e.message),
+        );
+    }
+    ui.horizontal(|ui| {
+        let can_run = parse_error.is_none() && !spans.is_empty() && !playback.is_playing();
+        if ui
+            .add_enabled(can_run, Button::new(strings.apply_sequence))
+            .clicked()
+        {
+            let made = if let [span] = spans.as_slice() {
+                history.perform_step(cube, &sequence[span.range.clone()])
+            } else {
+                let moves = spans
+                    .iter()
+                    .map(|span| Move::Step(sequence[span.range.clone()].to_string()))
+                    .collect();
+                history.make(
+                    cube,
+                    Move::Group {
+                        label: sequence.trim().to_string(),
+                        moves,
+                    },
+                )
+            };
+            match made {
+                Ok(()) => updated_cube = true,
+                Err(e) => error!("Could not apply sequence: {}", e),
+            }
+        }
+        if ui
+            .add_enabled(can_run, Button::new(strings.run_sequence))
+            .clicked()
+        {
+            playback.start(sequence, history, now_ms);
+        }
+    });
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+    updated_cube
+}
+
+pub(super) fn known_transforms(
+    ui: &mut Ui,
+    strings: &Strings,
+    cube: &mut Cube,
+    history: &mut MoveHistory,
+) -> bool {
+    let mut updated_cube = false;
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.known_transforms);
+    ui.label(strings.transforms_applied_in_full);
+    ui.add_enabled_ui(cube.locked_layers().is_empty(), |ui| {
+        let mut transforms = KnownTransform::for_side_length(cube.side_length()).peekable();
+        if transforms.peek().is_none() {
+            ui.label(strings.no_known_transforms);
+        }
+        for transform in transforms {
+            if ui.button(transform_name(strings, transform)).clicked() {
+                match history.apply_transform(cube, transform) {
+                    Ok(()) => updated_cube = true,
+                    Err(e) => error!("Could not apply known transform: {}", e),
+                }
+            }
+        }
+    })
+    .response
+    .on_disabled_hover_text(strings.unlock_to_apply);
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+    updated_cube
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn shuffle(
+    ui: &mut Ui,
+    strings: &Strings,
+    moves: &mut usize,
+    instant: &mut bool,
+    cube: &mut Cube,
+    history: &mut MoveHistory,
+    playback: &mut Playback,
+    now_ms: f64,
+) -> bool {
+    let mut updated_cube = false;
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.shuffle_heading);
+    ui.add(Slider::new(moves, MIN_SHUFFLE_MOVES..=MAX_SHUFFLE_MOVES).text(strings.shuffle_moves));
+    ui.checkbox(instant, strings.instant_shuffle);
+    ui.add_enabled_ui(cube.locked_layers().is_empty(), |ui| {
+        if ui.button(strings.shuffle).clicked() {
+            if *instant {
+                playback.stop();
+                history.record(Move::Group {
+                    label: (strings.shuffled)(*moves),
+                    moves: cube
+                        .shuffle(*moves)
+                        .into_iter()
+                        .map(Move::Rotation)
+                        .collect(),
+                });
+                updated_cube = true;
+            } else {
+                let side_length = cube.side_length();
+                let rotations = (0..*moves)
+                    .map(|_| Move::Rotation(Rotation::random(side_length)))
+                    .collect();
+                playback.start_fast((strings.shuffled)(*moves), rotations, history, now_ms);
+            }
+        }
+    })
+    .response
+    .on_disabled_hover_text(strings.unlock_to_apply);
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+    updated_cube
+}
+
+pub(super) fn solve(
+    ui: &mut Ui,
+    strings: &Strings,
+    cube: &Cube,
+    history: &MoveHistory,
+    playback: &mut Playback,
 ) {
     ui.add_space(EXTRA_SPACING);
-    ui.heading("Control Cube");
-    ui.label("Click and drag directly on the cube to make a rotation");
-    ui.label("You must only drag across one face of the cube");
-    ui.label(
-        "Dragging to another face, diagonally, or for a very small distance will be cancelled",
+    ui.heading(strings.solve_heading);
+    ui.label(strings.solve_hint);
+    ui.add_enabled_ui(cube.side_length() == SOLVABLE_SIDE_LENGTH, |ui| {
+        if ui.button(strings.solve).clicked() {
+            match layer_by_layer::solve(cube) {
+                Ok(solution) if solution.is_empty() => info!("The cube is already solved"),
+                Ok(solution) => {
+                    info!("Found a solution of {} moves", solution.len());
+                    playback.load(solution.into_iter().map(Move::Rotation).collect(), history);
+                }
+                Err(e) => error!("Could not solve the cube: {}", e),
+            }
+        }
+    })
+    .response
+    .on_disabled_hover_text(strings.only_3x3_solvable);
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+}
+
+pub(super) fn playback(
+    ui: &mut Ui,
+    strings: &Strings,
+    cube: &mut Cube,
+    history: &mut MoveHistory,
+    playback: &mut Playback,
+    now_ms: f64,
+) -> bool {
+    let mut updated_cube = false;
+    if !playback.is_loaded() {
+        return updated_cube;
+    }
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.playback_heading);
+    let (applied, total) = playback.progress();
+    ui.add(
+        ProgressBar::new(applied as f32 / total as f32)
+            .text((strings.playback_progress)(applied, total)),
+    );
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(0 < applied, Button::new(strings.step_back))
+            .clicked()
+        {
+            playback.pause();
+            updated_cube |= playback.step_back(cube, history, now_ms);
+        }
+        if playback.is_playing() {
+            if ui.button(strings.pause).clicked() {
+                playback.pause();
+            }
+        } else if ui
+            .add_enabled(applied < total, Button::new(strings.play))
+            .clicked()
+        {
+            playback.play(now_ms);
+        }
+        if ui
+            .add_enabled(applied < total, Button::new(strings.step_forward))
+            .clicked()
+        {
+            playback.pause();
+            updated_cube |= playback.step_forward(cube, history, now_ms);
+        }
+        if ui
+            .add_enabled(applied < total, Button::new(strings.jump_to_end))
+            .clicked()
+        {
+            updated_cube |= playback.jump_to_end(cube, history);
+        }
+        if ui.button(strings.stop_sequence).clicked() {
+            playback.stop();
+        }
+    });
+    ui.add(
+        Slider::new(
+            &mut playback.moves_per_second,
+            MIN_MOVES_PER_SECOND..=MAX_MOVES_PER_SECOND,
+        )
+        .text(strings.moves_per_second),
     );
+    ComboBox::from_label(strings.easing)
+        .selected_text(easing_name(strings, playback.easing))
+        .show_ui(ui, |ui| {
+            for option in Easing::ALL {
+                ui.selectable_value(&mut playback.easing, option, easing_name(strings, option));
+            }
+        });
     ui.add_space(EXTRA_SPACING);
-    ui.label("Alternatively, use the buttons below");
-    rotate_buttons!(ui, cube, instanced_square);
+    ui.separator();
+    updated_cube
+}
+
+pub(super) fn move_history(
+    ui: &mut Ui,
+    strings: &Strings,
+    cube: &mut Cube,
+    history: &mut MoveHistory,
+    playback: &mut Playback,
+) -> bool {
+    let mut jump_to = None;
     ui.add_space(EXTRA_SPACING);
-    ui.label("Moves of inner rows or columns are not currently supported");
+    ui.heading(strings.move_history);
+    ui.label(strings.move_history_hint);
+    let mut updated_cube = false;
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(0 < history.position(), Button::new(strings.undo))
+            .clicked()
+        {
+            updated_cube |= playback.undo(cube, history);
+        }
+        if ui
+            .add_enabled(
+                history.position() < history.moves().len(),
+                Button::new(strings.redo),
+            )
+            .clicked()
+        {
+            updated_cube |= playback.redo(cube, history);
+        }
+    });
+    ScrollArea::vertical()
+        .id_source("move_history")
+        .max_height(MOVE_HISTORY_HEIGHT)
+        .show(ui, |ui| {
+            if ui
+                .selectable_label(history.position() == 0, strings.history_start)
+                .clicked()
+            {
+                jump_to = Some(0);
+            }
+            for (i, made) in history.moves().iter().enumerate() {
+                let label =
+                    |made: &Move| made.label(|transform| transform_name(strings, transform));
+                let mut text = RichText::new(format!("{}. {}", i + 1, label(made))).monospace();
+                if history.position() <= i {
+                    text = text.weak();
+                }
+                let mut response = ui.selectable_label(history.position() == i + 1, text);
+                if let Move::Group { moves, .. } = made {
+                    response = response
+                        .on_hover_text(moves.iter().map(label).collect::<Vec<_>>().join(" "));
+                }
+                if response.clicked() {
+                    jump_to = Some(i + 1);
+                }
+            }
+        });
     ui.add_space(EXTRA_SPACING);
     ui.separator();
+    if let Some(position) = jump_to {
+        playback.jump_to(position, cube, history);
+        updated_cube = true;
+    }
+    updated_cube
+}
+
+fn palette_name(strings: &Strings, palette: Palette) -> &'static str {
+    match palette {
+        Palette::Default => strings.default_palette,
+        Palette::ColourBlind => strings.colour_blind_palette,
+        Palette::Custom => strings.custom_palette,
+    }
+}
+
+fn preset_name(strings: &Strings, preset: CameraPreset) -> &'static str {
+    match preset {
+        CameraPreset::FrontRightUp => strings.front_right_up,
+        CameraPreset::BackLeftDown => strings.back_left_down,
+        CameraPreset::Isometric => strings.isometric,
+        CameraPreset::Front => strings.front_view,
+        CameraPreset::Back => strings.back_view,
+        CameraPreset::Left => strings.left_view,
+        CameraPreset::Right => strings.right_view,
+        CameraPreset::Up => strings.top_view,
+        CameraPreset::Down => strings.bottom_view,
+    }
+}
+
+fn easing_name(strings: &Strings, easing: Easing) -> &'static str {
+    match easing {
+        Easing::Linear => strings.linear,
+        Easing::EaseInOut => strings.ease_in_out,
+        Easing::Bounce => strings.bounce,
+    }
+}
+
+fn animation_style_name(strings: &Strings, style: AnimationStyle) -> &'static str {
+    match style {
+        AnimationStyle::Turn => strings.turn_style,
+        AnimationStyle::SnapAndFade => strings.snap_and_fade,
+        AnimationStyle::InstantDeepLayers => strings.instant_deep_layers,
+        AnimationStyle::Instant => strings.instant,
+    }
 }
 
+pub(super) fn transform_name(strings: &Strings, transform: KnownTransform) -> &'static str {
+    match transform {
+        KnownTransform::Checkerboard => strings.checkerboard,
+        KnownTransform::CheckerboardCorners => strings.checkerboard_corners,
+        KnownTransform::CubeInCubeInCube => strings.cube_in_cube_in_cube,
+        KnownTransform::Frames => strings.frames,
+        KnownTransform::SideStripes => strings.side_stripes,
+        KnownTransform::Stripes => strings.stripes,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(super) fn control_camera(
     ui: &mut Ui,
+    strings: &Strings,
     camera: &mut Camera,
     viewport: Viewport,
+    transition: &mut Option<CameraTransition>,
+    now_ms: f64,
     render_axes: &mut bool,
-) {
+    auto_frame: &mut bool,
+    show_rear_view: &mut bool,
+    show_net: &mut bool,
+    explode: &mut f32,
+) -> bool {
     ui.add_space(EXTRA_SPACING);
-    ui.heading("Control Camera etc.");
-    ui.label("The camera can be moved with a click and drag starting from the blank space around the cube, or by dragging from one face to any other face or empty space");
-    if ui.button("Reset camera").clicked() {
+    ui.heading(strings.control_camera);
+    ui.label(strings.camera_help);
+    ui.label(strings.touch_help);
+    if ui.button(strings.reset_camera).clicked() {
+        *transition = None;
         *camera = initial_camera(viewport);
     }
-    ui.add(Checkbox::new(render_axes, "Show axes"));
+    if ui.button(strings.frame_cube).clicked() {
+        frame_cube(camera);
+    }
+    ui.label(strings.camera_presets);
+    ui.horizontal_wrapped(|ui| {
+        for preset in CameraPreset::ALL {
+            if ui.button(preset_name(strings, preset)).clicked() {
+                *transition = Some(CameraTransition::to_preset(camera, preset, now_ms));
+            }
+        }
+    });
+    ui.add(Checkbox::new(auto_frame, strings.auto_frame));
+    ui.add(Checkbox::new(show_rear_view, strings.show_rear_view));
+    ui.add(Checkbox::new(show_net, strings.show_net));
+    let exploded = ui
+        .add(Slider::new(explode, 0.0..=MAX_EXPLODE).text(strings.exploded_view))
+        .on_hover_text(strings.exploded_view_hint)
+        .changed();
+    ui.add(Checkbox::new(render_axes, strings.show_axes));
     if *render_axes {
-        ui.colored_label(Rgba::from_rgb(0.15, 0.15, 1.), "F is the blue axis");
-        ui.colored_label(Rgba::RED, "R is the red axis");
-        ui.colored_label(Rgba::GREEN, "U is the green axis");
+        ui.colored_label(Rgba::from_rgb(0.15, 0.15, 1.), strings.f_axis);
+        ui.colored_label(Rgba::RED, strings.r_axis);
+        ui.colored_label(Rgba::GREEN, strings.u_axis);
     }
 
     ui.add_space(EXTRA_SPACING);
     ui.separator();
+    exploded
 }
 
-#[cfg(not(target_arch = "wasm32"))]
-pub(super) fn debug(
+pub(super) fn diagnostics(
     ui: &mut Ui,
-    cube: &Cube,
+    strings: &Strings,
+    show_stats: &mut bool,
+    show_log: &mut bool,
+) {
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.diagnostics);
+    ui.add(Checkbox::new(show_stats, strings.show_stats));
+    ui.add(Checkbox::new(show_log, strings.show_log));
+
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+}
+
+pub(super) fn saved_state(ui: &mut Ui, strings: &Strings, cube: &Cube) -> bool {
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.saved_state);
+    ui.label(strings.saved_state_hint);
+    let reset = ui.button(strings.reset_everything).clicked();
+    #[cfg(target_arch = "wasm32")]
+    if ui
+        .button(strings.copy_share_link)
+        .on_hover_text(strings.share_link_hint)
+        .clicked()
+    {
+        match super::persistence::location::share(cube) {
+            Some(link) => {
+                info!("Copied share link {}", link);
+                ui.output_mut(|output| output.copied_text = link);
+            }
+            None => error!("Could not create a share link"),
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = cube;
+
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+    reset
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn image(
+    ui: &mut Ui,
+    strings: &Strings,
+    scale: &mut u32,
     ctx: &Context,
     viewport: Viewport,
     camera: &Camera,
@@ -142,14 +791,33 @@ pub(super) fn debug(
     inner_cube: &Gm<Mesh, ColorMaterial>,
 ) {
     ui.add_space(EXTRA_SPACING);
-    ui.heading("Debug");
-    if ui.button("Print cube to terminal").clicked() {
-        info!("\n{cube}");
-    }
+    ui.heading(strings.image);
+    ui.add(Slider::new(scale, MIN_IMAGE_SCALE..=MAX_IMAGE_SCALE).text(strings.image_scale));
 
-    if ui.button("Save as image").clicked() {
-        if let Err(e) = save_as_image(ctx, viewport, camera, tiles, inner_cube) {
-            error!("Could not save image file: {}", e);
+    ui.horizontal(|ui| {
+        if ui.button(strings.save_as_image).clicked() {
+            if let Err(e) = save_as_image(ctx, viewport, camera, tiles, inner_cube, *scale) {
+                error!("Could not save image file: {}", e);
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if ui.button(strings.copy_image).clicked() {
+            match copy_image(ctx, viewport, camera, tiles, inner_cube, *scale) {
+                Ok(()) => info!("Copied image to clipboard"),
+                Err(e) => error!("Could not copy image to clipboard: {}", e),
+            }
         }
+    });
+
+    ui.add_space(EXTRA_SPACING);
+    ui.separator();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn debug(ui: &mut Ui, strings: &Strings, cube: &Cube, scheme: &ColourScheme) {
+    ui.add_space(EXTRA_SPACING);
+    ui.heading(strings.debug);
+    if ui.button(strings.print_cube).clicked() {
+        info!("\n{}", cube.display_with_colour_scheme(scheme));
     }
 }
@@ -0,0 +1,141 @@
+use std::{collections::VecDeque, time::Duration};
+
+use three_d::egui::{Align2, Context, Grid, Id, Window};
+use web_time::Instant;
+
+use super::i18n::Strings;
+
+const FRAME_WINDOW: usize = 120;
+const OVERLAY_MARGIN: f32 = 10.;
+
+pub(super) struct FrameStats {
+    frame_times_ms: VecDeque<f64>,
+    last_instance_rebuild: Option<Duration>,
+}
+
+impl FrameStats {
+    pub(super) fn new() -> Self {
+        Self {
+            frame_times_ms: VecDeque::with_capacity(FRAME_WINDOW),
+            last_instance_rebuild: None,
+        }
+    }
+
+    pub(super) fn record_frame(&mut self, elapsed_time_ms: f64) {
+        if self.frame_times_ms.len() == FRAME_WINDOW {
+            self.frame_times_ms.pop_front();
+        }
+        self.frame_times_ms.push_back(elapsed_time_ms);
+    }
+
+    pub(super) fn time_instance_rebuild(&mut self, rebuild: impl FnOnce()) {
+        let start = Instant::now();
+        rebuild();
+        self.last_instance_rebuild = Some(start.elapsed());
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn mean_frame_time_ms(&self) -> Option<f64> {
+        if self.frame_times_ms.is_empty() {
+            return None;
+        }
+        Some(self.frame_times_ms.iter().sum::<f64>() / self.frame_times_ms.len() as f64)
+    }
+
+    fn max_frame_time_ms(&self) -> Option<f64> {
+        self.frame_times_ms.iter().copied().reduce(f64::max)
+    }
+
+    fn fps(&self) -> Option<f64> {
+        self.mean_frame_time_ms()
+            .filter(|mean| *mean > 0.)
+            .map(|mean| 1000. / mean)
+    }
+
+    pub(super) fn overlay(&self, gui_ctx: &Context, strings: &Strings, instance_count: usize) {
+        Window::new(strings.statistics)
+            .id(Id::new("statistics"))
+            .anchor(Align2::RIGHT_TOP, [-OVERLAY_MARGIN, OVERLAY_MARGIN])
+            .collapsible(false)
+            .resizable(false)
+            .show(gui_ctx, |ui| {
+                Grid::new("statistics_grid").show(ui, |ui| {
+                    ui.label(strings.fps);
+                    ui.label(format_opt(self.fps(), |fps| format!("{fps:.1}")));
+                    ui.end_row();
+
+                    ui.label(strings.frame_time_mean);
+                    ui.label(format_opt(self.mean_frame_time_ms(), |ms| {
+                        format!("{ms:.2} ms")
+                    }));
+                    ui.end_row();
+
+                    ui.label(strings.frame_time_max);
+                    ui.label(format_opt(self.max_frame_time_ms(), |ms| {
+                        format!("{ms:.2} ms")
+                    }));
+                    ui.end_row();
+
+                    ui.label(strings.instances);
+                    ui.label(format!("{instance_count}"));
+                    ui.end_row();
+
+                    ui.label(strings.last_instance_rebuild);
+                    ui.label(format_opt(self.last_instance_rebuild, |duration| {
+                        format!("{duration:.2?}")
+                    }));
+                    ui.end_row();
+                });
+            });
+    }
+}
+
+fn format_opt<T>(value: Option<T>, format: impl FnOnce(T) -> String) -> String {
+    value.map_or_else(|| "-".to_string(), format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_no_frames_has_no_fps() {
+        let stats = FrameStats::new();
+
+        assert_eq!(None, stats.fps());
+        assert_eq!(None, stats.max_frame_time_ms());
+    }
+
+    #[test]
+    fn test_fps_from_mean_frame_time() {
+        let mut stats = FrameStats::new();
+        stats.record_frame(10.);
+        stats.record_frame(30.);
+
+        assert_eq!(Some(20.), stats.mean_frame_time_ms());
+        assert_eq!(Some(50.), stats.fps());
+        assert_eq!(Some(30.), stats.max_frame_time_ms());
+    }
+
+    #[test]
+    fn test_frame_window_drops_oldest_frames() {
+        let mut stats = FrameStats::new();
+        stats.record_frame(1000.);
+        (0..FRAME_WINDOW).for_each(|_| stats.record_frame(20.));
+
+        assert_eq!(FRAME_WINDOW, stats.frame_times_ms.len());
+        assert_eq!(Some(20.), stats.max_frame_time_ms());
+    }
+
+    #[test]
+    fn test_time_instance_rebuild_runs_rebuild() {
+        let mut stats = FrameStats::new();
+        let mut rebuilt = false;
+
+        stats.time_instance_rebuild(|| rebuilt = true);
+
+        assert!(rebuilt);
+        assert!(stats.last_instance_rebuild.is_some());
+    }
+}
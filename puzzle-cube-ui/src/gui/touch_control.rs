@@ -0,0 +1,294 @@
+use three_d::{Camera, Event, InnerSpace, Vec2, Vec3};
+
+const ORBIT_SPEED: f32 = 0.01;
+const ZOOM_SPEED: f32 = 0.02;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(not(any(test, target_arch = "wasm32")), allow(dead_code))]
+pub(super) enum TouchInput {
+    Start { id: i32, position: Vec2 },
+    Move { id: i32, position: Vec2 },
+    End { id: i32 },
+}
+
+/// Two finger gestures, which only ever reach the render loop as mouse events made from the first finger.
+/// Moving both fingers together orbits the camera and pinching zooms it, while single finger drags are left to the mouse events to turn the cube or orbit as a mouse drag would.
+pub(super) struct TouchControl {
+    touches: Vec<(i32, Vec2)>,
+    gesturing: bool,
+    target: Vec3,
+    min_distance: f32,
+    max_distance: f32,
+}
+
+impl TouchControl {
+    pub(super) fn new(target: Vec3, min_distance: f32, max_distance: f32) -> Self {
+        Self {
+            touches: vec![],
+            gesturing: false,
+            target,
+            min_distance,
+            max_distance,
+        }
+    }
+
+    /// Returns whether the camera moved.
+    pub(super) fn handle_inputs(
+        &mut self,
+        inputs: impl IntoIterator<Item = TouchInput>,
+        camera: &mut Camera,
+    ) -> bool {
+        let mut moved = false;
+        for input in inputs {
+            let Some((shift, spread)) = self.track(input) else {
+                continue;
+            };
+            camera.rotate_around_with_fixed_up(
+                &self.target,
+                -ORBIT_SPEED * shift.x,
+                ORBIT_SPEED * shift.y,
+            );
+            camera.zoom_towards(
+                &self.target,
+                ZOOM_SPEED * spread,
+                self.min_distance,
+                self.max_distance,
+            );
+            moved = true;
+        }
+        moved
+    }
+
+    /// Whether a second finger has touched since every finger was last lifted, so the mouse events made from the first finger should be ignored.
+    pub(super) fn is_gesturing(&self) -> bool {
+        self.gesturing
+    }
+
+    /// Marks the mouse events made from the touches as handled, so the orbit control leaves the camera to the gesture.
+    pub(super) fn claim_mouse_events(events: &mut [Event]) {
+        for event in events {
+            match event {
+                Event::MousePress { handled, .. }
+                | Event::MouseRelease { handled, .. }
+                | Event::MouseMotion { handled, .. }
+                | Event::MouseWheel { handled, .. } => *handled = true,
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns how far the midpoint of the first two fingers moved and how much further apart they spread, when the input moved one of them.
+    fn track(&mut self, input: TouchInput) -> Option<(Vec2, f32)> {
+        match input {
+            TouchInput::Start { id, position } => {
+                self.touches.push((id, position));
+                self.gesturing |= 1 < self.touches.len();
+                None
+            }
+            TouchInput::End { id } => {
+                self.touches.retain(|&(touch_id, _)| touch_id != id);
+                self.gesturing &= !self.touches.is_empty();
+                None
+            }
+            TouchInput::Move { id, position } => {
+                let index = self
+                    .touches
+                    .iter()
+                    .position(|&(touch_id, _)| touch_id == id)?;
+                let before = self.first_two()?;
+                self.touches[index].1 = position;
+                let after = self.first_two()?;
+                (index < 2).then(|| gesture(before, after))
+            }
+        }
+    }
+
+    fn first_two(&self) -> Option<[Vec2; 2]> {
+        match self.touches[..] {
+            [(_, first), (_, second), ..] => Some([first, second]),
+            _ => None,
+        }
+    }
+}
+
+fn gesture(before: [Vec2; 2], after: [Vec2; 2]) -> (Vec2, f32) {
+    let midpoint = |[first, second]: [Vec2; 2]| (first + second) / 2.;
+    let spread = |[first, second]: [Vec2; 2]| (second - first).magnitude();
+    (
+        midpoint(after) - midpoint(before),
+        spread(after) - spread(before),
+    )
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) mod listener {
+    use std::{cell::RefCell, rc::Rc};
+
+    use three_d::vec2;
+    use tracing::warn;
+    use wasm_bindgen::{closure::Closure, JsCast as _};
+
+    use super::TouchInput;
+
+    const EVENTS: [&str; 4] = ["touchstart", "touchmove", "touchend", "touchcancel"];
+
+    /// Collects the touches made on the canvas between frames, as three-d only passes on the first finger.
+    pub(super) struct TouchListener {
+        inputs: Rc<RefCell<Vec<TouchInput>>>,
+    }
+
+    impl TouchListener {
+        pub(super) fn attach() -> Self {
+            let inputs = Rc::new(RefCell::new(vec![]));
+            let Some(canvas) = web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.query_selector("canvas").ok().flatten())
+            else {
+                warn!("Could not find the canvas, so two finger gestures will not work");
+                return Self { inputs };
+            };
+            let scale = web_sys::window().map_or(1., |window| window.device_pixel_ratio()) as f32;
+            for name in EVENTS {
+                let inputs = inputs.clone();
+                let on_touch = Closure::<dyn FnMut(web_sys::TouchEvent)>::new(
+                    move |event: web_sys::TouchEvent| {
+                        let touches = event.changed_touches();
+                        for touch in (0..touches.length()).filter_map(|i| touches.get(i)) {
+                            let id = touch.identifier();
+                            #[allow(clippy::cast_precision_loss)]
+                            let position = vec2(
+                                touch.client_x() as f32 * scale,
+                                touch.client_y() as f32 * scale,
+                            );
+                            inputs.borrow_mut().push(match name {
+                                "touchstart" => TouchInput::Start { id, position },
+                                "touchmove" => TouchInput::Move { id, position },
+                                _ => TouchInput::End { id },
+                            });
+                        }
+                    },
+                );
+                if canvas
+                    .add_event_listener_with_callback(name, on_touch.as_ref().unchecked_ref())
+                    .is_err()
+                {
+                    warn!("Could not listen for {} events on the canvas", name);
+                }
+                on_touch.forget();
+            }
+            Self { inputs }
+        }
+
+        pub(super) fn drain(&self) -> Vec<TouchInput> {
+            self.inputs.borrow_mut().drain(..).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use three_d::{vec2, vec3};
+
+    fn control() -> TouchControl {
+        TouchControl::new(vec3(0., 0., 0.), 1., 80.)
+    }
+
+    #[test]
+    fn test_one_finger_is_left_to_mouse_events() {
+        let mut control = control();
+
+        assert_eq!(
+            None,
+            control.track(TouchInput::Start {
+                id: 1,
+                position: vec2(0., 0.)
+            })
+        );
+        assert_eq!(
+            None,
+            control.track(TouchInput::Move {
+                id: 1,
+                position: vec2(10., 0.)
+            })
+        );
+        assert!(!control.is_gesturing());
+    }
+
+    #[test]
+    fn test_second_finger_starts_gesture_until_all_lift() {
+        let mut control = control();
+        for id in [1, 2] {
+            control.track(TouchInput::Start {
+                id,
+                position: vec2(0., 0.),
+            });
+        }
+        assert!(control.is_gesturing());
+
+        control.track(TouchInput::End { id: 2 });
+        assert!(control.is_gesturing());
+        control.track(TouchInput::End { id: 1 });
+        assert!(!control.is_gesturing());
+    }
+
+    #[test]
+    fn test_moving_one_of_two_fingers_shifts_and_spreads() {
+        let mut control = control();
+        control.track(TouchInput::Start {
+            id: 1,
+            position: vec2(0., 0.),
+        });
+        control.track(TouchInput::Start {
+            id: 2,
+            position: vec2(10., 0.),
+        });
+
+        assert_eq!(
+            Some((vec2(-2.5, 0.), -5.)),
+            control.track(TouchInput::Move {
+                id: 2,
+                position: vec2(5., 0.)
+            })
+        );
+    }
+
+    #[test]
+    fn test_moving_fingers_together_shifts_without_spreading() {
+        assert_eq!(
+            (vec2(3., 4.), 0.),
+            gesture([vec2(0., 0.), vec2(10., 0.)], [vec2(3., 4.), vec2(13., 4.)])
+        );
+    }
+
+    #[test]
+    fn test_pinching_spreads_without_shifting() {
+        assert_eq!(
+            (vec2(0., 0.), 20.),
+            gesture(
+                [vec2(-10., 0.), vec2(10., 0.)],
+                [vec2(-20., 0.), vec2(20., 0.)]
+            )
+        );
+    }
+
+    #[test]
+    fn test_third_finger_is_ignored() {
+        let mut control = control();
+        for id in [1, 2, 3] {
+            control.track(TouchInput::Start {
+                id,
+                position: vec2(0., 0.),
+            });
+        }
+
+        assert_eq!(
+            None,
+            control.track(TouchInput::Move {
+                id: 3,
+                position: vec2(50., 50.)
+            })
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::f32::consts::PI;
 
 use rusty_puzzle_cube::cube::face::Face;
-use three_d::{radians, vec3, Mat4, Matrix4, Rad, Vector3};
+use three_d::{radians, vec3, vec4, Mat4, Matrix4, Rad, Vector3};
 
 const QUARTER_TURN: Rad<f32> = radians(0.5 * PI);
 const HALF_TURN: Rad<f32> = radians(PI);
@@ -9,6 +9,8 @@ const TRANSLATE_UP: Vector3<f32> = vec3(0., 1., 0.);
 const TRANSLATE_TOWARD: Vector3<f32> = vec3(0., 0., 1.);
 const TRANSLATE_RIGHT: Vector3<f32> = vec3(1., 0., 0.);
 
+pub(super) const MAX_EXPLODE: f32 = 1.;
+
 pub(super) fn quarter_turn_around_x() -> Matrix4<f32> {
     Mat4::from_angle_x(QUARTER_TURN)
 }
@@ -79,23 +81,63 @@ pub(super) fn move_face_into_place(face: Face) -> Matrix4<f32> {
     }
 }
 
+/// Moves a sticker placed on the cube by `placed` along with the rest of its cubie, away from the centre of the cube by `explode` times the cubie's distance from it.
+pub(super) fn explode_from_centre(
+    side_length: f32,
+    placed: Matrix4<f32>,
+    explode: f32,
+) -> Matrix4<f32> {
+    let sticker_centre = (placed * vec4(0., 0., 0., 1.)).truncate();
+    let outward = (placed * vec4(0., 0., 1., 0.)).truncate();
+    let cubie_centre = sticker_centre - outward / side_length;
+    Mat4::from_translation(cubie_centre * explode)
+}
+
 #[allow(clippy::cast_precision_loss)]
 pub(super) fn cubie_face_to_transformation(
     side_length: usize,
     face: Face,
     x: usize,
     y: usize,
+    explode: f32,
 ) -> Matrix4<f32> {
-    move_face_into_place(face)
-        * position_from_origin_centered_to(side_length as f32, x as f32, y as f32)
+    let placed = move_face_into_place(face)
+        * position_from_origin_centered_to(side_length as f32, x as f32, y as f32);
+    explode_from_centre(side_length as f32, placed, explode)
+        * placed
         * scale_down(side_length as f32)
 }
 
+// World-space positions of a single sticker, for overlays that need to line up with the rendered cube
+#[cfg_attr(not(test), allow(dead_code))]
+pub(super) fn sticker_centre(
+    side_length: usize,
+    face: Face,
+    row: usize,
+    col: usize,
+) -> Vector3<f32> {
+    (cubie_face_to_transformation(side_length, face, col, row, 0.) * vec4(0., 0., 0., 1.))
+        .truncate()
+}
+
+// Corners run clockwise from the top left, as seen when looking at the face from outside the cube
+#[cfg_attr(not(test), allow(dead_code))]
+pub(super) fn sticker_corners(
+    side_length: usize,
+    face: Face,
+    row: usize,
+    col: usize,
+) -> [Vector3<f32>; 4] {
+    let transformation = cubie_face_to_transformation(side_length, face, col, row, 0.);
+    [(-1., 1.), (1., 1.), (1., -1.), (-1., -1.)]
+        .map(|(x, y)| (transformation * vec4(x, y, 0., 1.)).truncate())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
-    use three_d::{Angle as _, Deg, Vector4};
+    use three_d::{Angle as _, Deg, InnerSpace as _, Vector4};
 
     fn assert_mat_eq_with_tolerance(m1: Matrix4<f32>, m2: Matrix4<f32>) {
         assert_vec_eq_with_tolerance(m1.w, m2.w);
@@ -111,6 +153,13 @@ mod tests {
         assert_eq_with_tolerance(v1.z, v2.z);
     }
 
+    fn assert_vec3_eq_with_tolerance(v1: Vector3<f32>, v2: Vector3<f32>) {
+        assert!(
+            (v1 - v2).magnitude() < 1e-5,
+            "{v1:?} was not close enough to {v2:?}"
+        );
+    }
+
     fn assert_eq_with_tolerance(f1: f32, f2: f32) {
         let diff = f1 - f2;
         let abs = diff.abs();
@@ -451,4 +500,71 @@ mod tests {
 
         assert_mat_eq_with_tolerance(expected, actual);
     }
+
+    #[test]
+    fn test_sticker_centre_front_middle() {
+        assert_vec3_eq_with_tolerance(vec3(0., 0., 1.), sticker_centre(3, Face::Front, 1, 1));
+    }
+
+    #[test]
+    fn test_sticker_centre_up_first_row_is_next_to_back() {
+        assert_vec3_eq_with_tolerance(vec3(0., 1., -2. / 3.), sticker_centre(3, Face::Up, 0, 1));
+    }
+
+    #[test]
+    fn test_sticker_centre_right_first_col_is_next_to_front() {
+        assert_vec3_eq_with_tolerance(
+            vec3(1., 2. / 3., 2. / 3.),
+            sticker_centre(3, Face::Right, 0, 0),
+        );
+    }
+
+    #[test]
+    fn test_explode_moves_whole_cubie_outward() {
+        let corner = |face, x, y, explode| {
+            (cubie_face_to_transformation(3, face, x, y, explode) * vec4(0., 0., 0., 1.)).truncate()
+        };
+
+        // The front up left corner cubie is centred at (-2/3, 2/3, 2/3), so every sticker on it moves by the same amount
+        let shift = vec3(-2. / 3., 2. / 3., 2. / 3.) * 0.5;
+        assert_vec3_eq_with_tolerance(
+            corner(Face::Front, 0, 0, 0.) + shift,
+            corner(Face::Front, 0, 0, 0.5),
+        );
+        assert_vec3_eq_with_tolerance(
+            corner(Face::Up, 0, 2, 0.) + shift,
+            corner(Face::Up, 0, 2, 0.5),
+        );
+        assert_vec3_eq_with_tolerance(
+            corner(Face::Left, 2, 0, 0.) + shift,
+            corner(Face::Left, 2, 0, 0.5),
+        );
+    }
+
+    #[test]
+    fn test_explode_leaves_centre_of_odd_cube_in_place() {
+        assert_vec3_eq_with_tolerance(
+            vec3(0., 0., 1.),
+            (cubie_face_to_transformation(1, Face::Front, 0, 0, MAX_EXPLODE)
+                * vec4(0., 0., 0., 1.))
+            .truncate(),
+        );
+    }
+
+    #[test]
+    fn test_sticker_corners_1x1_front() {
+        let corners = sticker_corners(1, Face::Front, 0, 0);
+
+        for (expected, actual) in [
+            vec3(-0.9, 0.9, 1.),
+            vec3(0.9, 0.9, 1.),
+            vec3(0.9, -0.9, 1.),
+            vec3(-0.9, -0.9, 1.),
+        ]
+        .into_iter()
+        .zip(corners)
+        {
+            assert_vec3_eq_with_tolerance(expected, actual);
+        }
+    }
 }
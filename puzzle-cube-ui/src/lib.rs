@@ -1,5 +1,6 @@
 mod demo;
 mod gui;
+mod logging;
 
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
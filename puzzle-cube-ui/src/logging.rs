@@ -0,0 +1,200 @@
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write as _},
+    sync::{Arc, Mutex},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, reload, Layer, Registry};
+
+const LOG_CAPACITY: usize = 500;
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::INFO;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LogLine {
+    pub(crate) level: Level,
+    pub(crate) target: String,
+    pub(crate) message: String,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct LogBuffer(Arc<Mutex<VecDeque<LogLine>>>);
+
+impl LogBuffer {
+    fn push(&self, line: LogLine) {
+        let mut lines = self
+            .0
+            .lock()
+            .expect("Log buffer lock should not be poisoned");
+        if lines.len() == LOG_CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    pub(crate) fn lines(&self) -> Vec<LogLine> {
+        self.0
+            .lock()
+            .expect("Log buffer lock should not be poisoned")
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn clear(&self) {
+        self.0
+            .lock()
+            .expect("Log buffer lock should not be poisoned")
+            .clear();
+    }
+}
+
+struct RingBufferLayer {
+    buffer: LogBuffer,
+}
+
+impl<S: Subscriber> Layer<S> for RingBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.buffer.push(LogLine {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+        });
+    }
+}
+
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.message, " {}={value:?}", field.name());
+        }
+    }
+}
+
+pub(crate) struct Logging {
+    buffer: LogBuffer,
+    level: reload::Handle<LevelFilter, Registry>,
+}
+
+impl Logging {
+    pub(crate) fn buffer(&self) -> &LogBuffer {
+        &self.buffer
+    }
+
+    pub(crate) fn level(&self) -> LevelFilter {
+        self.level.clone_current().unwrap_or(DEFAULT_LEVEL)
+    }
+
+    pub(crate) fn set_level(&self, level: LevelFilter) {
+        if let Err(e) = self.level.modify(|current| *current = level) {
+            tracing::error!("Could not change log verbosity: {}", e);
+        }
+    }
+}
+
+pub(crate) fn init() -> Logging {
+    let buffer = LogBuffer::default();
+    let (level_layer, level) = reload::Layer::new(DEFAULT_LEVEL);
+    let subscriber = tracing_subscriber::registry()
+        .with(level_layer)
+        .with(RingBufferLayer {
+            buffer: buffer.clone(),
+        });
+
+    #[cfg(not(target_arch = "wasm32"))]
+    subscriber.with(tracing_subscriber::fmt::layer()).init();
+
+    // The console logger is already installed as the `log` backend, and tracing's `log-always` feature forwards events to it
+    #[cfg(target_arch = "wasm32")]
+    tracing::subscriber::set_global_default(subscriber)
+        .expect("Global tracing subscriber should only be set once");
+
+    Logging { buffer, level }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tracing::{debug, info, warn};
+
+    fn capture(level: LevelFilter, log: impl FnOnce()) -> Vec<LogLine> {
+        let buffer = LogBuffer::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(level)
+            .with(RingBufferLayer {
+                buffer: buffer.clone(),
+            });
+        tracing::subscriber::with_default(subscriber, log);
+        buffer.lines()
+    }
+
+    #[test]
+    fn test_events_are_recorded() {
+        let lines = capture(LevelFilter::INFO, || {
+            info!("Cube created");
+            warn!(side_length = 4, "Unsupported move");
+        });
+
+        assert_eq!(
+            vec![
+                LogLine {
+                    level: Level::INFO,
+                    target: module_path!().to_string(),
+                    message: "Cube created".to_string(),
+                },
+                LogLine {
+                    level: Level::WARN,
+                    target: module_path!().to_string(),
+                    message: "Unsupported move side_length=4".to_string(),
+                },
+            ],
+            lines
+        );
+    }
+
+    #[test]
+    fn test_events_below_level_are_dropped() {
+        let lines = capture(LevelFilter::INFO, || debug!("Too verbose"));
+
+        assert_eq!(Vec::<LogLine>::new(), lines);
+    }
+
+    #[test]
+    fn test_buffer_drops_oldest_lines() {
+        let lines = capture(LevelFilter::INFO, || {
+            (0..=LOG_CAPACITY).for_each(|i| info!("{i}"));
+        });
+
+        assert_eq!(LOG_CAPACITY, lines.len());
+        assert_eq!("1", lines[0].message);
+    }
+
+    #[test]
+    fn test_clear() {
+        let buffer = LogBuffer::default();
+        buffer.push(LogLine {
+            level: Level::INFO,
+            target: String::new(),
+            message: String::new(),
+        });
+
+        buffer.clear();
+
+        assert_eq!(Vec::<LogLine>::new(), buffer.lines());
+    }
+}
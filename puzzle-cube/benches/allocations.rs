@@ -0,0 +1,82 @@
+//! Counts heap allocations rather than timing anything, as rotations are meant to work in place and a stray allocation is easy to miss in timings.
+//! Run with `cargo bench --bench allocations`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use rusty_puzzle_cube::{
+    cube::{face::Face, rotation::Rotation, Cube},
+    notation::parse_sequence,
+};
+
+const SEQUENCE: &str = "R U R' U' R' F R2 U' R' U' R U R' F' L D2 B' L'";
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during(work: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    work();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn main() {
+    let rotations = parse_sequence(SEQUENCE).expect("Benchmark sequence should be valid");
+    println!("{:<30}{:>12}", "work", "allocations");
+    for side_length in [2, 3, 4, 7, 50] {
+        let mut cube = Cube::create(side_length);
+        for face in [Face::Up, Face::Front, Face::Right] {
+            for rotation in [Rotation::clockwise(face), Rotation::anticlockwise(face)] {
+                let allocations = allocations_during(|| cube.rotate(rotation));
+                assert_eq!(
+                    0, allocations,
+                    "{rotation:?} should turn a {side_length}x{side_length} cube in place"
+                );
+            }
+        }
+
+        let allocations = allocations_during(|| {
+            rotations.iter().for_each(|&rotation| cube.rotate(rotation));
+        });
+        assert_eq!(
+            0, allocations,
+            "A sequence of rotations should turn a {side_length}x{side_length} cube in place"
+        );
+        println!(
+            "{:<30}{:>12}",
+            format!("sequence {side_length}x{side_length}"),
+            allocations
+        );
+    }
+
+    let mut cube = Cube::create(3);
+    let allocations = allocations_during(|| cube.rotate_batch(&rotations));
+    println!("{:<30}{:>12}", "batched sequence 3x3", allocations);
+
+    let allocations = allocations_during(|| {
+        parse_sequence(SEQUENCE).expect("Benchmark sequence should be valid");
+    });
+    println!("{:<30}{:>12}", "parse sequence", allocations);
+}
@@ -0,0 +1,54 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rusty_puzzle_cube::notation::{format_sequence, parse_sequence, tokenise};
+
+const SEQUENCE: &str = "R U R' U' R' F R2 U' R' U' R U R' F' L D2 B' L' (R U)2 M' E S2 Rw' 3Fw";
+
+fn repeated(repeats: usize) -> String {
+    vec![SEQUENCE; repeats].join(" ")
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_sequence");
+    for repeats in [1, 10, 100] {
+        let sequence = repeated(repeats);
+        group.throughput(Throughput::Bytes(sequence.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(repeats),
+            &sequence,
+            |b, sequence| b.iter(|| parse_sequence(black_box(sequence))),
+        );
+    }
+    group.finish();
+}
+
+fn tokenise_sequence(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tokenise");
+    for repeats in [1, 10, 100] {
+        let sequence = repeated(repeats);
+        group.throughput(Throughput::Bytes(sequence.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(repeats),
+            &sequence,
+            |b, sequence| b.iter(|| tokenise(black_box(sequence))),
+        );
+    }
+    group.finish();
+}
+
+fn format(c: &mut Criterion) {
+    let mut group = c.benchmark_group("format_sequence");
+    for repeats in [1, 10, 100] {
+        let rotations =
+            parse_sequence(&repeated(repeats)).expect("Benchmark sequence should be valid");
+        group.throughput(Throughput::Elements(rotations.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(repeats),
+            &rotations,
+            |b, rotations| b.iter(|| format_sequence(black_box(rotations))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, tokenise_sequence, format);
+criterion_main!(benches);
@@ -0,0 +1,121 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rusty_puzzle_cube::{
+    cube::{face::Face, rotation::Rotation, Cube},
+    notation::parse_sequence,
+};
+
+const SEQUENCE: &str = "R U R' U' R' F R2 U' R' U' R U R' F' L D2 B' L'";
+
+fn rotate_each_face(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rotate_each_face");
+    for side_length in [2, 3, 4, 7, 50] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(side_length),
+            &side_length,
+            |b, &side_length| {
+                let mut cube = Cube::create(side_length);
+                b.iter(|| {
+                    for face in [
+                        Face::Up,
+                        Face::Down,
+                        Face::Front,
+                        Face::Right,
+                        Face::Back,
+                        Face::Left,
+                    ] {
+                        cube.rotate(black_box(Rotation::clockwise(face)));
+                    }
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn perform_sequence(c: &mut Criterion) {
+    let rotations = parse_sequence(SEQUENCE).expect("Benchmark sequence should be valid");
+    let mut group = c.benchmark_group("perform_sequence");
+    for side_length in [2, 3] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(side_length),
+            &side_length,
+            |b, &side_length| {
+                let mut cube = Cube::create(side_length);
+                b.iter(|| {
+                    rotations
+                        .iter()
+                        .for_each(|&rotation| cube.rotate(black_box(rotation)));
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn perform_sequence_batched(c: &mut Criterion) {
+    let rotations = parse_sequence(SEQUENCE).expect("Benchmark sequence should be valid");
+    let mut group = c.benchmark_group("perform_sequence_batched");
+    for side_length in [2, 3] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(side_length),
+            &side_length,
+            |b, &side_length| {
+                let mut cube = Cube::create(side_length);
+                b.iter(|| cube.rotate_batch(black_box(&rotations)));
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Check the work done by each rotation before timing anything, as counts catch algorithmic regressions that timings are too noisy to show.
+#[cfg(feature = "instrumentation")]
+fn assert_rotation_counters(_: &mut Criterion) {
+    for side_length in [2, 3, 4, 7] {
+        let counters_for = |rotation: Rotation| {
+            let mut cube = Cube::create(side_length);
+            Cube::reset_rotation_counters();
+            cube.rotate(rotation);
+            Cube::rotation_counters()
+        };
+        for face in [Face::Up, Face::Front, Face::Right] {
+            let clockwise = counters_for(Rotation::clockwise(face));
+            let anticlockwise = counters_for(Rotation::anticlockwise(face));
+
+            assert_eq!(
+                clockwise, anticlockwise,
+                "Anticlockwise {face:?} should do the same work as clockwise on a {side_length}x{side_length} cube"
+            );
+            assert_eq!(
+                4, clockwise.slice_copies,
+                "{face:?} should move each adjacent slice once on a {side_length}x{side_length} cube"
+            );
+            assert_eq!(
+                0, clockwise.allocations,
+                "{face:?} should not allocate on a {side_length}x{side_length} cube"
+            );
+            assert!(
+                clockwise.element_swaps <= side_length * side_length + 4 * side_length,
+                "{face:?} should swap each sticker at most once on a {side_length}x{side_length} cube, but made {} swaps",
+                clockwise.element_swaps
+            );
+        }
+    }
+}
+
+#[cfg(feature = "instrumentation")]
+criterion_group!(
+    benches,
+    assert_rotation_counters,
+    rotate_each_face,
+    perform_sequence,
+    perform_sequence_batched
+);
+#[cfg(not(feature = "instrumentation"))]
+criterion_group!(
+    benches,
+    rotate_each_face,
+    perform_sequence,
+    perform_sequence_batched
+);
+criterion_main!(benches);
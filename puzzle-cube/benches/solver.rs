@@ -0,0 +1,51 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::StdRng, SeedableRng};
+use rusty_puzzle_cube::{cube::Cube, solver::layer_by_layer};
+
+const SCRAMBLE_MOVES: usize = 25;
+const SEEDS: [u64; 3] = [1, 2, 3];
+
+fn scrambled(seed: u64) -> Cube {
+    let mut cube = Cube::create(3);
+    cube.shuffle_with_rng(SCRAMBLE_MOVES, &mut StdRng::seed_from_u64(seed));
+    cube
+}
+
+fn solve_layer_by_layer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("layer_by_layer");
+    for seed in SEEDS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(seed),
+            &scrambled(seed),
+            |b, cube| {
+                b.iter(|| layer_by_layer::solve(black_box(cube)));
+            },
+        );
+    }
+    group.finish();
+}
+
+#[cfg(feature = "two-phase")]
+fn solve_two_phase(c: &mut Criterion) {
+    use rusty_puzzle_cube::solver::two_phase;
+
+    let mut group = c.benchmark_group("two_phase");
+    // Each solve searches for a short solution, so fewer samples keep the run to a sensible length
+    group.sample_size(10);
+    for seed in SEEDS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(seed),
+            &scrambled(seed),
+            |b, cube| {
+                b.iter(|| two_phase::solve(black_box(cube)));
+            },
+        );
+    }
+    group.finish();
+}
+
+#[cfg(feature = "two-phase")]
+criterion_group!(benches, solve_layer_by_layer, solve_two_phase);
+#[cfg(not(feature = "two-phase"))]
+criterion_group!(benches, solve_layer_by_layer);
+criterion_main!(benches);
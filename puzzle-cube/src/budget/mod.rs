@@ -0,0 +1,212 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+/// A flag that can be shared with a long-running operation and set from elsewhere (such as a UI thread) to ask that operation to stop.
+///
+/// Clones share the same flag, so cancelling any clone cancels them all.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every operation holding a clone of this token to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`CancellationToken::cancel`] has been called on this token or any of its clones.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Why an operation stopped before completing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptReason {
+    /// The operation's cancellation token was cancelled.
+    Cancelled,
+    /// The operation's deadline passed.
+    DeadlineExceeded,
+    /// The operation used its full allowance of rotations.
+    RotationLimitReached,
+}
+
+/// The error returned by an operation that stopped before completing, recording how far it got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interrupted {
+    /// Why the operation stopped.
+    pub reason: InterruptReason,
+    /// How many rotations the operation had completed when it stopped.
+    pub completed_rotations: usize,
+}
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.reason {
+            InterruptReason::Cancelled => "cancelled",
+            InterruptReason::DeadlineExceeded => "deadline exceeded",
+            InterruptReason::RotationLimitReached => "rotation limit reached",
+        };
+        write!(
+            f,
+            "Operation stopped after {} rotations: {reason}",
+            self.completed_rotations
+        )
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+/// Limits that a long-running operation checks as it goes, stopping early with [`Interrupted`] once any of them is hit.
+///
+/// A default budget has no limits.
+/// ```
+/// # use rusty_puzzle_cube::budget::{Budget, CancellationToken};
+/// let token = CancellationToken::new();
+/// let budget = Budget::default()
+///     .with_cancellation(token.clone())
+///     .with_max_rotations(1_000_000);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    cancellation: Option<CancellationToken>,
+    deadline: Option<Instant>,
+    max_rotations: Option<usize>,
+}
+
+impl Budget {
+    /// Stop once the given token is cancelled.
+    #[must_use]
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Stop once the given instant has passed.
+    ///
+    /// `std::time::Instant` is not available on `wasm32-unknown-unknown`, so deadlines cannot be used there; use a cancellation token instead.
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stop before performing more than the given number of rotations.
+    #[must_use]
+    pub fn with_max_rotations(mut self, max_rotations: usize) -> Self {
+        self.max_rotations = Some(max_rotations);
+        self
+    }
+
+    /// Check whether an operation that has completed `completed_rotations` rotations may perform another.
+    /// # Errors
+    /// Will return an Err variant when any limit of this budget has been hit
+    pub fn check(&self, completed_rotations: usize) -> Result<(), Interrupted> {
+        let interrupted = |reason| {
+            Err(Interrupted {
+                reason,
+                completed_rotations,
+            })
+        };
+
+        if self
+            .cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+        {
+            interrupted(InterruptReason::Cancelled)
+        } else if self
+            .max_rotations
+            .is_some_and(|max| completed_rotations >= max)
+        {
+            interrupted(InterruptReason::RotationLimitReached)
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            interrupted(InterruptReason::DeadlineExceeded)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_budget_is_unlimited() {
+        assert_eq!(Ok(()), Budget::default().check(usize::MAX - 1));
+    }
+
+    #[test]
+    fn test_cancelling_a_clone_cancels_the_budget() {
+        let token = CancellationToken::new();
+        let budget = Budget::default().with_cancellation(token.clone());
+
+        assert_eq!(Ok(()), budget.check(3));
+        token.cancel();
+
+        assert_eq!(
+            Err(Interrupted {
+                reason: InterruptReason::Cancelled,
+                completed_rotations: 3
+            }),
+            budget.check(3)
+        );
+    }
+
+    #[test]
+    fn test_max_rotations() {
+        let budget = Budget::default().with_max_rotations(2);
+
+        assert_eq!(Ok(()), budget.check(1));
+        assert_eq!(
+            Err(Interrupted {
+                reason: InterruptReason::RotationLimitReached,
+                completed_rotations: 2
+            }),
+            budget.check(2)
+        );
+    }
+
+    #[test]
+    fn test_deadline_in_the_past() {
+        let budget = Budget::default().with_deadline(Instant::now());
+
+        assert_eq!(
+            Err(Interrupted {
+                reason: InterruptReason::DeadlineExceeded,
+                completed_rotations: 0
+            }),
+            budget.check(0)
+        );
+    }
+
+    #[test]
+    fn test_interrupted_display() {
+        let interrupted = Interrupted {
+            reason: InterruptReason::RotationLimitReached,
+            completed_rotations: 10,
+        };
+
+        assert_eq!(
+            "Operation stopped after 10 rotations: rotation limit reached",
+            interrupted.to_string()
+        );
+    }
+}
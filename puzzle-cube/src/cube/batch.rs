@@ -0,0 +1,147 @@
+use std::collections::BTreeMap;
+
+use super::{
+    direction::Direction,
+    face::Face,
+    helpers::{rotate_layer_180_degrees, rotate_layer_of_rotations_90_degrees},
+    instrumentation,
+    rotation::Rotation,
+    Cube,
+};
+
+impl Cube {
+    /// Perform the given rotations in order, combining neighbouring turns around the same axis so that each layer is turned at most once for them.
+    ///
+    /// Turns around one axis do not affect each other, so a run of them is reduced to how far each layer turns overall.
+    /// Layers that turn back to where they started are not touched, and layers that turn half way round move each sticker once, rather than twice as two quarter turns would.
+    /// The cube ends in the same state as performing each rotation with [`Cube::rotate`].
+    /// ```
+    /// # use rusty_puzzle_cube::{cube::Cube, notation::parse_sequence};
+    /// let rotations = parse_sequence("R L' R2 M U D").unwrap();
+    /// let mut batched = Cube::create(3);
+    /// batched.rotate_batch(&rotations);
+    ///
+    /// let mut one_at_a_time = Cube::create(3);
+    /// rotations.iter().for_each(|&rotation| one_at_a_time.rotate(rotation));
+    /// assert_eq!(one_at_a_time, batched);
+    /// ```
+    /// # Panics
+    /// Will panic if any rotation's layer is not within this cube, or if any rotation turns a locked layer, in which case the cube is left unchanged
+    pub fn rotate_batch(&mut self, rotations: &[Rotation]) {
+        for &rotation in rotations {
+            assert!(
+                rotation.layer < self.side_length,
+                "Cannot turn layer {} of a cube with side length {}",
+                rotation.layer,
+                self.side_length
+            );
+            if let Err(e) = self.check_unlocked(rotation) {
+                panic!("{e}");
+            }
+        }
+
+        let n = self.side_length;
+        for run in rotations.chunk_by(|a, b| axis(a.relative_to) == axis(b.relative_to)) {
+            let face = axis(run[0].relative_to);
+            let mut quarter_turns = BTreeMap::new();
+            for rotation in run {
+                let (layer, turns) = if rotation.relative_to == face {
+                    (rotation.layer, clockwise_quarter_turns(rotation.direction))
+                } else {
+                    (
+                        n - 1 - rotation.layer,
+                        clockwise_quarter_turns(rotation.direction.opposite()),
+                    )
+                };
+                let total = quarter_turns.entry(layer).or_insert(0);
+                *total = (*total + turns) % 4;
+            }
+            for (layer, turns) in quarter_turns {
+                match turns {
+                    1 => self.rotate(Rotation::clockwise(face).at_layer(layer)),
+                    2 => self.rotate_half(face, layer),
+                    3 => self.rotate(Rotation::anticlockwise(face).at_layer(layer)),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn rotate_half(&mut self, face: Face, layer: usize) {
+        instrumentation::count_rotation();
+        rotate_layer_180_degrees(&mut self.side_map, face, layer);
+        if let Some(sticker_origin_map) = self.sticker_origin_map.as_mut() {
+            rotate_layer_180_degrees(sticker_origin_map, face, layer);
+        }
+        if let Some(sticker_rotation_map) = self.sticker_rotation_map.as_mut() {
+            for _ in 0..2 {
+                rotate_layer_of_rotations_90_degrees(
+                    sticker_rotation_map,
+                    face,
+                    layer,
+                    Direction::Clockwise,
+                );
+            }
+        }
+    }
+}
+
+/// The face that turns around each axis are counted from, so turns of opposite faces can be combined.
+fn axis(face: Face) -> Face {
+    match face {
+        Face::Up | Face::Down => Face::Up,
+        Face::Front | Face::Back => Face::Front,
+        Face::Right | Face::Left => Face::Right,
+    }
+}
+
+fn clockwise_quarter_turns(direction: Direction) -> u8 {
+    match direction {
+        Direction::Clockwise => 1,
+        Direction::Anticlockwise => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_sequence;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_rotate_batch_matches_rotating_one_at_a_time() {
+        let rotations =
+            parse_sequence("R L' R2 M U D2 U' E2 F B S2 F' R U R' U' 2R2 L2 2L'").unwrap();
+        for side_length in 1..=5 {
+            let rotations = rotations
+                .iter()
+                .copied()
+                .filter(|rotation| rotation.layer < side_length)
+                .collect::<Vec<_>>();
+            let mut batched = Cube::create_supercube(side_length);
+            batched.rotate_batch(&rotations);
+
+            let mut one_at_a_time = Cube::create_supercube(side_length);
+            rotations
+                .iter()
+                .for_each(|&rotation| one_at_a_time.rotate(rotation));
+            assert_eq!(one_at_a_time, batched, "{side_length}x{side_length}");
+        }
+    }
+
+    #[test]
+    fn test_rotations_that_cancel_out_leave_cube_untouched() {
+        let mut cube = Cube::create(3);
+        cube.rotate_batch(&parse_sequence("R L R' L' U2 D2 U2 D2").unwrap());
+
+        assert_eq!(Cube::create(3), cube);
+    }
+
+    #[test]
+    #[should_panic(expected = "The Up face is locked, so it cannot be turned")]
+    fn test_rotate_batch_panics_on_locked_layer() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(Face::Down, 2);
+        cube.rotate_batch(&parse_sequence("R U").unwrap());
+    }
+}
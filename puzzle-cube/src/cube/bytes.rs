@@ -0,0 +1,209 @@
+use enum_map::EnumMap;
+
+use super::{cubie_face::CubieFace, grid::Grid, Cube};
+
+const BITS_PER_STICKER: usize = 3;
+const HEADER_BYTES: usize = 2;
+
+impl Cube {
+    /// Encode the colours of this cube in a compact binary format, for storing many positions or sending them over a network.
+    ///
+    /// The format is:
+    /// - 2 bytes holding the side length `n` as a little-endian `u16`
+    /// - `6 * n * n` stickers of 3 bits each, packed starting from the least significant bit of each byte
+    ///
+    /// Stickers are written side by side in the order Up, Down, Front, Right, Back, Left, each row by row as laid out in the cube's net.
+    /// Each sticker is written as 0 to 5 for white, yellow, blue, orange, green and red. Any bits left over in the last byte are 0.
+    /// A 3x3 cube takes 23 bytes. Custom display characters, sticker origins and locked layers are not included.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::Cube;
+    /// let cube = Cube::create(3);
+    /// let bytes = cube.to_bytes();
+    /// assert_eq!(23, bytes.len());
+    /// assert_eq!(Ok(cube), Cube::from_bytes(&bytes));
+    /// ```
+    /// # Panics
+    /// Will panic if the side length does not fit in a `u16`
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let side_length = u16::try_from(self.side_length)
+            .expect("Cubes larger than u16::MAX cannot be encoded as bytes");
+        let sticker_count = 6 * self.side_length * self.side_length;
+
+        let mut bytes = vec![0; HEADER_BYTES + (sticker_count * BITS_PER_STICKER).div_ceil(8)];
+        bytes[..HEADER_BYTES].copy_from_slice(&side_length.to_le_bytes());
+        self.side_map
+            .values()
+            .flat_map(|side| side.cells())
+            .enumerate()
+            .for_each(|(i, &cubie)| write_sticker(&mut bytes[HEADER_BYTES..], i, code(cubie)));
+        bytes
+    }
+
+    /// Create a cube from bytes in the format written by [`Cube::to_bytes`].
+    /// # Errors
+    /// Will return an Err variant when the bytes are not the length given by their side length, contain a sticker code above 5 or non-zero padding bits, or do not contain `n * n` stickers of each colour
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let (header, stickers) = bytes
+            .split_first_chunk::<HEADER_BYTES>()
+            .ok_or("Cube bytes are too short to hold a side length")?;
+        let side_length = usize::from(u16::from_le_bytes(*header));
+        let too_large = || {
+            format!("Cube bytes for side length {side_length} would hold too many stickers for this platform")
+        };
+        let sticker_count = side_length
+            .checked_mul(side_length)
+            .and_then(|stickers_per_side| stickers_per_side.checked_mul(6))
+            .ok_or_else(too_large)?;
+        let expected_len = sticker_count
+            .checked_mul(BITS_PER_STICKER)
+            .ok_or_else(too_large)?
+            .div_ceil(8);
+        if side_length == 0 || stickers.len() != expected_len {
+            return Err(format!(
+                "Cube bytes for side length {side_length} must hold {expected_len} bytes of stickers, but held {}",
+                stickers.len()
+            ));
+        }
+        let padding_bits = expected_len * 8 - sticker_count * BITS_PER_STICKER;
+        if padding_bits > 0 && stickers[expected_len - 1] >> (8 - padding_bits) != 0 {
+            return Err("Cube bytes have non-zero padding bits".to_string());
+        }
+
+        let cubies = (0..sticker_count)
+            .map(|i| cubie_face(read_sticker(stickers, i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut sides = cubies.chunks(side_length * side_length);
+        let side_map = EnumMap::from_fn(|_| {
+            let side = sides.next().expect("Sticker count was checked above");
+            Grid::from_fn(side_length, |row, col| side[row * side_length + col])
+        });
+        let cube = Cube::try_from_side_map(side_map)?;
+        cube.check_colour_counts().map_err(|e| e.to_string())?;
+        Ok(cube)
+    }
+}
+
+fn write_sticker(stickers: &mut [u8], index: usize, code: u8) {
+    let bit = index * BITS_PER_STICKER;
+    let shifted = u16::from(code) << (bit % 8);
+    let [low, high] = shifted.to_le_bytes();
+    stickers[bit / 8] |= low;
+    if high != 0 {
+        stickers[bit / 8 + 1] |= high;
+    }
+}
+
+fn read_sticker(stickers: &[u8], index: usize) -> u8 {
+    let bit = index * BITS_PER_STICKER;
+    let low = stickers[bit / 8];
+    let high = stickers.get(bit / 8 + 1).copied().unwrap_or(0);
+    let [code, _] = (u16::from_le_bytes([low, high]) >> (bit % 8)).to_le_bytes();
+    code & 0b111
+}
+
+fn code(cubie: CubieFace) -> u8 {
+    match cubie {
+        CubieFace::White(_) => 0,
+        CubieFace::Yellow(_) => 1,
+        CubieFace::Blue(_) => 2,
+        CubieFace::Orange(_) => 3,
+        CubieFace::Green(_) => 4,
+        CubieFace::Red(_) => 5,
+    }
+}
+
+fn cubie_face(code: u8) -> Result<CubieFace, String> {
+    match code {
+        0 => Ok(CubieFace::White(None)),
+        1 => Ok(CubieFace::Yellow(None)),
+        2 => Ok(CubieFace::Blue(None)),
+        3 => Ok(CubieFace::Orange(None)),
+        4 => Ok(CubieFace::Green(None)),
+        5 => Ok(CubieFace::Red(None)),
+        _ => Err(format!("Unsupported sticker code: [{code}]")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::perform_3x3_sequence;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_solved_1x1_to_bytes() {
+        // Codes 0 to 5 in 3 bits each, filling each byte from its least significant bit, then 6 padding bits
+        assert_eq!(
+            vec![1, 0, 0b1000_1000, 0b1100_0110, 0b0000_0010],
+            Cube::create(1).to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_bytes_round_trip() {
+        for side_length in 1..=6 {
+            let mut cube = Cube::create(side_length);
+            perform_3x3_sequence("R U2 F' L D B2 R' U'", &mut cube).unwrap();
+
+            assert_eq!(Ok(cube.clone()), Cube::from_bytes(&cube.to_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_unique_characters_are_dropped() {
+        assert_eq!(
+            Ok(Cube::create(3)),
+            Cube::from_bytes(&Cube::create_with_unique_characters(3).to_bytes())
+        );
+    }
+
+    #[test]
+    fn test_invalid_bytes() {
+        let solved = Cube::create(1).to_bytes();
+        let with_bytes = |edit: fn(&mut Vec<u8>)| {
+            let mut bytes = solved.clone();
+            edit(&mut bytes);
+            Cube::from_bytes(&bytes)
+        };
+
+        assert_eq!(
+            Err("Cube bytes are too short to hold a side length".to_string()),
+            Cube::from_bytes(&[1])
+        );
+        assert_eq!(
+            Err(
+                "Cube bytes for side length 1 must hold 3 bytes of stickers, but held 2"
+                    .to_string()
+            ),
+            with_bytes(|bytes| {
+                bytes.pop();
+            })
+        );
+        assert!(with_bytes(|bytes| bytes[0] = 0).is_err());
+        assert_eq!(
+            Err("Cube bytes have non-zero padding bits".to_string()),
+            with_bytes(|bytes| bytes[4] |= 0b1000_0000)
+        );
+        assert_eq!(
+            Err("Unsupported sticker code: [7]".to_string()),
+            with_bytes(|bytes| bytes[2] |= 0b111)
+        );
+        assert!(with_bytes(|bytes| bytes[2] |= 0b001).is_err());
+    }
+
+    #[test]
+    fn test_huge_side_length_header() {
+        let result = Cube::from_bytes(&[0xff, 0xff, 0]);
+
+        // 6 * 65535 * 65535 stickers only fit in a 64 bit usize, where the length check rejects them instead
+        if usize::BITS < 64 {
+            assert_eq!(
+                Err("Cube bytes for side length 65535 would hold too many stickers for this platform".to_string()),
+                result
+            );
+        } else {
+            assert!(result.is_err());
+        }
+    }
+}
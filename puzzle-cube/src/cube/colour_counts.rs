@@ -0,0 +1,151 @@
+use std::fmt;
+
+use super::{cubie_face::CubieFace, Cube};
+
+const COLOURS: [CubieFace; 6] = [
+    CubieFace::Blue(None),
+    CubieFace::Green(None),
+    CubieFace::Orange(None),
+    CubieFace::Red(None),
+    CubieFace::White(None),
+    CubieFace::Yellow(None),
+];
+
+/// The error returned when a cube does not have one side's worth of stickers of each colour, so cannot be a real cube.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColourCountError {
+    /// The colour with the wrong number of stickers, without a display char.
+    pub colour: CubieFace,
+    /// How many stickers of this colour the cube has.
+    pub count: usize,
+    /// How many stickers of each colour a cube of this size should have.
+    pub expected: usize,
+}
+
+impl fmt::Display for ColourCountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let amount = if self.count > self.expected {
+            "Too many"
+        } else {
+            "Too few"
+        };
+        write!(
+            f,
+            "{amount} {} stickers ({}/{})",
+            colour_name(self.colour),
+            self.count,
+            self.expected
+        )
+    }
+}
+
+impl std::error::Error for ColourCountError {}
+
+impl Cube {
+    /// Returns how many stickers of each colour the cube has, with each colour given without a display char, in the order the colours are declared in [`CubieFace`].
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, cubie_face::CubieFace};
+    /// let counts = Cube::create(3).colour_counts();
+    /// assert_eq!((CubieFace::Blue(None), 9), counts[0]);
+    /// ```
+    #[must_use]
+    pub fn colour_counts(&self) -> [(CubieFace, usize); 6] {
+        let mut counts = COLOURS.map(|colour| (colour, 0));
+        for cubie in self.side_map.values().flat_map(|side| side.cells()) {
+            counts[colour_index(*cubie)].1 += 1;
+        }
+        counts
+    }
+
+    /// Checks that the cube has exactly one side's worth of stickers of each colour, which is quick to check and catches most mistakes made when entering a cube by hand, before any deeper check such as [`Cube::check_solvable`].
+    /// ```
+    /// # use rusty_puzzle_cube::cube::Cube;
+    /// let one_front_sticker_is_white = "UUUUUUUUURRRRRRRRRUFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB";
+    /// let error = Cube::try_from_facelet_string(one_front_sticker_is_white).unwrap_err();
+    /// assert_eq!("Too few Blue stickers (8/9)", error);
+    /// ```
+    /// # Errors
+    /// Will return an Err variant naming the first colour, in the order of [`Cube::colour_counts`], with the wrong number of stickers
+    pub fn check_colour_counts(&self) -> Result<(), ColourCountError> {
+        let expected = self.side_length * self.side_length;
+        match self
+            .colour_counts()
+            .into_iter()
+            .find(|&(_, count)| count != expected)
+        {
+            Some((colour, count)) => Err(ColourCountError {
+                colour,
+                count,
+                expected,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn colour_index(cubie: CubieFace) -> usize {
+    match cubie {
+        CubieFace::Blue(_) => 0,
+        CubieFace::Green(_) => 1,
+        CubieFace::Orange(_) => 2,
+        CubieFace::Red(_) => 3,
+        CubieFace::White(_) => 4,
+        CubieFace::Yellow(_) => 5,
+    }
+}
+
+fn colour_name(cubie: CubieFace) -> &'static str {
+    ["Blue", "Green", "Orange", "Red", "White", "Yellow"][colour_index(cubie)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::face::Face;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_counts_ignore_display_chars() {
+        let counts = Cube::create_with_unique_characters(2).colour_counts();
+
+        assert_eq!(COLOURS.map(|colour| (colour, 4)), counts);
+    }
+
+    #[test]
+    fn test_scrambled_cube_has_legal_counts() {
+        let mut cube = Cube::create(4);
+        crate::notation::perform_3x3_sequence("R U F' L2 D B", &mut cube).unwrap();
+
+        assert_eq!(Ok(()), cube.check_colour_counts());
+    }
+
+    #[test]
+    fn test_wrong_counts_name_the_colour() {
+        let mut cube = Cube::create(3);
+        cube.side_map[Face::Down][0][0] = CubieFace::Orange(None);
+
+        let error = cube.check_colour_counts().unwrap_err();
+        assert_eq!(
+            ColourCountError {
+                colour: CubieFace::Orange(None),
+                count: 10,
+                expected: 9,
+            },
+            error
+        );
+        assert_eq!("Too many Orange stickers (10/9)", error.to_string());
+
+        cube.side_map[Face::Down][0][0] = CubieFace::Yellow(None);
+        cube.side_map[Face::Up][0][0] = CubieFace::Blue(None);
+        assert_eq!(
+            "Too many Blue stickers (10/9)",
+            cube.check_colour_counts().unwrap_err().to_string()
+        );
+        cube.side_map[Face::Front][0][0] = CubieFace::White(None);
+        cube.side_map[Face::Right][0][0] = CubieFace::White(None);
+        assert_eq!(
+            "Too few Orange stickers (8/9)",
+            cube.check_colour_counts().unwrap_err().to_string()
+        );
+    }
+}
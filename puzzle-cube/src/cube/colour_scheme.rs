@@ -0,0 +1,182 @@
+use std::{fmt, mem};
+
+use enum_map::EnumMap;
+
+use super::{cubie_face::CubieFace, face::Face, render_text::TextRenderOptions, Cube};
+
+/// A type representing the colour of the stickers each face of a solved cube starts with.
+pub type ColourScheme = EnumMap<Face, CubieFace>;
+
+/// The colour scheme used by [`Cube::create`], with white opposite yellow, blue opposite green, and orange opposite red.
+pub const WESTERN: ColourScheme = EnumMap::from_array([
+    CubieFace::White(None),
+    CubieFace::Yellow(None),
+    CubieFace::Blue(None),
+    CubieFace::Orange(None),
+    CubieFace::Green(None),
+    CubieFace::Red(None),
+]);
+
+/// The colour scheme common in Japan, with white opposite blue, yellow opposite green, and orange opposite red.
+pub const JAPANESE: ColourScheme = EnumMap::from_array([
+    CubieFace::White(None),
+    CubieFace::Blue(None),
+    CubieFace::Yellow(None),
+    CubieFace::Orange(None),
+    CubieFace::Green(None),
+    CubieFace::Red(None),
+]);
+
+/// Checks that a colour scheme gives every face a different colour, so that a cube using it can be solved.
+/// # Errors
+/// Will return an Err variant when two faces are given the same colour
+pub fn check(scheme: &ColourScheme) -> Result<(), String> {
+    let colours = scheme.values().map(mem::discriminant).collect::<Vec<_>>();
+    if (1..colours.len()).any(|i| colours[..i].contains(&colours[i])) {
+        return Err("A colour scheme must give every face a different colour".to_string());
+    }
+    Ok(())
+}
+
+/// Returns the colour a sticker has in the given colour scheme, reading its current colour as the face that colour starts on in [`WESTERN`].
+///
+/// Any display char is kept.
+/// ```
+/// # use rusty_puzzle_cube::cube::{colour_scheme::{recolour, JAPANESE}, cubie_face::CubieFace};
+/// assert_eq!(CubieFace::Blue(Some('d')), recolour(CubieFace::Yellow(Some('d')), &JAPANESE));
+/// assert_eq!(CubieFace::Red(None), recolour(CubieFace::Red(None), &JAPANESE));
+/// ```
+#[must_use]
+pub fn recolour(cubie: CubieFace, scheme: &ColourScheme) -> CubieFace {
+    let face = WESTERN
+        .iter()
+        .find(|(_, colour)| mem::discriminant(*colour) == mem::discriminant(&cubie))
+        .map(|(face, _)| face)
+        .expect("The western scheme uses every colour");
+    with_display_char(scheme[face], display_char(cubie))
+}
+
+fn display_char(cubie: CubieFace) -> Option<char> {
+    match cubie {
+        CubieFace::Blue(c)
+        | CubieFace::Green(c)
+        | CubieFace::Orange(c)
+        | CubieFace::Red(c)
+        | CubieFace::White(c)
+        | CubieFace::Yellow(c) => c,
+    }
+}
+
+pub(super) fn with_display_char(colour: CubieFace, c: Option<char>) -> CubieFace {
+    match colour {
+        CubieFace::Blue(_) => CubieFace::Blue(c),
+        CubieFace::Green(_) => CubieFace::Green(c),
+        CubieFace::Orange(_) => CubieFace::Orange(c),
+        CubieFace::Red(_) => CubieFace::Red(c),
+        CubieFace::White(_) => CubieFace::White(c),
+        CubieFace::Yellow(_) => CubieFace::Yellow(c),
+    }
+}
+
+/// A cube written out as text with its stickers recoloured, as returned by [`Cube::display_with_colour_scheme`].
+pub struct SchemedCube<'a> {
+    cube: &'a Cube,
+    scheme: &'a ColourScheme,
+}
+
+impl fmt::Display for SchemedCube<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = TextRenderOptions {
+            colour_scheme: *self.scheme,
+            ..TextRenderOptions::default()
+        };
+        write!(f, "{}", self.cube.format_with(&options))
+    }
+}
+
+impl Cube {
+    /// Returns a copy of this cube with every sticker recoloured into the given colour scheme, as described for [`recolour`].
+    ///
+    /// A cube created with [`Cube::create`] uses [`WESTERN`], so this turns it into a solved cube of the given scheme.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, colour_scheme::JAPANESE, cubie_face::CubieFace, face::Face};
+    /// let cube = Cube::create(3).with_colour_scheme(&JAPANESE).unwrap();
+    /// assert!(cube.is_solved());
+    /// assert!(matches!(cube.oriented_side(Face::Down)[1][1], CubieFace::Blue(_)));
+    /// ```
+    /// # Errors
+    /// Will return an Err variant when the colour scheme gives two faces the same colour
+    pub fn with_colour_scheme(&self, scheme: &ColourScheme) -> Result<Self, String> {
+        check(scheme)?;
+        let mut cube = self.clone();
+        for cubie in cube.side_map.values_mut().flat_map(|side| side.cells_mut()) {
+            *cubie = recolour(*cubie, scheme);
+        }
+        Ok(cube)
+    }
+
+    /// Returns this cube written out as text, as by its `Display` implementation, but with every sticker recoloured into the given colour scheme without changing the cube.
+    #[must_use]
+    pub fn display_with_colour_scheme<'a>(&'a self, scheme: &'a ColourScheme) -> SchemedCube<'a> {
+        SchemedCube { cube: self, scheme }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::rotation::Rotation;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_western_matches_create() {
+        let cube = Cube::create(1);
+        for (face, colour) in WESTERN {
+            assert_eq!(colour, cube.oriented_side(face)[0][0]);
+        }
+    }
+
+    #[test]
+    fn test_check_rejects_repeated_colour() {
+        let mut scheme = WESTERN;
+        scheme[Face::Down] = CubieFace::White(None);
+
+        assert_eq!(Ok(()), check(&JAPANESE));
+        assert_eq!(
+            Err("A colour scheme must give every face a different colour".to_string()),
+            check(&scheme)
+        );
+        assert!(Cube::create(2).with_colour_scheme(&scheme).is_err());
+    }
+
+    #[test]
+    fn test_recolouring_commutes_with_rotation() {
+        let rotation = Rotation::clockwise(Face::Front);
+        let mut rotated_then_recoloured = Cube::create_with_unique_characters(3);
+        rotated_then_recoloured.rotate(rotation);
+        let rotated_then_recoloured = rotated_then_recoloured
+            .with_colour_scheme(&JAPANESE)
+            .unwrap();
+
+        let mut recoloured_then_rotated = Cube::create_with_unique_characters(3)
+            .with_colour_scheme(&JAPANESE)
+            .unwrap();
+        recoloured_then_rotated.rotate(rotation);
+
+        assert_eq!(recoloured_then_rotated, rotated_then_recoloured);
+    }
+
+    #[test]
+    fn test_display_with_colour_scheme() {
+        let cube = Cube::create(2);
+
+        assert_eq!(
+            cube.with_colour_scheme(&JAPANESE).unwrap().to_string(),
+            cube.display_with_colour_scheme(&JAPANESE).to_string()
+        );
+        assert_eq!(
+            cube.to_string(),
+            cube.display_with_colour_scheme(&WESTERN).to_string()
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use enum_map::EnumMap;
+
+use super::{cubie_face::CubieFace, grid::Grid, Cube, Side};
+
+const COLOURS: [fn(Option<char>) -> CubieFace; 6] = [
+    CubieFace::White,
+    CubieFace::Yellow,
+    CubieFace::Blue,
+    CubieFace::Orange,
+    CubieFace::Green,
+    CubieFace::Red,
+];
+
+impl Cube {
+    /// Print this cube as text no wider than `max_cells_per_side` squares per side, followed by a legend, so very large cubes can be inspected in a terminal.
+    ///
+    /// Each side is split into square blocks of stickers, and each block is shown as the most common colour within it, with ties going to the colour that comes first of white, yellow, blue, orange, green and red.
+    /// Cubes that already fit are printed in full. A `max_cells_per_side` of 0 is treated as 1.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::Cube;
+    /// let compact = Cube::create(100).to_compact_string(10);
+    /// assert_eq!(10 + 10 + 10 + 1, compact.lines().count());
+    /// ```
+    #[must_use]
+    pub fn to_compact_string(&self, max_cells_per_side: usize) -> String {
+        let block_length = self.side_length.div_ceil(max_cells_per_side.max(1));
+        let compact = Cube {
+            side_length: self.side_length.div_ceil(block_length),
+            side_map: EnumMap::from_fn(|face| downsample(&self.side_map[face], block_length)),
+            sticker_origin_map: None,
+            sticker_rotation_map: None,
+            locked_layers: Vec::new(),
+        };
+
+        let n = self.side_length;
+        let legend = if block_length == 1 {
+            format!("Each square is one sticker of a {n}x{n} cube")
+        } else {
+            format!("Each square is the most common colour in a block of up to {block_length}x{block_length} stickers of a {n}x{n} cube")
+        };
+        format!("{compact}{legend}\n")
+    }
+}
+
+fn downsample(side: &Side, block_length: usize) -> Side {
+    Grid::from_fn(side.len().div_ceil(block_length), |block_row, block_col| {
+        let mut counts = [0_usize; COLOURS.len()];
+        side.rows()
+            .skip(block_row * block_length)
+            .take(block_length)
+            .flat_map(|row| row.iter().skip(block_col * block_length).take(block_length))
+            .for_each(|cubie| counts[colour_index(*cubie)] += 1);
+        let dominant = counts
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, &count)| count)
+            .map_or(0, |(i, _)| i);
+        COLOURS[dominant](None)
+    })
+}
+
+fn colour_index(cubie: CubieFace) -> usize {
+    match cubie {
+        CubieFace::White(_) => 0,
+        CubieFace::Yellow(_) => 1,
+        CubieFace::Blue(_) => 2,
+        CubieFace::Orange(_) => 3,
+        CubieFace::Green(_) => 4,
+        CubieFace::Red(_) => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::{face::Face, rotation::Rotation};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_small_cube_is_printed_in_full() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(Face::Right));
+
+        assert_eq!(
+            format!("{cube}Each square is one sticker of a 3x3 cube\n"),
+            cube.to_compact_string(10)
+        );
+    }
+
+    #[test]
+    fn test_large_cube_is_downsampled() {
+        let compact = Cube::create(25).to_compact_string(10);
+
+        assert_eq!(
+            format!(
+                "{}Each square is the most common colour in a block of up to 3x3 stickers of a 25x25 cube\n",
+                Cube::create(9)
+            ),
+            compact
+        );
+    }
+
+    #[test]
+    fn test_downsample_picks_most_common_colour() {
+        let side = Grid::try_from(vec![
+            vec![
+                CubieFace::Red(None),
+                CubieFace::Blue(None),
+                CubieFace::Blue(None),
+            ],
+            vec![
+                CubieFace::Red(None),
+                CubieFace::Red(None),
+                CubieFace::Blue(None),
+            ],
+            vec![
+                CubieFace::Green(None),
+                CubieFace::Green(None),
+                CubieFace::Yellow(None),
+            ],
+        ])
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                vec![CubieFace::Red(None), CubieFace::Blue(None)],
+                vec![CubieFace::Green(None), CubieFace::Yellow(None)],
+            ],
+            downsample(&side, 2).to_rows()
+        );
+    }
+
+    #[test]
+    fn test_downsample_ties_go_to_first_colour() {
+        let side = Grid::try_from(vec![
+            vec![CubieFace::Red(None), CubieFace::White(None)],
+            vec![CubieFace::Orange(None), CubieFace::Yellow(None)],
+        ])
+        .unwrap();
+
+        assert_eq!(
+            vec![vec![CubieFace::White(None)]],
+            downsample(&side, 2).to_rows()
+        );
+    }
+
+    #[test]
+    fn test_zero_cells_is_one_cell() {
+        let compact = Cube::create(4).to_compact_string(0);
+
+        assert_eq!(3 + 1, compact.lines().count());
+    }
+}
@@ -7,7 +7,10 @@ const DEFAULT_CUBIE_CHAR: char = '■';
 /// Representing a single tile on a single side of a cube.
 ///
 /// Optionally contains a `char` that will be used instead of the default square char when rendering as text.
-#[derive(Copy, Clone, Debug, PartialEq)]
+///
+/// The ordering of `CubieFace`s has no meaning beyond being consistent, so that cubes can be given a canonical form.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CubieFace {
     /// Blue CubieFace is the default for the front face.
     Blue(Option<char>),
@@ -27,31 +30,33 @@ impl CubieFace {
     /// Creates a `ColoredString` that can be terminal printed, using this `CubieFace`s custom display `char` if present, or the default square `char` if not.
     #[must_use]
     pub fn get_coloured_display_char(self) -> ColoredString {
-        match self {
-            CF::Blue(Some(c))
-            | CF::Green(Some(c))
-            | CF::Orange(Some(c))
-            | CF::Red(Some(c))
-            | CF::White(Some(c))
-            | CF::Yellow(Some(c)) => self.colourise_string(&format!("{c}")),
+        self.colourise_string(&self.display_char().to_string())
+    }
 
-            CF::Blue(None)
-            | CF::Green(None)
-            | CF::Orange(None)
-            | CF::Red(None)
-            | CF::White(None)
-            | CF::Yellow(None) => self.colourise_string(&format!("{DEFAULT_CUBIE_CHAR}")),
+    pub(super) fn display_char(self) -> char {
+        match self {
+            CF::Blue(c)
+            | CF::Green(c)
+            | CF::Orange(c)
+            | CF::Red(c)
+            | CF::White(c)
+            | CF::Yellow(c) => c.unwrap_or(DEFAULT_CUBIE_CHAR),
         }
     }
 
-    fn colourise_string(self, string: &str) -> ColoredString {
+    pub(super) fn colourise_string(self, string: &str) -> ColoredString {
+        let (r, g, b) = self.rgb();
+        string.truecolor(r, g, b)
+    }
+
+    pub(super) fn rgb(self) -> (u8, u8, u8) {
         match self {
-            CF::Blue(_) => string.truecolor(0, 0, 255),
-            CF::Green(_) => string.truecolor(0, 255, 0),
-            CF::Orange(_) => string.truecolor(255, 127, 0),
-            CF::Red(_) => string.truecolor(255, 0, 0),
-            CF::White(_) => string.truecolor(255, 255, 255),
-            CF::Yellow(_) => string.truecolor(255, 255, 0),
+            CF::Blue(_) => (0, 0, 255),
+            CF::Green(_) => (0, 255, 0),
+            CF::Orange(_) => (255, 127, 0),
+            CF::Red(_) => (255, 0, 0),
+            CF::White(_) => (255, 255, 255),
+            CF::Yellow(_) => (255, 255, 0),
         }
     }
 }
@@ -0,0 +1,20 @@
+/// The direction a face is turned in, from the perspective of looking directly at that face from outside the cube.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Turn the face clockwise
+    Clockwise,
+    /// Turn the face anticlockwise
+    Anticlockwise,
+}
+
+impl Direction {
+    /// The direction that undoes a turn in this direction.
+    #[must_use]
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Clockwise => Direction::Anticlockwise,
+            Direction::Anticlockwise => Direction::Clockwise,
+        }
+    }
+}
@@ -3,7 +3,8 @@ use Face as F;
 use IndexAlignment as IA;
 
 /// An enum representing the six sides of the cube.
-#[derive(Debug, Clone, Copy, Enum, PartialEq)]
+#[derive(Debug, Clone, Copy, Enum, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Face {
     /// The Up face starts as white cubies
     Up,
@@ -20,6 +21,26 @@ pub enum Face {
 }
 
 impl Face {
+    /// Returns the faces bordering this one above, to the right, below and to the left, in that order, as seen when looking directly at this face from outside the cube.
+    ///
+    /// This is the orientation each side has in the net printed for a cube, where the Up face sits above Front, the Down face below it, and Left, Front, Right and Back run left to right between them.
+    /// Rows of a side count down from the border with the face above, and columns count across from the border with the face to the left.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::face::Face;
+    /// assert_eq!([Face::Back, Face::Right, Face::Front, Face::Left], Face::Up.neighbours());
+    /// ```
+    #[must_use]
+    pub fn neighbours(self) -> [Face; 4] {
+        match self {
+            F::Up => [F::Back, F::Right, F::Front, F::Left],
+            F::Down => [F::Front, F::Right, F::Back, F::Left],
+            F::Front => [F::Up, F::Right, F::Down, F::Left],
+            F::Right => [F::Up, F::Back, F::Down, F::Front],
+            F::Back => [F::Up, F::Left, F::Down, F::Right],
+            F::Left => [F::Up, F::Front, F::Down, F::Back],
+        }
+    }
+
     pub(crate) fn adjacent_faces_clockwise(self) -> [(Face, IndexAlignment); 4] {
         match self {
             F::Up => [
@@ -60,9 +81,26 @@ impl Face {
             ],
         }
     }
+
+    /// The face on the other side of the cube.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::face::Face;
+    /// assert_eq!(Face::Down, Face::Up.opposite());
+    /// ```
+    #[must_use]
+    pub fn opposite(self) -> Face {
+        match self {
+            F::Up => F::Down,
+            F::Down => F::Up,
+            F::Front => F::Back,
+            F::Back => F::Front,
+            F::Right => F::Left,
+            F::Left => F::Right,
+        }
+    }
 }
 
-/// This enum describes an edge of the 2d side, where a side is a `Vec<Vec<CubieFace>>`.
+/// This enum describes an edge of the 2d side, where a side is a [`Grid`](super::grid::Grid) of `CubieFace`.
 ///
 /// For example, given a 3x3 side with numbers representing `CubieFace` instances:
 ///```text
@@ -0,0 +1,255 @@
+use enum_map::EnumMap;
+
+use super::{
+    colour_scheme::{self, with_display_char, ColourScheme, WESTERN},
+    cubie_face::CubieFace,
+    face::Face as F,
+    grid::Grid,
+    Cube,
+};
+
+const FACE_ORDER: [F; 6] = [F::Up, F::Right, F::Front, F::Down, F::Left, F::Back];
+
+/// How an imported cube should be held once it has been read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportOrientation {
+    /// Keep the cube held as it was written, so each face of the string becomes the same face of the cube.
+    AsWritten,
+    /// Turn the cube as a whole so it is held as a newly created cube is, as described for [`Cube::normalised_orientation`].
+    Normalised,
+}
+
+/// Options for reading a facelet string written by another tool, for [`Cube::try_from_facelet_string_with`].
+///
+/// Facelet strings name each sticker by the face it belongs on, not by its colour, so a tool that holds a solved cube with green in front rather than blue writes its letters for other colours than this crate does.
+/// Giving that tool's colour scheme reads each letter as the colour it means there, and normalising the orientation then turns the cube so it lands the way this crate holds it.
+/// ```
+/// # use rusty_puzzle_cube::cube::{Cube, colour_scheme::WESTERN, cubie_face::CubieFace, face::Face, facelets::{ImportOptions, ImportOrientation}};
+/// let mut green_front = WESTERN;
+/// green_front[Face::Front] = CubieFace::Green(None);
+/// green_front[Face::Back] = CubieFace::Blue(None);
+/// green_front[Face::Right] = CubieFace::Red(None);
+/// green_front[Face::Left] = CubieFace::Orange(None);
+/// let options = ImportOptions { scheme: green_front, orientation: ImportOrientation::Normalised };
+///
+/// let solved = Cube::create(3).to_facelet_string();
+/// assert_eq!(Ok(Cube::create(3)), Cube::try_from_facelet_string_with(&solved, &options));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// The colour each face's letter stands for in the tool that wrote the string.
+    pub scheme: ColourScheme,
+    /// How to hold the cube once it has been read.
+    pub orientation: ImportOrientation,
+}
+
+impl Default for ImportOptions {
+    /// Read letters in this crate's [`WESTERN`] scheme and keep the cube as written, as [`Cube::try_from_facelet_string`] does.
+    fn default() -> Self {
+        Self {
+            scheme: WESTERN,
+            orientation: ImportOrientation::AsWritten,
+        }
+    }
+}
+
+impl Cube {
+    /// Encode the colours of this cube as a facelet string, as used by Kociemba-style solvers and many online tools.
+    ///
+    /// Sides are written in the order Up, Right, Front, Down, Left, Back, each row by row as laid out in the cube's net. Each sticker is written as the letter of the face its colour starts on, so a solved 3x3 is `UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB`.
+    /// Cubes of any side length are supported, with `6 * side_length * side_length` characters.
+    #[must_use]
+    pub fn to_facelet_string(&self) -> String {
+        FACE_ORDER
+            .iter()
+            .flat_map(|&face| self.side_map[face].cells())
+            .map(|cubie| facelet_char(*cubie))
+            .collect()
+    }
+
+    /// Create a cube from a facelet string in the format written by [`Cube::to_facelet_string`].
+    ///
+    /// The side length is worked out from the length of the string.
+    /// # Errors
+    /// Will return an Err variant when the string is not `6 * n * n` characters long for some `n`, contains a character other than `U`, `R`, `F`, `D`, `L` or `B`, or does not contain `n * n` of each character
+    pub fn try_from_facelet_string(facelets: &str) -> Result<Self, String> {
+        Self::try_from_facelet_string_with(facelets, &ImportOptions::default())
+    }
+
+    /// Create a cube from a facelet string written by a tool using the colour scheme and orientation described by the options.
+    /// # Errors
+    /// Will return an Err variant for the same strings as [`Cube::try_from_facelet_string`], or when the scheme gives two faces the same colour
+    pub fn try_from_facelet_string_with(
+        facelets: &str,
+        options: &ImportOptions,
+    ) -> Result<Self, String> {
+        colour_scheme::check(&options.scheme)?;
+        let cubies = facelets
+            .chars()
+            .map(|facelet| {
+                cubie_face(facelet).map(|face| with_display_char(options.scheme[face], None))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let stickers_per_side = cubies.len() / 6;
+        let side_length = stickers_per_side.isqrt();
+        if side_length == 0 || cubies.len() != 6 * side_length * side_length {
+            return Err(format!(
+                "Facelet string must be 6 * n * n characters long, but was {}",
+                cubies.len()
+            ));
+        }
+
+        let mut sides = cubies
+            .chunks(stickers_per_side)
+            .map(|side| Grid::from_fn(side_length, |row, col| side[row * side_length + col]));
+        let [up, right, front, down, left, back] = std::array::from_fn(|_| sides.next().unwrap());
+        let cube =
+            Cube::try_from_side_map(EnumMap::from_array([up, down, front, right, back, left]))?;
+        cube.check_colour_counts().map_err(|e| e.to_string())?;
+        Ok(match options.orientation {
+            ImportOrientation::AsWritten => cube,
+            ImportOrientation::Normalised => cube.normalised_orientation(),
+        })
+    }
+}
+
+pub(super) fn facelet_char(cubie: CubieFace) -> char {
+    match cubie {
+        CubieFace::White(_) => 'U',
+        CubieFace::Orange(_) => 'R',
+        CubieFace::Blue(_) => 'F',
+        CubieFace::Yellow(_) => 'D',
+        CubieFace::Red(_) => 'L',
+        CubieFace::Green(_) => 'B',
+    }
+}
+
+/// Returns the face a facelet letter names.
+fn cubie_face(facelet: char) -> Result<F, String> {
+    match facelet {
+        'U' => Ok(F::Up),
+        'R' => Ok(F::Right),
+        'F' => Ok(F::Front),
+        'D' => Ok(F::Down),
+        'L' => Ok(F::Left),
+        'B' => Ok(F::Back),
+        _ => Err(format!("Unsupported facelet: [{facelet}]")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::rotation::Rotation;
+    use pretty_assertions::assert_eq;
+
+    const SOLVED: &str = "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB";
+
+    #[test]
+    fn test_solved_to_facelet_string() {
+        assert_eq!(SOLVED, Cube::create(3).to_facelet_string());
+    }
+
+    #[test]
+    fn test_single_turns_match_kociemba_layout() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(F::Up));
+        assert_eq!(
+            "UUUUUUUUUBBBRRRRRRRRRFFFFFFDDDDDDDDDFFFLLLLLLLLLBBBBBB",
+            cube.to_facelet_string()
+        );
+
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(F::Right));
+        assert_eq!(
+            "UUFUUFUUFRRRRRRRRRFFDFFDFFDDDBDDBDDBLLLLLLLLLUBBUBBUBB",
+            cube.to_facelet_string()
+        );
+    }
+
+    #[test]
+    fn test_facelet_string_round_trip() {
+        for side_length in 1..=5 {
+            let mut cube = Cube::create(side_length);
+            crate::notation::perform_3x3_sequence("R U2 F' L D' B2", &mut cube).unwrap();
+
+            let facelets = cube.to_facelet_string();
+
+            assert_eq!(6 * side_length * side_length, facelets.len());
+            assert_eq!(Ok(cube), Cube::try_from_facelet_string(&facelets));
+        }
+    }
+
+    #[test]
+    fn test_unique_characters_are_dropped() {
+        assert_eq!(
+            Ok(Cube::create(3)),
+            Cube::try_from_facelet_string(
+                &Cube::create_with_unique_characters(3).to_facelet_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_import_options_recolour_and_reorient() {
+        let mut green_front = WESTERN;
+        green_front[F::Front] = CubieFace::Green(None);
+        green_front[F::Back] = CubieFace::Blue(None);
+        green_front[F::Right] = CubieFace::Red(None);
+        green_front[F::Left] = CubieFace::Orange(None);
+        let mut options = ImportOptions {
+            scheme: green_front,
+            orientation: ImportOrientation::AsWritten,
+        };
+
+        let as_written = Cube::try_from_facelet_string_with(SOLVED, &options).unwrap();
+        assert_eq!(
+            Cube::create(3).with_colour_scheme(&green_front),
+            Ok(as_written.clone())
+        );
+        assert!(matches!(
+            as_written.oriented_side(F::Front)[1][1],
+            CubieFace::Green(_)
+        ));
+
+        options.orientation = ImportOrientation::Normalised;
+        assert_eq!(
+            Ok(Cube::create(3)),
+            Cube::try_from_facelet_string_with(SOLVED, &options)
+        );
+    }
+
+    #[test]
+    fn test_import_options_reject_invalid_schemes() {
+        let mut scheme = WESTERN;
+        scheme[F::Down] = CubieFace::White(None);
+
+        assert_eq!(
+            Err("A colour scheme must give every face a different colour".to_string()),
+            Cube::try_from_facelet_string_with(
+                SOLVED,
+                &ImportOptions {
+                    scheme,
+                    ..ImportOptions::default()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_invalid_facelet_strings() {
+        assert_eq!(
+            Err("Facelet string must be 6 * n * n characters long, but was 53".to_string()),
+            Cube::try_from_facelet_string(&SOLVED[1..])
+        );
+        assert_eq!(
+            Err("Facelet string must be 6 * n * n characters long, but was 0".to_string()),
+            Cube::try_from_facelet_string("")
+        );
+        assert_eq!(
+            Err("Unsupported facelet: [x]".to_string()),
+            Cube::try_from_facelet_string(&SOLVED.replacen('F', "x", 1))
+        );
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    ops::{Index, IndexMut},
+    slice::{ChunksExact, ChunksExactMut},
+};
+
+/// A square grid of cells stored in one contiguous buffer, row by row, so that rows can be copied as slices and cloning never needs an allocation per row.
+///
+/// Indexing with a row number gives that row as a slice, so cells are read as `grid[row][col]`, the same as for nested vectors.
+/// ```
+/// # use rusty_puzzle_cube::cube::grid::Grid;
+/// let grid = Grid::from_fn(3, |row, col| row * 3 + col);
+/// assert_eq!(5, grid[1][2]);
+/// assert_eq!(&[6, 7, 8], &grid[2]);
+/// assert_eq!(Ok(grid), Grid::try_from(vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8]]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    side_length: usize,
+    cells: Box<[T]>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid with `side_length` rows and columns, where each cell is the result of calling `cell` with its row and column.
+    pub fn from_fn(side_length: usize, mut cell: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..side_length)
+            .flat_map(|row| (0..side_length).map(move |col| (row, col)))
+            .map(|(row, col)| cell(row, col))
+            .collect();
+        Self { side_length, cells }
+    }
+
+    /// Create a grid with `side_length` rows and columns, with every cell a copy of `value`.
+    pub fn filled(side_length: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            side_length,
+            cells: vec![value; side_length * side_length].into_boxed_slice(),
+        }
+    }
+
+    /// Returns the number of rows, which is also the number of columns.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.side_length
+    }
+
+    /// Returns whether the grid has no cells at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.side_length == 0
+    }
+
+    /// Returns every cell, row by row.
+    #[must_use]
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Returns every cell mutably, row by row.
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Returns an iterator over the rows, from the first row to the last.
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.cells.chunks_exact(self.side_length.max(1))
+    }
+
+    /// Returns an iterator over the rows mutably, from the first row to the last.
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.cells.chunks_exact_mut(self.side_length.max(1))
+    }
+
+    /// Returns the index into [`Grid::cells`] of the cell at the given row and column.
+    #[must_use]
+    pub fn index_of(&self, row: usize, col: usize) -> usize {
+        row * self.side_length + col
+    }
+
+    /// Returns a copy of the grid as one vector per row.
+    #[must_use]
+    pub fn to_rows(&self) -> Vec<Vec<T>>
+    where
+        T: Clone,
+    {
+        self.rows().map(<[T]>::to_vec).collect()
+    }
+}
+
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Self {
+            side_length: 0,
+            cells: Box::new([]),
+        }
+    }
+}
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid<T> {
+    type Error = String;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let side_length = rows.len();
+        if let Some(row) = rows.iter().find(|row| row.len() != side_length) {
+            return Err(format!(
+                "Grid must be square, but has {side_length} rows and a row of length {}",
+                row.len()
+            ));
+        }
+        Ok(Self {
+            side_length,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = [T];
+
+    fn index(&self, row: usize) -> &Self::Output {
+        assert!(
+            row < self.side_length,
+            "Row {row} is outside a grid of {} rows",
+            self.side_length
+        );
+        let start = row * self.side_length;
+        &self.cells[start..start + self.side_length]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, row: usize) -> &mut Self::Output {
+        assert!(
+            row < self.side_length,
+            "Row {row} is outside a grid of {} rows",
+            self.side_length
+        );
+        let start = row * self.side_length;
+        &mut self.cells[start..start + self.side_length]
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a [T];
+    type IntoIter = ChunksExact<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_rows_are_contiguous() {
+        let grid = Grid::from_fn(3, |row, col| (row, col));
+
+        assert_eq!(&[(1, 0), (1, 1), (1, 2)], &grid[1]);
+        assert_eq!((2, 1), grid.cells()[grid.index_of(2, 1)]);
+        assert_eq!(3, grid.rows().count());
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut grid = Grid::filled(2, 'a');
+        grid[1][0] = 'b';
+
+        assert_eq!(vec![vec!['a', 'a'], vec!['b', 'a']], grid.to_rows());
+    }
+
+    #[test]
+    fn test_try_from_rows_must_be_square() {
+        assert_eq!(
+            Err("Grid must be square, but has 2 rows and a row of length 1".to_string()),
+            Grid::try_from(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(
+            Err("Grid must be square, but has 1 rows and a row of length 2".to_string()),
+            Grid::try_from(vec![vec![1, 2]])
+        );
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid = Grid::<u8>::try_from(vec![]).unwrap();
+
+        assert!(grid.is_empty());
+        assert_eq!(0, grid.rows().count());
+    }
+
+    #[test]
+    #[should_panic(expected = "Row 2 is outside a grid of 2 rows")]
+    fn test_index_outside_grid() {
+        let _ = &Grid::filled(2, 0)[2];
+    }
+}
@@ -1,17 +1,33 @@
-use super::{cubie_face::CubieFace, Side};
-use crate::cube::IA;
+use std::mem;
+
+use enum_map::{Enum, EnumMap};
+
+use super::{
+    cubie_face::CubieFace, direction::Direction, grid::Grid, instrumentation,
+    sticker_origin::StickerOrigin, Side,
+};
+use crate::cube::{F, IA};
+
+/// Sides at least this long are turned using every thread when the `rayon` feature is enabled, as smaller sides turn faster than threads can be handed the work.
+#[cfg(feature = "rayon")]
+const PARALLEL_SIDE_LENGTH: usize = 256;
+
+/// What is needed of the values held for each sticker, such as colours and origins, so that big sides can be turned across threads when the `rayon` feature is enabled.
+#[cfg(feature = "rayon")]
+pub(super) trait StickerValue: Copy + Send + Sync {}
+#[cfg(feature = "rayon")]
+impl<T: Copy + Send + Sync> StickerValue for T {}
+#[cfg(not(feature = "rayon"))]
+pub(super) trait StickerValue {}
+#[cfg(not(feature = "rayon"))]
+impl<T> StickerValue for T {}
 
 pub(super) fn create_side(
     side_length: usize,
     colour_variant_creator: &dyn Fn(Option<char>) -> CubieFace,
 ) -> Side {
     assert!(side_length > 0, "create_side must have 1 <= side_length");
-    let mut side = vec![];
-    for _outer in 0..side_length {
-        let inner_vec = vec![colour_variant_creator(None); side_length];
-        side.push(inner_vec);
-    }
-    side
+    Grid::filled(side_length, colour_variant_creator(None))
 }
 
 pub(super) fn create_side_with_unique_characters(
@@ -22,36 +38,275 @@ pub(super) fn create_side_with_unique_characters(
         (1..=8).contains(&side_length),
         "create_side_with_unique_characters must have 1 <= side_length <= 8"
     );
-    let mut side = vec![];
-    for outer in 0..side_length {
-        let mut inner_vec = vec![];
-        for inner in 0..side_length {
-            let value = u32::try_from((side_length * outer) + inner)
-                .expect("side_length is limited to 8 so conversion to u32 should never fail");
-            let display_char = char::from_u32('0' as u32 + value);
-            inner_vec.push(colour_variant_creator(display_char));
+    Grid::from_fn(side_length, |outer, inner| {
+        let value = u32::try_from((side_length * outer) + inner)
+            .expect("side_length is limited to 8 so conversion to u32 should never fail");
+        let display_char = char::from_u32('0' as u32 + value);
+        colour_variant_creator(display_char)
+    })
+}
+
+pub(super) fn create_side_of_origins(side_length: usize, face: F) -> Grid<StickerOrigin> {
+    assert!(
+        side_length > 0,
+        "create_side_of_origins must have 1 <= side_length"
+    );
+    Grid::from_fn(side_length, |row, col| StickerOrigin::new(face, row, col))
+}
+
+pub(super) fn rotate_layer_90_degrees<T: StickerValue>(
+    side_map: &mut EnumMap<F, Grid<T>>,
+    face: F,
+    layer: usize,
+    direction: Direction,
+) {
+    let side_length = side_map[face].len();
+    if layer == 0 {
+        rotate_side_90_degrees(&mut side_map[face], direction);
+    }
+    if layer + 1 == side_length {
+        rotate_side_90_degrees(&mut side_map[face.opposite()], direction.opposite());
+    }
+    rotate_only_adjacents(side_map, face, layer, direction);
+}
+
+/// Turn a layer half a turn, moving each sticker once rather than twice as two quarter turns would.
+pub(super) fn rotate_layer_180_degrees<T: StickerValue>(
+    side_map: &mut EnumMap<F, Grid<T>>,
+    face: F,
+    layer: usize,
+) {
+    let side_length = side_map[face].len();
+    if layer == 0 {
+        rotate_side_180_degrees(&mut side_map[face]);
+    }
+    if layer + 1 == side_length {
+        rotate_side_180_degrees(&mut side_map[face.opposite()]);
+    }
+
+    let adjacents = face.adjacent_faces_clockwise();
+    for position in 0..side_length {
+        let cell_of = |(adjacent_face, adjacent_alignment): &(F, IA)| {
+            let index = strip_cell(side_length, adjacent_alignment, layer, position);
+            (*adjacent_face, index)
+        };
+        swap_between_sides(side_map, cell_of(&adjacents[0]), cell_of(&adjacents[2]));
+        swap_between_sides(side_map, cell_of(&adjacents[1]), cell_of(&adjacents[3]));
+    }
+    instrumentation::count_swaps(2 * side_length);
+    for _ in adjacents {
+        instrumentation::count_slice_copy();
+    }
+}
+
+/// Turn a layer of a map of sticker rotations, so that each rotation both moves with its sticker and turns by however much its sticker turns.
+///
+/// Rotations are counted in clockwise quarter turns, from 0 to 3.
+pub(super) fn rotate_layer_of_rotations_90_degrees(
+    rotation_map: &mut EnumMap<F, Grid<u8>>,
+    face: F,
+    layer: usize,
+    direction: Direction,
+) {
+    let side_length = rotation_map[face].len();
+    rotate_layer_90_degrees(rotation_map, face, layer, direction);
+    if layer == 0 {
+        turn_all(&mut rotation_map[face], quarter_turns(direction));
+    }
+    if layer + 1 == side_length {
+        turn_all(
+            &mut rotation_map[face.opposite()],
+            quarter_turns(direction.opposite()),
+        );
+    }
+
+    let adjacents = face.adjacent_faces_clockwise();
+    for (i, (adjacent_face, adjacent_alignment)) in adjacents.iter().enumerate() {
+        let from = match direction {
+            Direction::Clockwise => (i + 3) % 4,
+            Direction::Anticlockwise => (i + 1) % 4,
+        };
+        // Stickers keep their rotation relative to the strip they travel along, which points a different way on each side
+        let turn = (strip_quarter_turns(adjacent_alignment) + 4
+            - strip_quarter_turns(&adjacents[from].1))
+            % 4;
+        let cells = rotation_map[*adjacent_face].cells_mut();
+        for position in 0..side_length {
+            let cell = &mut cells[strip_cell(side_length, adjacent_alignment, layer, position)];
+            *cell = (*cell + turn) % 4;
         }
-        side.push(inner_vec);
     }
-    side
 }
 
-pub(super) fn get_clockwise_slice_of_side(side: &Side, index_alignment: &IA) -> Vec<CubieFace> {
+fn quarter_turns(direction: Direction) -> u8 {
+    match direction {
+        Direction::Clockwise => 1,
+        Direction::Anticlockwise => 3,
+    }
+}
+
+fn turn_all(side: &mut Grid<u8>, turn: u8) {
+    side.cells_mut()
+        .iter_mut()
+        .for_each(|rotation| *rotation = (*rotation + turn) % 4);
+}
+
+/// Which way a strip of stickers runs, as clockwise quarter turns from pointing up its side, following the positions of [`strip_cell`].
+fn strip_quarter_turns(index_alignment: &IA) -> u8 {
     match index_alignment {
-        IA::OuterStart => side
-            .iter()
-            .map(|inner| inner.first().expect("Side inner had no member").to_owned())
-            .collect::<Vec<CubieFace>>(),
-        IA::OuterEnd => side
-            .iter()
-            .map(|inner| inner.last().expect("Side inner had no member").to_owned())
-            .rev()
-            .collect::<Vec<CubieFace>>(),
-        IA::InnerFirst => {
-            let mut inner_first_vec = side.first().expect("Side had no inner").to_owned();
-            inner_first_vec.reverse();
-            inner_first_vec
+        IA::OuterEnd => 0,
+        IA::InnerLast => 1,
+        IA::OuterStart => 2,
+        IA::InnerFirst => 3,
+    }
+}
+
+/// Turn the whole cube as R does, so the Front side moves to Up.
+pub(super) fn turn_whole_cube_x<T: StickerValue>(side_map: &mut EnumMap<F, Grid<T>>) {
+    let up = mem::take(&mut side_map[F::Up]);
+    side_map[F::Up] = mem::take(&mut side_map[F::Front]);
+    side_map[F::Front] = mem::take(&mut side_map[F::Down]);
+    side_map[F::Down] = mem::take(&mut side_map[F::Back]);
+    side_map[F::Back] = up;
+
+    rotate_side_180_degrees(&mut side_map[F::Down]);
+    rotate_side_180_degrees(&mut side_map[F::Back]);
+    rotate_side_90_degrees(&mut side_map[F::Right], Direction::Clockwise);
+    rotate_side_90_degrees(&mut side_map[F::Left], Direction::Anticlockwise);
+}
+
+/// Turn the whole cube as U does, so the Front side moves to Left.
+pub(super) fn turn_whole_cube_y<T: StickerValue>(side_map: &mut EnumMap<F, Grid<T>>) {
+    let front = mem::take(&mut side_map[F::Front]);
+    side_map[F::Front] = mem::take(&mut side_map[F::Right]);
+    side_map[F::Right] = mem::take(&mut side_map[F::Back]);
+    side_map[F::Back] = mem::take(&mut side_map[F::Left]);
+    side_map[F::Left] = front;
+
+    rotate_side_90_degrees(&mut side_map[F::Up], Direction::Clockwise);
+    rotate_side_90_degrees(&mut side_map[F::Down], Direction::Anticlockwise);
+}
+
+fn rotate_side_180_degrees<T>(side: &mut Grid<T>) {
+    reverse(side.cells_mut());
+}
+
+/// Turn the stickers of one side a quarter turn, by cycling each set of four stickers that swap places with three swaps.
+fn rotate_side_90_degrees<T: StickerValue>(side: &mut Grid<T>, direction: Direction) {
+    let n = side.len();
+    #[cfg(feature = "rayon")]
+    if n >= PARALLEL_SIDE_LENGTH {
+        rotate_side_90_degrees_in_parallel(side, direction);
+        return;
+    }
+    for row in 0..n / 2 {
+        for col in row..n - 1 - row {
+            let ring = [
+                side.index_of(row, col),
+                side.index_of(col, n - 1 - row),
+                side.index_of(n - 1 - row, n - 1 - col),
+                side.index_of(n - 1 - col, row),
+            ];
+            let cells = side.cells_mut();
+            match direction {
+                Direction::Clockwise => ring[1..].iter().for_each(|&i| cells.swap(ring[0], i)),
+                Direction::Anticlockwise => {
+                    ring[1..].iter().rev().for_each(|&i| cells.swap(ring[0], i));
+                }
+            }
+            instrumentation::count_swaps(3);
+        }
+    }
+}
+
+/// Turn the stickers of one side a quarter turn by filling each row from a copy of the side, with rows filled on different threads.
+#[cfg(feature = "rayon")]
+fn rotate_side_90_degrees_in_parallel<T: StickerValue>(side: &mut Grid<T>, direction: Direction) {
+    use rayon::prelude::*;
+
+    let n = side.len();
+    let source = side.cells().to_vec();
+    side.cells_mut()
+        .par_chunks_exact_mut(n)
+        .enumerate()
+        .for_each(|(row, cells)| {
+            for (col, cell) in cells.iter_mut().enumerate() {
+                *cell = match direction {
+                    Direction::Clockwise => source[(n - 1 - col) * n + row],
+                    Direction::Anticlockwise => source[col * n + n - 1 - row],
+                };
+            }
+        });
+    instrumentation::count_slice_copy();
+}
+
+fn reverse<T>(values: &mut [T]) {
+    values.reverse();
+    instrumentation::count_swaps(values.len() / 2);
+}
+
+/// Move the strips of stickers on the four sides around the turning layer a quarter turn, in place.
+///
+/// Each set of four stickers that swap places is cycled with three swaps, as for the stickers of a side, so no strip is ever copied out.
+fn rotate_only_adjacents<T>(
+    side_map: &mut EnumMap<F, Grid<T>>,
+    face: F,
+    layer: usize,
+    direction: Direction,
+) {
+    let adjacents = face.adjacent_faces_clockwise();
+    let side_length = side_map[face].len();
+    let swap_order = match direction {
+        Direction::Clockwise => [1, 2, 3],
+        Direction::Anticlockwise => [3, 2, 1],
+    };
+    for position in 0..side_length {
+        let cell_of = |(adjacent_face, adjacent_alignment): &(F, IA)| {
+            let index = strip_cell(side_length, adjacent_alignment, layer, position);
+            (*adjacent_face, index)
+        };
+        let first = cell_of(&adjacents[0]);
+        for i in swap_order {
+            swap_between_sides(side_map, first, cell_of(&adjacents[i]));
         }
-        IA::InnerLast => side.last().expect("Side had no inner").to_owned(),
     }
+    instrumentation::count_swaps(3 * side_length);
+    for _ in adjacents {
+        instrumentation::count_slice_copy();
+    }
+}
+
+/// The index of the row or column that is `layer` layers in from the edge described by `index_alignment`.
+fn index_at_layer(side_length: usize, index_alignment: &IA, layer: usize) -> usize {
+    match index_alignment {
+        IA::OuterStart | IA::InnerFirst => layer,
+        IA::OuterEnd | IA::InnerLast => side_length - 1 - layer,
+    }
+}
+
+/// The index into [`Grid::cells`] of the sticker at `position` along a strip, counting clockwise around the turning face.
+fn strip_cell(side_length: usize, index_alignment: &IA, layer: usize, position: usize) -> usize {
+    let index = index_at_layer(side_length, index_alignment, layer);
+    let (row, col) = match index_alignment {
+        IA::OuterStart => (position, index),
+        IA::OuterEnd => (side_length - 1 - position, index),
+        IA::InnerFirst => (index, side_length - 1 - position),
+        IA::InnerLast => (index, position),
+    };
+    row * side_length + col
+}
+
+fn swap_between_sides<T>(
+    side_map: &mut EnumMap<F, Grid<T>>,
+    (face_a, index_a): (F, usize),
+    (face_b, index_b): (F, usize),
+) {
+    let [side_a, side_b] = side_map
+        .as_mut_slice()
+        .get_disjoint_mut([face_a.into_usize(), face_b.into_usize()])
+        .expect("the sides around a turning layer are always four different sides");
+    mem::swap(
+        &mut side_a.cells_mut()[index_a],
+        &mut side_b.cells_mut()[index_b],
+    );
 }
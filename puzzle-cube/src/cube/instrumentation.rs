@@ -0,0 +1,129 @@
+#[cfg(feature = "instrumentation")]
+use std::cell::Cell;
+
+#[cfg(feature = "instrumentation")]
+use super::Cube;
+
+/// Totals of the work done by every rotation performed on the current thread since the counters were last reset.
+///
+/// Rotations of cubes with sticker origin tracking do the work twice, once for the colours and once for the origins, and are counted as such.
+#[cfg(feature = "instrumentation")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RotationCounters {
+    /// The rotations performed, where each is a single quarter turn in either direction, or a half turn combined by [`Cube::rotate_batch`].
+    pub rotations: usize,
+    /// The rows or columns of stickers moved from one side to another.
+    pub slice_copies: usize,
+    /// The pairs of stickers, or rows of stickers, swapped in place.
+    pub element_swaps: usize,
+    /// The heap allocations made to hold rows or columns of stickers while they are moved, which rotations no longer need.
+    pub allocations: usize,
+}
+
+#[cfg(feature = "instrumentation")]
+thread_local! {
+    static COUNTERS: Cell<RotationCounters> = Cell::new(RotationCounters::default());
+}
+
+#[cfg(feature = "instrumentation")]
+impl Cube {
+    /// Returns the work done by rotations on the current thread since the counters were last reset.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// Cube::reset_rotation_counters();
+    /// Cube::create(3).rotate(Rotation::anticlockwise(Face::Front));
+    /// let counters = Cube::rotation_counters();
+    /// assert_eq!(1, counters.rotations);
+    /// assert_eq!(4, counters.slice_copies);
+    /// ```
+    #[must_use]
+    pub fn rotation_counters() -> RotationCounters {
+        COUNTERS.get()
+    }
+
+    /// Set every rotation counter on the current thread back to zero.
+    pub fn reset_rotation_counters() {
+        COUNTERS.set(RotationCounters::default());
+    }
+}
+
+// Without the `instrumentation` feature these do nothing, so rotations pay nothing for them.
+
+#[cfg(feature = "instrumentation")]
+fn count(update: impl FnOnce(&mut RotationCounters)) {
+    let mut counters = COUNTERS.get();
+    update(&mut counters);
+    COUNTERS.set(counters);
+}
+
+pub(super) fn count_rotation() {
+    #[cfg(feature = "instrumentation")]
+    count(|counters| counters.rotations += 1);
+}
+
+pub(super) fn count_slice_copy() {
+    #[cfg(feature = "instrumentation")]
+    count(|counters| counters.slice_copies += 1);
+}
+
+#[cfg_attr(not(feature = "instrumentation"), allow(unused_variables))]
+pub(super) fn count_swaps(swaps: usize) {
+    #[cfg(feature = "instrumentation")]
+    count(|counters| counters.element_swaps += swaps);
+}
+
+#[cfg(all(test, feature = "instrumentation"))]
+mod tests {
+    use super::*;
+    use crate::cube::{face::Face, rotation::Rotation};
+    use pretty_assertions::assert_eq;
+
+    fn counters_for(side_length: usize, rotation: Rotation) -> RotationCounters {
+        let mut cube = Cube::create(side_length);
+        Cube::reset_rotation_counters();
+        cube.rotate(rotation);
+        Cube::rotation_counters()
+    }
+
+    #[test]
+    fn test_anticlockwise_does_the_same_work_as_clockwise() {
+        for side_length in 1..=7 {
+            for face in [Face::Up, Face::Front, Face::Left] {
+                assert_eq!(
+                    counters_for(side_length, Rotation::clockwise(face)),
+                    counters_for(side_length, Rotation::anticlockwise(face)),
+                    "{face:?} of a {side_length}x{side_length} cube"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_face_turn_counters() {
+        let counters = counters_for(3, Rotation::clockwise(Face::Right));
+
+        assert_eq!(1, counters.rotations);
+        assert_eq!(4, counters.slice_copies);
+        // 3 swaps for each of the 2 rings of 4 stickers turned on the Right side itself, and 3 for each of the 3 sets of 4 stickers moved around it
+        assert_eq!(2 * 3 + 3 * 3, counters.element_swaps);
+        assert_eq!(0, counters.allocations);
+    }
+
+    #[test]
+    fn test_inner_layers_do_not_turn_a_side() {
+        let counters = counters_for(5, Rotation::clockwise(Face::Front).at_layer(2));
+
+        assert_eq!(4, counters.slice_copies);
+        assert_eq!(3 * 5, counters.element_swaps);
+    }
+
+    #[test]
+    fn test_counters_include_sticker_origins() {
+        let mut cube = Cube::create_with_sticker_origins(3);
+        Cube::reset_rotation_counters();
+        cube.rotate(Rotation::clockwise(Face::Up));
+
+        assert_eq!(1, Cube::rotation_counters().rotations);
+        assert_eq!(8, Cube::rotation_counters().slice_copies);
+    }
+}
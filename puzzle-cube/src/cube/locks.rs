@@ -0,0 +1,155 @@
+use super::{face::Face, rotation::Rotation, Cube};
+
+impl Cube {
+    /// Lock the layer that is `layer` layers in from `face`, so that rotations of it are rejected until it is unlocked.
+    ///
+    /// The same layer counted from the opposite face is locked too, so locking layer 0 of `Up` also locks the last layer of `Down`.
+    /// Locks are settings of this cube rather than part of its state, so they are not saved with it.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// let mut cube = Cube::create(3);
+    /// cube.lock_layer(Face::Up, 0);
+    /// assert!(cube.try_rotate(Rotation::clockwise(Face::Up)).is_err());
+    /// assert!(cube.try_rotate(Rotation::clockwise(Face::Down).at_layer(2)).is_err());
+    /// assert!(cube.try_rotate(Rotation::clockwise(Face::Front)).is_ok());
+    /// ```
+    /// # Panics
+    /// Will panic if `layer` is not within this cube, that is if it is not less than the side length
+    pub fn lock_layer(&mut self, face: Face, layer: usize) {
+        assert!(
+            layer < self.side_length,
+            "Cannot lock layer {layer} of a cube with side length {}",
+            self.side_length
+        );
+        if !self.is_layer_locked(face, layer) {
+            self.locked_layers.push((face, layer));
+        }
+    }
+
+    /// Unlock the layer that is `layer` layers in from `face`, however it was locked.
+    pub fn unlock_layer(&mut self, face: Face, layer: usize) {
+        let side_length = self.side_length;
+        self.locked_layers
+            .retain(|&locked| !same_layer(side_length, locked, (face, layer)));
+    }
+
+    /// Unlock every layer of this cube.
+    pub fn unlock_all(&mut self) {
+        self.locked_layers.clear();
+    }
+
+    /// Returns whether the layer that is `layer` layers in from `face` is locked.
+    #[must_use]
+    pub fn is_layer_locked(&self, face: Face, layer: usize) -> bool {
+        self.locked_layers
+            .iter()
+            .any(|&locked| same_layer(self.side_length, locked, (face, layer)))
+    }
+
+    /// Returns each locked layer, as the face and layer it was locked with.
+    #[must_use]
+    pub fn locked_layers(&self) -> &[(Face, usize)] {
+        &self.locked_layers
+    }
+
+    pub(super) fn has_unlocked_layer(&self) -> bool {
+        [Face::Up, Face::Front, Face::Right]
+            .into_iter()
+            .any(|face| (0..self.side_length).any(|layer| !self.is_layer_locked(face, layer)))
+    }
+
+    /// Check that the given rotation does not turn a locked layer, without performing it.
+    /// # Errors
+    /// Will return an Err variant when the rotation turns a locked layer
+    pub fn check_unlocked(&self, rotation: Rotation) -> Result<(), String> {
+        let Rotation {
+            relative_to, layer, ..
+        } = rotation;
+        if !self.is_layer_locked(relative_to, layer) {
+            Ok(())
+        } else if layer == 0 {
+            Err(format!(
+                "The {relative_to:?} face is locked, so it cannot be turned"
+            ))
+        } else {
+            Err(format!(
+                "Layer {layer} from the {relative_to:?} face is locked, so it cannot be turned"
+            ))
+        }
+    }
+
+    /// Perform the given rotation, unless it turns a locked layer.
+    /// # Errors
+    /// Will return an Err variant when the rotation turns a locked layer, in which case the cube is left unchanged
+    /// # Panics
+    /// Will panic if the rotation's layer is not within this cube, that is if it is not less than the side length
+    pub fn try_rotate(&mut self, rotation: Rotation) -> Result<(), String> {
+        self.check_unlocked(rotation)?;
+        self.rotate(rotation);
+        Ok(())
+    }
+}
+
+fn same_layer(side_length: usize, a: (Face, usize), b: (Face, usize)) -> bool {
+    a == b || (a.0.opposite() == b.0 && a.1 + b.1 + 1 == side_length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_locked_face_rejects_rotation_and_is_unchanged() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(Face::Right, 0);
+
+        assert_eq!(
+            Err("The Right face is locked, so it cannot be turned".to_string()),
+            cube.try_rotate(Rotation::anticlockwise(Face::Right))
+        );
+        assert_eq!(Cube::create(3).side_map(), cube.side_map());
+    }
+
+    #[test]
+    fn test_locked_inner_layer_is_locked_from_both_sides() {
+        let mut cube = Cube::create(5);
+        cube.lock_layer(Face::Front, 1);
+
+        assert!(cube.is_layer_locked(Face::Back, 3));
+        assert!(!cube.is_layer_locked(Face::Back, 1));
+        assert_eq!(
+            Err("Layer 3 from the Back face is locked, so it cannot be turned".to_string()),
+            cube.check_unlocked(Rotation::clockwise(Face::Back).at_layer(3))
+        );
+        assert_eq!(
+            Ok(()),
+            cube.try_rotate(Rotation::clockwise(Face::Front).at_layer(2))
+        );
+    }
+
+    #[test]
+    fn test_unlock_layer_from_opposite_face() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(Face::Up, 0);
+        cube.lock_layer(Face::Down, 2);
+        cube.lock_layer(Face::Left, 1);
+
+        cube.unlock_layer(Face::Down, 2);
+        assert!(!cube.is_layer_locked(Face::Up, 0));
+        assert!(cube.is_layer_locked(Face::Right, 1));
+
+        assert_eq!(&[(Face::Left, 1)], cube.locked_layers());
+
+        cube.unlock_all();
+        assert!(!cube.is_layer_locked(Face::Left, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Layer 1 from the Right face is locked, so it cannot be turned")]
+    fn test_rotate_panics_on_locked_layer() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(Face::Left, 1);
+        cube.rotate(Rotation::clockwise(Face::Right).at_layer(1));
+    }
+}
@@ -12,13 +12,18 @@ macro_rules! create_cube_from_sides {
         back: $back:expr,
         left: $left:expr $(,)?
     ) => {
-        Cube::create_from_sides($top, $bottom, $front, $right, $back, $left)
+        Cube::try_from_sides($top, $bottom, $front, $right, $back, $left)
+            .expect("Every side of a custom cube must be square and the same size")
     };
 }
 
 /// Easily create one side of a cube. Useful for creating custom cube states in tests.
 ///
 /// With CubieFace in scope, each line of the side is defined as the colours `CubieFace` provides, and ended by a semicolon. These will be created without the optional custom display char.
+/// Colours can also be written as their first letter, `W Y B O G R`, which keeps large sides readable, and the two styles can be mixed.
+///
+/// The side length is the number of lines. Every line must have that many colours, which is checked when compiling, naming the line that is wrong.
+/// A side of a single colour can instead be created by giving the colour and the side length.
 /// ```no_run
 /// # use rusty_puzzle_cube::create_cube_side;
 /// use rusty_puzzle_cube::cube::cubie_face::CubieFace;
@@ -27,16 +32,94 @@ macro_rules! create_cube_from_sides {
 ///     White White Yellow;
 ///     Blue Red White;
 /// );
+/// let same_side = create_cube_side!(
+///     G O G;
+///     W W Y;
+///     B R W;
+/// );
+/// let solid_side = create_cube_side!(W; 4);
 /// ```
 #[cfg(test)]
 #[macro_export]
 macro_rules! create_cube_side {
-    ($colour:ident ; $side_length:literal) => {
-        vec![vec![CubieFace::$colour(None) ; $side_length] ; $side_length]
+    (@cubie W) => { CubieFace::White(None) };
+    (@cubie Y) => { CubieFace::Yellow(None) };
+    (@cubie B) => { CubieFace::Blue(None) };
+    (@cubie O) => { CubieFace::Orange(None) };
+    (@cubie G) => { CubieFace::Green(None) };
+    (@cubie R) => { CubieFace::Red(None) };
+    (@cubie $colour:ident) => { CubieFace::$colour(None) };
+    ($colour:ident ; $side_length:expr) => {
+        vec![vec![create_cube_side!(@cubie $colour) ; $side_length] ; $side_length]
     };
-    ( $( $($colour:ident)+ ; )+ ) => {
+    ( $( $($colour:ident)+ ; )+ ) => {{
+        const SIDE_LENGTH: usize = [$( stringify!($($colour)+) ),+].len();
+        $(
+            const _: () = assert!(
+                [$( stringify!($colour) ),+].len() == SIDE_LENGTH,
+                concat!(
+                    "Line `",
+                    stringify!($($colour)+),
+                    "` must have one colour for each line of the side"
+                )
+            );
+        )+
         vec![ $(
-            vec![ $(CubieFace::$colour(None),)* ],
+            vec![ $(create_cube_side!(@cubie $colour),)* ],
         )* ]
-    };
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cube::{cubie_face::CubieFace, Cube};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_shorthand_matches_full_names() {
+        assert_eq!(
+            create_cube_side!(
+                Green Orange Green;
+                White White Yellow;
+                Blue Red White;
+            ),
+            create_cube_side!(
+                G O G;
+                W White Y;
+                B R W;
+            )
+        );
+    }
+
+    #[test]
+    fn test_solid_side_shorthand() {
+        assert_eq!(create_cube_side!(Yellow; 4), create_cube_side!(Y; 4));
+        assert_eq!(4, create_cube_side!(Y; 2 + 2).len());
+    }
+
+    #[test]
+    fn test_side_length_inferred_from_lines() {
+        let side = create_cube_side!(
+            W W W W;
+            W W W W;
+            W W W W;
+            W W W W;
+        );
+
+        assert_eq!(create_cube_side!(White; 4), side);
+    }
+
+    #[test]
+    fn test_cube_from_shorthand_sides() {
+        let cube = create_cube_from_sides!(
+            top: create_cube_side!(W; 2),
+            bottom: create_cube_side!(Y; 2),
+            front: create_cube_side!(B; 2),
+            right: create_cube_side!(O; 2),
+            back: create_cube_side!(G; 2),
+            left: create_cube_side!(R; 2),
+        );
+
+        assert_eq!(Cube::create(2), cube);
+    }
 }
@@ -1,36 +1,127 @@
 use std::{fmt, mem};
 
 use enum_map::{enum_map, EnumMap};
-use itertools::izip;
+use rand::Rng;
 
-use crate::cube::helpers::{create_side, create_side_with_unique_characters};
+use crate::budget::{Budget, Interrupted};
+use crate::cube::helpers::{
+    create_side, create_side_of_origins, create_side_with_unique_characters,
+    rotate_layer_90_degrees, rotate_layer_of_rotations_90_degrees, turn_whole_cube_x,
+    turn_whole_cube_y,
+};
 
+use self::colour_scheme::{with_display_char, WESTERN};
 use self::cubie_face::CubieFace;
+use self::direction::Direction;
 use self::face::{Face as F, IndexAlignment as IA};
-use self::helpers::get_clockwise_slice_of_side;
+use self::grid::Grid;
+use self::render_text::TextRenderOptions;
+use self::rotation::Rotation;
+use self::sticker_origin::StickerOrigin;
+
+/// Counts of the stickers of each colour, for rejecting impossible cubes before checking anything deeper.
+pub mod colour_counts;
+
+/// Colour schemes giving the colour each face of a solved cube starts with, so cubes can be created and shown in schemes other than the default.
+pub mod colour_scheme;
+
+mod batch;
+
+mod bytes;
+
+mod compact;
 
 /// An enum representing an individual cubie within one side of the cube, hence it only represents one face of the cubie.
 pub mod cubie_face;
 
+/// An enum representing the direction a face is turned in.
+pub mod direction;
+
 /// An enum representing the faces of a cube, and providing a mapping for 'adjacents' and `IndexAlignment` that are used to perform rotations of a face.
 pub mod face;
 
+/// Options for reading facelet strings written by other tools, whose colours or way of holding the cube may differ from this crate's.
+pub mod facelets;
+
+/// A type representing a square grid stored as one contiguous buffer, used for the stickers of each side.
+pub mod grid;
+
 pub(crate) mod helpers;
 
+mod locks;
+
+/// Counters of the work done by rotations, for catching performance regressions in benchmarks.
+#[cfg(feature = "instrumentation")]
+pub mod instrumentation;
+#[cfg(not(feature = "instrumentation"))]
+mod instrumentation;
+
 /// Macros that aid in creating custom cube states for test cases.
 pub mod macros;
 
+/// Types describing a 3x3 cube by its corner and edge pieces, and how each is placed and oriented.
+pub mod pieces;
+
+/// A type representing turns of opposite faces that are made at the same time.
+pub mod parallel_rotation;
+
+/// Drawing a cube as an SVG, either as a flat net or as an isometric projection, for embedding in web pages and bug reports.
+#[cfg(feature = "svg")]
+pub mod render_svg;
+
+/// Options for writing a cube out as text, choosing the layout of its net, spacing, colour and the character used for each sticker.
+pub mod render_text;
+
+/// A type representing a single turn of one face, so that moves can be stored and passed around.
+pub mod rotation;
+
+/// Types for ordered, optionally grouped and labelled, lists of rotations.
+pub mod sequence;
+
+#[cfg(feature = "serde")]
+mod serde_support;
+
+/// A type recording where a sticker started, used by cubes created with sticker origin tracking.
+pub mod sticker_origin;
+
 /// A type representing a mapping between a face of the cube and the type that holds the cubies currently on that face.
-pub type SideMap = EnumMap<F, Box<Side>>;
-type Side = Vec<Vec<CubieFace>>;
+pub type SideMap = EnumMap<F, Side>;
+type Side = Grid<CubieFace>;
+
+/// A type representing a mapping between a face of the cube and the origins of the stickers currently on that face.
+pub type StickerOriginMap = EnumMap<F, Grid<StickerOrigin>>;
 
-const HORIZONTAL_PADDING: &str = " ";
+/// A type representing a mapping between a face of the cube and how far the stickers currently on that face are turned, in clockwise quarter turns from 0 to 3.
+pub type StickerRotationMap = EnumMap<F, Grid<u8>>;
+
+/// A quarter turn of the whole cube, named after the standard notation for cube rotations.
+#[derive(Clone, Copy)]
+enum WholeCubeTurn {
+    /// Turn as R does.
+    X,
+    /// Turn as U does.
+    Y,
+}
 
 /// A representation of a cube that can be manipulated via making pre-defined rotations.
-#[derive(PartialEq)]
+///
+/// With the `serde` feature enabled, cubes can be serialised and deserialised. Deserialising checks that every side is the same square size, so a malformed save is rejected rather than producing a cube that panics when rotated.
+///
+/// Cubes can be stored in a `HashSet` or as `HashMap` keys, for example to detect positions that have already been seen in a search.
+/// Use [`Cube::canonicalise`] first if positions that differ only by how the cube is held should be treated as the same.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "serde_support::UncheckedCube")
+)]
 pub struct Cube {
     side_length: usize,
     side_map: SideMap,
+    sticker_origin_map: Option<StickerOriginMap>,
+    sticker_rotation_map: Option<StickerRotationMap>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    locked_layers: Vec<(F, usize)>,
 }
 
 impl Cube {
@@ -44,13 +135,11 @@ impl Cube {
         Self {
             side_length,
             side_map: enum_map! {
-                F::Up => Box::new(create_side(side_length, &CubieFace::White)),
-                F::Down => Box::new(create_side(side_length, &CubieFace::Yellow)),
-                F::Front => Box::new(create_side(side_length, &CubieFace::Blue)),
-                F::Right => Box::new(create_side(side_length, &CubieFace::Orange)),
-                F::Back => Box::new(create_side(side_length, &CubieFace::Green)),
-                F::Left => Box::new(create_side(side_length, &CubieFace::Red)),
+                face => create_side(side_length, &|c| with_display_char(WESTERN[face], c)),
             },
+            sticker_origin_map: None,
+            sticker_rotation_map: None,
+            locked_layers: Vec::new(),
         }
     }
 
@@ -59,33 +148,388 @@ impl Cube {
     /// This can be useful for printing out the cube to terminal to check that moves being made are exactly as expect, not just the same colours as we expect.
     ///
     /// The provided `side_length` here must be >=1 and <=8 to allow for unique, visible characters per cubie in the basic ascii range.
+    /// To tell stickers apart on bigger cubes, use [`Cube::create_with_sticker_origins`], whose origins can be numbered with [`StickerOrigin::id`].
     #[must_use]
     pub fn create_with_unique_characters(side_length: usize) -> Self {
         Self {
             side_length,
             side_map: enum_map! {
-                F::Up => Box::new(create_side_with_unique_characters(side_length, &CubieFace::White)),
-                F::Down => Box::new(create_side_with_unique_characters(side_length, &CubieFace::Yellow)),
-                F::Front => Box::new(create_side_with_unique_characters(side_length, &CubieFace::Blue)),
-                F::Right => Box::new(create_side_with_unique_characters(side_length, &CubieFace::Orange)),
-                F::Back => Box::new(create_side_with_unique_characters(side_length, &CubieFace::Green)),
-                F::Left => Box::new(create_side_with_unique_characters(side_length, &CubieFace::Red)),
+                face => create_side_with_unique_characters(
+                    side_length,
+                    &|c| with_display_char(WESTERN[face], c),
+                ),
             },
+            sticker_origin_map: None,
+            sticker_rotation_map: None,
+            locked_layers: Vec::new(),
+        }
+    }
+
+    /// Create a new `Cube` instance with `side_length` cubies along each edge, where every sticker also records the position it started in.
+    ///
+    /// The origins follow their stickers through every rotation, which allows checking exactly where each sticker has travelled to, not just whether the colours match.
+    ///
+    /// The provided `side_length` here must be >=1 and <=65536.
+    /// ```no_run
+    /// # use rusty_puzzle_cube::cube::Cube;
+    /// let cube = Cube::create_with_sticker_origins(5);
+    /// assert_eq!(Some(true), cube.is_every_sticker_at_origin());
+    /// ```
+    #[must_use]
+    pub fn create_with_sticker_origins(side_length: usize) -> Self {
+        Self {
+            sticker_origin_map: Some(enum_map! {
+                face => create_side_of_origins(side_length, face),
+            }),
+            ..Self::create(side_length)
         }
     }
 
+    /// Create a new super cube with `side_length` cubies along each edge, where every sticker records both the position it started in and how far it has been turned.
+    ///
+    /// On a real cube the stickers of centre pieces can be turned in place without anything else changing, which matters for cubes printed with pictures rather than plain colours.
+    /// Each sticker starts at a rotation of 0, and is turned a quarter clockwise for each quarter turn that carries it clockwise, as seen from outside the cube on whichever side it is on.
+    ///
+    /// The provided `side_length` here must be >=1 and <=65536.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// let mut cube = Cube::create_supercube(3);
+    /// cube.rotate(Rotation::clockwise(Face::Up));
+    /// assert_eq!(Some(1), cube.sticker_rotation_map().map(|map| map[Face::Up][1][1]));
+    /// assert_eq!(Some(false), cube.is_every_sticker_upright());
+    /// ```
+    #[must_use]
+    pub fn create_supercube(side_length: usize) -> Self {
+        Self {
+            sticker_rotation_map: Some(enum_map! {
+                _ => Grid::filled(side_length, 0),
+            }),
+            ..Self::create_with_sticker_origins(side_length)
+        }
+    }
+
+    /// Create a `Cube` in the state described by `side_map`, such as one previously read from [`Cube::side_map`].
+    /// # Errors
+    /// Will return an Err variant when the sides are empty or not all the same size
+    pub fn try_from_side_map(side_map: SideMap) -> Result<Self, String> {
+        let side_length = side_map[F::Up].len();
+        if side_length == 0 {
+            return Err("Cube sides must not be empty".to_string());
+        }
+        for (face, side) in &side_map {
+            if side.len() != side_length {
+                return Err(format!(
+                    "{face:?} side must be {side_length}x{side_length} to match the Up side"
+                ));
+            }
+        }
+        Ok(Self {
+            side_length,
+            side_map,
+            sticker_origin_map: None,
+            sticker_rotation_map: None,
+            locked_layers: Vec::new(),
+        })
+    }
+
+    /// Create a `Cube` in a fully custom state from the rows of each side, with every side laid out as described for [`Cube::oriented_side`], such as when loading a saved game or setting up a position for a trainer.
+    ///
+    /// The state is not checked to be one that can be reached by turning a solved cube, which [`Cube::check_solvable`] can do for a 3x3 cube.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, cubie_face::CubieFace};
+    /// let side = |colour: fn(Option<char>) -> CubieFace| vec![vec![colour(None); 2]; 2];
+    /// let cube = Cube::try_from_sides(
+    ///     side(CubieFace::White),
+    ///     side(CubieFace::Yellow),
+    ///     side(CubieFace::Blue),
+    ///     side(CubieFace::Orange),
+    ///     side(CubieFace::Green),
+    ///     side(CubieFace::Red),
+    /// );
+    /// assert_eq!(Ok(Cube::create(2)), cube);
+    /// ```
+    /// # Errors
+    /// Will return an Err variant when a side is not square, or the sides are empty or not all the same size
+    pub fn try_from_sides(
+        up: Vec<Vec<CubieFace>>,
+        down: Vec<Vec<CubieFace>>,
+        front: Vec<Vec<CubieFace>>,
+        right: Vec<Vec<CubieFace>>,
+        back: Vec<Vec<CubieFace>>,
+        left: Vec<Vec<CubieFace>>,
+    ) -> Result<Self, String> {
+        let mut side_map = SideMap::default();
+        for (face, rows) in [
+            (F::Up, up),
+            (F::Down, down),
+            (F::Front, front),
+            (F::Right, right),
+            (F::Back, back),
+            (F::Left, left),
+        ] {
+            side_map[face] = Grid::try_from(rows).map_err(|e| format!("{face:?} side: {e}"))?;
+        }
+        Self::try_from_side_map(side_map)
+    }
+
     /// Returns the amount of cubies along each edge of this cube.
     #[must_use]
     pub fn side_length(&self) -> usize {
         self.side_length
     }
 
+    /// Returns roughly how many bytes of memory this cube uses, including any sticker origins and rotations.
+    #[must_use]
+    pub fn approx_memory_bytes(&self) -> usize {
+        let origin_bytes = if self.sticker_origin_map.is_some() {
+            approx_side_map_bytes::<StickerOrigin>(self.side_length)
+        } else {
+            0
+        };
+        let rotation_bytes = if self.sticker_rotation_map.is_some() {
+            approx_side_map_bytes::<u8>(self.side_length)
+        } else {
+            0
+        };
+        mem::size_of::<Self>()
+            .saturating_add(approx_side_map_bytes::<CubieFace>(self.side_length))
+            .saturating_add(origin_bytes)
+            .saturating_add(rotation_bytes)
+    }
+
+    /// Returns roughly how many bytes of memory [`Cube::create`] would use for the given side length, so very large cubes can be warned about before they are created.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::Cube;
+    /// assert_eq!(Cube::create(3).approx_memory_bytes(), Cube::approx_memory_bytes_for(3));
+    /// ```
+    #[must_use]
+    pub fn approx_memory_bytes_for(side_length: usize) -> usize {
+        mem::size_of::<Self>().saturating_add(approx_side_map_bytes::<CubieFace>(side_length))
+    }
+
     /// Returns the mapping of faces of the cube to the data structure of cubies on those faces to allow fully custom rendering of the cube.
+    ///
+    /// Every side is laid out as described for [`Cube::oriented_side`].
     #[must_use]
     pub fn side_map(&self) -> &SideMap {
         &self.side_map
     }
 
+    /// Returns the stickers of one side as seen when looking directly at that side from outside the cube, so `side[row][col]` counts rows down from the top and columns across from the left.
+    ///
+    /// Which neighbouring face is at the top of each side is given by [`Face::neighbours`], and matches the net printed for the cube: the top of Up borders Back, the top of Down borders Front, and the top of every other side borders Up.
+    /// Renderers can rely on this for every face, without needing to know how rotations are carried out.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, cubie_face::CubieFace, face::Face, rotation::Rotation};
+    /// let mut cube = Cube::create(3);
+    /// cube.rotate(Rotation::clockwise(Face::Right));
+    /// let front = cube.oriented_side(Face::Front);
+    /// assert!(matches!(front[0][2], CubieFace::Yellow(_)));
+    /// assert!(matches!(front[0][0], CubieFace::Blue(_)));
+    /// ```
+    ///
+    /// [`Face::neighbours`]: face::Face::neighbours
+    #[must_use]
+    pub fn oriented_side(&self, face: F) -> &Grid<CubieFace> {
+        &self.side_map[face]
+    }
+
+    /// Returns the mapping of faces of the cube to the origins of the stickers currently on those faces, if this cube was created with sticker origin tracking.
+    #[must_use]
+    pub fn sticker_origin_map(&self) -> Option<&StickerOriginMap> {
+        self.sticker_origin_map.as_ref()
+    }
+
+    /// Returns whether every sticker is back in the exact position it started in, if this cube was created with sticker origin tracking.
+    ///
+    /// This is stricter than every face being a single colour, as stickers of the same colour must also not have swapped places.
+    #[must_use]
+    pub fn is_every_sticker_at_origin(&self) -> Option<bool> {
+        let sticker_origin_map = self.sticker_origin_map.as_ref()?;
+        Some(sticker_origin_map.iter().all(|(face, side)| {
+            side.rows().enumerate().all(|(row, origins)| {
+                origins
+                    .iter()
+                    .enumerate()
+                    .all(|(col, origin)| origin.is_at(face, row, col))
+            })
+        }))
+    }
+
+    /// Returns the mapping of faces of the cube to how far the stickers currently on those faces are turned, if this cube was created as a super cube.
+    #[must_use]
+    pub fn sticker_rotation_map(&self) -> Option<&StickerRotationMap> {
+        self.sticker_rotation_map.as_ref()
+    }
+
+    /// Returns whether every sticker is the right way up on its side, if this cube was created as a super cube.
+    ///
+    /// Together with [`Cube::is_every_sticker_at_origin`] this shows whether a super cube is solved, including the centres that a plain cube could leave twisted.
+    #[must_use]
+    pub fn is_every_sticker_upright(&self) -> Option<bool> {
+        let sticker_rotation_map = self.sticker_rotation_map.as_ref()?;
+        Some(
+            sticker_rotation_map
+                .values()
+                .all(|side| side.cells().iter().all(|&rotation| rotation == 0)),
+        )
+    }
+
+    /// Returns which faces are a single colour, whichever colour that is.
+    ///
+    /// Any custom display characters are ignored, so cubes created with unique characters are compared by colour alone.
+    #[must_use]
+    pub fn solved_faces(&self) -> EnumMap<F, bool> {
+        EnumMap::from_fn(|face| {
+            let side = &self.side_map[face];
+            let colour = mem::discriminant(&side[0][0]);
+            side.cells()
+                .iter()
+                .all(|cubie| mem::discriminant(cubie) == colour)
+        })
+    }
+
+    /// Returns whether every face is a single colour, for any side length and any overall orientation of the cube.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// let mut cube = Cube::create(4);
+    /// assert!(cube.is_solved());
+    /// cube.rotate(Rotation::clockwise(Face::Right));
+    /// assert!(!cube.is_solved());
+    /// ```
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.solved_faces().values().all(|&solved| solved)
+    }
+
+    /// Returns a copy of this cube turned as a whole so that the white centre is on the Up face and the blue centre is on the Front face, as on a newly created cube.
+    ///
+    /// Cubes with an even side length have no fixed centres, so instead the white, blue and red corner is brought to where it starts, between the Up, Front and Left faces, with white facing up.
+    /// Any sticker origins move with their stickers. If no orientation meets this convention, for example because the colours are not those of a real cube, an unchanged copy is returned.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// let mut cube = Cube::create(2);
+    /// cube.rotate(Rotation::clockwise(Face::Up));
+    /// cube.rotate(Rotation::anticlockwise(Face::Down));
+    /// assert_eq!(Cube::create(2), cube.normalised_orientation());
+    /// ```
+    #[must_use]
+    pub fn normalised_orientation(&self) -> Self {
+        let n = self.side_length;
+        let is_normalised = |cube: &Cube| {
+            let sides = &cube.side_map;
+            if n % 2 == 1 {
+                let mid = n / 2;
+                matches!(sides[F::Up][mid][mid], CubieFace::White(_))
+                    && matches!(sides[F::Front][mid][mid], CubieFace::Blue(_))
+            } else {
+                matches!(sides[F::Up][n - 1][0], CubieFace::White(_))
+                    && matches!(sides[F::Front][0][0], CubieFace::Blue(_))
+                    && matches!(sides[F::Left][0][n - 1], CubieFace::Red(_))
+            }
+        };
+        self.orientations()
+            .find(is_normalised)
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// Returns whether this cube has the same stickers as the other cube, once one of them has been turned as a whole into some orientation.
+    ///
+    /// Sticker origins are not compared.
+    #[must_use]
+    pub fn eq_up_to_orientation(&self, other: &Self) -> bool {
+        self.side_length == other.side_length
+            && self
+                .orientations()
+                .any(|cube| cube.side_map == other.side_map)
+    }
+
+    /// Returns a copy of this cube turned as a whole into a canonical orientation, so that every orientation of the same position gives an equal cube.
+    ///
+    /// Unlike [`Cube::normalised_orientation`] this works for any stickers, whether or not their colours could be those of a real cube, but the chosen orientation has no meaning beyond being consistent.
+    /// Any sticker origins move with their stickers but are not used to choose the orientation.
+    /// ```
+    /// # use std::collections::HashSet;
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// let mut r = Cube::create(2);
+    /// r.rotate(Rotation::clockwise(Face::Right));
+    /// let mut l = Cube::create(2);
+    /// l.rotate(Rotation::clockwise(Face::Left));
+    ///
+    /// let mut seen = HashSet::new();
+    /// assert!(seen.insert(r.canonicalise()));
+    /// assert!(!seen.insert(l.canonicalise()));
+    /// ```
+    #[must_use]
+    pub fn canonicalise(&self) -> Self {
+        self.orientations()
+            .min_by_key(|cube| {
+                cube.side_map
+                    .values()
+                    .flat_map(|side| side.cells())
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .expect("Every cube has 24 orientations")
+    }
+
+    /// Every one of the 24 ways of holding this cube, starting with the current orientation.
+    fn orientations(&self) -> impl Iterator<Item = Self> + '_ {
+        const TO_UP: [&[WholeCubeTurn]; 6] = [
+            &[],
+            &[WholeCubeTurn::X],
+            &[WholeCubeTurn::X, WholeCubeTurn::X],
+            &[WholeCubeTurn::X, WholeCubeTurn::X, WholeCubeTurn::X],
+            &[WholeCubeTurn::Y, WholeCubeTurn::X],
+            &[
+                WholeCubeTurn::Y,
+                WholeCubeTurn::Y,
+                WholeCubeTurn::Y,
+                WholeCubeTurn::X,
+            ],
+        ];
+        TO_UP.into_iter().flat_map(|turns| {
+            let mut cube = self.clone();
+            turns.iter().for_each(|&turn| cube.turn_whole(turn));
+            (0..4).map(move |_| {
+                let orientation = cube.clone();
+                cube.turn_whole(WholeCubeTurn::Y);
+                orientation
+            })
+        })
+    }
+
+    fn turn_whole(&mut self, turn: WholeCubeTurn) {
+        if let Some(sticker_rotation_map) = self.sticker_rotation_map.as_mut() {
+            let face = match turn {
+                WholeCubeTurn::X => F::Right,
+                WholeCubeTurn::Y => F::Up,
+            };
+            for layer in 0..self.side_length {
+                rotate_layer_of_rotations_90_degrees(
+                    sticker_rotation_map,
+                    face,
+                    layer,
+                    Direction::Clockwise,
+                );
+            }
+        }
+        match turn {
+            WholeCubeTurn::X => {
+                turn_whole_cube_x(&mut self.side_map);
+                if let Some(sticker_origin_map) = self.sticker_origin_map.as_mut() {
+                    turn_whole_cube_x(sticker_origin_map);
+                }
+            }
+            WholeCubeTurn::Y => {
+                turn_whole_cube_y(&mut self.side_map);
+                if let Some(sticker_origin_map) = self.sticker_origin_map.as_mut() {
+                    turn_whole_cube_y(sticker_origin_map);
+                }
+            }
+        }
+    }
+
     /// Rotate the given face 90° clockwise from the perspective of looking directly at that face from outside the cube.
     /// ```no_run
     /// # use rusty_puzzle_cube::cube::{Cube, face::Face};
@@ -93,8 +537,7 @@ impl Cube {
     /// cube.rotate_face_90_degrees_clockwise(Face::Front);
     /// ```
     pub fn rotate_face_90_degrees_clockwise(&mut self, face: F) {
-        self.rotate_face_90_degrees_clockwise_without_adjacents(face);
-        self.rotate_face_90_degrees_clockwise_only_adjacents(face);
+        self.rotate(Rotation::clockwise(face));
     }
 
     /// Rotate the given face 90° anticlockwise from the perspective of looking directly at that face from outside the cube.
@@ -104,139 +547,117 @@ impl Cube {
     /// cube.rotate_face_90_degrees_anticlockwise(Face::Front);
     /// ```
     pub fn rotate_face_90_degrees_anticlockwise(&mut self, face: F) {
-        self.rotate_face_90_degrees_clockwise(face);
-        self.rotate_face_90_degrees_clockwise(face);
-        self.rotate_face_90_degrees_clockwise(face);
-    }
-
-    fn rotate_face_90_degrees_clockwise_without_adjacents(&mut self, face: F) {
-        let side: &mut Vec<Vec<CubieFace>> = &mut self.side_map[face];
-        side.reverse();
-        for i in 1..self.side_length {
-            let (left, right) = side.split_at_mut(i);
-            (0..i).for_each(|j| {
-                mem::swap(&mut left[j][i], &mut right[0][j]);
-            });
-        }
-    }
-
-    fn rotate_face_90_degrees_clockwise_only_adjacents(&mut self, face: F) {
-        let adjacents = face.adjacent_faces_clockwise();
-        let slice_0 = get_clockwise_slice_of_side(&self.side_map[adjacents[0].0], &adjacents[0].1);
-        let slice_1 = get_clockwise_slice_of_side(&self.side_map[adjacents[1].0], &adjacents[1].1);
-        let slice_2 = get_clockwise_slice_of_side(&self.side_map[adjacents[2].0], &adjacents[2].1);
-        let slice_3 = get_clockwise_slice_of_side(&self.side_map[adjacents[3].0], &adjacents[3].1);
-
-        let final_order = {
-            let mut preliminary_order = adjacents.iter();
-            let first_element = preliminary_order.next();
-            preliminary_order
-                .chain(first_element)
-                .collect::<Vec<&(F, IA)>>()
-        };
-
-        self.copy_adjacent_over(final_order[0], slice_0);
-        self.copy_adjacent_over(final_order[1], slice_1);
-        self.copy_adjacent_over(final_order[2], slice_2);
-        self.copy_adjacent_over(final_order[3], slice_3);
+        self.rotate(Rotation::anticlockwise(face));
     }
 
-    fn copy_adjacent_over(
-        &mut self,
-        (target_face, target_alignment): &(F, IA),
-        unadjusted_values: Vec<CubieFace>,
-    ) {
-        let values = if target_alignment == &IA::InnerFirst || target_alignment == &IA::OuterEnd {
-            let mut new_values = unadjusted_values.clone();
-            new_values.reverse();
-            new_values
-        } else {
-            unadjusted_values
-        };
-
-        let side = &mut self.side_map[*target_face];
-        match target_alignment {
-            IA::OuterStart | IA::OuterEnd => {
-                let inner_index = match target_alignment {
-                    IA::OuterStart => 0,
-                    IA::OuterEnd => self.side_length - 1,
-                    _ => unreachable!("outer match guard clauses this one to only allow IA::OuterStart and IA::OuterEnd"),
-                };
-                for (i, value) in values.iter().enumerate() {
-                    value.clone_into(&mut side[i][inner_index]);
-                }
-            }
-            IA::InnerFirst => {
-                side.first_mut()
-                    .expect("Side had no inner")
-                    .clone_from_slice(&values);
-            }
-            IA::InnerLast => {
-                side.last_mut()
-                    .expect("Side had no inner")
-                    .clone_from_slice(&values);
+    /// Perform the given rotation.
+    /// ```no_run
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// let mut cube = Cube::default();
+    /// cube.rotate(Rotation::anticlockwise(Face::Front));
+    /// cube.rotate(Rotation::clockwise(Face::Left).at_layer(1));
+    /// ```
+    /// # Panics
+    /// Will panic if the rotation's layer is not within this cube, that is if it is not less than the side length, or if the layer is locked, which [`Cube::try_rotate`] reports as an error instead
+    pub fn rotate(&mut self, rotation: Rotation) {
+        let Rotation {
+            relative_to,
+            direction,
+            layer,
+        } = rotation;
+        assert!(
+            layer < self.side_length,
+            "Cannot turn layer {layer} of a cube with side length {}",
+            self.side_length
+        );
+        if !self.locked_layers.is_empty() {
+            if let Err(e) = self.check_unlocked(rotation) {
+                panic!("{e}");
             }
         }
+        instrumentation::count_rotation();
+        rotate_layer_90_degrees(&mut self.side_map, relative_to, layer, direction);
+        if let Some(sticker_origin_map) = self.sticker_origin_map.as_mut() {
+            rotate_layer_90_degrees(sticker_origin_map, relative_to, layer, direction);
+        }
+        if let Some(sticker_rotation_map) = self.sticker_rotation_map.as_mut() {
+            rotate_layer_of_rotations_90_degrees(
+                sticker_rotation_map,
+                relative_to,
+                layer,
+                direction,
+            );
+        }
     }
 
-    fn write_indented_single_side(&self, f: &mut fmt::Formatter, face: F) -> fmt::Result {
-        let side = self.side_map[face].as_ref();
-        for cubie_row in side {
-            write!(
-                f,
-                "{}",
-                format!(" {HORIZONTAL_PADDING}").repeat(self.side_length)
-            )?;
-            Cube::write_cubie_row(f, cubie_row)?;
-            writeln!(f)?;
-        }
-        Ok(())
+    /// Perform the given number of random rotations, returning them in the order they were performed so the shuffle can be recorded, shown or undone.
+    ///
+    /// Locked layers are never chosen, so when every layer is locked no rotations are performed.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::Cube;
+    /// let mut cube = Cube::create(3);
+    /// let shuffle = cube.shuffle(20);
+    /// assert_eq!(20, shuffle.len());
+    /// shuffle.iter().rev().for_each(|rotation| cube.rotate(rotation.inverse()));
+    /// assert_eq!(Cube::create(3), cube);
+    /// ```
+    pub fn shuffle(&mut self, moves: usize) -> Vec<Rotation> {
+        self.shuffle_with_rng(moves, &mut rand::thread_rng())
     }
 
-    fn write_unindented_four_sides(
-        &self,
-        f: &mut fmt::Formatter,
-        face_a: F,
-        face_b: F,
-        face_c: F,
-        face_d: F,
-    ) -> fmt::Result {
-        let side_a = self.side_map[face_a].iter();
-        let side_b = self.side_map[face_b].iter();
-        let side_c = self.side_map[face_c].iter();
-        let side_d = self.side_map[face_d].iter();
-
-        for (cubie_row_a, cubie_row_b, cubie_row_c, cubie_row_d) in
-            izip!(side_a, side_b, side_c, side_d)
-        {
-            Cube::write_cubie_row(f, cubie_row_a)?;
-            write!(f, "{HORIZONTAL_PADDING}")?;
-            Cube::write_cubie_row(f, cubie_row_b)?;
-            write!(f, "{HORIZONTAL_PADDING}")?;
-            Cube::write_cubie_row(f, cubie_row_c)?;
-            write!(f, "{HORIZONTAL_PADDING}")?;
-            Cube::write_cubie_row(f, cubie_row_d)?;
-            writeln!(f)?;
+    /// Shuffle as with [`Cube::shuffle`], but choosing rotations with the given random number generator, so that a scramble can be reproduced from its seed.
+    /// ```
+    /// # use rand::{rngs::StdRng, SeedableRng};
+    /// # use rusty_puzzle_cube::cube::Cube;
+    /// let mut cube = Cube::create(3);
+    /// let mut shared = Cube::create(3);
+    /// cube.shuffle_with_rng(20, &mut StdRng::seed_from_u64(42));
+    /// shared.shuffle_with_rng(20, &mut StdRng::seed_from_u64(42));
+    /// assert_eq!(shared, cube);
+    /// ```
+    pub fn shuffle_with_rng(&mut self, moves: usize, rng: &mut impl Rng) -> Vec<Rotation> {
+        if !self.has_unlocked_layer() {
+            return vec![];
         }
-        Ok(())
+        let rotations = (0..moves)
+            .map(|_| loop {
+                let rotation = Rotation::random_with_rng(self.side_length, rng);
+                if self.check_unlocked(rotation).is_ok() {
+                    break rotation;
+                }
+            })
+            .collect::<Vec<_>>();
+        rotations.iter().for_each(|&rotation| self.rotate(rotation));
+        rotations
     }
 
-    fn write_cubie_row(f: &mut fmt::Formatter, cubie_row: &[CubieFace]) -> fmt::Result {
-        let joined_by_padding = cubie_row
-            .iter()
-            .map(|c| c.get_coloured_display_char().to_string())
-            .collect::<Vec<String>>()
-            .join(HORIZONTAL_PADDING);
-        write!(f, "{joined_by_padding}")?;
+    /// Perform the given rotations in order, checking the budget before each one.
+    ///
+    /// When the budget is exceeded, the rotations completed so far remain applied.
+    /// Rotations that turn a locked layer are skipped, leaving the cube as [`Cube::try_rotate`] would.
+    /// # Errors
+    /// Will return an Err variant when the budget is exceeded before every rotation is performed
+    pub fn rotate_within_budget(
+        &mut self,
+        rotations: &[Rotation],
+        budget: &Budget,
+    ) -> Result<(), Interrupted> {
+        for (completed_rotations, &rotation) in rotations.iter().enumerate() {
+            budget.check(completed_rotations)?;
+            if self.check_unlocked(rotation).is_ok() {
+                self.rotate(rotation);
+            }
+        }
         Ok(())
     }
+}
 
-    fn print_to_formatter(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.write_indented_single_side(f, F::Up)?;
-        self.write_unindented_four_sides(f, F::Left, F::Front, F::Right, F::Back)?;
-        self.write_indented_single_side(f, F::Down)?;
-        Ok(())
-    }
+fn approx_side_map_bytes<T>(side_length: usize) -> usize {
+    side_length
+        .saturating_mul(side_length)
+        .saturating_mul(mem::size_of::<T>())
+        .saturating_add(mem::size_of::<Grid<T>>())
+        .saturating_mul(6)
 }
 
 impl Default for Cube {
@@ -247,71 +668,21 @@ impl Default for Cube {
 
 impl fmt::Debug for Cube {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.print_to_formatter(f)?;
-        Ok(())
+        write!(f, "{}", self.format_with(&TextRenderOptions::default()))
     }
 }
 
 impl fmt::Display for Cube {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.print_to_formatter(f)?;
-        Ok(())
-    }
-}
-
-#[cfg(test)]
-macro_rules! assert_side_lengths {
-    ($side_length:expr, $($side:expr),* $(,)?) => {
-        $(
-            assert_eq!($side_length, $side.len(),
-                "{} had outer length {} but was expected to have length {}",
-                stringify!($side), $side.len(), $side_length);
-            $side
-                .iter()
-                .enumerate()
-                .for_each(|(index, inner)|
-                    assert_eq!($side_length, inner.len(),
-                        "{} had inner (index {}) length {} but was expected to have length {}",
-                        stringify!($side), index, inner.len(), $side_length));
-        )*
-    };
-}
-
-#[cfg(test)]
-impl Cube {
-    pub fn create_from_sides(
-        top: Side,
-        bottom: Side,
-        front: Side,
-        right: Side,
-        back: Side,
-        left: Side,
-    ) -> Self {
-        let side_length = top.len();
-        assert_side_lengths!(side_length, top, bottom, front, right, back, left);
-
-        let boxed_top = Box::new(top);
-        let boxed_bottom = Box::new(bottom);
-        let boxed_front = Box::new(front);
-        let boxed_right = Box::new(right);
-        let boxed_back = Box::new(back);
-        let boxed_left = Box::new(left);
-        Self {
-            side_length,
-            side_map: enum_map! {
-                F::Up => boxed_top.clone(),
-                F::Down => boxed_bottom.clone(),
-                F::Front => boxed_front.clone(),
-                F::Right => boxed_right.clone(),
-                F::Back => boxed_back.clone(),
-                F::Left => boxed_left.clone(),
-            },
-        }
+        write!(f, "{}", self.format_with(&TextRenderOptions::default()))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use crate::notation::{parse_sequence, perform_3x3_sequence};
     use crate::{create_cube_from_sides, create_cube_side};
 
     use super::*;
@@ -323,6 +694,47 @@ mod tests {
         assert_eq!(cube.side_length, cube.side_length());
     }
 
+    #[test]
+    fn test_oriented_side_borders_match_neighbours() {
+        let n = 4;
+        for face in [F::Up, F::Down, F::Front, F::Right, F::Back, F::Left] {
+            for (border, neighbour) in face.neighbours().into_iter().enumerate() {
+                let is_on_border = |row: usize, col: usize| match border {
+                    0 => row == 0,
+                    1 => col == n - 1,
+                    2 => row == n - 1,
+                    _ => col == 0,
+                };
+                let mut cube = Cube::create_with_sticker_origins(n);
+                cube.rotate(Rotation::clockwise(neighbour));
+
+                let origins = &cube.sticker_origin_map().unwrap()[face];
+                for row in 0..n {
+                    for col in 0..n {
+                        assert_eq!(
+                            is_on_border(row, col),
+                            !origins[row][col].is_at(face, row, col),
+                            "Turning {neighbour:?} should move exactly the {face:?} stickers on their border, checking row {row} col {col}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_oriented_side_corners_are_shared_with_neighbours() {
+        let cube = Cube::create_with_sticker_origins(3);
+        let mut turned = cube.clone();
+        turned.rotate(Rotation::clockwise(F::Up));
+
+        // Turning Up moves Front's top row to Left, so Front's top left sticker lands on Left's top left
+        let left = &turned.sticker_origin_map().unwrap()[F::Left];
+        assert!(left[0][0].is_at(F::Front, 0, 0));
+        assert!(left[0][2].is_at(F::Front, 0, 2));
+        assert_eq!(&cube.side_map()[F::Front], cube.oriented_side(F::Front));
+    }
+
     #[test]
     fn test_side_map_getter() {
         let cube = Cube::default();
@@ -385,6 +797,531 @@ mod tests {
         assert_eq!(expected_cube, cube);
     }
 
+    #[test]
+    fn test_untracked_cube_has_no_sticker_origins() {
+        let cube = Cube::create(3);
+
+        assert_eq!(None, cube.sticker_origin_map());
+        assert_eq!(None, cube.is_every_sticker_at_origin());
+    }
+
+    #[test]
+    fn test_sticker_origins_start_in_place() {
+        let cube = Cube::create_with_sticker_origins(4);
+
+        assert_eq!(Cube::create(4).side_map, cube.side_map);
+        assert_eq!(Some(true), cube.is_every_sticker_at_origin());
+        let origin = cube.sticker_origin_map().unwrap()[F::Right][2][3];
+        assert!(origin.is_at(F::Right, 2, 3));
+    }
+
+    #[test]
+    fn test_sticker_origins_follow_rotations() {
+        let mut cube = Cube::create_with_sticker_origins(3);
+
+        cube.rotate_face_90_degrees_clockwise(F::Front);
+
+        assert_eq!(Some(false), cube.is_every_sticker_at_origin());
+        let origins = cube.sticker_origin_map().unwrap();
+        assert!(origins[F::Front][0][2].is_at(F::Front, 0, 0));
+        assert!(origins[F::Right][0][0].is_at(F::Up, 2, 0));
+        assert!(origins[F::Down][0][0].is_at(F::Right, 2, 0));
+    }
+
+    #[test]
+    fn test_sticker_origins_match_colours_after_many_rotations() {
+        let mut cube = Cube::create_with_sticker_origins(4);
+        let solved_colours = Cube::create(4);
+
+        for face in [
+            F::Front,
+            F::Right,
+            F::Up,
+            F::Left,
+            F::Back,
+            F::Down,
+            F::Right,
+        ] {
+            cube.rotate_face_90_degrees_clockwise(face);
+        }
+
+        let origins = cube.sticker_origin_map().unwrap();
+        for (face, side) in cube.side_map() {
+            for (row, cubie_row) in side.rows().enumerate() {
+                for (col, cubie) in cubie_row.iter().enumerate() {
+                    let origin = origins[face][row][col];
+                    assert_eq!(
+                        &solved_colours.side_map()[origin.face()][origin.row()][origin.col()],
+                        cubie
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sticker_origins_restored_by_inverse_rotations() {
+        let mut cube = Cube::create_with_sticker_origins(5);
+
+        cube.rotate_face_90_degrees_clockwise(F::Front);
+        cube.rotate_face_90_degrees_clockwise(F::Up);
+        cube.rotate_face_90_degrees_anticlockwise(F::Up);
+        cube.rotate_face_90_degrees_anticlockwise(F::Front);
+
+        assert_eq!(Some(true), cube.is_every_sticker_at_origin());
+    }
+
+    #[test]
+    fn test_same_colours_in_different_places_is_not_at_origin() {
+        let mut cube = Cube::create_with_sticker_origins(4);
+
+        // (R U)105 restores every corner and edge, but leaves the R and U centres turned 90°
+        for _ in 0..105 {
+            cube.rotate_face_90_degrees_clockwise(F::Right);
+            cube.rotate_face_90_degrees_clockwise(F::Up);
+        }
+
+        assert_eq!(Cube::create(4).side_map, cube.side_map);
+        assert_eq!(Some(false), cube.is_every_sticker_at_origin());
+    }
+
+    #[test]
+    fn test_sticker_rotations_match_stickers_split_into_quarters() {
+        // Each sticker of a cube is split into a 2x2 block of a cube twice the size, so how the block has turned shows how the sticker has turned
+        let sequence = parse_sequence("R U' F2 2L D B' 2U' L2 F' M S E'").unwrap();
+        let mut cube = Cube::create_supercube(3);
+        let mut split = Cube::create_with_sticker_origins(6);
+        for rotation in sequence {
+            cube.rotate(rotation);
+            for layer in [2 * rotation.layer, 2 * rotation.layer + 1] {
+                split.rotate(rotation.at_layer(layer));
+            }
+        }
+
+        let rotations = cube.sticker_rotation_map().unwrap();
+        let split_origins = split.sticker_origin_map().unwrap();
+        for (face, side) in rotations {
+            for (row, cells) in side.rows().enumerate() {
+                for (col, &rotation) in cells.iter().enumerate() {
+                    let top_left = split_origins[face][2 * row][2 * col];
+                    let expected = match (top_left.row() % 2, top_left.col() % 2) {
+                        (0, 0) => 0,
+                        (1, 0) => 1,
+                        (1, 1) => 2,
+                        _ => 3,
+                    };
+                    assert_eq!(expected, rotation, "{face:?} [{row}][{col}]");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_supercube_centre_can_be_twisted_alone() {
+        let mut cube = Cube::create_supercube(3);
+        perform_3x3_sequence("U R L U2 R' L' U R L U2 R' L'", &mut cube).unwrap();
+
+        assert_eq!(Some(true), cube.is_every_sticker_at_origin());
+        assert_eq!(Some(false), cube.is_every_sticker_upright());
+        assert_eq!(2, cube.sticker_rotation_map().unwrap()[F::Up][1][1]);
+    }
+
+    #[test]
+    fn test_whole_cube_turns_turn_sticker_rotations() {
+        let mut cube = Cube::create_supercube(4);
+        perform_3x3_sequence("R U F' L2 D B", &mut cube).unwrap();
+
+        let mut turned = cube.clone();
+        turned.turn_whole(WholeCubeTurn::X);
+        for layer in 0..4 {
+            cube.rotate(Rotation::clockwise(F::Right).at_layer(layer));
+        }
+        assert_eq!(cube, turned);
+    }
+
+    #[test]
+    fn test_big_side_turns_carry_every_sticker() {
+        // Big enough to be turned across threads with the rayon feature
+        let n = 300;
+        let mut cube = Cube::create_with_sticker_origins(n);
+
+        cube.rotate(Rotation::clockwise(F::Up));
+        let up = &cube.sticker_origin_map().unwrap()[F::Up];
+        for (row, col) in [(0, 0), (0, n - 1), (17, 250), (n - 1, 3)] {
+            assert!(up[row][col].is_at(F::Up, n - 1 - col, row));
+        }
+
+        cube.rotate(Rotation::anticlockwise(F::Up));
+        cube.rotate(Rotation::anticlockwise(F::Down));
+        cube.rotate(Rotation::clockwise(F::Down));
+        assert_eq!(Some(true), cube.is_every_sticker_at_origin());
+    }
+
+    #[test]
+    fn test_new_cubes_are_solved() {
+        for side_length in 1..=5 {
+            assert!(Cube::create(side_length).is_solved());
+            assert!(Cube::create_with_unique_characters(side_length).is_solved());
+        }
+    }
+
+    #[test]
+    fn test_solved_faces_after_one_turn() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(F::Right));
+
+        assert!(!cube.is_solved());
+        assert_eq!(
+            enum_map! {
+                F::Up | F::Down | F::Front | F::Back => false,
+                F::Right | F::Left => true,
+            },
+            cube.solved_faces()
+        );
+    }
+
+    #[test]
+    fn test_recoloured_cube_is_solved() {
+        let side_map = enum_map! {
+            F::Up => create_side(2, &CubieFace::Blue),
+            F::Down => create_side(2, &CubieFace::Green),
+            F::Front => create_side(2, &CubieFace::Yellow),
+            F::Right => create_side(2, &CubieFace::Orange),
+            F::Back => create_side(2, &CubieFace::White),
+            F::Left => create_side(2, &CubieFace::Red),
+        };
+
+        assert!(Cube::try_from_side_map(side_map).unwrap().is_solved());
+    }
+
+    #[test]
+    fn test_turning_every_layer_turns_whole_cube() {
+        for side_length in 1..=5 {
+            let mut turned = Cube::create_with_sticker_origins(side_length);
+            perform_3x3_sequence("R U2 F' L D B2", &mut turned).unwrap();
+            let mut rotated = turned.clone();
+
+            turned.turn_whole(WholeCubeTurn::X);
+            (0..side_length)
+                .for_each(|layer| rotated.rotate(Rotation::clockwise(F::Right).at_layer(layer)));
+            assert_eq!(turned, rotated);
+
+            turned.turn_whole(WholeCubeTurn::Y);
+            (0..side_length)
+                .for_each(|layer| rotated.rotate(Rotation::clockwise(F::Up).at_layer(layer)));
+            assert_eq!(turned, rotated);
+        }
+    }
+
+    #[test]
+    fn test_last_layer_is_opposite_face() {
+        for face in [F::Up, F::Down, F::Front, F::Right, F::Back, F::Left] {
+            let mut from_layer = Cube::create_with_unique_characters(4);
+            let mut from_face = from_layer.clone();
+
+            from_layer.rotate(Rotation::clockwise(face).at_layer(3));
+            from_face.rotate(Rotation::anticlockwise(face.opposite()));
+
+            assert_eq!(from_face, from_layer);
+        }
+    }
+
+    #[test]
+    fn test_layer_matches_same_layer_from_opposite_face() {
+        let mut cube = Cube::create_with_unique_characters(5);
+        let mut from_opposite = cube.clone();
+
+        cube.rotate(Rotation::clockwise(F::Front).at_layer(1));
+        from_opposite.rotate(Rotation::anticlockwise(F::Back).at_layer(3));
+
+        assert_eq!(from_opposite, cube);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot turn layer 3 of a cube with side length 3")]
+    fn test_rotate_layer_outside_cube() {
+        Cube::create(3).rotate(Rotation::clockwise(F::Front).at_layer(3));
+    }
+
+    #[test]
+    fn test_whole_cube_turns_match_outer_faces_on_2x2() {
+        let mut turned = Cube::create_with_unique_characters(2);
+        let mut rotated = turned.clone();
+
+        turned.turn_whole(WholeCubeTurn::X);
+        rotated.rotate(Rotation::clockwise(F::Right));
+        rotated.rotate(Rotation::anticlockwise(F::Left));
+        assert_eq!(rotated, turned);
+
+        turned.turn_whole(WholeCubeTurn::Y);
+        rotated.rotate(Rotation::clockwise(F::Up));
+        rotated.rotate(Rotation::anticlockwise(F::Down));
+        assert_eq!(rotated, turned);
+    }
+
+    #[test]
+    fn test_orientations_are_distinct() {
+        for side_length in 2..=3 {
+            let cube = Cube::create(side_length);
+            let orientations = cube.orientations().collect::<Vec<_>>();
+
+            assert_eq!(24, orientations.len());
+            assert_eq!(cube, orientations[0]);
+            for (i, a) in orientations.iter().enumerate() {
+                assert!(a.is_solved());
+                assert!(orientations[i + 1..].iter().all(|b| a != b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_whole_cube_turns_keep_sticker_origins_with_their_stickers() {
+        let mut cube = Cube::create_with_sticker_origins(4);
+        perform_3x3_sequence("R U F' L2 D B", &mut cube).unwrap();
+
+        let solved_colours = Cube::create(4);
+        for turned in cube.orientations() {
+            let origins = turned.sticker_origin_map().unwrap();
+            for (face, side) in turned.side_map() {
+                for (row, cubies) in side.rows().enumerate() {
+                    for (col, cubie) in cubies.iter().enumerate() {
+                        let origin = &origins[face][row][col];
+                        assert_eq!(
+                            &solved_colours.side_map()[origin.face()][origin.row()][origin.col()],
+                            cubie
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalised_orientation_is_shared_by_all_orientations() {
+        for side_length in 1..=4 {
+            let mut cube = Cube::create(side_length);
+            perform_3x3_sequence("R U2 F' L D B2", &mut cube).unwrap();
+            let normalised = cube.normalised_orientation();
+
+            for turned in cube.orientations() {
+                assert_eq!(normalised, turned.normalised_orientation());
+                assert!(turned.eq_up_to_orientation(&cube));
+            }
+        }
+    }
+
+    #[test]
+    fn test_normalised_orientation_of_solved_cube() {
+        let mut cube = Cube::create(3);
+        cube.turn_whole(WholeCubeTurn::X);
+        cube.turn_whole(WholeCubeTurn::Y);
+
+        assert_ne!(Cube::create(3), cube);
+        assert_eq!(Cube::create(3), cube.normalised_orientation());
+    }
+
+    #[test]
+    fn test_eq_up_to_orientation_differs_by_a_turn() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(F::Front));
+
+        assert!(!cube.eq_up_to_orientation(&Cube::create(3)));
+        assert!(!Cube::create(2).eq_up_to_orientation(&Cube::create(3)));
+    }
+
+    #[test]
+    fn test_canonicalise_is_shared_by_all_orientations() {
+        for side_length in 1..=4 {
+            let mut cube = Cube::create_with_unique_characters(side_length);
+            perform_3x3_sequence("R U2 F' L D B2", &mut cube).unwrap();
+            let canonical = cube.canonicalise();
+
+            let distinct = cube
+                .orientations()
+                .map(|turned| turned.canonicalise())
+                .collect::<HashSet<_>>();
+            assert_eq!(HashSet::from([canonical]), distinct);
+        }
+    }
+
+    #[test]
+    fn test_canonicalise_keeps_different_positions_apart() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(F::Front));
+
+        assert_ne!(Cube::create(3).canonicalise(), cube.canonicalise());
+    }
+
+    #[test]
+    fn test_approx_memory_bytes() {
+        let small = Cube::approx_memory_bytes_for(3);
+        let large = Cube::approx_memory_bytes_for(300);
+
+        assert!(6 * 9 * mem::size_of::<CubieFace>() < small);
+        assert!(6 * 300 * 300 * mem::size_of::<CubieFace>() < large);
+        assert!(large < 2 * 6 * 300 * 300 * mem::size_of::<CubieFace>());
+        assert!(small < Cube::create_with_sticker_origins(3).approx_memory_bytes());
+        assert_eq!(usize::MAX, Cube::approx_memory_bytes_for(usize::MAX));
+    }
+
+    #[test]
+    fn test_try_from_side_map_round_trip() {
+        let mut cube = Cube::create_with_unique_characters(4);
+        cube.rotate(Rotation::clockwise(F::Left));
+
+        let rebuilt = Cube::try_from_side_map(cube.side_map().clone());
+
+        assert_eq!(Ok(cube), rebuilt);
+    }
+
+    #[test]
+    fn test_try_from_side_map_mismatched_sides() {
+        let mut side_map = Cube::create(3).side_map().clone();
+        side_map[F::Back] = Grid::filled(2, CubieFace::Green(None));
+
+        assert_eq!(
+            Err("Back side must be 3x3 to match the Up side".to_string()),
+            Cube::try_from_side_map(side_map)
+        );
+    }
+
+    #[test]
+    fn test_ragged_side_can_not_become_a_grid() {
+        let mut rows = Cube::create(2).side_map()[F::Right].to_rows();
+        rows[1].push(CubieFace::Red(None));
+
+        assert!(Grid::try_from(rows).is_err());
+    }
+
+    #[test]
+    fn test_try_from_sides_names_the_bad_side() {
+        let sides = || {
+            [F::Up, F::Down, F::Front, F::Right, F::Back, F::Left]
+                .map(|face| Cube::create(2).side_map()[face].to_rows())
+        };
+        let [up, down, front, mut right, back, left] = sides();
+        right[1].push(CubieFace::Orange(None));
+
+        assert_eq!(
+            Err(
+                "Right side: Grid must be square, but has 2 rows and a row of length 3".to_string()
+            ),
+            Cube::try_from_sides(up, down, front, right, back, left)
+        );
+
+        let [up, down, front, right, back, _] = sides();
+        assert_eq!(
+            Err("Left side must be 2x2 to match the Up side".to_string()),
+            Cube::try_from_sides(up, down, front, right, back, create_cube_side!(R; 3))
+        );
+    }
+
+    #[test]
+    fn test_rotate_matches_face_rotation_methods() {
+        let mut cube_under_test = Cube::create(3);
+        let mut control_cube = Cube::create(3);
+
+        cube_under_test.rotate(Rotation::clockwise(F::Right));
+        cube_under_test.rotate(Rotation::anticlockwise(F::Down));
+        control_cube.rotate_face_90_degrees_clockwise(F::Right);
+        control_cube.rotate_face_90_degrees_anticlockwise(F::Down);
+
+        assert_eq!(control_cube, cube_under_test);
+    }
+
+    #[test]
+    fn test_rotate_inverse_restores_cube() {
+        let rotation = Rotation::clockwise(F::Back);
+        let mut cube = Cube::create(3);
+
+        cube.rotate(rotation);
+        cube.rotate(rotation.inverse());
+
+        assert_eq!(Cube::create(3), cube);
+    }
+
+    #[test]
+    fn test_shuffle_returns_the_rotations_performed() {
+        let mut shuffled = Cube::create(4);
+
+        let rotations = shuffled.shuffle(30);
+
+        let mut replayed = Cube::create(4);
+        rotations
+            .iter()
+            .for_each(|&rotation| replayed.rotate(rotation));
+        assert_eq!(30, rotations.len());
+        assert_eq!(replayed, shuffled);
+        assert!(Cube::create(4).shuffle(0).is_empty());
+    }
+
+    #[test]
+    fn test_shuffle_never_turns_locked_layers() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(F::Up, 0);
+        cube.lock_layer(F::Right, 1);
+
+        let rotations = cube.shuffle(200);
+
+        assert_eq!(200, rotations.len());
+        assert!(rotations
+            .iter()
+            .all(|&rotation| cube.check_unlocked(rotation).is_ok()));
+    }
+
+    #[test]
+    fn test_shuffle_with_every_layer_locked() {
+        let mut cube = Cube::create(2);
+        for face in [F::Up, F::Front, F::Right] {
+            cube.lock_layer(face, 0);
+            cube.lock_layer(face, 1);
+        }
+
+        assert!(cube.shuffle(10).is_empty());
+        assert_eq!(Cube::create(2).side_map(), cube.side_map());
+    }
+
+    #[test]
+    fn test_rotate_within_budget_skips_locked_layers() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(F::Up, 0);
+
+        let result = cube.rotate_within_budget(
+            &[Rotation::clockwise(F::Up), Rotation::clockwise(F::Front)],
+            &Budget::default(),
+        );
+
+        let mut expected = Cube::create(3);
+        expected.rotate(Rotation::clockwise(F::Front));
+        assert_eq!(Ok(()), result);
+        assert_eq!(expected.side_map(), cube.side_map());
+    }
+
+    #[test]
+    fn test_rotate_within_budget_keeps_partial_progress() {
+        let rotations = [
+            Rotation::clockwise(F::Front),
+            Rotation::clockwise(F::Right),
+            Rotation::clockwise(F::Up),
+        ];
+        let mut cube_under_test = Cube::create(3);
+        let mut control_cube = Cube::create(3);
+        control_cube.rotate(rotations[0]);
+        control_cube.rotate(rotations[1]);
+
+        let result = cube_under_test
+            .rotate_within_budget(&rotations, &Budget::default().with_max_rotations(2));
+
+        assert_eq!(
+            Err(Interrupted {
+                reason: crate::budget::InterruptReason::RotationLimitReached,
+                completed_rotations: 2
+            }),
+            result
+        );
+        assert_eq!(control_cube, cube_under_test);
+    }
+
     #[test]
     fn test_default_3x3_cube_display_and_debug_repr() {
         let cube = Cube::default();
@@ -0,0 +1,152 @@
+use super::{face::Face, rotation::Rotation, Cube};
+
+/// Turns of opposite faces made at the same time, such as a robot turning `R` and `L'` together.
+///
+/// Opposite faces never share a sticker, so the turns can be applied one after the other in simulation and give the same result as turning them together. The turns of each face are kept together so exporters can still tell which moves were made in parallel.
+/// ```
+/// # use rusty_puzzle_cube::cube::{face::Face, parallel_rotation::ParallelRotation, rotation::Rotation};
+/// let parallel = ParallelRotation::new(vec![
+///     vec![Rotation::clockwise(Face::Right)],
+///     vec![Rotation::anticlockwise(Face::Left)],
+/// ])
+/// .unwrap();
+/// assert_eq!(2, parallel.members().count());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParallelRotation {
+    rotations: Vec<Rotation>,
+}
+
+impl ParallelRotation {
+    /// Group the turns of each face into a single parallel move. Each member holds the rotations of one face, such as two clockwise rotations for a half turn.
+    /// # Errors
+    /// Will return an Err variant when there are fewer than two members, when a member is empty or turns more than one face, or when the faces turned are not opposite each other
+    pub fn new(members: Vec<Vec<Rotation>>) -> Result<Self, String> {
+        if members.len() < 2 {
+            return Err("A parallel move needs at least two faces".to_string());
+        }
+        let faces = members
+            .iter()
+            .map(|member| {
+                let face = member
+                    .first()
+                    .ok_or("Each face in a parallel move must turn at least once")?
+                    .relative_to;
+                if member.iter().any(|rotation| rotation.relative_to != face) {
+                    return Err(
+                        "Each member of a parallel move must turn a single face".to_string()
+                    );
+                }
+                Ok(face)
+            })
+            .collect::<Result<Vec<Face>, String>>()?;
+        for (i, &face) in faces.iter().enumerate() {
+            if let Some(&other) = faces[i + 1..]
+                .iter()
+                .find(|&&other| other != face.opposite())
+            {
+                return Err(format!(
+                    "Only opposite faces can be turned in parallel, not {face:?} and {other:?}"
+                ));
+            }
+        }
+
+        Ok(Self {
+            rotations: members.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Every rotation in this parallel move, with the rotations of each face kept together.
+    #[must_use]
+    pub fn rotations(&self) -> &[Rotation] {
+        &self.rotations
+    }
+
+    /// The rotations of each face turned by this parallel move.
+    pub fn members(&self) -> impl Iterator<Item = &[Rotation]> + '_ {
+        self.rotations
+            .chunk_by(|a, b| a.relative_to == b.relative_to)
+    }
+
+    /// The parallel move that undoes this one.
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        Self {
+            rotations: self.rotations.iter().map(|r| r.inverse()).collect(),
+        }
+    }
+
+    /// Perform this parallel move on the given cube.
+    pub fn apply_to(&self, cube: &mut Cube) {
+        self.rotations
+            .iter()
+            .for_each(|&rotation| cube.rotate(rotation));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn r() -> Rotation {
+        Rotation::clockwise(Face::Right)
+    }
+
+    fn l_prime() -> Rotation {
+        Rotation::anticlockwise(Face::Left)
+    }
+
+    #[test]
+    fn test_members_are_kept_together() {
+        let parallel = ParallelRotation::new(vec![vec![r(), r()], vec![l_prime()]]).unwrap();
+
+        assert_eq!(
+            vec![&[r(), r()][..], &[l_prime()][..]],
+            parallel.members().collect::<Vec<_>>()
+        );
+        assert_eq!(&[r(), r(), l_prime()], parallel.rotations());
+    }
+
+    #[test]
+    fn test_order_of_members_does_not_matter() {
+        let mut r_first = Cube::create(3);
+        let mut l_first = Cube::create(3);
+
+        ParallelRotation::new(vec![vec![r()], vec![l_prime()]])
+            .unwrap()
+            .apply_to(&mut r_first);
+        ParallelRotation::new(vec![vec![l_prime()], vec![r()]])
+            .unwrap()
+            .apply_to(&mut l_first);
+
+        assert_eq!(r_first, l_first);
+    }
+
+    #[test]
+    fn test_inverse_undoes_parallel_move() {
+        let parallel = ParallelRotation::new(vec![vec![r(), r()], vec![l_prime()]]).unwrap();
+        let mut cube = Cube::create(3);
+
+        parallel.apply_to(&mut cube);
+        parallel.inverse().apply_to(&mut cube);
+
+        assert_eq!(Cube::create(3), cube);
+    }
+
+    #[test]
+    fn test_adjacent_faces_are_rejected() {
+        assert_eq!(
+            Err("Only opposite faces can be turned in parallel, not Right and Up".to_string()),
+            ParallelRotation::new(vec![vec![r()], vec![Rotation::clockwise(Face::Up)]])
+        );
+    }
+
+    #[test]
+    fn test_invalid_members_are_rejected() {
+        assert!(ParallelRotation::new(vec![vec![r()]]).is_err());
+        assert!(ParallelRotation::new(vec![vec![r()], vec![]]).is_err());
+        assert!(ParallelRotation::new(vec![vec![r(), l_prime()], vec![l_prime()]]).is_err());
+        assert!(ParallelRotation::new(vec![vec![r()], vec![r()]]).is_err());
+    }
+}
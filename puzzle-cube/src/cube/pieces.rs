@@ -0,0 +1,507 @@
+use enum_map::Enum;
+
+use super::{face::Face, Cube};
+
+const PIECE_SIDE_LENGTH: usize = 3;
+
+/// Facelet indices, as in [`Cube::to_facelet_string`], of the stickers of each edge slot, with the faces those stickers are on, in the order of [`Edge::ALL`].
+pub(crate) const EDGE_SLOTS: [[(usize, Face); 2]; 12] = [
+    [(5, Face::Up), (10, Face::Right)],
+    [(7, Face::Up), (19, Face::Front)],
+    [(3, Face::Up), (37, Face::Left)],
+    [(1, Face::Up), (46, Face::Back)],
+    [(32, Face::Down), (16, Face::Right)],
+    [(28, Face::Down), (25, Face::Front)],
+    [(30, Face::Down), (43, Face::Left)],
+    [(34, Face::Down), (52, Face::Back)],
+    [(23, Face::Front), (12, Face::Right)],
+    [(21, Face::Front), (41, Face::Left)],
+    [(50, Face::Back), (39, Face::Left)],
+    [(48, Face::Back), (14, Face::Right)],
+];
+
+/// Facelet indices, as in [`Cube::to_facelet_string`], of the stickers of each corner slot, with the faces those stickers are on, in the order of [`Corner::ALL`].
+///
+/// The stickers of each corner are listed clockwise, starting from the one on Up or Down.
+pub(crate) const CORNER_SLOTS: [[(usize, Face); 3]; 8] = [
+    [(8, Face::Up), (9, Face::Right), (20, Face::Front)],
+    [(6, Face::Up), (18, Face::Front), (38, Face::Left)],
+    [(0, Face::Up), (36, Face::Left), (47, Face::Back)],
+    [(2, Face::Up), (45, Face::Back), (11, Face::Right)],
+    [(29, Face::Down), (26, Face::Front), (15, Face::Right)],
+    [(27, Face::Down), (44, Face::Left), (24, Face::Front)],
+    [(33, Face::Down), (53, Face::Back), (42, Face::Left)],
+    [(35, Face::Down), (17, Face::Right), (51, Face::Back)],
+];
+
+const CENTRES: [(usize, Face); 6] = [
+    (4, Face::Up),
+    (13, Face::Right),
+    (22, Face::Front),
+    (31, Face::Down),
+    (40, Face::Left),
+    (49, Face::Back),
+];
+
+/// The eight corner pieces of a 3x3 cube, each named by the faces it sits between when solved, which also name the slots the pieces can be in.
+#[derive(Debug, Clone, Copy, Enum, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Corner {
+    /// The corner between Up, Right and Front
+    UpRightFront,
+    /// The corner between Up, Front and Left
+    UpFrontLeft,
+    /// The corner between Up, Left and Back
+    UpLeftBack,
+    /// The corner between Up, Back and Right
+    UpBackRight,
+    /// The corner between Down, Front and Right
+    DownFrontRight,
+    /// The corner between Down, Left and Front
+    DownLeftFront,
+    /// The corner between Down, Back and Left
+    DownBackLeft,
+    /// The corner between Down, Right and Back
+    DownRightBack,
+}
+
+impl Corner {
+    /// Every corner, in the order used for the slots of a [`CornerState`].
+    pub const ALL: [Corner; 8] = [
+        Corner::UpRightFront,
+        Corner::UpFrontLeft,
+        Corner::UpLeftBack,
+        Corner::UpBackRight,
+        Corner::DownFrontRight,
+        Corner::DownLeftFront,
+        Corner::DownBackLeft,
+        Corner::DownRightBack,
+    ];
+
+    /// Returns the faces this corner sits between when solved, clockwise from the one on Up or Down.
+    #[must_use]
+    pub fn faces(self) -> [Face; 3] {
+        CORNER_SLOTS[self.into_usize()].map(|(_, face)| face)
+    }
+}
+
+/// The twelve edge pieces of a 3x3 cube, each named by the faces it sits between when solved, which also name the slots the pieces can be in.
+#[derive(Debug, Clone, Copy, Enum, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edge {
+    /// The edge between Up and Right
+    UpRight,
+    /// The edge between Up and Front
+    UpFront,
+    /// The edge between Up and Left
+    UpLeft,
+    /// The edge between Up and Back
+    UpBack,
+    /// The edge between Down and Right
+    DownRight,
+    /// The edge between Down and Front
+    DownFront,
+    /// The edge between Down and Left
+    DownLeft,
+    /// The edge between Down and Back
+    DownBack,
+    /// The edge between Front and Right
+    FrontRight,
+    /// The edge between Front and Left
+    FrontLeft,
+    /// The edge between Back and Left
+    BackLeft,
+    /// The edge between Back and Right
+    BackRight,
+}
+
+impl Edge {
+    /// Every edge, in the order used for the slots of an [`EdgeState`].
+    pub const ALL: [Edge; 12] = [
+        Edge::UpRight,
+        Edge::UpFront,
+        Edge::UpLeft,
+        Edge::UpBack,
+        Edge::DownRight,
+        Edge::DownFront,
+        Edge::DownLeft,
+        Edge::DownBack,
+        Edge::FrontRight,
+        Edge::FrontLeft,
+        Edge::BackLeft,
+        Edge::BackRight,
+    ];
+
+    /// Returns the faces this edge sits between when solved, with the one its orientation is measured from first.
+    #[must_use]
+    pub fn faces(self) -> [Face; 2] {
+        EDGE_SLOTS[self.into_usize()].map(|(_, face)| face)
+    }
+}
+
+/// Which corner piece is in each corner slot, and how it is twisted there, with slots in the order of [`Corner::ALL`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CornerState {
+    /// The piece in each slot.
+    pub permutation: [Corner; 8],
+    /// How far each piece is twisted clockwise from sitting correctly in its slot, from 0 to 2, measured by where its Up or Down sticker is.
+    pub orientation: [u8; 8],
+}
+
+impl CornerState {
+    /// Returns whether the pieces are an odd number of swaps from being in their own slots.
+    ///
+    /// For any cube reached by turning a solved one, this is the same as for the edges.
+    #[must_use]
+    pub fn is_odd_permutation(&self) -> bool {
+        is_odd(&self.permutation.map(Corner::into_usize))
+    }
+
+    /// Returns the twist of every corner added together, from 0 to 2, which is 0 for any cube reached by turning a solved one.
+    #[must_use]
+    pub fn total_twist(&self) -> u8 {
+        self.orientation.iter().sum::<u8>() % 3
+    }
+}
+
+/// Which edge piece is in each edge slot, and whether it is flipped there, with slots in the order of [`Edge::ALL`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeState {
+    /// The piece in each slot.
+    pub permutation: [Edge; 12],
+    /// Whether each piece is flipped in its slot, as 0 or 1, measured by where the sticker of its first face is.
+    pub orientation: [u8; 12],
+}
+
+impl EdgeState {
+    /// Returns whether the pieces are an odd number of swaps from being in their own slots.
+    ///
+    /// For any cube reached by turning a solved one, this is the same as for the corners.
+    #[must_use]
+    pub fn is_odd_permutation(&self) -> bool {
+        is_odd(&self.permutation.map(Edge::into_usize))
+    }
+
+    /// Returns the flip of every edge added together, as 0 or 1, which is 0 for any cube reached by turning a solved one.
+    #[must_use]
+    pub fn total_flip(&self) -> u8 {
+        self.orientation.iter().sum::<u8>() % 2
+    }
+}
+
+impl Cube {
+    /// Returns which corner piece is in each corner slot of a 3x3 cube, and how it is twisted there.
+    ///
+    /// Pieces are recognised by the colours of the centres, so this works for recoloured cubes too.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, pieces::Corner, rotation::Rotation};
+    /// let mut cube = Cube::create(3);
+    /// cube.rotate(Rotation::clockwise(Face::Up));
+    /// let corners = cube.corner_state().unwrap();
+    /// assert_eq!(Corner::UpBackRight, corners.permutation[0]);
+    /// assert_eq!(0, corners.total_twist());
+    /// ```
+    /// # Errors
+    /// Will return an Err variant when the cube is not 3x3, or when the stickers of a corner slot do not make up one of the corner pieces
+    pub fn corner_state(&self) -> Result<CornerState, String> {
+        let stickers = self.read_pieces()?;
+        let mut state = CornerState {
+            permutation: Corner::ALL,
+            orientation: [0; 8],
+        };
+        for (slot, slot_stickers) in CORNER_SLOTS.iter().enumerate() {
+            let colours = slot_stickers.map(|(i, _)| stickers[i]);
+            let not_a_piece = || {
+                format!(
+                    "The stickers in the {:?} slot do not make up a corner piece",
+                    Corner::ALL[slot]
+                )
+            };
+            let orientation = colours
+                .iter()
+                .position(|&colour| colour == Face::Up || colour == Face::Down)
+                .ok_or_else(not_a_piece)?;
+            let piece = Corner::ALL
+                .into_iter()
+                .find(|piece| (0..3).all(|i| piece.faces()[i] == colours[(orientation + i) % 3]))
+                .ok_or_else(not_a_piece)?;
+            state.permutation[slot] = piece;
+            state.orientation[slot] =
+                u8::try_from(orientation).expect("A corner has only three stickers");
+        }
+        Ok(state)
+    }
+
+    /// Returns which edge piece is in each edge slot of a 3x3 cube, and whether it is flipped there.
+    ///
+    /// Pieces are recognised by the colours of the centres, so this works for recoloured cubes too.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, pieces::Edge, rotation::Rotation};
+    /// let mut cube = Cube::create(3);
+    /// cube.rotate(Rotation::clockwise(Face::Front));
+    /// let edges = cube.edge_state().unwrap();
+    /// assert_eq!(Edge::FrontLeft, edges.permutation[1]);
+    /// assert_eq!(1, edges.orientation[1]);
+    /// ```
+    /// # Errors
+    /// Will return an Err variant when the cube is not 3x3, or when the stickers of an edge slot do not make up one of the edge pieces
+    pub fn edge_state(&self) -> Result<EdgeState, String> {
+        let stickers = self.read_pieces()?;
+        let mut state = EdgeState {
+            permutation: Edge::ALL,
+            orientation: [0; 12],
+        };
+        for (slot, slot_stickers) in EDGE_SLOTS.iter().enumerate() {
+            let colours = slot_stickers.map(|(i, _)| stickers[i]);
+            let (piece, orientation) = Edge::ALL
+                .into_iter()
+                .find_map(|piece| match piece.faces() {
+                    faces if faces == colours => Some((piece, 0)),
+                    [first, second] if [second, first] == colours => Some((piece, 1)),
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "The stickers in the {:?} slot do not make up an edge piece",
+                        Edge::ALL[slot]
+                    )
+                })?;
+            state.permutation[slot] = piece;
+            state.orientation[slot] = orientation;
+        }
+        Ok(state)
+    }
+
+    /// Checks that a 3x3 cube is in a state that can be reached by turning a solved cube, such as one built with [`Cube::try_from_sides`].
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// let mut cube = Cube::create(3);
+    /// cube.rotate(Rotation::clockwise(Face::Right));
+    /// assert_eq!(Ok(()), cube.check_solvable());
+    /// ```
+    /// # Errors
+    /// Will return an Err variant describing the first problem found, such as the wrong number of stickers of a colour, a twisted corner, a flipped edge, two pieces swapped, or the cube not being 3x3
+    pub fn check_solvable(&self) -> Result<(), String> {
+        self.check_colour_counts().map_err(|e| e.to_string())?;
+        let corners = self.corner_state()?;
+        let edges = self.edge_state()?;
+        if let Some(piece) = Corner::ALL
+            .into_iter()
+            .find(|piece| !corners.permutation.contains(piece))
+        {
+            return Err(format!(
+                "The {piece:?} corner is missing, as another corner appears twice"
+            ));
+        }
+        if let Some(piece) = Edge::ALL
+            .into_iter()
+            .find(|piece| !edges.permutation.contains(piece))
+        {
+            return Err(format!(
+                "The {piece:?} edge is missing, as another edge appears twice"
+            ));
+        }
+        if corners.total_twist() != 0 {
+            return Err("A corner is twisted, so the cube cannot be solved".to_string());
+        }
+        if edges.total_flip() != 0 {
+            return Err("An edge is flipped, so the cube cannot be solved".to_string());
+        }
+        if corners.is_odd_permutation() != edges.is_odd_permutation() {
+            return Err("Two pieces are swapped, so the cube cannot be solved".to_string());
+        }
+        Ok(())
+    }
+
+    fn read_pieces(&self) -> Result<[Face; 54], String> {
+        if self.side_length != PIECE_SIDE_LENGTH {
+            return Err(format!(
+                "Only 3x3 cubes can be described by their pieces, not {0}x{0}",
+                self.side_length
+            ));
+        }
+        read_stickers(self)
+            .ok_or_else(|| "The cube has stickers that match the colour of no centre".to_string())
+    }
+}
+
+/// Read the colour of every sticker of a 3x3 cube, given as the face whose centre has that colour, in the order of [`Cube::to_facelet_string`].
+///
+/// Returns `None` when a sticker matches the colour of no centre.
+pub(crate) fn read_stickers(cube: &Cube) -> Option<[Face; 54]> {
+    let facelets = cube.to_facelet_string().chars().collect::<Vec<_>>();
+    let face_of = |facelet: char| {
+        CENTRES
+            .iter()
+            .find_map(|&(i, face)| (facelets[i] == facelet).then_some(face))
+    };
+    let mut stickers = [Face::Up; 54];
+    for (sticker, &facelet) in stickers.iter_mut().zip(&facelets) {
+        *sticker = face_of(facelet)?;
+    }
+    Some(stickers)
+}
+
+fn is_odd(pieces: &[usize]) -> bool {
+    let inversions = (0..pieces.len())
+        .flat_map(|i| (i + 1..pieces.len()).map(move |j| (i, j)))
+        .filter(|&(i, j)| pieces[i] > pieces[j])
+        .count();
+    inversions % 2 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cube::{cubie_face::CubieFace, rotation::Rotation},
+        notation::perform_3x3_sequence,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_solved_pieces_are_home() {
+        let cube = Cube::create(3);
+
+        assert_eq!(
+            Ok(CornerState {
+                permutation: Corner::ALL,
+                orientation: [0; 8],
+            }),
+            cube.corner_state()
+        );
+        assert_eq!(
+            Ok(EdgeState {
+                permutation: Edge::ALL,
+                orientation: [0; 12],
+            }),
+            cube.edge_state()
+        );
+    }
+
+    #[test]
+    fn test_right_turn() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(Face::Right));
+
+        let corners = cube.corner_state().unwrap();
+        assert_eq!(Corner::DownFrontRight, corners.permutation[0]);
+        assert_eq!([2, 0, 0, 1, 1, 0, 0, 2], corners.orientation);
+        let edges = cube.edge_state().unwrap();
+        assert_eq!(Edge::FrontRight, edges.permutation[0]);
+        assert_eq!([0; 12], edges.orientation);
+    }
+
+    #[test]
+    fn test_scrambled_pieces_obey_cube_laws() {
+        let mut cube = Cube::create(3);
+        perform_3x3_sequence("R U2 F' L D B2 R' U F2 D' L2 B", &mut cube).unwrap();
+
+        let corners = cube.corner_state().unwrap();
+        let edges = cube.edge_state().unwrap();
+        assert_eq!(0, corners.total_twist());
+        assert_eq!(0, edges.total_flip());
+        assert_eq!(corners.is_odd_permutation(), edges.is_odd_permutation());
+        for piece in Corner::ALL {
+            assert!(corners.permutation.contains(&piece));
+        }
+    }
+
+    #[test]
+    fn test_quarter_turn_is_odd() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(Face::Up));
+
+        assert!(cube.corner_state().unwrap().is_odd_permutation());
+        assert!(cube.edge_state().unwrap().is_odd_permutation());
+    }
+
+    #[test]
+    fn test_check_solvable() {
+        let mut cube = Cube::create(3);
+        assert_eq!(Ok(()), cube.check_solvable());
+        perform_3x3_sequence("F2 U' R L' B D2", &mut cube).unwrap();
+        assert_eq!(Ok(()), cube.check_solvable());
+    }
+
+    #[test]
+    fn test_check_solvable_finds_twisted_corner() {
+        let mut cube = Cube::create(3);
+        cube.side_map[Face::Up][2][2] = CubieFace::Blue(None);
+        cube.side_map[Face::Right][0][0] = CubieFace::White(None);
+        cube.side_map[Face::Front][0][2] = CubieFace::Orange(None);
+
+        assert_eq!(
+            Err("A corner is twisted, so the cube cannot be solved".to_string()),
+            cube.check_solvable()
+        );
+    }
+
+    #[test]
+    fn test_check_solvable_finds_flipped_edge() {
+        let mut cube = Cube::create(3);
+        cube.side_map[Face::Up][2][1] = CubieFace::Blue(None);
+        cube.side_map[Face::Front][0][1] = CubieFace::White(None);
+
+        assert_eq!(
+            Err("An edge is flipped, so the cube cannot be solved".to_string()),
+            cube.check_solvable()
+        );
+    }
+
+    #[test]
+    fn test_check_solvable_finds_swapped_pieces() {
+        let mut cube = Cube::create(3);
+        cube.side_map[Face::Front][0][1] = CubieFace::Orange(None);
+        cube.side_map[Face::Right][0][1] = CubieFace::Blue(None);
+
+        assert_eq!(
+            Err("Two pieces are swapped, so the cube cannot be solved".to_string()),
+            cube.check_solvable()
+        );
+    }
+
+    #[test]
+    fn test_check_solvable_finds_repeated_piece() {
+        let mut cube = Cube::create(3);
+        cube.side_map[Face::Right][0][1] = CubieFace::Red(None);
+        assert_eq!(
+            Err("Too few Orange stickers (8/9)".to_string()),
+            cube.check_solvable()
+        );
+
+        cube.side_map[Face::Left][2][1] = CubieFace::Orange(None);
+        assert_eq!(
+            Err("The UpRight edge is missing, as another edge appears twice".to_string()),
+            cube.check_solvable()
+        );
+    }
+
+    #[test]
+    fn test_only_3x3_cubes_have_pieces() {
+        assert_eq!(
+            Err("Only 3x3 cubes can be described by their pieces, not 4x4".to_string()),
+            Cube::create(4).corner_state()
+        );
+    }
+
+    #[test]
+    fn test_impossible_pieces() {
+        let mut cube = Cube::create(3);
+        cube.side_map[Face::Up][2][2] = CubieFace::Orange(None);
+
+        assert_eq!(
+            Err("The stickers in the UpRightFront slot do not make up a corner piece".to_string()),
+            cube.corner_state()
+        );
+        assert!(cube.edge_state().is_ok());
+
+        cube.side_map[Face::Up][1][2] = CubieFace::Orange(None);
+        assert_eq!(
+            Err("The stickers in the UpRight slot do not make up an edge piece".to_string()),
+            cube.edge_state()
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::Write;
+
+use super::{
+    colour_scheme::{recolour, ColourScheme, WESTERN},
+    cubie_face::CubieFace,
+    face::Face as F,
+    render_text::{turned_cell, NetLayout},
+    Cube,
+};
+
+const STROKE: &str = "#000000";
+
+/// What an SVG of a cube shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SvgView {
+    /// Every side, laid out flat as in the text rendered with the same layout.
+    Net(NetLayout),
+    /// The Up, Front and Right sides, drawn as an isometric projection of the cube.
+    Isometric,
+}
+
+/// Options for drawing a cube as an SVG with [`Cube::to_svg`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgRenderOptions {
+    /// What is drawn.
+    pub view: SvgView,
+    /// The width of each sticker in pixels, measured along its edges in the isometric view.
+    pub sticker_size: u32,
+    /// The colour scheme stickers are recoloured into, as described for [`recolour`].
+    pub colour_scheme: ColourScheme,
+}
+
+impl Default for SvgRenderOptions {
+    fn default() -> Self {
+        Self {
+            view: SvgView::Net(NetLayout::Cross),
+            sticker_size: 20,
+            colour_scheme: WESTERN,
+        }
+    }
+}
+
+impl Cube {
+    /// Returns this cube drawn as a standalone SVG document with the given options.
+    ///
+    /// Each sticker is drawn as its own outlined shape, one per line, so the output suits being embedded in web pages or bug reports.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, render_svg::SvgRenderOptions};
+    /// let svg = Cube::create(3).to_svg(&SvgRenderOptions::default());
+    /// assert!(svg.starts_with("<svg"));
+    /// assert_eq!(54, svg.matches("<rect").count());
+    /// ```
+    #[must_use]
+    pub fn to_svg(&self, options: &SvgRenderOptions) -> String {
+        match options.view {
+            SvgView::Net(layout) => self.net_svg(layout, options),
+            SvgView::Isometric => self.isometric_svg(options),
+        }
+    }
+
+    fn net_svg(&self, layout: NetLayout, options: &SvgRenderOptions) -> String {
+        let placements = layout.placements();
+        let rows = placements.iter().map(|p| p.row).max().unwrap_or(0) + 1;
+        let cols = placements.iter().map(|p| p.col).max().unwrap_or(0) + 1;
+        let n = self.side_length;
+        let size = options.sticker_size as usize;
+
+        let mut svg = svg_start((cols * n * size) as f64, (rows * n * size) as f64);
+        for placement in placements {
+            for row in 0..n {
+                for col in 0..n {
+                    let cubie = turned_cell(
+                        &self.side_map[placement.face],
+                        placement.quarter_turns,
+                        row,
+                        col,
+                    );
+                    let x = (placement.col * n + col) * size;
+                    let y = (placement.row * n + row) * size;
+                    writeln!(
+                        svg,
+                        r#"<rect x="{x}" y="{y}" width="{size}" height="{size}" fill="{}" stroke="{STROKE}"/>"#,
+                        fill(cubie, options)
+                    )
+                    .expect("Writing to a String cannot fail");
+                }
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn isometric_svg(&self, options: &SvgRenderOptions) -> String {
+        let n = self.side_length as f64;
+        let size = f64::from(options.sticker_size);
+        let cos = size * 3_f64.sqrt() / 2.0;
+        let project =
+            |(x, y, z): (f64, f64, f64)| ((x + z) * cos, (1.5 * n + (x - z) / 2.0 - y) * size);
+
+        let mut svg = svg_start(2.0 * n * cos, 2.0 * n * size);
+        for face in [F::Up, F::Front, F::Right] {
+            for (i, cubie) in self.side_map[face].cells().iter().enumerate() {
+                let (row, col) = ((i / self.side_length) as f64, (i % self.side_length) as f64);
+                let corners =
+                    [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(across, down)| {
+                        let (r, c) = (row + down, col + across);
+                        match face {
+                            F::Up => (c, n, n - r),
+                            F::Front => (c, n - r, 0.0),
+                            _ => (n, n - r, c),
+                        }
+                    });
+                let points = corners
+                    .map(|corner| {
+                        let (x, y) = project(corner);
+                        format!("{x:.2},{y:.2}")
+                    })
+                    .join(" ");
+                writeln!(
+                    svg,
+                    r#"<polygon points="{points}" fill="{}" stroke="{STROKE}"/>"#,
+                    fill(*cubie, options)
+                )
+                .expect("Writing to a String cannot fail");
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn svg_start(width: f64, height: f64) -> String {
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.2}" height="{height:.2}" viewBox="0 0 {width:.2} {height:.2}">"#
+    ) + "\n"
+}
+
+fn fill(cubie: CubieFace, options: &SvgRenderOptions) -> &'static str {
+    match recolour(cubie, &options.colour_scheme) {
+        CubieFace::Blue(_) => "#0000ff",
+        CubieFace::Green(_) => "#00ff00",
+        CubieFace::Orange(_) => "#ff7f00",
+        CubieFace::Red(_) => "#ff0000",
+        CubieFace::White(_) => "#ffffff",
+        CubieFace::Yellow(_) => "#ffff00",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::{colour_scheme::JAPANESE, rotation::Rotation};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_net_places_stickers_as_text_does() {
+        let options = SvgRenderOptions {
+            sticker_size: 10,
+            ..SvgRenderOptions::default()
+        };
+
+        let svg = Cube::create(1).to_svg(&options);
+
+        assert_eq!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="40.00" height="30.00" viewBox="0 0 40.00 30.00">
+<rect x="10" y="0" width="10" height="10" fill="#ffffff" stroke="#000000"/>
+<rect x="0" y="10" width="10" height="10" fill="#ff0000" stroke="#000000"/>
+<rect x="10" y="10" width="10" height="10" fill="#0000ff" stroke="#000000"/>
+<rect x="20" y="10" width="10" height="10" fill="#ff7f00" stroke="#000000"/>
+<rect x="30" y="10" width="10" height="10" fill="#00ff00" stroke="#000000"/>
+<rect x="10" y="20" width="10" height="10" fill="#ffff00" stroke="#000000"/>
+</svg>
+"##,
+            svg
+        );
+    }
+
+    #[test]
+    fn test_net_uses_colour_scheme() {
+        let options = SvgRenderOptions {
+            view: SvgView::Net(NetLayout::Strip),
+            colour_scheme: JAPANESE,
+            ..SvgRenderOptions::default()
+        };
+
+        let svg = Cube::create(4).to_svg(&options);
+
+        assert_eq!(96, svg.matches("<rect").count());
+        assert_eq!(16, svg.matches("#0000ff").count());
+        assert!(svg.contains(r#"width="480.00" height="80.00""#));
+    }
+
+    #[test]
+    fn test_isometric_draws_three_sides() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(F::Front));
+        let options = SvgRenderOptions {
+            view: SvgView::Isometric,
+            ..SvgRenderOptions::default()
+        };
+
+        let svg = cube.to_svg(&options);
+
+        assert_eq!(27, svg.matches("<polygon").count());
+        assert_eq!(9, svg.matches("#0000ff").count());
+        assert_eq!(3, svg.matches("#ff0000").count());
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_isometric_corners_meet() {
+        let options = SvgRenderOptions {
+            view: SvgView::Isometric,
+            sticker_size: 10,
+            ..SvgRenderOptions::default()
+        };
+
+        let svg = Cube::create(1).to_svg(&options);
+
+        assert_eq!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="17.32" height="20.00" viewBox="0 0 17.32 20.00">
+<polygon points="8.66,0.00 17.32,5.00 8.66,10.00 0.00,5.00" fill="#ffffff" stroke="#000000"/>
+<polygon points="0.00,5.00 8.66,10.00 8.66,20.00 0.00,15.00" fill="#0000ff" stroke="#000000"/>
+<polygon points="8.66,10.00 17.32,5.00 17.32,15.00 8.66,20.00" fill="#ff7f00" stroke="#000000"/>
+</svg>
+"##,
+            svg
+        );
+    }
+}
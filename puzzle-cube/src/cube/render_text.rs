@@ -0,0 +1,428 @@
+use colored::Colorize;
+
+use super::{
+    colour_scheme::{recolour, ColourScheme, WESTERN},
+    cubie_face::CubieFace,
+    face::Face as F,
+    facelets::facelet_char,
+    grid::Grid,
+    Cube,
+};
+
+/// How the six sides of a cube are laid out when written as text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NetLayout {
+    /// Up above Front, Down below it, and Left, Front, Right and Back running left to right between them.
+    #[default]
+    Cross,
+    /// Left, Up and Right running left to right along the top, with Front, Down and Back below Up, each side turned to meet its neighbours.
+    T,
+    /// Every side in a single row, in the order Up, Right, Front, Down, Left, Back, each as seen by looking directly at it.
+    Strip,
+}
+
+/// Which character is written for each sticker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StickerChars {
+    /// The sticker's custom display char if it has one, or a square if not.
+    #[default]
+    Display,
+    /// The first letter of the sticker's colour, such as `W` for white.
+    ColourInitials,
+    /// The letter of the face the sticker's colour starts on, as in [`Cube::to_facelet_string`].
+    FaceLetters,
+}
+
+/// How many pixels tall each sticker of the Front and Right sides is in the isometric view, where it is twice as many pixels tall.
+const ISOMETRIC_SCALE: usize = 2;
+
+/// Options for writing a cube out as text with [`Cube::format_with`].
+///
+/// The default options give the same text as the cube's `Display` implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextRenderOptions {
+    /// How the sides are laid out.
+    pub layout: NetLayout,
+    /// Whether a space is written between stickers.
+    pub spacing: bool,
+    /// Whether each sticker is coloured with ANSI escape codes.
+    pub ansi_colour: bool,
+    /// Which character is written for each sticker.
+    pub sticker_chars: StickerChars,
+    /// The colour scheme stickers are recoloured into, as described for [`recolour`].
+    pub colour_scheme: ColourScheme,
+    /// Whether to draw the Up, Front and Right sides as a pseudo-3D isometric view instead of the net given by `layout`.
+    ///
+    /// With ANSI colour each character is a half-block holding two square pixels, so `spacing` and `sticker_chars` are not used. Without it, each character is the sticker char of its upper pixel.
+    pub isometric: bool,
+}
+
+impl Default for TextRenderOptions {
+    fn default() -> Self {
+        Self {
+            layout: NetLayout::default(),
+            spacing: true,
+            ansi_colour: true,
+            sticker_chars: StickerChars::default(),
+            colour_scheme: WESTERN,
+            isometric: false,
+        }
+    }
+}
+
+impl TextRenderOptions {
+    /// Options for text without escape codes, such as for logs or docs, writing the first letter of each sticker's colour.
+    #[must_use]
+    pub fn plain() -> Self {
+        Self {
+            ansi_colour: false,
+            sticker_chars: StickerChars::ColourInitials,
+            ..Self::default()
+        }
+    }
+
+    /// Options for the shortest plain text, with no spacing and every side in a single row.
+    #[must_use]
+    pub fn compact() -> Self {
+        Self {
+            layout: NetLayout::Strip,
+            spacing: false,
+            ..Self::plain()
+        }
+    }
+}
+
+/// Where a side is placed in a net, by row and column of sides, and how many quarter turns clockwise it is turned from its oriented view.
+pub(super) struct Placement {
+    pub(super) face: F,
+    pub(super) row: usize,
+    pub(super) col: usize,
+    pub(super) quarter_turns: usize,
+}
+
+const fn place(face: F, row: usize, col: usize, quarter_turns: usize) -> Placement {
+    Placement {
+        face,
+        row,
+        col,
+        quarter_turns,
+    }
+}
+
+const CROSS: [Placement; 6] = [
+    place(F::Up, 0, 1, 0),
+    place(F::Left, 1, 0, 0),
+    place(F::Front, 1, 1, 0),
+    place(F::Right, 1, 2, 0),
+    place(F::Back, 1, 3, 0),
+    place(F::Down, 2, 1, 0),
+];
+
+const T: [Placement; 6] = [
+    place(F::Left, 0, 0, 1),
+    place(F::Up, 0, 1, 0),
+    place(F::Right, 0, 2, 3),
+    place(F::Front, 1, 1, 0),
+    place(F::Down, 2, 1, 0),
+    place(F::Back, 3, 1, 2),
+];
+
+const STRIP: [Placement; 6] = [
+    place(F::Up, 0, 0, 0),
+    place(F::Right, 0, 1, 0),
+    place(F::Front, 0, 2, 0),
+    place(F::Down, 0, 3, 0),
+    place(F::Left, 0, 4, 0),
+    place(F::Back, 0, 5, 0),
+];
+
+impl NetLayout {
+    pub(super) fn placements(self) -> &'static [Placement; 6] {
+        match self {
+            NetLayout::Cross => &CROSS,
+            NetLayout::T => &T,
+            NetLayout::Strip => &STRIP,
+        }
+    }
+}
+
+impl Cube {
+    /// Returns this cube written out as text with the given options.
+    ///
+    /// Every line ends in a newline, and no line has trailing spaces.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, render_text::TextRenderOptions};
+    /// assert_eq!("WOBYRG\n", Cube::create(1).format_with(&TextRenderOptions::compact()));
+    /// ```
+    #[must_use]
+    pub fn format_with(&self, options: &TextRenderOptions) -> String {
+        if options.isometric {
+            return self.format_isometric(options);
+        }
+        let placements = options.layout.placements();
+        let rows = placements.iter().map(|p| p.row).max().unwrap_or(0) + 1;
+        let cols = placements.iter().map(|p| p.col).max().unwrap_or(0) + 1;
+        let n = self.side_length;
+        let separator = if options.spacing { " " } else { "" };
+
+        let mut text = String::new();
+        for net_row in 0..rows {
+            for row in 0..n {
+                let mut cells = Vec::with_capacity(cols * n);
+                for net_col in 0..cols {
+                    match placements
+                        .iter()
+                        .find(|p| p.row == net_row && p.col == net_col)
+                    {
+                        Some(placement) => cells.extend((0..n).map(|col| {
+                            let cubie = turned_cell(
+                                &self.side_map[placement.face],
+                                placement.quarter_turns,
+                                row,
+                                col,
+                            );
+                            sticker_text(cubie, options)
+                        })),
+                        None => cells.extend((0..n).map(|_| " ".to_string())),
+                    }
+                }
+                text.push_str(cells.join(separator).trim_end());
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    fn format_isometric(&self, options: &TextRenderOptions) -> String {
+        let pixels = self.isometric_pixels();
+        let mut text = String::new();
+        for pair in pixels.chunks(2) {
+            let (upper, lower) = (&pair[0], pair.get(1));
+            let line = upper
+                .iter()
+                .enumerate()
+                .map(|(x, &top)| {
+                    let bottom = lower.and_then(|row| row[x]);
+                    if options.ansi_colour {
+                        let recoloured = |cubie| recolour(cubie, &options.colour_scheme);
+                        half_block(top.map(recoloured), bottom.map(recoloured))
+                    } else {
+                        top.or(bottom)
+                            .map_or(" ".to_string(), |cubie| sticker_text(cubie, options))
+                    }
+                })
+                .collect::<String>();
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Rasterises the Up, Front and Right sides onto square pixels, with the cube at `(x, y, z)` in `[0, n]` projected so `x` runs down and right, `z` up and right, and `y` straight up.
+    fn isometric_pixels(&self) -> Vec<Vec<Option<CubieFace>>> {
+        let n = self.side_length;
+        let size = 4 * ISOMETRIC_SCALE * n;
+        let scale = ISOMETRIC_SCALE as f64;
+        let top = 3.0 * scale * n as f64;
+        let side_length = n as f64;
+        let sticker = |face: F, row: f64, col: f64| {
+            let (row, col) = (row.floor() as usize, col.floor() as usize);
+            self.side_map[face][row.min(n - 1)][col.min(n - 1)]
+        };
+        let in_side = |value: f64| (0.0..side_length).contains(&value);
+
+        (0..size)
+            .map(|py| {
+                (0..size)
+                    .map(|px| {
+                        let (across, down) = (px as f64 + 0.5, py as f64 + 0.5 - top);
+                        let x_plus_z = across / (2.0 * scale);
+                        let x_minus_z = (down + 2.0 * scale * side_length) / scale;
+                        let (x, z) = ((x_plus_z + x_minus_z) / 2.0, (x_plus_z - x_minus_z) / 2.0);
+                        if in_side(x) && in_side(z) {
+                            return Some(sticker(F::Up, side_length - z, x));
+                        }
+                        let x = across / (2.0 * scale);
+                        let y = (scale * x - down) / (2.0 * scale);
+                        if in_side(x) && in_side(y) {
+                            return Some(sticker(F::Front, side_length - y, x));
+                        }
+                        let z = across / (2.0 * scale) - side_length;
+                        let y = (scale * (side_length - z) - down) / (2.0 * scale);
+                        if in_side(z) && in_side(y) {
+                            return Some(sticker(F::Right, side_length - y, z));
+                        }
+                        None
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn half_block(top: Option<CubieFace>, bottom: Option<CubieFace>) -> String {
+    match (top, bottom) {
+        (None, None) => " ".to_string(),
+        (Some(top), None) => top.colourise_string("▀").to_string(),
+        (None, Some(bottom)) => bottom.colourise_string("▄").to_string(),
+        (Some(top), Some(bottom)) => {
+            let (r, g, b) = bottom.rgb();
+            top.colourise_string("▀").on_truecolor(r, g, b).to_string()
+        }
+    }
+}
+
+pub(super) fn turned_cell(
+    side: &Grid<CubieFace>,
+    quarter_turns: usize,
+    row: usize,
+    col: usize,
+) -> CubieFace {
+    let last = side.len() - 1;
+    match quarter_turns % 4 {
+        0 => side[row][col],
+        1 => side[last - col][row],
+        2 => side[last - row][last - col],
+        _ => side[col][last - row],
+    }
+}
+
+fn sticker_text(cubie: CubieFace, options: &TextRenderOptions) -> String {
+    let recoloured = recolour(cubie, &options.colour_scheme);
+    let c = match options.sticker_chars {
+        StickerChars::Display => recoloured.display_char(),
+        StickerChars::ColourInitials => colour_initial(recoloured),
+        StickerChars::FaceLetters => facelet_char(cubie),
+    };
+    if options.ansi_colour {
+        recoloured.colourise_string(&c.to_string()).to_string()
+    } else {
+        c.to_string()
+    }
+}
+
+fn colour_initial(cubie: CubieFace) -> char {
+    match cubie {
+        CubieFace::Blue(_) => 'B',
+        CubieFace::Green(_) => 'G',
+        CubieFace::Orange(_) => 'O',
+        CubieFace::Red(_) => 'R',
+        CubieFace::White(_) => 'W',
+        CubieFace::Yellow(_) => 'Y',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::{colour_scheme::JAPANESE, rotation::Rotation};
+    use pretty_assertions::assert_eq;
+
+    fn plain(layout: NetLayout) -> TextRenderOptions {
+        TextRenderOptions {
+            layout,
+            ..TextRenderOptions::plain()
+        }
+    }
+
+    #[test]
+    fn test_cross_layout() {
+        let expected = "    W W
+    W W
+R R B B O O G G
+R R B B O O G G
+    Y Y
+    Y Y
+";
+        assert_eq!(
+            expected,
+            Cube::create(2).format_with(&plain(NetLayout::Cross))
+        );
+    }
+
+    #[test]
+    fn test_t_layout_turns_sides_to_meet() {
+        let mut cube = Cube::create(2);
+        cube.rotate(Rotation::clockwise(F::Front));
+
+        let expected = "R R W W O O
+Y Y R R W W
+    B B
+    B B
+    O O
+    Y Y
+    G G
+    G G
+";
+        assert_eq!(expected, cube.format_with(&plain(NetLayout::T)));
+    }
+
+    #[test]
+    fn test_compact_strip_of_face_letters() {
+        let cube = Cube::create(2);
+        let options = TextRenderOptions {
+            sticker_chars: StickerChars::FaceLetters,
+            ..TextRenderOptions::compact()
+        };
+
+        let text = cube.format_with(&options);
+
+        assert_eq!("UURRFFDDLLBB\nUURRFFDDLLBB\n", text);
+    }
+
+    #[test]
+    fn test_colour_scheme_option() {
+        let options = TextRenderOptions {
+            colour_scheme: JAPANESE,
+            ..TextRenderOptions::compact()
+        };
+
+        assert_eq!("WOYBRG\n", Cube::create(1).format_with(&options));
+    }
+
+    #[test]
+    fn test_isometric_plain() {
+        let mut cube = Cube::create(2);
+        cube.rotate(Rotation::clockwise(F::Right));
+        let options = TextRenderOptions {
+            isometric: true,
+            ..TextRenderOptions::plain()
+        };
+
+        let expected = "     WWWWWW
+ WWWWWWWWWWBBBB
+BWWWWWWBBBBBBBBO
+BBBBYBBBBBBOOOOO
+BBBBYYYYOOOOOOOO
+BBBBYYYYOOOOOOOO
+ BBBYYYYOOOOOOO
+     YYYOOO
+";
+        assert_eq!(expected, cube.format_with(&options));
+    }
+
+    #[test]
+    fn test_isometric_half_blocks() {
+        let options = TextRenderOptions {
+            isometric: true,
+            ..TextRenderOptions::default()
+        };
+
+        let text = Cube::create(3).format_with(&options);
+
+        assert_eq!(12, text.lines().count());
+        assert!(text.contains('▀'));
+        assert!(text.contains('▄'));
+        assert!(!text.contains('■'));
+    }
+
+    #[test]
+    fn test_default_options_match_display() {
+        let cube = Cube::create_with_unique_characters(3);
+
+        assert_eq!(
+            cube.to_string(),
+            cube.format_with(&TextRenderOptions::default())
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use enum_map::Enum;
+use rand::Rng;
+
+use super::{direction::Direction, face::Face};
+
+/// A single 90° turn of one layer of the cube, which is a face unless the layer is set back from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rotation {
+    /// The face being turned.
+    pub relative_to: Face,
+    /// The direction the face is turned in.
+    pub direction: Direction,
+    /// How many layers in from the face the turned layer is. Layer 0 is the face itself, and the last layer is the opposite face.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub layer: usize,
+}
+
+impl Rotation {
+    /// A 90° clockwise turn of the given face.
+    #[must_use]
+    pub fn clockwise(face: Face) -> Self {
+        Self {
+            relative_to: face,
+            direction: Direction::Clockwise,
+            layer: 0,
+        }
+    }
+
+    /// A 90° anticlockwise turn of the given face.
+    #[must_use]
+    pub fn anticlockwise(face: Face) -> Self {
+        Self {
+            relative_to: face,
+            direction: Direction::Anticlockwise,
+            layer: 0,
+        }
+    }
+
+    /// The same turn, made to the layer that is `layer` layers in from the face instead.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{face::Face, rotation::Rotation};
+    /// let middle_slice = Rotation::clockwise(Face::Left).at_layer(1);
+    /// assert_eq!(1, middle_slice.layer);
+    /// ```
+    #[must_use]
+    pub fn at_layer(self, layer: usize) -> Self {
+        Self { layer, ..self }
+    }
+
+    /// A turn of any layer of a cube with the given side length, in either direction, chosen at random.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::rotation::Rotation;
+    /// let rotation = Rotation::random(4);
+    /// assert!(rotation.layer < 4);
+    /// ```
+    /// # Panics
+    /// Will panic if the side length is 0, as such a cube has no layers to turn
+    #[must_use]
+    pub fn random(side_length: usize) -> Self {
+        Self::random_with_rng(side_length, &mut rand::thread_rng())
+    }
+
+    /// A turn chosen at random as with [`Rotation::random`], but using the given random number generator, so a seeded generator picks the same turn every time.
+    /// ```
+    /// # use rand::{rngs::StdRng, SeedableRng};
+    /// # use rusty_puzzle_cube::cube::rotation::Rotation;
+    /// let first = Rotation::random_with_rng(3, &mut StdRng::seed_from_u64(7));
+    /// let second = Rotation::random_with_rng(3, &mut StdRng::seed_from_u64(7));
+    /// assert_eq!(first, second);
+    /// ```
+    /// # Panics
+    /// Will panic if the side length is 0, as such a cube has no layers to turn
+    #[must_use]
+    pub fn random_with_rng(side_length: usize, rng: &mut impl Rng) -> Self {
+        assert!(
+            side_length > 0,
+            "A cube with side length 0 has no layers to turn"
+        );
+        let face = Face::from_usize(rng.gen_range(0..Face::LENGTH));
+        let rotation = if rng.gen() {
+            Self::clockwise(face)
+        } else {
+            Self::anticlockwise(face)
+        };
+        rotation.at_layer(rng.gen_range(0..side_length))
+    }
+
+    /// The rotation that undoes this one.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        Self {
+            direction: self.direction.opposite(),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::{assert_eq, assert_ne};
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_random_rotations_cover_every_face_direction_and_layer() {
+        let rotations = (0..1000).map(|_| Rotation::random(3)).collect::<Vec<_>>();
+
+        let faces = rotations
+            .iter()
+            .map(|r| r.relative_to)
+            .collect::<HashSet<_>>();
+        let layers = rotations.iter().map(|r| r.layer).collect::<HashSet<_>>();
+        assert_eq!(Face::LENGTH, faces.len());
+        assert_eq!(HashSet::from([0, 1, 2]), layers);
+        assert!(rotations
+            .iter()
+            .any(|r| r.direction == Direction::Clockwise));
+        assert!(rotations
+            .iter()
+            .any(|r| r.direction == Direction::Anticlockwise));
+    }
+
+    #[test]
+    fn test_seeded_random_rotations_repeat() {
+        let rotations = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..20)
+                .map(|_| Rotation::random_with_rng(5, &mut rng))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(rotations(1), rotations(1));
+        assert_ne!(rotations(1), rotations(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "A cube with side length 0 has no layers to turn")]
+    fn test_random_rotation_needs_a_layer() {
+        let _ = Rotation::random(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "A cube with side length 0 has no layers to turn")]
+    fn test_seeded_random_rotation_needs_a_layer() {
+        let _ = Rotation::random_with_rng(0, &mut StdRng::seed_from_u64(1));
+    }
+}
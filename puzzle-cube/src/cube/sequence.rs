@@ -0,0 +1,387 @@
+use std::slice;
+
+use super::{face::Face, parallel_rotation::ParallelRotation, rotation::Rotation, Cube};
+
+/// A single entry in a [`Sequence`], either one rotation or a labelled group of rotations that belong together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Op {
+    /// A single rotation, such as one made by the user.
+    Single(Rotation),
+    /// Rotations made as part of one larger operation, such as a scramble or a known transform, with a label describing it.
+    Group(Vec<Rotation>, String),
+    /// Turns of opposite faces made at the same time.
+    Parallel(ParallelRotation),
+}
+
+impl Op {
+    /// The rotations this op performs, in order.
+    #[must_use]
+    pub fn rotations(&self) -> &[Rotation] {
+        match self {
+            Op::Single(rotation) => slice::from_ref(rotation),
+            Op::Group(rotations, _) => rotations,
+            Op::Parallel(parallel) => parallel.rotations(),
+        }
+    }
+
+    /// The label of this op, if it is a group.
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            Op::Single(_) | Op::Parallel(_) => None,
+            Op::Group(_, label) => Some(label),
+        }
+    }
+
+    /// The op that undoes this one. Groups keep their label.
+    #[must_use]
+    pub fn inverted(&self) -> Self {
+        match self {
+            Op::Single(rotation) => Op::Single(rotation.inverse()),
+            Op::Group(rotations, label) => Op::Group(
+                rotations
+                    .iter()
+                    .rev()
+                    .copied()
+                    .map(Rotation::inverse)
+                    .collect(),
+                label.clone(),
+            ),
+            Op::Parallel(parallel) => Op::Parallel(parallel.inverse()),
+        }
+    }
+
+    /// Perform this op on the given cube.
+    pub fn apply_to(&self, cube: &mut Cube) {
+        self.rotations()
+            .iter()
+            .for_each(|&rotation| cube.rotate(rotation));
+    }
+
+    /// The ops left after dropping every rotation that `keep` rejects, as described for [`Sequence::filter`].
+    fn filtered(&self, keep: &mut impl FnMut(Rotation) -> bool) -> Vec<Op> {
+        match self {
+            Op::Single(rotation) => {
+                if keep(*rotation) {
+                    vec![Op::Single(*rotation)]
+                } else {
+                    vec![]
+                }
+            }
+            Op::Group(rotations, label) => {
+                let rotations = rotations
+                    .iter()
+                    .copied()
+                    .filter(|&rotation| keep(rotation))
+                    .collect::<Vec<_>>();
+                if rotations.is_empty() {
+                    vec![]
+                } else {
+                    vec![Op::Group(rotations, label.clone())]
+                }
+            }
+            Op::Parallel(parallel) => {
+                let members = parallel
+                    .members()
+                    .map(|member| {
+                        member
+                            .iter()
+                            .copied()
+                            .filter(|&rotation| keep(rotation))
+                            .collect::<Vec<_>>()
+                    })
+                    .filter(|member| !member.is_empty())
+                    .collect::<Vec<_>>();
+                if members.len() < 2 {
+                    members.into_iter().flatten().map(Op::Single).collect()
+                } else {
+                    let parallel = ParallelRotation::new(members).expect(
+                        "Members of a parallel move still turn opposite faces when filtered",
+                    );
+                    vec![Op::Parallel(parallel)]
+                }
+            }
+        }
+    }
+}
+
+/// Named filters for [`Sequence::filter`], for comparing sequences while ignoring the moves that do not matter to the comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationFilter {
+    /// Drop turns of the Up face, so an execution matches its reference algorithm whatever adjustments of the Up face (AUF) were made around it.
+    SkipUpFace,
+    /// Keep only the turns of outer layers, which are the rotations that move corners.
+    CornerMovesOnly,
+}
+
+impl RotationFilter {
+    /// Returns whether this filter keeps the given rotation of a cube with the given side length.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{face::Face, rotation::Rotation, sequence::RotationFilter};
+    /// assert!(!RotationFilter::SkipUpFace.keeps(Rotation::clockwise(Face::Down).at_layer(2), 3));
+    /// assert!(!RotationFilter::CornerMovesOnly.keeps(Rotation::clockwise(Face::Right).at_layer(1), 3));
+    /// ```
+    #[must_use]
+    pub fn keeps(self, rotation: Rotation, side_length: usize) -> bool {
+        let Rotation {
+            relative_to, layer, ..
+        } = rotation;
+        let is_outer = layer == 0 || layer + 1 == side_length;
+        match self {
+            RotationFilter::SkipUpFace => {
+                let turns_up_face = (relative_to == Face::Up && layer == 0)
+                    || (relative_to == Face::Down && layer + 1 == side_length);
+                !turns_up_face
+            }
+            RotationFilter::CornerMovesOnly => is_outer,
+        }
+    }
+}
+
+impl From<Rotation> for Op {
+    fn from(rotation: Rotation) -> Self {
+        Op::Single(rotation)
+    }
+}
+
+impl From<ParallelRotation> for Op {
+    fn from(parallel: ParallelRotation) -> Self {
+        Op::Parallel(parallel)
+    }
+}
+
+/// An ordered list of ops, keeping any grouping and labels so they survive undo, redo, replays and exports.
+/// ```
+/// # use rusty_puzzle_cube::cube::{face::Face, rotation::Rotation, sequence::Sequence, Cube};
+/// let mut sequence = Sequence::new();
+/// sequence.push_group("Scramble", vec![Rotation::clockwise(Face::Front), Rotation::clockwise(Face::Up)]);
+/// sequence.push(Rotation::anticlockwise(Face::Right));
+///
+/// let mut cube = Cube::create(3);
+/// sequence.apply_to(&mut cube);
+/// sequence.inverted().apply_to(&mut cube);
+/// assert_eq!(Cube::create(3), cube);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sequence {
+    ops: Vec<Op>,
+}
+
+impl Sequence {
+    /// Create an empty sequence.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an op to the end of this sequence.
+    pub fn push(&mut self, op: impl Into<Op>) {
+        self.ops.push(op.into());
+    }
+
+    /// Add a labelled group of rotations to the end of this sequence.
+    pub fn push_group(&mut self, label: impl Into<String>, rotations: Vec<Rotation>) {
+        self.ops.push(Op::Group(rotations, label.into()));
+    }
+
+    /// The ops in this sequence, in order.
+    #[must_use]
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+
+    /// How many ops are in this sequence, counting each group once.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Whether this sequence has no ops.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Every rotation in this sequence, in order, ignoring grouping.
+    pub fn rotations(&self) -> impl DoubleEndedIterator<Item = Rotation> + '_ {
+        self.ops
+            .iter()
+            .flat_map(|op| op.rotations().iter().copied())
+    }
+
+    /// The rotations that undo this sequence, in the order they should be performed.
+    pub fn inverse_rotations(&self) -> impl Iterator<Item = Rotation> + '_ {
+        self.rotations().rev().map(Rotation::inverse)
+    }
+
+    /// The sequence that undoes this one, keeping the grouping and labels of each op.
+    #[must_use]
+    pub fn inverted(&self) -> Self {
+        Self {
+            ops: self.ops.iter().rev().map(Op::inverted).collect(),
+        }
+    }
+
+    /// Perform every op of this sequence on the given cube.
+    pub fn apply_to(&self, cube: &mut Cube) {
+        self.ops.iter().for_each(|op| op.apply_to(cube));
+    }
+
+    /// The sequence left after dropping every rotation that `keep` rejects, such as one of the [`RotationFilter`] presets.
+    ///
+    /// Groups keep their label unless every rotation in them is dropped, in which case the group is dropped too. A parallel move that is left turning only one face becomes a single op per rotation.
+    /// ```
+    /// # use rusty_puzzle_cube::{cube::{sequence::{RotationFilter, Sequence}}, notation::parse_sequence};
+    /// let execution = Sequence::from(parse_sequence("U R U R' U'").unwrap());
+    /// let reference = Sequence::from(parse_sequence("R U R' U' U2").unwrap());
+    /// let skip_auf = |rotation| RotationFilter::SkipUpFace.keeps(rotation, 3);
+    /// assert_eq!(reference.filter(skip_auf), execution.filter(skip_auf));
+    /// ```
+    #[must_use]
+    pub fn filter(&self, mut keep: impl FnMut(Rotation) -> bool) -> Self {
+        self.ops
+            .iter()
+            .flat_map(|op| op.filtered(&mut keep))
+            .collect()
+    }
+}
+
+impl From<Vec<Rotation>> for Sequence {
+    fn from(rotations: Vec<Rotation>) -> Self {
+        rotations.into_iter().map(Op::Single).collect()
+    }
+}
+
+impl FromIterator<Op> for Sequence {
+    fn from_iter<I: IntoIterator<Item = Op>>(iter: I) -> Self {
+        Self {
+            ops: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Sequence {
+    type Item = &'a Op;
+    type IntoIter = slice::Iter<'a, Op>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ops.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::face::Face;
+    use pretty_assertions::assert_eq;
+
+    fn f() -> Rotation {
+        Rotation::clockwise(Face::Front)
+    }
+
+    fn u_prime() -> Rotation {
+        Rotation::anticlockwise(Face::Up)
+    }
+
+    #[test]
+    fn test_filter_keeps_grouping() {
+        let mut sequence = Sequence::new();
+        sequence.push(u_prime());
+        sequence.push_group("Scramble", vec![f(), u_prime(), f()]);
+        sequence.push_group("Adjust", vec![u_prime(), u_prime()]);
+        sequence.push(f());
+
+        let mut expected = Sequence::new();
+        expected.push_group("Scramble", vec![f(), f()]);
+        expected.push(f());
+
+        assert_eq!(
+            expected,
+            sequence.filter(|rotation| RotationFilter::SkipUpFace.keeps(rotation, 3))
+        );
+    }
+
+    #[test]
+    fn test_filter_splits_parallel_left_with_one_face() {
+        let r = Rotation::clockwise(Face::Right);
+        let l_prime = Rotation::anticlockwise(Face::Left);
+        let slice = Rotation::clockwise(Face::Right).at_layer(1);
+        let parallel = ParallelRotation::new(vec![vec![r, r], vec![l_prime]]).unwrap();
+        let sequence = Sequence::from_iter([Op::Parallel(parallel.clone()), Op::Single(slice)]);
+
+        assert_eq!(
+            Sequence::from_iter([Op::Parallel(parallel)]),
+            sequence.filter(|rotation| RotationFilter::CornerMovesOnly.keeps(rotation, 3))
+        );
+        assert_eq!(
+            Sequence::from(vec![r, r]),
+            sequence.filter(|rotation| rotation.relative_to == Face::Right && rotation.layer == 0)
+        );
+    }
+
+    #[test]
+    fn test_op_rotations_and_label() {
+        let single = Op::Single(f());
+        let group = Op::Group(vec![f(), u_prime()], "Scramble".to_string());
+
+        assert_eq!(&[f()], single.rotations());
+        assert_eq!(None, single.label());
+        assert_eq!(&[f(), u_prime()], group.rotations());
+        assert_eq!(Some("Scramble"), group.label());
+    }
+
+    #[test]
+    fn test_op_inverted_keeps_label() {
+        let group = Op::Group(vec![f(), u_prime()], "Checkerboard".to_string());
+
+        assert_eq!(
+            Op::Group(
+                vec![u_prime().inverse(), f().inverse()],
+                "Checkerboard".to_string()
+            ),
+            group.inverted()
+        );
+    }
+
+    #[test]
+    fn test_sequence_inverted() {
+        let mut sequence = Sequence::new();
+        sequence.push(f());
+        sequence.push_group("Scramble", vec![u_prime(), f()]);
+
+        let expected = Sequence::from_iter([
+            Op::Group(
+                vec![f().inverse(), u_prime().inverse()],
+                "Scramble".to_string(),
+            ),
+            Op::Single(f().inverse()),
+        ]);
+        assert_eq!(expected, sequence.inverted());
+        assert_eq!(
+            expected.rotations().collect::<Vec<_>>(),
+            sequence.inverse_rotations().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_len_counts_groups_once() {
+        let mut sequence = Sequence::new();
+        sequence.push_group("Scramble", vec![f(), f(), u_prime()]);
+        sequence.push(u_prime());
+
+        assert_eq!(2, sequence.len());
+        assert_eq!(4, sequence.rotations().count());
+    }
+
+    #[test]
+    fn test_inverted_undoes_sequence() {
+        let sequence = Sequence::from(vec![f(), u_prime(), Rotation::clockwise(Face::Left)]);
+        let mut cube = Cube::create(4);
+
+        sequence.apply_to(&mut cube);
+        assert_ne!(Cube::create(4), cube);
+        sequence.inverted().apply_to(&mut cube);
+
+        assert_eq!(Cube::create(4), cube);
+    }
+}
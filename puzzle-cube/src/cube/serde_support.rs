@@ -0,0 +1,190 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{grid::Grid, Cube, SideMap, StickerOriginMap, StickerRotationMap};
+
+/// Grids are written as one list per row, as sides were before they were stored in one buffer, so earlier saves still load.
+impl<T: Serialize> Serialize for Grid<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.rows())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Grid<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Grid::try_from(Vec::<Vec<T>>::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// The serialised form of a [`Cube`], checked before it becomes a cube so malformed input can not produce one that panics when rotated.
+#[derive(Deserialize)]
+pub(super) struct UncheckedCube {
+    side_length: usize,
+    side_map: SideMap,
+    sticker_origin_map: Option<StickerOriginMap>,
+    sticker_rotation_map: Option<StickerRotationMap>,
+}
+
+impl TryFrom<UncheckedCube> for Cube {
+    type Error = String;
+
+    fn try_from(unchecked: UncheckedCube) -> Result<Self, Self::Error> {
+        let mut cube = Cube::try_from_side_map(unchecked.side_map)?;
+        let side_length = cube.side_length;
+        if unchecked.side_length != side_length {
+            return Err(format!(
+                "Cube side length {} does not match its {side_length}x{side_length} sides",
+                unchecked.side_length
+            ));
+        }
+        if let Some(sticker_origin_map) = &unchecked.sticker_origin_map {
+            let mismatched = sticker_origin_map
+                .values()
+                .any(|side| side.len() != side_length);
+            if mismatched {
+                return Err(format!(
+                    "Sticker origins must be {side_length}x{side_length} on every side to match the cube"
+                ));
+            }
+        }
+        if let Some(sticker_rotation_map) = &unchecked.sticker_rotation_map {
+            let mismatched = sticker_rotation_map
+                .values()
+                .any(|side| side.len() != side_length);
+            if mismatched {
+                return Err(format!(
+                    "Sticker rotations must be {side_length}x{side_length} on every side to match the cube"
+                ));
+            }
+            if sticker_rotation_map
+                .values()
+                .any(|side| side.cells().iter().any(|&rotation| rotation > 3))
+            {
+                return Err("Sticker rotations must be quarter turns from 0 to 3".to_string());
+            }
+        }
+        cube.sticker_origin_map = unchecked.sticker_origin_map;
+        cube.sticker_rotation_map = unchecked.sticker_rotation_map;
+        Ok(cube)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cube::{face::Face, rotation::Rotation},
+        known_transforms::checkerboard_corners,
+        notation::parse_sequence,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_cube_round_trip() {
+        let mut cube = Cube::create(4);
+        checkerboard_corners(&mut cube);
+
+        let json = serde_json::to_string(&cube).unwrap();
+
+        assert_eq!(cube, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn test_cube_with_sticker_origins_round_trip() {
+        let mut cube = Cube::create_with_sticker_origins(3);
+        cube.rotate(Rotation::clockwise(Face::Right));
+
+        let restored: Cube = serde_json::from_str(&serde_json::to_string(&cube).unwrap()).unwrap();
+
+        assert_eq!(cube, restored);
+        assert_eq!(Some(false), restored.is_every_sticker_at_origin());
+    }
+
+    #[test]
+    fn test_supercube_round_trip() {
+        let mut cube = Cube::create_supercube(3);
+        cube.rotate(Rotation::clockwise(Face::Up));
+
+        let restored: Cube = serde_json::from_str(&serde_json::to_string(&cube).unwrap()).unwrap();
+
+        assert_eq!(cube, restored);
+        assert_eq!(Some(false), restored.is_every_sticker_upright());
+    }
+
+    #[test]
+    fn test_rotations_round_trip() {
+        let rotations = parse_sequence("F R' M2").unwrap();
+
+        let json = serde_json::to_string(&rotations).unwrap();
+
+        assert_eq!(
+            r#"[{"relative_to":"Front","direction":"Clockwise","layer":0},{"relative_to":"Right","direction":"Anticlockwise","layer":0},{"relative_to":"Left","direction":"Clockwise","layer":1},{"relative_to":"Left","direction":"Clockwise","layer":1}]"#,
+            json
+        );
+        assert_eq!(
+            rotations,
+            serde_json::from_str::<Vec<Rotation>>(&json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rotation_without_layer_turns_face() {
+        let json = r#"{"relative_to":"Front","direction":"Anticlockwise"}"#;
+
+        assert_eq!(
+            Rotation::anticlockwise(Face::Front),
+            serde_json::from_str::<Rotation>(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_mismatched_side_length_is_rejected() {
+        let mut json = serde_json::to_value(Cube::create(2)).unwrap();
+        json["side_length"] = 3.into();
+
+        let error = serde_json::from_value::<Cube>(json).unwrap_err();
+
+        assert_eq!(
+            "Cube side length 3 does not match its 2x2 sides",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_sides_are_written_as_rows() {
+        let json = serde_json::to_value(Cube::create(2)).unwrap();
+
+        assert_eq!(
+            serde_json::json!([[{ "Blue": null }, { "Blue": null }], [{ "Blue": null }, { "Blue": null }]]),
+            json["side_map"]["Front"]
+        );
+    }
+
+    #[test]
+    fn test_ragged_side_is_rejected() {
+        let mut json = serde_json::to_value(Cube::create(2)).unwrap();
+        json["side_map"]["Left"][1] = serde_json::json!([{ "Red": null }]);
+
+        assert!(serde_json::from_value::<Cube>(json).is_err());
+    }
+
+    #[test]
+    fn test_mismatched_sticker_origins_are_rejected() {
+        let mut json = serde_json::to_value(Cube::create_with_sticker_origins(2)).unwrap();
+        json["sticker_origin_map"]["Up"] = serde_json::json!([]);
+
+        assert!(serde_json::from_value::<Cube>(json).is_err());
+    }
+
+    #[test]
+    fn test_invalid_sticker_rotations_are_rejected() {
+        let mut json = serde_json::to_value(Cube::create_supercube(2)).unwrap();
+        json["sticker_rotation_map"]["Up"][0][1] = 4.into();
+
+        assert_eq!(
+            "Sticker rotations must be quarter turns from 0 to 3",
+            serde_json::from_value::<Cube>(json)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use enum_map::Enum;
+
+use super::face::Face;
+
+/// The position a single sticker occupied when its cube was created.
+///
+/// Row and column are stored as `u16` to keep tracking affordable on big cubes, so tracked cubes are limited to a side length of 65536.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StickerOrigin {
+    face: Face,
+    row: u16,
+    col: u16,
+}
+
+impl StickerOrigin {
+    pub(crate) fn new(face: Face, row: usize, col: usize) -> Self {
+        Self {
+            face,
+            row: u16::try_from(row).expect("Sticker origin row must fit in a u16"),
+            col: u16::try_from(col).expect("Sticker origin col must fit in a u16"),
+        }
+    }
+
+    /// The face this sticker started on.
+    #[must_use]
+    pub fn face(self) -> Face {
+        self.face
+    }
+
+    /// The row, within its starting face, that this sticker started on.
+    #[must_use]
+    pub fn row(self) -> usize {
+        usize::from(self.row)
+    }
+
+    /// The column, within its starting face, that this sticker started on.
+    #[must_use]
+    pub fn col(self) -> usize {
+        usize::from(self.col)
+    }
+
+    /// A number identifying this sticker among the `6 * side_length * side_length` stickers of its cube, from 0 up to but not including that count.
+    ///
+    /// Stickers are numbered side by side in the order Up, Down, Front, Right, Back, Left, each row by row, so every sticker keeps the same number wherever it travels, on cubes of any size.
+    /// ```
+    /// # use rusty_puzzle_cube::cube::{Cube, face::Face, rotation::Rotation};
+    /// let mut cube = Cube::create_with_sticker_origins(10);
+    /// cube.rotate(Rotation::clockwise(Face::Up));
+    /// let origin = cube.sticker_origin_map().unwrap()[Face::Front][0][0];
+    /// assert_eq!(Face::Right, origin.face());
+    /// assert_eq!(3 * 100, origin.id(10));
+    /// ```
+    /// # Panics
+    /// Will panic if this sticker's row or column is not less than `side_length`, so it could not have come from a cube of that size
+    #[must_use]
+    pub fn id(self, side_length: usize) -> usize {
+        assert!(
+            self.row() < side_length && self.col() < side_length,
+            "Sticker origin [{}][{}] is outside a cube with side length {side_length}",
+            self.row,
+            self.col
+        );
+        (self.face.into_usize() * side_length + self.row()) * side_length + self.col()
+    }
+
+    /// Whether this sticker is currently at the provided position, which would mean it is back where it started.
+    #[must_use]
+    pub fn is_at(self, face: Face, row: usize, col: usize) -> bool {
+        self.face == face && self.row() == row && self.col() == col
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_getters() {
+        let origin = StickerOrigin::new(Face::Right, 3, 7);
+
+        assert_eq!(Face::Right, origin.face());
+        assert_eq!(3, origin.row());
+        assert_eq!(7, origin.col());
+    }
+
+    #[test]
+    fn test_is_at() {
+        let origin = StickerOrigin::new(Face::Back, 1, 2);
+
+        assert!(origin.is_at(Face::Back, 1, 2));
+        assert!(!origin.is_at(Face::Front, 1, 2));
+        assert!(!origin.is_at(Face::Back, 2, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Sticker origin row must fit in a u16")]
+    fn test_row_too_large() {
+        let _ = StickerOrigin::new(Face::Up, 65536, 0);
+    }
+
+    #[test]
+    fn test_ids_number_every_sticker_once() {
+        let side_length = 12;
+        let mut ids = [
+            Face::Up,
+            Face::Down,
+            Face::Front,
+            Face::Right,
+            Face::Back,
+            Face::Left,
+        ]
+        .into_iter()
+        .flat_map(|face| {
+            (0..side_length).flat_map(move |row| {
+                (0..side_length).map(move |col| StickerOrigin::new(face, row, col).id(side_length))
+            })
+        })
+        .collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        assert_eq!((0..6 * side_length * side_length).collect::<Vec<_>>(), ids);
+    }
+
+    #[test]
+    #[should_panic(expected = "Sticker origin [3][0] is outside a cube with side length 3")]
+    fn test_id_outside_cube() {
+        let _ = StickerOrigin::new(Face::Up, 3, 0).id(3);
+    }
+
+    #[test]
+    fn test_is_small() {
+        assert_eq!(6, std::mem::size_of::<StickerOrigin>());
+    }
+}
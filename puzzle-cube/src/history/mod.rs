@@ -0,0 +1,236 @@
+use std::collections::VecDeque;
+
+use crate::cube::{rotation::Rotation, Cube};
+
+/// A cube that records every rotation applied to it, so that rotations can be undone and redone.
+///
+/// History is unbounded by default. A capacity can be set, in which case the oldest rotations are forgotten once it is reached, and can no longer be undone.
+/// ```
+/// # use rusty_puzzle_cube::{cube::{Cube, face::Face, rotation::Rotation}, history::HistoryCube};
+/// let mut history_cube = HistoryCube::new(Cube::create(3));
+/// history_cube.rotate(Rotation::clockwise(Face::Right));
+/// history_cube.undo();
+/// assert_eq!(&Cube::create(3), history_cube.cube());
+/// history_cube.redo();
+/// assert_eq!(vec![Rotation::clockwise(Face::Right)], history_cube.history());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryCube {
+    cube: Cube,
+    done: VecDeque<Rotation>,
+    undone: Vec<Rotation>,
+    capacity: Option<usize>,
+}
+
+impl HistoryCube {
+    /// Start recording rotations applied to the given cube, from its current state.
+    #[must_use]
+    pub fn new(cube: Cube) -> Self {
+        Self {
+            cube,
+            done: VecDeque::new(),
+            undone: vec![],
+            capacity: None,
+        }
+    }
+
+    /// Only remember up to `capacity` rotations, forgetting the oldest ones first.
+    #[must_use]
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self.forget_beyond_capacity();
+        self.undone.truncate(capacity);
+        self
+    }
+
+    /// Returns the cube in its current state.
+    #[must_use]
+    pub fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// Stop recording and return the cube in its current state.
+    #[must_use]
+    pub fn into_cube(self) -> Cube {
+        self.cube
+    }
+
+    /// Apply the rotation to the cube and record it. Any rotations that were undone can no longer be redone.
+    pub fn rotate(&mut self, rotation: Rotation) {
+        self.cube.rotate(rotation);
+        self.done.push_back(rotation);
+        self.undone.clear();
+        self.forget_beyond_capacity();
+    }
+
+    /// Reverse the most recent recorded rotation, returning it, or `None` when there is nothing to undo.
+    pub fn undo(&mut self) -> Option<Rotation> {
+        let rotation = self.done.pop_back()?;
+        self.cube.rotate(rotation.inverse());
+        self.undone.push(rotation);
+        Some(rotation)
+    }
+
+    /// Apply again the most recently undone rotation, returning it, or `None` when there is nothing to redo.
+    pub fn redo(&mut self) -> Option<Rotation> {
+        let rotation = self.undone.pop()?;
+        self.cube.rotate(rotation);
+        self.done.push_back(rotation);
+        Some(rotation)
+    }
+
+    /// Returns whether there is a recorded rotation that can be undone.
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    /// Returns whether there is an undone rotation that can be redone.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Returns the recorded rotations that have not been undone, oldest first.
+    #[must_use]
+    pub fn history(&self) -> Vec<Rotation> {
+        self.done.iter().copied().collect()
+    }
+
+    /// Apply the recorded rotations that have not been undone to another cube, oldest first.
+    pub fn replay_onto(&self, cube: &mut Cube) {
+        self.done.iter().for_each(|&rotation| cube.rotate(rotation));
+    }
+
+    /// Forget every recorded and undone rotation, keeping the cube in its current state.
+    pub fn clear_history(&mut self) {
+        self.done.clear();
+        self.undone.clear();
+    }
+
+    fn forget_beyond_capacity(&mut self) {
+        if let Some(capacity) = self.capacity {
+            let excess = self.done.len().saturating_sub(capacity);
+            self.done.drain(..excess);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::face::Face;
+    use pretty_assertions::assert_eq;
+
+    fn rotations() -> [Rotation; 3] {
+        [
+            Rotation::clockwise(Face::Right),
+            Rotation::anticlockwise(Face::Up),
+            Rotation::clockwise(Face::Front),
+        ]
+    }
+
+    #[test]
+    fn test_undo_everything_restores_cube() {
+        let mut history_cube = HistoryCube::new(Cube::create(3));
+        rotations()
+            .into_iter()
+            .for_each(|rotation| history_cube.rotate(rotation));
+
+        assert_eq!(rotations().to_vec(), history_cube.history());
+        assert_eq!(Some(rotations()[2]), history_cube.undo());
+        assert_eq!(Some(rotations()[1]), history_cube.undo());
+        assert_eq!(Some(rotations()[0]), history_cube.undo());
+        assert_eq!(None, history_cube.undo());
+        assert_eq!(&Cube::create(3), history_cube.cube());
+        assert!(history_cube.can_redo());
+    }
+
+    #[test]
+    fn test_redo_reapplies_undone_rotations() {
+        let mut history_cube = HistoryCube::new(Cube::create(3));
+        rotations()
+            .into_iter()
+            .for_each(|rotation| history_cube.rotate(rotation));
+        let expected = history_cube.cube().clone();
+
+        history_cube.undo();
+        history_cube.undo();
+        assert_eq!(Some(rotations()[1]), history_cube.redo());
+        assert_eq!(Some(rotations()[2]), history_cube.redo());
+        assert_eq!(None, history_cube.redo());
+
+        assert_eq!(&expected, history_cube.cube());
+        assert_eq!(rotations().to_vec(), history_cube.history());
+    }
+
+    #[test]
+    fn test_rotate_after_undo_discards_redo() {
+        let mut history_cube = HistoryCube::new(Cube::create(3));
+        history_cube.rotate(rotations()[0]);
+        history_cube.undo();
+        history_cube.rotate(rotations()[1]);
+
+        assert!(!history_cube.can_redo());
+        assert_eq!(None, history_cube.redo());
+        assert_eq!(vec![rotations()[1]], history_cube.history());
+    }
+
+    #[test]
+    fn test_capacity_forgets_oldest_rotations() {
+        let mut history_cube = HistoryCube::new(Cube::create(3)).with_capacity(2);
+        rotations()
+            .into_iter()
+            .for_each(|rotation| history_cube.rotate(rotation));
+
+        assert_eq!(rotations()[1..].to_vec(), history_cube.history());
+        history_cube.undo();
+        history_cube.undo();
+        assert!(!history_cube.can_undo());
+
+        let mut expected = Cube::create(3);
+        expected.rotate(rotations()[0]);
+        assert_eq!(&expected, history_cube.cube());
+    }
+
+    #[test]
+    fn test_with_capacity_trims_existing_history() {
+        let mut history_cube = HistoryCube::new(Cube::create(3));
+        rotations()
+            .into_iter()
+            .for_each(|rotation| history_cube.rotate(rotation));
+
+        let history_cube = history_cube.with_capacity(1);
+
+        assert_eq!(vec![rotations()[2]], history_cube.history());
+    }
+
+    #[test]
+    fn test_replay_onto_matches_cube() {
+        let mut history_cube = HistoryCube::new(Cube::create(4));
+        rotations()
+            .into_iter()
+            .for_each(|rotation| history_cube.rotate(rotation));
+        history_cube.undo();
+
+        let mut replayed = Cube::create(4);
+        history_cube.replay_onto(&mut replayed);
+
+        assert_eq!(history_cube.cube(), &replayed);
+    }
+
+    #[test]
+    fn test_clear_history_keeps_cube() {
+        let mut history_cube = HistoryCube::new(Cube::create(3));
+        history_cube.rotate(rotations()[0]);
+        history_cube.rotate(rotations()[1]);
+        history_cube.undo();
+        let expected = history_cube.cube().clone();
+
+        history_cube.clear_history();
+
+        assert!(!history_cube.can_undo());
+        assert!(!history_cube.can_redo());
+        assert_eq!(expected, history_cube.into_cube());
+    }
+}
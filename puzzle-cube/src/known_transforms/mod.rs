@@ -1,4 +1,145 @@
-use crate::{cube::Cube, notation::perform_3x3_sequence};
+use crate::{cube::Cube, notation::perform_3x3_sequence, patterns};
+
+/// The side lengths of cube that a known transform is designed for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SideLengths {
+    /// Only cubes with exactly this side length.
+    Only(usize),
+    /// Cubes with at least this side length.
+    AtLeast(usize),
+    /// Cubes with an odd side length of at least 3, which have a middle layer.
+    Odd,
+}
+
+impl SideLengths {
+    /// Returns whether a cube with the provided side length is one of these side lengths.
+    #[must_use]
+    pub fn contains(self, side_length: usize) -> bool {
+        match self {
+            SideLengths::Only(only) => side_length == only,
+            SideLengths::AtLeast(min) => side_length >= min,
+            SideLengths::Odd => side_length >= 3 && side_length % 2 == 1,
+        }
+    }
+}
+
+/// A named transform that turns a solved cube into a pattern, so frontends can list every pattern and offer only those designed for the cube at hand.
+/// ```
+/// # use rusty_puzzle_cube::{cube::Cube, known_transforms::KnownTransform};
+/// let mut cube = Cube::create(5);
+/// let transforms = KnownTransform::for_side_length(5).collect::<Vec<_>>();
+/// assert!(transforms.contains(&KnownTransform::Checkerboard));
+///
+/// KnownTransform::Checkerboard.apply_to(&mut cube).unwrap();
+/// assert!(KnownTransform::SideStripes.apply_to(&mut cube).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownTransform {
+    /// See [`checkerboard`].
+    Checkerboard,
+    /// See [`checkerboard_corners`].
+    CheckerboardCorners,
+    /// See [`cube_in_cube_in_cube`].
+    CubeInCubeInCube,
+    /// See [`frames`].
+    Frames,
+    /// See [`side_stripes`].
+    SideStripes,
+    /// See [`stripes`].
+    Stripes,
+}
+
+impl KnownTransform {
+    /// Every known transform.
+    pub const ALL: [KnownTransform; 6] = [
+        KnownTransform::Checkerboard,
+        KnownTransform::CheckerboardCorners,
+        KnownTransform::CubeInCubeInCube,
+        KnownTransform::Frames,
+        KnownTransform::SideStripes,
+        KnownTransform::Stripes,
+    ];
+
+    /// Returns the name of this transform, in English.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            KnownTransform::Checkerboard => "Checkerboard",
+            KnownTransform::CheckerboardCorners => "Checkerboard corners",
+            KnownTransform::CubeInCubeInCube => "Cube in cube in cube",
+            KnownTransform::Frames => "Frames",
+            KnownTransform::SideStripes => "Side stripes",
+            KnownTransform::Stripes => "Stripes",
+        }
+    }
+
+    /// Returns the side lengths of cube this transform is designed for.
+    #[must_use]
+    pub fn side_lengths(self) -> SideLengths {
+        match self {
+            KnownTransform::Checkerboard => SideLengths::Odd,
+            KnownTransform::CheckerboardCorners
+            | KnownTransform::CubeInCubeInCube
+            | KnownTransform::Frames => SideLengths::AtLeast(3),
+            KnownTransform::SideStripes => SideLengths::Only(2),
+            KnownTransform::Stripes => SideLengths::AtLeast(2),
+        }
+    }
+
+    /// Returns every known transform designed for cubes with the provided side length, in the order of [`KnownTransform::ALL`].
+    pub fn for_side_length(side_length: usize) -> impl Iterator<Item = KnownTransform> {
+        Self::ALL
+            .into_iter()
+            .filter(move |transform| transform.side_lengths().contains(side_length))
+    }
+
+    /// Apply this transform to the provided cube.
+    /// # Errors
+    /// Will return an Err variant when the cube's side length is not one this transform is designed for, in which case the cube is left unchanged
+    pub fn apply_to(self, cube: &mut Cube) -> Result<(), String> {
+        let n = cube.side_length();
+        if !self.side_lengths().contains(n) {
+            return Err(format!(
+                "{} is not designed for a {n}x{n} cube",
+                self.name()
+            ));
+        }
+        match self {
+            KnownTransform::Checkerboard => checkerboard(cube),
+            KnownTransform::CheckerboardCorners => checkerboard_corners(cube),
+            KnownTransform::CubeInCubeInCube => cube_in_cube_in_cube(cube),
+            KnownTransform::Frames => frames(cube),
+            KnownTransform::SideStripes => side_stripes(cube),
+            KnownTransform::Stripes => stripes(cube),
+        }
+        Ok(())
+    }
+}
+
+/// Apply the rotations from [`patterns::checkerboard`], which turn a solved cube with an odd side length into a checkerboard on every face.
+///
+/// On a 3x3 cube this gives the same pattern as [`checkerboard_corners`]. Cubes with an even side length are left unchanged, as they cannot be made into a checkerboard this way.
+pub fn checkerboard(cube: &mut Cube) {
+    if let Ok(rotations) = patterns::checkerboard(cube.side_length()) {
+        rotations
+            .into_iter()
+            .for_each(|rotation| cube.rotate(rotation));
+    }
+}
+
+/// Apply the rotations from [`patterns::stripes`] for the provided cube's side length.
+pub fn stripes(cube: &mut Cube) {
+    patterns::stripes(cube.side_length())
+        .into_iter()
+        .for_each(|rotation| cube.rotate(rotation));
+}
+
+/// Apply the rotations from [`patterns::frames`] for the provided cube's side length.
+pub fn frames(cube: &mut Cube) {
+    patterns::frames(cube.side_length())
+        .into_iter()
+        .for_each(|rotation| cube.rotate(rotation));
+}
 
 /// Apply a sequence to the provided cube that will turn a 3x3 cube into a checkerboard.
 ///
@@ -20,6 +161,14 @@ pub fn cube_in_cube_in_cube(cube: &mut Cube) {
     perform_3x3_sequence(sequence, cube).expect("Known transforms must use valid sequences");
 }
 
+/// Apply a sequence to the provided cube that will turn a 2x2 cube into one where every face around the middle has two stripes, leaving Up and Down solved.
+/// # Panics
+/// Will panic if local variable `sequence` contains a malformed sequence. This would be considered a bug.
+pub fn side_stripes(cube: &mut Cube) {
+    let sequence = "R2 F2 R2";
+    perform_3x3_sequence(sequence, cube).expect("Known transforms must use valid sequences");
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cube::cubie_face::CubieFace;
@@ -111,4 +260,80 @@ mod tests {
 
         assert_eq!(expected_cube, cube);
     }
+
+    #[test]
+    fn test_checkerboard_on_odd_cubes() {
+        for side_length in [3, 5, 7] {
+            let mut cube = Cube::create(side_length);
+
+            checkerboard(&mut cube);
+
+            for (face, side) in cube.side_map() {
+                let centre = side[side_length / 2][side_length / 2];
+                for (row, col) in
+                    (0..side_length).flat_map(|row| (0..side_length).map(move |col| (row, col)))
+                {
+                    assert_eq!(
+                        (row + col) % 2 == 0,
+                        side[row][col] == centre,
+                        "{face:?} side of {side_length}x{side_length} at ({row}, {col})"
+                    );
+                }
+            }
+        }
+
+        let mut cube = Cube::create(3);
+        checkerboard(&mut cube);
+        let mut control_cube = Cube::create(3);
+        checkerboard_corners(&mut control_cube);
+        assert_eq!(control_cube, cube);
+    }
+
+    #[test]
+    fn test_side_stripes() {
+        let mut cube = Cube::create(2);
+
+        side_stripes(&mut cube);
+
+        assert_eq!("UUUURLRLBFBFDDDDLRLRFBFB", cube.to_facelet_string());
+    }
+
+    #[test]
+    fn test_for_side_length() {
+        assert_eq!(
+            vec![KnownTransform::SideStripes, KnownTransform::Stripes],
+            KnownTransform::for_side_length(2).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                KnownTransform::CheckerboardCorners,
+                KnownTransform::CubeInCubeInCube,
+                KnownTransform::Frames,
+                KnownTransform::Stripes
+            ],
+            KnownTransform::for_side_length(4).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                KnownTransform::Checkerboard,
+                KnownTransform::CheckerboardCorners,
+                KnownTransform::CubeInCubeInCube,
+                KnownTransform::Frames,
+                KnownTransform::Stripes
+            ],
+            KnownTransform::for_side_length(5).collect::<Vec<_>>()
+        );
+        assert_eq!(0, KnownTransform::for_side_length(1).count());
+    }
+
+    #[test]
+    fn test_apply_to_unsupported_side_length() {
+        let mut cube = Cube::create(4);
+
+        assert_eq!(
+            Err("Checkerboard is not designed for a 4x4 cube".to_string()),
+            KnownTransform::Checkerboard.apply_to(&mut cube)
+        );
+        assert_eq!(Cube::create(4), cube);
+    }
 }
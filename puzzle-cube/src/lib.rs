@@ -1,11 +1,51 @@
 #![warn(missing_docs)]
 //! Crate providing a puzzle cube implementation, with the ability to apply string-encoded sequences of moves.
 
+/// Module providing cancellation and limits for long-running operations.
+pub mod budget;
+
 /// Module providing the core cube implementation.
 pub mod cube;
 
+/// Module providing a cube that records the rotations applied to it, allowing them to be undone and redone.
+pub mod history;
+
 /// Module providing some pre-defined patterns that can be applied to a cube.
 pub mod known_transforms;
 
+/// Module providing counts of the moves in a sequence of rotations, in the standard metrics used to report solutions.
+pub mod metrics;
+
 /// Module providing the ability to parse string-encoded sequences of moves and apply them to a cube.
 pub mod notation;
+
+/// Module providing generators of the rotations that turn a solved cube of any size into a pattern.
+pub mod patterns;
+
+/// Module providing recognition of the standard cases left by the last layer of a 3x3x3 cube, once the first two layers are solved.
+pub mod recognition;
+
+/// Module providing a cube that passes each rotation applied to it on to sinks such as logs, robots and network clients.
+pub mod recording;
+
+/// Module providing records of solving sessions that can be shared as annotated text and replayed.
+pub mod reconstruction;
+
+/// Module providing the ability to export sequences of moves as commands for cube-solving robots.
+pub mod robot;
+
+/// Module providing searches for the shortest sequences of moves that solve a cube, guided by pluggable heuristics.
+pub mod search;
+
+/// Module providing timed solving sessions, keeping every solve's scramble and moves along with the averages used to report progress.
+pub mod session;
+
+/// Module providing solvers that find the rotations to return a cube to its solved state.
+pub mod solver;
+
+/// Module providing guided training sessions for drilling algorithms.
+pub mod trainer;
+
+/// Module providing helpers shared by the tests of several modules.
+#[cfg(test)]
+mod test_support;
@@ -0,0 +1,232 @@
+use std::{fmt, ops::Add};
+
+use crate::{
+    cube::{direction::Direction, face::Face, rotation::Rotation, Cube},
+    recording::{MoveSink, RecordingCube},
+};
+
+/// The length of a sequence of rotations in each of the standard metrics used to report solutions.
+///
+/// Neighbouring turns of the same layer are counted as the single move they would be written as, so two clockwise turns of Right are one half turn, and turns that cancel out are not counted at all.
+/// Turns of an inner layer count as the two outer turns they equal, up to how the cube is held, except in the slice turn metric.
+/// ```
+/// # use rusty_puzzle_cube::{metrics::MoveCounts, notation::parse_sequence};
+/// let counts = MoveCounts::of(&parse_sequence("R U2 M' R'").unwrap(), 3).unwrap();
+/// assert_eq!(MoveCounts { htm: 5, qtm: 6, stm: 4 }, counts);
+/// assert_eq!("5 HTM, 6 QTM, 4 STM", counts.to_string());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MoveCounts {
+    /// The half turn metric, where a turn of a face by any amount is one move.
+    pub htm: usize,
+    /// The quarter turn metric, where a half turn of a face is two moves.
+    pub qtm: usize,
+    /// The slice turn metric, where a turn of any single layer by any amount is one move.
+    pub stm: usize,
+}
+
+impl MoveCounts {
+    /// Count the moves made by the given rotations of a cube with the given side length.
+    /// # Errors
+    /// Will return an Err variant when any rotation turns a layer that is not within a cube of the given side length
+    pub fn of(rotations: &[Rotation], side_length: usize) -> Result<Self, String> {
+        let mut counter = MoveCounter::new();
+        for &rotation in rotations {
+            counter.count(rotation, side_length)?;
+        }
+        Ok(counter.counts())
+    }
+
+    fn of_turn(quarter_turns: u8, is_outer: bool) -> Self {
+        let quarters = match quarter_turns {
+            0 => return Self::default(),
+            2 => 2,
+            _ => 1,
+        };
+        let per_layer = if is_outer { 1 } else { 2 };
+        Self {
+            htm: per_layer,
+            qtm: quarters * per_layer,
+            stm: 1,
+        }
+    }
+}
+
+impl Add for MoveCounts {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            htm: self.htm + other.htm,
+            qtm: self.qtm + other.qtm,
+            stm: self.stm + other.stm,
+        }
+    }
+}
+
+impl fmt::Display for MoveCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} HTM, {} QTM, {} STM", self.htm, self.qtm, self.stm)
+    }
+}
+
+/// A layer of a cube, named by the face its axis is counted from so that the same layer turned from either side is recognised.
+type Layer = (Face, usize);
+
+/// A [`MoveSink`] that counts the moves made by the rotations passed to it, in each of the metrics of [`MoveCounts`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveCounter {
+    settled: MoveCounts,
+    pending: Option<(Layer, u8, bool)>,
+}
+
+impl MoveCounter {
+    /// Create a counter that has not counted any moves yet.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the moves counted so far.
+    #[must_use]
+    pub fn counts(&self) -> MoveCounts {
+        let pending = self
+            .pending
+            .map_or_else(MoveCounts::default, |(_, quarter_turns, is_outer)| {
+                MoveCounts::of_turn(quarter_turns, is_outer)
+            });
+        self.settled + pending
+    }
+
+    fn count(&mut self, rotation: Rotation, side_length: usize) -> Result<(), String> {
+        let (layer, quarter_turns) = along_axis(rotation, side_length)?;
+        match &mut self.pending {
+            Some((pending_layer, pending_turns, _)) if *pending_layer == layer => {
+                *pending_turns = (*pending_turns + quarter_turns) % 4;
+            }
+            _ => {
+                self.settled = self.counts();
+                let is_outer = rotation.layer == 0 || rotation.layer + 1 == side_length;
+                self.pending = Some((layer, quarter_turns, is_outer));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MoveSink for MoveCounter {
+    fn on_move(&mut self, rotation: Rotation, state_after: &Cube) {
+        self.count(rotation, state_after.side_length())
+            .expect("A rotation already made on a cube turns a layer within it");
+    }
+}
+
+/// A cube that counts the moves made by the rotations applied to it.
+/// ```
+/// # use rusty_puzzle_cube::{cube::{Cube, face::Face, rotation::Rotation}, metrics::{MoveCounter, MoveCountingCube}};
+/// let mut cube = MoveCountingCube::new(Cube::create(3), MoveCounter::new());
+/// cube.rotate(Rotation::clockwise(Face::Right));
+/// cube.rotate(Rotation::clockwise(Face::Right));
+/// assert_eq!(2, cube.sink().counts().qtm);
+/// ```
+pub type MoveCountingCube = RecordingCube<MoveCounter>;
+
+/// The layer a rotation turns, counted from the Up, Front or Right face, and how many clockwise quarter turns it makes as seen from that face.
+fn along_axis(rotation: Rotation, side_length: usize) -> Result<(Layer, u8), String> {
+    let Rotation {
+        relative_to,
+        direction,
+        layer,
+    } = rotation;
+    if layer >= side_length {
+        return Err(format!(
+            "Cannot turn layer {layer} of a cube with side length {side_length}"
+        ));
+    }
+    let quarter_turns = |direction| match direction {
+        Direction::Clockwise => 1,
+        Direction::Anticlockwise => 3,
+    };
+    Ok(match relative_to {
+        Face::Up | Face::Front | Face::Right => ((relative_to, layer), quarter_turns(direction)),
+        Face::Down | Face::Back | Face::Left => (
+            (relative_to.opposite(), side_length - 1 - layer),
+            quarter_turns(direction.opposite()),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_sequence;
+    use pretty_assertions::assert_eq;
+
+    fn counts(sequence: &str, side_length: usize) -> MoveCounts {
+        MoveCounts::of(&parse_sequence(sequence).unwrap(), side_length).unwrap()
+    }
+
+    #[test]
+    fn test_outer_turns() {
+        assert_eq!(
+            MoveCounts {
+                htm: 4,
+                qtm: 5,
+                stm: 4
+            },
+            counts("R U R' F2", 3)
+        );
+    }
+
+    #[test]
+    fn test_slice_turns_count_double_except_in_stm() {
+        assert_eq!(
+            MoveCounts {
+                htm: 4,
+                qtm: 6,
+                stm: 2
+            },
+            counts("M2 E", 3)
+        );
+    }
+
+    #[test]
+    fn test_neighbouring_turns_of_a_layer_are_merged() {
+        assert_eq!(counts("R'", 3), counts("R R R", 3));
+        assert_eq!(MoveCounts::default(), counts("R R'", 2));
+        assert_eq!(counts("R2", 3), counts("R 3L'", 3));
+    }
+
+    #[test]
+    fn test_inner_layers_depend_on_side_length() {
+        assert_eq!(counts("2R", 3), counts("M'", 3));
+        assert_eq!(
+            MoveCounts {
+                htm: 1,
+                qtm: 1,
+                stm: 1
+            },
+            counts("2R", 2)
+        );
+    }
+
+    #[test]
+    fn test_layers_outside_the_cube_are_rejected() {
+        assert_eq!(
+            Err("Cannot turn layer 3 of a cube with side length 3".to_string()),
+            MoveCounts::of(&parse_sequence("R 4L").unwrap(), 3)
+        );
+        assert_eq!(
+            Err("Cannot turn layer 2 of a cube with side length 2".to_string()),
+            MoveCounts::of(&parse_sequence("3R").unwrap(), 2)
+        );
+    }
+
+    #[test]
+    fn test_move_counting_cube() {
+        let mut cube = MoveCountingCube::new(Cube::create(3), MoveCounter::new());
+        cube.rotate_all(parse_sequence("R U2 R'").unwrap());
+
+        assert_eq!(counts("R U2 R'", 3), cube.sink().counts());
+    }
+}
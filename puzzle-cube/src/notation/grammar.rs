@@ -0,0 +1,291 @@
+use std::fmt::Write as _;
+
+use crate::cube::face::Face;
+
+pub(super) const TOKEN_SEPARATOR: char = ' ';
+pub(super) const CHAR_FOR_ANTICLOCKWISE: char = '\'';
+pub(super) const CHAR_FOR_TURN_TWICE: char = '2';
+pub(super) const PARALLEL_OPEN: char = '(';
+pub(super) const PARALLEL_SEPARATOR: char = ',';
+pub(super) const PARALLEL_CLOSE: char = ')';
+pub(super) const WIDE_MARKER: char = 'w';
+pub(super) const BRACKET_OPEN: char = '[';
+pub(super) const COMMUTATOR_SEPARATOR: char = ',';
+pub(super) const CONJUGATE_SEPARATOR: char = ':';
+pub(super) const BRACKET_CLOSE: char = ']';
+
+const FACES: [(char, Face); 6] = [
+    ('F', Face::Front),
+    ('R', Face::Right),
+    ('U', Face::Up),
+    ('L', Face::Left),
+    ('B', Face::Back),
+    ('D', Face::Down),
+];
+
+const WIDE_FACES: [(char, Face); 6] = [
+    ('f', Face::Front),
+    ('r', Face::Right),
+    ('u', Face::Up),
+    ('l', Face::Left),
+    ('b', Face::Back),
+    ('d', Face::Down),
+];
+
+const SLICES: [(char, Face); 3] = [('M', Face::Left), ('E', Face::Down), ('S', Face::Front)];
+
+const MODIFIERS: [(char, Modifier); 2] = [
+    (CHAR_FOR_ANTICLOCKWISE, Modifier::Anticlockwise),
+    (CHAR_FOR_TURN_TWICE, Modifier::TurnTwice),
+];
+
+/// A suffix that changes how the face named by a token is rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    /// Rotate the face 90° anticlockwise instead of clockwise.
+    Anticlockwise,
+    /// Rotate the face 180°.
+    TurnTwice,
+}
+
+/// A machine-readable description of the notation accepted by [`crate::notation::perform_3x3_sequence`].
+///
+/// The parser reads the same tables, so frontends that highlight or validate notation using this description stay in sync with the parser as the grammar grows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grammar {
+    /// The character that must appear exactly once between each token.
+    pub separator: char,
+    /// Each character that names a face, with the face it rotates.
+    pub faces: &'static [(char, Face)],
+    /// The character that may follow a face character to turn the layers behind it too, such as `Rw`.
+    pub wide_marker: char,
+    /// Each lowercase character that names a wide move of a face in SiGN notation, such as `r` for `Rw`, with the face it rotates. These are rejected when parsing strictly.
+    pub wide_faces: &'static [(char, Face)],
+    /// Each character that names the middle layer of the cube, with the face whose turning direction it follows.
+    pub slices: &'static [(char, Face)],
+    /// Each character that may optionally follow a face character or a turn count, with its meaning. At most one modifier may be used per token.
+    pub modifiers: &'static [(char, Modifier)],
+    /// The character that opens a group of tokens turned in parallel, such as `(R, L')`.
+    pub parallel_open: char,
+    /// The character between tokens in a parallel group. It may be followed by the token separator.
+    pub parallel_separator: char,
+    /// The character that closes a group of tokens turned in parallel.
+    pub parallel_close: char,
+    /// The character that opens a commutator such as `[R, U]` or a conjugate such as `[F: R U R']`.
+    pub bracket_open: char,
+    /// The character between the two sequences of a commutator `[A, B]`, which is performed as `A B A' B'`.
+    pub commutator_separator: char,
+    /// The character between the two sequences of a conjugate `[A: B]`, which is performed as `A B A'`.
+    pub conjugate_separator: char,
+    /// The character that closes a commutator or conjugate.
+    pub bracket_close: char,
+}
+
+/// Returns the description of the notation accepted by this crate.
+#[must_use]
+pub fn grammar() -> Grammar {
+    Grammar {
+        separator: TOKEN_SEPARATOR,
+        faces: &FACES,
+        wide_marker: WIDE_MARKER,
+        wide_faces: &WIDE_FACES,
+        slices: &SLICES,
+        modifiers: &MODIFIERS,
+        parallel_open: PARALLEL_OPEN,
+        parallel_separator: PARALLEL_SEPARATOR,
+        parallel_close: PARALLEL_CLOSE,
+        bracket_open: BRACKET_OPEN,
+        commutator_separator: COMMUTATOR_SEPARATOR,
+        conjugate_separator: CONJUGATE_SEPARATOR,
+        bracket_close: BRACKET_CLOSE,
+    }
+}
+
+impl Grammar {
+    /// Returns the face named by the provided character, if it is a face character in this grammar.
+    #[must_use]
+    pub fn face_for(&self, c: char) -> Option<Face> {
+        lookup(self.faces, c)
+    }
+
+    /// Returns the face turned by the wide move named by the provided character, if it is a lowercase wide face character in this grammar.
+    #[must_use]
+    pub fn wide_face_for(&self, c: char) -> Option<Face> {
+        lookup(self.wide_faces, c)
+    }
+
+    /// Returns the face whose turning direction is followed by the middle layer named by the provided character, if it is a slice character in this grammar.
+    #[must_use]
+    pub fn slice_for(&self, c: char) -> Option<Face> {
+        lookup(self.slices, c)
+    }
+
+    /// Returns the modifier represented by the provided character, if it is a modifier character in this grammar.
+    #[must_use]
+    pub fn modifier_for(&self, c: char) -> Option<Modifier> {
+        lookup(self.modifiers, c)
+    }
+
+    /// Renders this grammar as EBNF (ISO 14977 style), suitable for documentation.
+    #[must_use]
+    pub fn to_ebnf(&self) -> String {
+        let mut ebnf = String::new();
+        let _ = writeln!(
+            ebnf,
+            "sequence = step, {{ {}, step }} ;",
+            quote(self.separator)
+        );
+        let _ = writeln!(
+            ebnf,
+            "step = token | ( parallel | group | bracketed ), [ repeat ] ;"
+        );
+        let _ = writeln!(
+            ebnf,
+            "parallel = {}, token, {{ {}, [ {} ], token }}-, {} ;",
+            quote(self.parallel_open),
+            quote(self.parallel_separator),
+            quote(self.separator),
+            quote(self.parallel_close)
+        );
+        let _ = writeln!(
+            ebnf,
+            "group = {}, sequence, {} ;",
+            quote(self.parallel_open),
+            quote(self.parallel_close)
+        );
+        let _ = writeln!(
+            ebnf,
+            "bracketed = {}, sequence, ( {} | {} ), [ {} ], sequence, {} ;",
+            quote(self.bracket_open),
+            quote(self.commutator_separator),
+            quote(self.conjugate_separator),
+            quote(self.separator),
+            quote(self.bracket_close)
+        );
+        let _ = writeln!(
+            ebnf,
+            "repeat = ? a whole number of repetitions ?, [ {inverse} ] | {inverse} ;",
+            inverse = quote(CHAR_FOR_ANTICLOCKWISE)
+        );
+        let _ = writeln!(
+            ebnf,
+            "token = ( [ layer ], ( face, [ {} ] | wide face ) | slice ), [ count ], [ modifier ] ;",
+            quote(self.wide_marker)
+        );
+        let _ = writeln!(
+            ebnf,
+            "layer = ? a whole number from 1, counting in from the face, or how many layers a wide move turns ? ;"
+        );
+        let _ = writeln!(
+            ebnf,
+            "count = ? a whole number of clockwise quarter turns, read modulo 4 ? ;"
+        );
+        let _ = writeln!(ebnf, "face = {} ;", alternatives(self.faces));
+        let _ = writeln!(ebnf, "wide face = {} ;", alternatives(self.wide_faces));
+        let _ = writeln!(ebnf, "slice = {} ;", alternatives(self.slices));
+        let _ = writeln!(ebnf, "modifier = {} ;", alternatives(self.modifiers));
+        ebnf
+    }
+}
+
+fn lookup<T: Copy>(table: &[(char, T)], c: char) -> Option<T> {
+    table
+        .iter()
+        .find_map(|(candidate, value)| (*candidate == c).then_some(*value))
+}
+
+fn quote(c: char) -> String {
+    if c == '\'' {
+        format!("\"{c}\"")
+    } else {
+        format!("'{c}'")
+    }
+}
+
+fn alternatives<T>(table: &[(char, T)]) -> String {
+    table
+        .iter()
+        .map(|(c, _)| quote(*c))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_face_lookup() {
+        let grammar = grammar();
+
+        assert_eq!(Some(Face::Back), grammar.face_for('B'));
+        assert_eq!(None, grammar.face_for('M'));
+        assert_eq!(None, grammar.face_for('b'));
+    }
+
+    #[test]
+    fn test_wide_face_lookup() {
+        let grammar = grammar();
+
+        assert_eq!(Some(Face::Back), grammar.wide_face_for('b'));
+        assert_eq!(None, grammar.wide_face_for('B'));
+        assert_eq!(None, grammar.wide_face_for('m'));
+    }
+
+    #[test]
+    fn test_slice_lookup() {
+        let grammar = grammar();
+
+        assert_eq!(Some(Face::Left), grammar.slice_for('M'));
+        assert_eq!(Some(Face::Down), grammar.slice_for('E'));
+        assert_eq!(Some(Face::Front), grammar.slice_for('S'));
+        assert_eq!(None, grammar.slice_for('F'));
+    }
+
+    #[test]
+    fn test_modifier_lookup() {
+        let grammar = grammar();
+
+        assert_eq!(Some(Modifier::Anticlockwise), grammar.modifier_for('\''));
+        assert_eq!(Some(Modifier::TurnTwice), grammar.modifier_for('2'));
+        assert_eq!(None, grammar.modifier_for('3'));
+    }
+
+    #[test]
+    fn test_every_face_named_once() {
+        let grammar = grammar();
+
+        for face in [
+            Face::Up,
+            Face::Down,
+            Face::Front,
+            Face::Right,
+            Face::Back,
+            Face::Left,
+        ] {
+            let count = grammar.faces.iter().filter(|(_, f)| *f == face).count();
+            assert_eq!(1, count, "{face:?} should be named exactly once");
+        }
+    }
+
+    #[test]
+    fn test_to_ebnf() {
+        let expected = r#"sequence = step, { ' ', step } ;
+step = token | ( parallel | group | bracketed ), [ repeat ] ;
+parallel = '(', token, { ',', [ ' ' ], token }-, ')' ;
+group = '(', sequence, ')' ;
+bracketed = '[', sequence, ( ',' | ':' ), [ ' ' ], sequence, ']' ;
+repeat = ? a whole number of repetitions ?, [ "'" ] | "'" ;
+token = ( [ layer ], ( face, [ 'w' ] | wide face ) | slice ), [ count ], [ modifier ] ;
+layer = ? a whole number from 1, counting in from the face, or how many layers a wide move turns ? ;
+count = ? a whole number of clockwise quarter turns, read modulo 4 ? ;
+face = 'F' | 'R' | 'U' | 'L' | 'B' | 'D' ;
+wide face = 'f' | 'r' | 'u' | 'l' | 'b' | 'd' ;
+slice = 'M' | 'E' | 'S' ;
+modifier = "'" | '2' ;
+"#;
+
+        assert_eq!(expected, grammar().to_ebnf());
+    }
+}
@@ -1,76 +1,579 @@
-use crate::cube::{face::Face, Cube};
+use std::{fmt, ops::Range};
 
-const CHAR_FOR_ANTICLOCKWISE: char = '\'';
-const CHAR_FOR_TURN_TWICE: char = '2';
+use crate::cube::{
+    direction::Direction,
+    face::Face,
+    parallel_rotation::ParallelRotation,
+    rotation::Rotation,
+    sequence::{Op, Sequence},
+    Cube,
+};
+use grammar::{
+    grammar, Modifier, BRACKET_CLOSE, BRACKET_OPEN, CHAR_FOR_ANTICLOCKWISE, CHAR_FOR_TURN_TWICE,
+    COMMUTATOR_SEPARATOR, CONJUGATE_SEPARATOR, PARALLEL_CLOSE, PARALLEL_OPEN, PARALLEL_SEPARATOR,
+    TOKEN_SEPARATOR, WIDE_MARKER,
+};
 
-// todo support 4x4 notation (needs new cube methods), such as cube_in_cube_etc: B' M2 U2 M2 B F2 R U' R U R2 U R2 F' U F' Uw Lw Uw' Fw2 Dw Rw' Uw Fw Dw2 Rw2
+/// A machine-readable description of the accepted notation.
+pub mod grammar;
+
+/// The side length assumed when parsing without a cube to turn, so slice moves turn layer 1.
+const NOTATION_SIDE_LENGTH: usize = 3;
+
+/// How many layers a wide move such as `Rw` turns when no number is given.
+const DEFAULT_WIDE_DEPTH: usize = 2;
+
+/// The most moves a group may expand to once its repeats, commutators and conjugates are written out, so that input such as `((R U)1000)1000` is rejected rather than exhausting memory.
+const MAX_EXPANDED_MOVES: usize = 100_000;
+
+/// Options for reading notation, for the functions ending in `_with`.
+///
+/// The default options accept everything in [`grammar::grammar`], as the other functions do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Whether lowercase face characters such as `r` are read as wide moves, as in SiGN notation, rather than rejected, as in WCA notation.
+    pub lowercase_wide_moves: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            lowercase_wide_moves: true,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Options that only accept notation used by the WCA, so wide moves must be written as `Rw`.
+    #[must_use]
+    pub fn strict() -> Self {
+        Self {
+            lowercase_wide_moves: false,
+        }
+    }
+}
 
 /// Perform a sequence of moves on a provided Cube instance.
+///
+/// Parallel groups such as `(R, L')` are performed one face after the other, which gives the same result as turning the faces together.
+/// Groups may be repeated or inverted, such as `(R U R' U')3`, and commutators `[A, B]` and conjugates `[A: B]` are performed as `A B A' B'` and `A B A'`.
+/// Slice moves such as `M` turn the middle layer of the cube, whatever its size, and wide moves such as `Rw`, or `r` in SiGN notation, turn the two outer layers, or as many as the number before them as in `3Rw`.
 /// # Errors
-/// Will return an Err variant when the input `token_sequence` is malformed
+/// Will return an Err variant when the input `token_sequence` is malformed, uses a slice move on a cube with an even side length, which has no middle layer, or turns a layer that is locked, in which case the steps before it remain applied
 pub fn perform_3x3_sequence(token_sequence: &str, cube: &mut Cube) -> Result<(), String> {
+    perform_3x3_sequence_with(token_sequence, cube, ParseOptions::default())
+}
+
+/// Perform a sequence of moves on a provided Cube instance, as for [`perform_3x3_sequence`], reading it with the given options.
+/// # Errors
+/// Will return an Err variant as for [`perform_3x3_sequence`], or when the sequence uses notation that the options do not accept
+pub fn perform_3x3_sequence_with(
+    token_sequence: &str,
+    cube: &mut Cube,
+    options: ParseOptions,
+) -> Result<(), String> {
     let token_sequence = token_sequence.trim();
 
-    token_sequence
-        .trim()
-        .split(' ')
-        .try_for_each(|token| apply_token(token.trim(), cube))?;
+    split_steps(token_sequence.trim())
+        .try_for_each(|step| apply_step(step.trim(), cube, options))?;
 
     Ok(())
 }
 
-fn apply_token(token: &str, cube: &mut Cube) -> Result<(), String> {
-    let base_token = get_base_token_if_valid(token);
+/// Parse a sequence into the rotations it describes, without performing them.
+///
+/// Half turns such as `F2` become two clockwise rotations, and other turn counts are read modulo 4, so `F3` and `F2'` become `F'` and `F2`. Parallel groups such as `(R, L')` become the rotations of each face in turn.
+/// Without a cube to turn, slice moves such as `M` are read as the middle layer of a 3x3 cube, which is layer 1.
+/// # Errors
+/// Will return an Err variant when the input `token_sequence` is malformed
+pub fn parse_sequence(token_sequence: &str) -> Result<Vec<Rotation>, ParseError> {
+    parse_sequence_with(token_sequence, ParseOptions::default())
+}
+
+/// Parse a sequence into the rotations it describes, as for [`parse_sequence`], reading it with the given options.
+/// ```
+/// # use rusty_puzzle_cube::notation::{parse_sequence, parse_sequence_with, ParseOptions};
+/// assert_eq!(parse_sequence("Rw U"), parse_sequence_with("r U", ParseOptions::default()));
+/// assert!(parse_sequence_with("r U", ParseOptions::strict()).is_err());
+/// ```
+/// # Errors
+/// Will return an Err variant when the input `token_sequence` is malformed, or uses notation that the options do not accept
+pub fn parse_sequence_with(
+    token_sequence: &str,
+    options: ParseOptions,
+) -> Result<Vec<Rotation>, ParseError> {
+    let reader = Reader {
+        side_length: NOTATION_SIDE_LENGTH,
+        options,
+    };
+    let ops = parse_spanned_steps(token_sequence, reader)?;
+    Ok(ops.iter().flat_map(Op::rotations).copied().collect())
+}
+
+/// Parse a sequence into ops, without performing them, keeping each parallel group such as `(R, L')` together as an [`Op::Parallel`].
+///
+/// Every other token becomes one [`Op::Single`] per rotation, so `F2` becomes two ops. Slice moves are read as for a 3x3 cube, as in [`parse_sequence`].
+/// ```
+/// # use rusty_puzzle_cube::{cube::sequence::Op, notation::parse_ops};
+/// let sequence = parse_ops("F (R, L') U2").unwrap();
+/// assert_eq!(4, sequence.len());
+/// assert!(matches!(sequence.ops()[1], Op::Parallel(_)));
+/// ```
+/// # Errors
+/// Will return an Err variant when the input `token_sequence` is malformed
+pub fn parse_ops(token_sequence: &str) -> Result<Sequence, ParseError> {
+    Ok(parse_spanned_steps(token_sequence, Reader::default())?
+        .into_iter()
+        .collect())
+}
+
+/// Write rotations as a notation sequence that [`parse_sequence`] reads back into the same turns of a 3x3 cube.
+///
+/// Two clockwise rotations of the same face in a row are written as a half turn, such as `F2`.
+/// Layer 1 is written as a slice move, as it is the middle layer of a 3x3 cube, so a clockwise turn of layer 1 from the Right face is written as `M'`, which is read back as the same turn relative to the Left face.
+/// Deeper layers are written with their layer number counted from 1 first, such as `3R`.
+/// Use [`to_string`] when the rotations read back must be exactly the same.
+/// ```
+/// # use rusty_puzzle_cube::notation::{format_sequence, parse_sequence};
+/// let rotations = parse_sequence("F2 R' M U").unwrap();
+/// assert_eq!("F2 R' M U", format_sequence(&rotations));
+/// ```
+#[must_use]
+pub fn format_sequence(rotations: &[Rotation]) -> String {
+    write_tokens(rotations, true)
+}
 
-    let face = match base_token {
-        Some('F') => Ok(Face::Front),
-        Some('R') => Ok(Face::Right),
-        Some('U') => Ok(Face::Up),
-        Some('L') => Ok(Face::Left),
-        Some('B') => Ok(Face::Back),
-        Some('D') => Ok(Face::Down),
-        _ => Err(format!("Unsupported token in notation string: [{token}]")),
-    }?;
+/// Write rotations as a notation sequence that [`parse_sequence`] reads back into exactly the same rotations, whatever their layers.
+///
+/// No rotations are written as an empty string, which is not a sequence that can be read back.
+///
+/// Two clockwise rotations of the same face in a row are written as a half turn, such as `F2`.
+/// Every layer other than the face itself is written with its layer number counted from 1 first, such as `2R` for layer 1, so no rotation is swapped for the same turn of the opposite face as [`format_sequence`] does for slice moves.
+/// ```
+/// # use rusty_puzzle_cube::{cube::{face::Face, rotation::Rotation}, notation::{parse_sequence, to_string}};
+/// let rotations = [
+///     Rotation::clockwise(Face::Front),
+///     Rotation::clockwise(Face::Front),
+///     Rotation::clockwise(Face::Right).at_layer(1),
+///     Rotation::anticlockwise(Face::Up).at_layer(4),
+/// ];
+/// assert_eq!("F2 2R 5U'", to_string(&rotations));
+/// assert_eq!(Ok(rotations.to_vec()), parse_sequence(&to_string(&rotations)));
+/// ```
+#[must_use]
+pub fn to_string(rotations: &[Rotation]) -> String {
+    write_tokens(rotations, false)
+}
 
-    let fn_to_apply = if token.ends_with(CHAR_FOR_ANTICLOCKWISE) {
-        Cube::rotate_face_90_degrees_anticlockwise
-    } else {
-        Cube::rotate_face_90_degrees_clockwise
+fn write_tokens(rotations: &[Rotation], use_slices: bool) -> String {
+    let grammar = grammar();
+    let char_for = |table: &[(char, Face)], face: Face| {
+        table.iter().find_map(|&(c, f)| (f == face).then_some(c))
     };
 
-    fn_to_apply(cube, face);
-    if token.ends_with(CHAR_FOR_TURN_TWICE) {
-        fn_to_apply(cube, face);
+    let mut tokens = vec![];
+    let mut rotations = rotations.iter().peekable();
+    while let Some(&rotation) = rotations.next() {
+        let face = rotation.relative_to;
+        let face_char = char_for(grammar.faces, face).expect("Every face is named in the grammar");
+        let (mut token, direction) = match rotation.layer {
+            0 => (face_char.to_string(), rotation.direction),
+            1 if use_slices => char_for(grammar.slices, face)
+                .map(|c| (c, rotation.direction))
+                .or_else(|| {
+                    char_for(grammar.slices, face.opposite())
+                        .map(|c| (c, rotation.direction.opposite()))
+                })
+                .map(|(c, direction)| (c.to_string(), direction))
+                .expect("Every axis has a slice named in the grammar"),
+            layer => (format!("{}{face_char}", layer + 1), rotation.direction),
+        };
+        if direction == Direction::Anticlockwise {
+            token.push(CHAR_FOR_ANTICLOCKWISE);
+        } else if rotations.next_if(|&&next| next == rotation).is_some() {
+            token.push(CHAR_FOR_TURN_TWICE);
+        }
+        tokens.push(token);
     }
+    tokens.join(&TOKEN_SEPARATOR.to_string())
+}
+
+/// A single token read from a notation sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenSpan {
+    /// The byte range of the token within the sequence it was read from.
+    pub range: Range<usize>,
+    /// Why the token is not valid notation, or `None` if it is valid.
+    pub error: Option<String>,
+}
 
+/// The error returned when a sequence cannot be parsed, locating the step that is not valid notation.
+///
+/// A parallel group, group, commutator or conjugate is a single step, as in [`tokenise`].
+/// ```
+/// # use rusty_puzzle_cube::notation::parse_sequence;
+/// let error = parse_sequence("F R@ U").unwrap_err();
+/// assert_eq!((1, 2..4), (error.token_index, error.span.clone()));
+/// assert_eq!("Unsupported token in notation string: [R@]", error.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The position of the step among the steps of the sequence, counted from 0.
+    pub token_index: usize,
+    /// The byte range of the step within the sequence it was read from.
+    pub span: Range<usize>,
+    /// Why the step is not valid notation.
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Split a sequence into the tokens that [`perform_3x3_sequence`] would apply, checking each one without performing any moves.
+///
+/// A parallel group such as `(R, L')` is a single token, and slice moves are checked as for a 3x3 cube, as in [`parse_sequence`]. Unlike [`perform_3x3_sequence`], this does not stop at the first invalid token, so every problem in the sequence can be reported at once.
+#[must_use]
+pub fn tokenise(token_sequence: &str) -> Vec<TokenSpan> {
+    spanned_steps(token_sequence)
+        .map(|(range, token)| TokenSpan {
+            range,
+            error: parse_step(token, Reader::default()).err(),
+        })
+        .collect()
+}
+
+/// Splits a sequence into its steps, each with its byte range within the sequence.
+fn spanned_steps(token_sequence: &str) -> impl Iterator<Item = (Range<usize>, &str)> + '_ {
+    let mut start = token_sequence.len() - token_sequence.trim_start().len();
+
+    split_steps(token_sequence.trim()).map(move |part| {
+        let token_start = start + part.len() - part.trim_start().len();
+        let token = part.trim();
+        start += part.len() + TOKEN_SEPARATOR.len_utf8();
+        (token_start..token_start + token.len(), token)
+    })
+}
+
+fn parse_spanned_steps(token_sequence: &str, reader: Reader) -> Result<Vec<Op>, ParseError> {
+    let mut ops = vec![];
+    for (token_index, (span, step)) in spanned_steps(token_sequence).enumerate() {
+        let step_ops = parse_step(step, reader).and_then(|step_ops| {
+            check_expanded_len(ops.len() + step_ops.len())
+                .map(|_| step_ops)
+                .ok_or_else(|| {
+                    format!("Sequence expands to more than {MAX_EXPANDED_MOVES} moves at: [{step}]")
+                })
+        });
+        ops.extend(step_ops.map_err(|message| ParseError {
+            token_index,
+            span,
+            message,
+        })?);
+    }
+    Ok(ops)
+}
+
+fn split_steps(token_sequence: &str) -> impl Iterator<Item = &str> + '_ {
+    let mut depth = 0_usize;
+    token_sequence.split(move |c| {
+        match c {
+            PARALLEL_OPEN | BRACKET_OPEN => depth += 1,
+            PARALLEL_CLOSE | BRACKET_CLOSE => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        c == TOKEN_SEPARATOR && depth == 0
+    })
+}
+
+/// Returns the byte index of the first `target` that is not nested within a group.
+fn find_top_level(text: &str, target: char) -> Option<usize> {
+    let mut depth = 0_usize;
+    text.char_indices().find_map(|(i, c)| {
+        match c {
+            PARALLEL_OPEN | BRACKET_OPEN => depth += 1,
+            PARALLEL_CLOSE | BRACKET_CLOSE => depth = depth.saturating_sub(1),
+            _ if c == target && depth == 0 => return Some(i),
+            _ => {}
+        }
+        None
+    })
+}
+
+fn apply_step(step: &str, cube: &mut Cube, options: ParseOptions) -> Result<(), String> {
+    let side_length = cube.side_length();
+    let ops = parse_step(
+        step,
+        Reader {
+            side_length,
+            options,
+        },
+    )?;
+    ops.iter()
+        .flat_map(Op::rotations)
+        .try_for_each(|&rotation| {
+            if rotation.layer >= side_length {
+                return Err(format!(
+                    "Layer {} is not within a {side_length}x{side_length} cube: [{step}]",
+                    rotation.layer + 1
+                ));
+            }
+            cube.check_unlocked(rotation)
+        })?;
+    ops.iter().for_each(|op| op.apply_to(cube));
     Ok(())
 }
 
-fn get_base_token_if_valid(token: &str) -> Option<char> {
-    let is_valid_2_char_token = token.len() == 2
-        && (token.ends_with(CHAR_FOR_ANTICLOCKWISE) || token.ends_with(CHAR_FOR_TURN_TWICE));
+fn parse_step(step: &str, reader: Reader) -> Result<Vec<Op>, String> {
+    let unsupported = || format!("Unsupported token in notation string: [{step}]");
+    let Some((open, inner, suffix)) = split_group(step) else {
+        return Ok(rotations_for_token(step, reader)?
+            .into_iter()
+            .map(Op::Single)
+            .collect());
+    };
+
+    let too_long = || {
+        format!(
+            "Group expands to more than {MAX_EXPANDED_MOVES} moves in notation string: [{step}]"
+        )
+    };
+    let ops = if open == BRACKET_OPEN {
+        if let Some(i) = find_top_level(inner, CONJUGATE_SEPARATOR) {
+            let (a, b) = (
+                parse_steps(&inner[..i], reader)?,
+                parse_steps(&inner[i + 1..], reader)?,
+            );
+            check_expanded_len(2 * a.len() + b.len()).ok_or_else(too_long)?;
+            [a.clone(), b, inverted(&a)].concat()
+        } else if let Some(i) = find_top_level(inner, COMMUTATOR_SEPARATOR) {
+            let (a, b) = (
+                parse_steps(&inner[..i], reader)?,
+                parse_steps(&inner[i + 1..], reader)?,
+            );
+            check_expanded_len(2 * (a.len() + b.len())).ok_or_else(too_long)?;
+            [a.clone(), b.clone(), inverted(&a), inverted(&b)].concat()
+        } else {
+            return Err(unsupported());
+        }
+    } else if find_top_level(inner, PARALLEL_SEPARATOR).is_some() {
+        vec![Op::Parallel(parse_parallel(step, inner, reader)?)]
+    } else {
+        parse_steps(inner, reader)?
+    };
 
-    if token.len() == 1 || is_valid_2_char_token {
-        token.chars().next()
+    let uncounted = suffix.trim_start_matches(|c: char| c.is_ascii_digit());
+    let count = &suffix[..suffix.len() - uncounted.len()];
+    let repetitions = if count.is_empty() {
+        1
     } else {
+        count.parse::<usize>().map_err(|_| unsupported())?
+    };
+    let ops = match uncounted.strip_prefix(CHAR_FOR_ANTICLOCKWISE) {
+        None if uncounted.is_empty() => ops,
+        Some("") => inverted(&ops),
+        _ => return Err(unsupported()),
+    };
+    ops.len()
+        .checked_mul(repetitions)
+        .and_then(check_expanded_len)
+        .ok_or_else(too_long)?;
+    Ok((0..repetitions).flat_map(|_| ops.iter().cloned()).collect())
+}
+
+fn check_expanded_len(len: usize) -> Option<usize> {
+    (len <= MAX_EXPANDED_MOVES).then_some(len)
+}
+
+/// Splits a step such as `(R U)3'` into its opening character, what is inside the group, and the suffix after the group.
+fn split_group(step: &str) -> Option<(char, &str, &str)> {
+    let open = step
+        .chars()
+        .next()
+        .filter(|&c| c == PARALLEL_OPEN || c == BRACKET_OPEN)?;
+    let close = if open == PARALLEL_OPEN {
+        PARALLEL_CLOSE
+    } else {
+        BRACKET_CLOSE
+    };
+    let mut depth = 0_usize;
+    let (end, c) = step.char_indices().find(|&(_, c)| {
+        match c {
+            PARALLEL_OPEN | BRACKET_OPEN => depth += 1,
+            PARALLEL_CLOSE | BRACKET_CLOSE => depth -= 1,
+            _ => {}
+        }
+        depth == 0
+    })?;
+    (c == close).then(|| (open, &step[1..end], &step[end + 1..]))
+}
+
+fn parse_steps(sequence: &str, reader: Reader) -> Result<Vec<Op>, String> {
+    let mut ops = vec![];
+    for step in split_steps(sequence.trim()) {
+        ops.extend(parse_step(step.trim(), reader)?);
+        if check_expanded_len(ops.len()).is_none() {
+            return Err(format!(
+                "Group expands to more than {MAX_EXPANDED_MOVES} moves in notation string: [{sequence}]"
+            ));
+        }
+    }
+    Ok(ops)
+}
+
+fn parse_parallel(step: &str, group: &str, reader: Reader) -> Result<ParallelRotation, String> {
+    let members = group
+        .split(PARALLEL_SEPARATOR)
+        .map(|token| {
+            rotations_for_token(token.strip_prefix(TOKEN_SEPARATOR).unwrap_or(token), reader)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    ParallelRotation::new(members).map_err(|e| format!("{e} in notation string: [{step}]"))
+}
+
+fn inverted(ops: &[Op]) -> Vec<Op> {
+    ops.iter().rev().map(Op::inverted).collect()
+}
+
+fn rotations_for_token(token: &str, reader: Reader) -> Result<Vec<Rotation>, String> {
+    let side_length = reader.side_length;
+    let (face, layer, quarter_turns) = parse_token(token, reader.options)?;
+    let layers = match layer {
+        Layer::Face => 0..1,
+        Layer::Counted(layer) => layer..layer + 1,
+        Layer::Wide(depth) => 0..depth,
+        Layer::Middle if side_length % 2 == 1 => side_length / 2..side_length / 2 + 1,
+        Layer::Middle => {
+            return Err(format!(
+                "Slice moves need a middle layer, which a {side_length}x{side_length} cube does not have: [{token}]"
+            ))
+        }
+    };
+    let turn = layers
+        .map(|layer| Rotation::clockwise(face).at_layer(layer))
+        .collect::<Vec<_>>();
+
+    Ok(match quarter_turns {
+        0 => vec![],
+        1 => turn,
+        2 => [turn.clone(), turn].concat(),
+        _ => turn.into_iter().map(Rotation::inverse).collect(),
+    })
+}
+
+/// Which layer a token turns, relative to the face it names.
+enum Layer {
+    Face,
+    /// A layer given by its number counted in from the face, such as the 2 in `2R`, stored as a rotation's layer.
+    Counted(usize),
+    /// How many layers a wide move turns together, counted in from the face, such as the 3 in `3Rw`.
+    Wide(usize),
+    Middle,
+}
+
+/// What a step is read against: the side length that slice moves find the middle layer of, and the options chosen by the caller.
+#[derive(Clone, Copy)]
+struct Reader {
+    side_length: usize,
+    options: ParseOptions,
+}
+
+impl Default for Reader {
+    fn default() -> Self {
+        Self {
+            side_length: NOTATION_SIDE_LENGTH,
+            options: ParseOptions::default(),
+        }
+    }
+}
+
+/// Reads a token as the face it turns, which layer, and how many quarter turns clockwise from 0 to 3.
+///
+/// Any number of turns may be given, such as the 3 in `R3`, and is normalised modulo 4 before the anticlockwise modifier reverses it.
+fn parse_token(token: &str, options: ParseOptions) -> Result<(Face, Layer, usize), String> {
+    let unsupported = || format!("Unsupported token in notation string: [{token}]");
+    let grammar = grammar();
+    let unnumbered = token.trim_start_matches(|c: char| c.is_ascii_digit());
+    let layer_number = &token[..token.len() - unnumbered.len()];
+    let number = if layer_number.is_empty() {
         None
+    } else {
+        Some(
+            layer_number
+                .parse::<usize>()
+                .ok()
+                .filter(|&number| number > 0)
+                .ok_or_else(unsupported)?,
+        )
+    };
+    let mut chars = unnumbered.chars();
+
+    let c = chars.next().ok_or_else(unsupported)?;
+    let (face, layer) = if let Some(face) = grammar.face_for(c) {
+        if chars.as_str().starts_with(WIDE_MARKER) {
+            chars.next();
+            (face, Layer::Wide(number.unwrap_or(DEFAULT_WIDE_DEPTH)))
+        } else {
+            (
+                face,
+                number.map_or(Layer::Face, |number| Layer::Counted(number - 1)),
+            )
+        }
+    } else if let Some(face) = grammar
+        .wide_face_for(c)
+        .filter(|_| options.lowercase_wide_moves)
+    {
+        (face, Layer::Wide(number.unwrap_or(DEFAULT_WIDE_DEPTH)))
+    } else if let Some(face) = grammar.slice_for(c).filter(|_| number.is_none()) {
+        (face, Layer::Middle)
+    } else {
+        return Err(unsupported());
+    };
+    if let Layer::Wide(depth) = layer {
+        check_expanded_len(depth).ok_or_else(|| {
+            format!("Wide moves may turn at most {MAX_EXPANDED_MOVES} layers: [{token}]")
+        })?;
     }
+
+    let rest = chars.as_str();
+    let uncounted = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    let count = &rest[..rest.len() - uncounted.len()];
+    let quarter_turns = if count.is_empty() {
+        1
+    } else {
+        count.parse::<usize>().map_err(|_| unsupported())? % 4
+    };
+    let mut chars = uncounted.chars();
+    let quarter_turns = match chars.next().map(|c| grammar.modifier_for(c)) {
+        None => quarter_turns,
+        Some(Some(Modifier::Anticlockwise)) => (4 - quarter_turns) % 4,
+        Some(_) => return Err(unsupported()),
+    };
+    if chars.next().is_some() {
+        return Err(unsupported());
+    }
+
+    Ok((face, layer, quarter_turns))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::cube::cubie_face::CubieFace;
-    use crate::{create_cube_from_sides, create_cube_side};
+    use crate::{create_cube_from_sides, create_cube_side, test_support::seeded_rng};
 
     use super::*;
     use pretty_assertions::assert_eq;
 
     #[test]
     #[should_panic]
-    fn test_apply_token_invalid_input() {
-        let invalid_token = "M";
+    fn test_apply_step_invalid_input() {
+        let invalid_token = "G";
         let mut cube = Cube::create(3);
-        apply_token(invalid_token, &mut cube).unwrap();
+        apply_step(invalid_token, &mut cube, ParseOptions::default()).unwrap();
     }
 
     macro_rules! test_invalid_token {
@@ -100,26 +603,25 @@ mod tests {
     }
 
     test_invalid_token!(
-        test_invalid_token_f_0: "F0",
-        test_invalid_token_f_1: "F1",
-        test_invalid_token_f_1_prime: "F1'",
-        test_invalid_token_f_2_prime: "F2'",
         test_invalid_token_f_prime_1: "F'1",
         test_invalid_token_f_prime_2: "F'2",
-        test_invalid_token_f_3: "F3",
+        test_invalid_token_f_prime_prime: "F''",
+        test_invalid_token_f_2_2_2: "F2'2",
         test_invalid_token_f_f: "FF",
         test_invalid_token_f_f_1: "FF1",
         test_invalid_token_f_f_2: "FF2",
-        test_invalid_token_f_2_2: "F22",
         test_invalid_token_1: "1",
         test_invalid_token_2: "2",
         test_invalid_token_3: "3",
+        test_invalid_token_0_r: "0R",
+        test_invalid_token_2_m: "2M",
+        test_invalid_token_r_2_r: "R2R",
     );
 
     test_invalid_sequence!(
         test_invalid_sequence_too_many_spaces: "F  R U", "",
         test_invalid_sequence_not_enough_spaces: "FR U", "FR",
-        test_invalid_sequence_multiple_individual_tokens: "F2' R'' UU", "F2'",
+        test_invalid_sequence_multiple_individual_tokens: "F2' R'' UU", "R''",
         test_invalid_sequence_invalid_single_char_token: "F2 R G U", "G",
         test_invalid_sequence_invalid_multi_char_token: "F2 R@ U", "R@",
     );
@@ -184,4 +686,512 @@ mod tests {
 
         assert_eq!(expected_cube, cube_under_test);
     }
+
+    #[test]
+    fn test_every_grammar_token_is_accepted() {
+        let grammar = grammar();
+        let mut cube = Cube::create(3);
+
+        for (face_char, _) in grammar.faces {
+            apply_step(&face_char.to_string(), &mut cube, ParseOptions::default()).unwrap();
+            for (modifier_char, _) in grammar.modifiers {
+                apply_step(
+                    &format!("{face_char}{modifier_char}"),
+                    &mut cube,
+                    ParseOptions::default(),
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_sequence() {
+        assert_eq!(
+            Ok(vec![
+                Rotation::clockwise(Face::Front),
+                Rotation::clockwise(Face::Front),
+                Rotation::anticlockwise(Face::Up),
+                Rotation::clockwise(Face::Left),
+            ]),
+            parse_sequence("F2 U' L")
+        );
+    }
+
+    #[test]
+    fn test_turn_counts_are_normalised() {
+        let r = Rotation::clockwise(Face::Right);
+        let cases = [
+            ("R3", vec![r.inverse()]),
+            ("R2'", vec![r, r]),
+            ("R3'", vec![r]),
+            ("R1'", vec![r.inverse()]),
+            ("R4", vec![]),
+            ("R0", vec![]),
+            ("R5", vec![r]),
+            ("R14", vec![r, r]),
+            ("2R3", vec![r.at_layer(1).inverse()]),
+        ];
+
+        for (token, expected) in cases {
+            assert_eq!(Ok(expected), parse_sequence(token), "{token}");
+        }
+    }
+
+    #[test]
+    fn test_wide_moves() {
+        let r = Rotation::clockwise(Face::Right);
+        let rw = vec![r, r.at_layer(1)];
+
+        assert_eq!(Ok(rw.clone()), parse_sequence("Rw"));
+        assert_eq!(Ok(rw.clone()), parse_sequence("r"));
+        assert_eq!(Ok(rw.clone()), parse_sequence("2Rw"));
+        assert_eq!(Ok([rw.clone(), rw].concat()), parse_sequence("r2"));
+        assert_eq!(
+            Ok(vec![
+                r.inverse(),
+                r.at_layer(1).inverse(),
+                r.at_layer(2).inverse()
+            ]),
+            parse_sequence("3Rw'")
+        );
+    }
+
+    #[test]
+    fn test_wide_moves_on_a_cube() {
+        let mut wide = Cube::create(4);
+        let mut layered = Cube::create(4);
+
+        perform_3x3_sequence("Uw 3Fw' l2", &mut wide).unwrap();
+        perform_3x3_sequence("U 2U F' 2F' 3F' L2 2L2", &mut layered).unwrap();
+
+        assert_eq!(layered, wide);
+        assert_eq!(
+            Err("Layer 4 is not within a 3x3 cube: [4Rw]".to_string()),
+            perform_3x3_sequence("4Rw", &mut Cube::create(3))
+        );
+    }
+
+    #[test]
+    fn test_strict_options_reject_lowercase() {
+        let mut cube = Cube::create(3);
+
+        assert_eq!(
+            Err("Unsupported token in notation string: [u]".to_string()),
+            parse_sequence_with("Rw u", ParseOptions::strict()).map_err(|e| e.to_string())
+        );
+        assert!(perform_3x3_sequence_with("r", &mut cube, ParseOptions::strict()).is_err());
+        assert!(perform_3x3_sequence_with("Rw", &mut cube, ParseOptions::strict()).is_ok());
+        assert!(parse_sequence("Mw").is_err());
+        assert!(parse_sequence("m").is_err());
+    }
+
+    #[test]
+    fn test_parse_sequence_invalid() {
+        assert_eq!(
+            Err(ParseError {
+                token_index: 1,
+                span: 2..3,
+                message: "Unsupported token in notation string: [Q]".to_string()
+            }),
+            parse_sequence("F Q")
+        );
+    }
+
+    #[test]
+    fn test_parse_error_spans_whole_step() {
+        let error = parse_ops("  R U (R U)x (R, L)").unwrap_err();
+
+        assert_eq!(2, error.token_index);
+        assert_eq!("(R U)x", &"  R U (R U)x (R, L)"[error.span]);
+    }
+
+    #[test]
+    fn test_tokenise_valid_sequence() {
+        let spans = tokenise("F R' U2");
+
+        assert_eq!(
+            vec![
+                TokenSpan {
+                    range: 0..1,
+                    error: None
+                },
+                TokenSpan {
+                    range: 2..4,
+                    error: None
+                },
+                TokenSpan {
+                    range: 5..7,
+                    error: None
+                },
+            ],
+            spans
+        );
+    }
+
+    #[test]
+    fn test_tokenise_reports_every_invalid_token() {
+        let spans = tokenise("  F x R3x");
+
+        assert_eq!(
+            vec![
+                TokenSpan {
+                    range: 2..3,
+                    error: None
+                },
+                TokenSpan {
+                    range: 4..5,
+                    error: Some("Unsupported token in notation string: [x]".to_string())
+                },
+                TokenSpan {
+                    range: 6..9,
+                    error: Some("Unsupported token in notation string: [R3x]".to_string())
+                },
+            ],
+            spans
+        );
+    }
+
+    #[test]
+    fn test_tokenise_agrees_with_perform_3x3_sequence() {
+        let sequence = "F2 R@ U";
+        let first_error = tokenise(sequence).into_iter().find_map(|span| span.error);
+
+        assert_eq!(
+            first_error.map(Err),
+            Some(perform_3x3_sequence(sequence, &mut Cube::create(3)))
+        );
+    }
+
+    #[test]
+    fn test_parse_ops_keeps_parallel_groups() {
+        let parallel = ParallelRotation::new(vec![
+            vec![Rotation::clockwise(Face::Right)],
+            vec![Rotation::anticlockwise(Face::Left)],
+        ])
+        .unwrap();
+
+        assert_eq!(
+            Ok(Sequence::from_iter([
+                Op::Single(Rotation::clockwise(Face::Front)),
+                Op::Parallel(parallel),
+                Op::Single(Rotation::clockwise(Face::Up)),
+            ])),
+            parse_ops("F (R, L') U")
+        );
+    }
+
+    #[test]
+    fn test_parallel_group_performs_each_face() {
+        let mut parallel = Cube::create(3);
+        let mut sequential = Cube::create(3);
+
+        perform_3x3_sequence("F (U2,D') R", &mut parallel).unwrap();
+        perform_3x3_sequence("F U2 D' R", &mut sequential).unwrap();
+
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn test_invalid_parallel_groups() {
+        assert_eq!(
+            Err(
+                "Only opposite faces can be turned in parallel, not Right and Up in notation string: [(R, U)]"
+                    .to_string()
+            ),
+            parse_sequence("F (R, U)").map_err(|e| e.to_string())
+        );
+        assert_eq!(
+            Err("Unsupported token in notation string: [Q]".to_string()),
+            parse_sequence("(R, Q)").map_err(|e| e.to_string())
+        );
+        assert!(parse_sequence("(R, L").is_err());
+        assert!(parse_sequence("(R, L]").is_err());
+    }
+
+    #[test]
+    fn test_repeated_groups() {
+        assert_eq!(
+            parse_sequence("R U R' U' R U R' U' R U R' U'"),
+            parse_sequence("(R U R' U')3")
+        );
+        assert_eq!(parse_sequence("U R' F'"), parse_sequence("(F R U')'"));
+        assert_eq!(parse_sequence("U R' U R'"), parse_sequence("(R U')2'"));
+        assert_eq!(parse_sequence("R L'"), parse_sequence("((R, L'))"));
+        assert_eq!(parse_sequence("R"), parse_sequence("(R)"));
+        assert_eq!(Ok(vec![]), parse_sequence("(R U)0"));
+    }
+
+    #[test]
+    fn test_nested_repeats_are_capped() {
+        let sequence = "(((R U)1000)1000)100";
+        let spans = tokenise(sequence);
+
+        assert_eq!(1, spans.len());
+        assert_eq!(
+            Some(format!(
+                "Group expands to more than {MAX_EXPANDED_MOVES} moves in notation string: [((R U)1000)1000]"
+            )),
+            spans[0].error
+        );
+        assert!(parse_sequence(sequence).is_err());
+        assert!(parse_sequence(&format!("[{sequence}, R]")).is_err());
+        assert!(parse_sequence(&format!("(R){}", usize::MAX)).is_err());
+        assert!(parse_sequence("(R)60000 (U)60000").is_err());
+        assert_eq!(
+            Ok(MAX_EXPANDED_MOVES),
+            parse_sequence(&format!("(R){MAX_EXPANDED_MOVES}")).map(|rotations| rotations.len())
+        );
+    }
+
+    #[test]
+    fn test_deep_wide_moves_are_capped() {
+        let error = "Wide moves may turn at most 100000 layers: [999999999Rw]".to_string();
+
+        assert_eq!(
+            Err(error.clone()),
+            parse_sequence("999999999Rw").map_err(|e| e.message)
+        );
+        assert_eq!(Some(error), tokenise("999999999Rw").remove(0).error);
+        assert!(parse_sequence("U 999999999r'").is_err());
+        assert_eq!(
+            Ok(MAX_EXPANDED_MOVES),
+            parse_sequence(&format!("{MAX_EXPANDED_MOVES}Rw")).map(|rotations| rotations.len())
+        );
+    }
+
+    #[test]
+    fn test_commutators_and_conjugates() {
+        assert_eq!(parse_sequence("R U R' U'"), parse_sequence("[R, U]"));
+        assert_eq!(parse_sequence("F R U R' F'"), parse_sequence("[F: R U R']"));
+        assert_eq!(
+            parse_sequence("R U R' U' F2 U R U' R' F' F'"),
+            parse_sequence("[R U R' U', F2]")
+        );
+        assert_eq!(
+            parse_sequence("F R U R' U' F'"),
+            parse_sequence("[F: [R, U]]")
+        );
+        assert_eq!(
+            parse_sequence("U R U' R' U R U' R'"),
+            parse_sequence("[U, R]2")
+        );
+
+        let mut conjugated = Cube::create(3);
+        let mut expanded = Cube::create(3);
+        perform_3x3_sequence("[(R, L'): F]", &mut conjugated).unwrap();
+        perform_3x3_sequence("R L' F R' L", &mut expanded).unwrap();
+        assert_eq!(expanded, conjugated);
+    }
+
+    #[test]
+    fn test_invalid_groups() {
+        for sequence in ["[R U]", "[R, U", "(R U)x", "(R U)'2", "[R, U]''"] {
+            assert_eq!(
+                Err(format!(
+                    "Unsupported token in notation string: [{sequence}]"
+                )),
+                parse_sequence(sequence).map_err(|e| e.to_string()),
+                "{sequence}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_tokenise_bracketed_step_is_one_token() {
+        let spans = tokenise("[R, U] (R U R' U')3 [F: R U R']");
+
+        assert_eq!(
+            vec![0..6, 7..19, 20..31],
+            spans
+                .iter()
+                .map(|span| span.range.clone())
+                .collect::<Vec<_>>()
+        );
+        assert!(spans.iter().all(|span| span.error.is_none()));
+    }
+
+    #[test]
+    fn test_tokenise_parallel_group_is_one_token() {
+        let spans = tokenise("F (R, L') (U, R)");
+
+        assert_eq!(
+            vec![0..1, 2..9, 10..16],
+            spans
+                .iter()
+                .map(|span| span.range.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![false, false, true],
+            spans
+                .iter()
+                .map(|span| span.error.is_some())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_format_sequence_round_trips() {
+        let rotations = parse_sequence("F2 F R' R' U D2 B L'").unwrap();
+
+        let formatted = format_sequence(&rotations);
+
+        assert_eq!("F2 F R' R' U D2 B L'", formatted);
+        assert_eq!(Ok(rotations), parse_sequence(&formatted));
+    }
+
+    #[test]
+    fn test_slice_moves_on_3x3() {
+        let mut m = Cube::create(3);
+        perform_3x3_sequence("M", &mut m).unwrap();
+        assert_eq!(
+            "UBUUBUUBURRRRRRRRRFUFFUFFUFDFDDFDDFDLLLLLLLLLBDBBDBBDB",
+            m.to_facelet_string()
+        );
+
+        let mut e = Cube::create(3);
+        perform_3x3_sequence("E", &mut e).unwrap();
+        assert_eq!(
+            "UUUUUUUUURRRFFFRRRFFFLLLFFFDDDDDDDDDLLLBBBLLLBBBRRRBBB",
+            e.to_facelet_string()
+        );
+
+        let mut s = Cube::create(3);
+        perform_3x3_sequence("S S'", &mut s).unwrap();
+        assert_eq!(Cube::create(3), s);
+    }
+
+    #[test]
+    fn test_slice_moves_turn_middle_layer_of_odd_cubes() {
+        let mut cube_under_test = Cube::create(5);
+        let mut control_cube = Cube::create(5);
+
+        perform_3x3_sequence("M E' S2", &mut cube_under_test).unwrap();
+
+        control_cube.rotate(Rotation::clockwise(Face::Left).at_layer(2));
+        control_cube.rotate(Rotation::anticlockwise(Face::Down).at_layer(2));
+        control_cube.rotate(Rotation::clockwise(Face::Front).at_layer(2));
+        control_cube.rotate(Rotation::clockwise(Face::Front).at_layer(2));
+
+        assert_eq!(control_cube, cube_under_test);
+    }
+
+    #[test]
+    fn test_slice_moves_on_even_cubes() {
+        let mut cube = Cube::create(4);
+
+        assert_eq!(
+            Err(
+                "Slice moves need a middle layer, which a 4x4 cube does not have: [M2]".to_string()
+            ),
+            perform_3x3_sequence("R M2", &mut cube)
+        );
+    }
+
+    #[test]
+    fn test_locked_layer_stops_sequence_before_its_step() {
+        let mut cube = Cube::create(3);
+        cube.lock_layer(Face::Right, 1);
+
+        assert_eq!(
+            Err("Layer 1 from the Left face is locked, so it cannot be turned".to_string()),
+            perform_3x3_sequence("U M2 R", &mut cube)
+        );
+
+        let mut control_cube = Cube::create(3);
+        control_cube.rotate(Rotation::clockwise(Face::Up));
+        control_cube.lock_layer(Face::Right, 1);
+        assert_eq!(control_cube, cube);
+    }
+
+    #[test]
+    fn test_parse_slice_moves_as_3x3() {
+        assert_eq!(
+            Ok(vec![
+                Rotation::clockwise(Face::Left).at_layer(1),
+                Rotation::anticlockwise(Face::Down).at_layer(1),
+            ]),
+            parse_sequence("M E'")
+        );
+        assert!(tokenise("(M, R) S2")
+            .iter()
+            .all(|span| span.error.is_none()));
+    }
+
+    #[test]
+    fn test_format_slice_moves() {
+        let rotations = parse_sequence("M E' S2 M'").unwrap();
+
+        assert_eq!("M E' S2 M'", format_sequence(&rotations));
+        assert_eq!(
+            "M' E S'",
+            format_sequence(&[
+                Rotation::clockwise(Face::Right).at_layer(1),
+                Rotation::anticlockwise(Face::Up).at_layer(1),
+                Rotation::clockwise(Face::Back).at_layer(1),
+            ])
+        );
+        assert_eq!(
+            "3R 4U'",
+            format_sequence(&[
+                Rotation::clockwise(Face::Right).at_layer(2),
+                Rotation::anticlockwise(Face::Up).at_layer(3),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_format_empty_sequence() {
+        assert_eq!("", format_sequence(&[]));
+        assert_eq!("", to_string(&[]));
+    }
+
+    #[test]
+    fn test_numbered_layers() {
+        let mut cube_under_test = Cube::create(5);
+        perform_3x3_sequence("2R 3U' 5F2", &mut cube_under_test).unwrap();
+
+        let mut control_cube = Cube::create(5);
+        control_cube.rotate(Rotation::clockwise(Face::Right).at_layer(1));
+        control_cube.rotate(Rotation::anticlockwise(Face::Up).at_layer(2));
+        control_cube.rotate(Rotation::clockwise(Face::Front).at_layer(4));
+        control_cube.rotate(Rotation::clockwise(Face::Front).at_layer(4));
+
+        assert_eq!(control_cube, cube_under_test);
+        assert_eq!(
+            Ok(vec![Rotation::clockwise(Face::Front)]),
+            parse_sequence("1F")
+        );
+    }
+
+    #[test]
+    fn test_numbered_layer_outside_cube() {
+        let mut cube = Cube::create(3);
+
+        assert_eq!(
+            Err("Layer 4 is not within a 3x3 cube: [4R']".to_string()),
+            perform_3x3_sequence("R 4R'", &mut cube)
+        );
+    }
+
+    #[test]
+    fn test_to_string_round_trips() {
+        let mut rng = seeded_rng(0x1b87_3593);
+        for length in 0..200 {
+            let rotations = (0..=length % 30)
+                .map(|_| Rotation::random_with_rng(12, &mut rng))
+                .collect::<Vec<_>>();
+
+            let written = to_string(&rotations);
+            assert_eq!(Ok(rotations), parse_sequence(&written), "{written}");
+        }
+    }
+
+    #[test]
+    fn test_to_string_keeps_layer_1_relative_to_its_face() {
+        let rotations = parse_sequence("M E' S2 2R'").unwrap();
+
+        assert_eq!("2L 2D' 2F2 2R'", to_string(&rotations));
+    }
 }
@@ -0,0 +1,121 @@
+use crate::cube::{face::Face, rotation::Rotation};
+
+/// The faces whose layers are turned for patterns that use every axis of the cube, one face per axis.
+const ONE_FACE_PER_AXIS: [Face; 3] = [Face::Right, Face::Front, Face::Up];
+
+/// The rotations that turn a solved cube with an odd side length into a checkerboard on every face, made of half turns of every other layer on each axis.
+/// ```
+/// # use rusty_puzzle_cube::{cube::Cube, known_transforms::checkerboard_corners, patterns};
+/// let mut cube = Cube::create(3);
+/// patterns::checkerboard(3).unwrap().into_iter().for_each(|rotation| cube.rotate(rotation));
+///
+/// let mut control_cube = Cube::create(3);
+/// checkerboard_corners(&mut control_cube);
+/// assert_eq!(control_cube, cube);
+/// ```
+/// # Errors
+/// Will return an Err variant when the side length is even, as the same half turns would then leave stripes on some faces
+pub fn checkerboard(side_length: usize) -> Result<Vec<Rotation>, String> {
+    if side_length % 2 == 1 {
+        Ok(half_turns(&ONE_FACE_PER_AXIS, (0..side_length).step_by(2)))
+    } else {
+        Err(format!(
+            "A {side_length}x{side_length} cube can not be made into a checkerboard with half turns of every other layer, as its side length is even"
+        ))
+    }
+}
+
+/// The rotations that turn a solved cube into stripes around the four faces between Left and Right, made of half turns of every other layer from the Right face.
+#[must_use]
+pub fn stripes(side_length: usize) -> Vec<Rotation> {
+    half_turns(&[Face::Right], (0..side_length).step_by(2))
+}
+
+/// The rotations that turn a solved cube into one where every face has a frame of the opposite colour, broken only at the corners, made of half turns of every inner layer on each axis.
+///
+/// Cubes smaller than 3x3 have no inner layers, so no rotations are needed.
+#[must_use]
+pub fn frames(side_length: usize) -> Vec<Rotation> {
+    half_turns(&ONE_FACE_PER_AXIS, 1..side_length.saturating_sub(1))
+}
+
+fn half_turns(faces: &[Face], layers: impl Iterator<Item = usize> + Clone) -> Vec<Rotation> {
+    faces
+        .iter()
+        .flat_map(|&face| {
+            layers.clone().flat_map(move |layer| {
+                let rotation = Rotation::clockwise(face).at_layer(layer);
+                [rotation, rotation]
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::Cube;
+    use pretty_assertions::assert_eq;
+
+    fn apply(rotations: &[Rotation], side_length: usize) -> Cube {
+        let mut cube = Cube::create(side_length);
+        rotations.iter().for_each(|&rotation| cube.rotate(rotation));
+        cube
+    }
+
+    fn faces(cube: &Cube) -> Vec<String> {
+        let n = cube.side_length();
+        cube.to_facelet_string()
+            .as_bytes()
+            .chunks(n * n)
+            .map(|face| String::from_utf8_lossy(face).into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_checkerboard_on_5x5() {
+        let cube = apply(&checkerboard(5).unwrap(), 5);
+
+        assert_eq!(
+            vec![
+                "UDUDUDUDUDUDUDUDUDUDUDUDU",
+                "RLRLRLRLRLRLRLRLRLRLRLRLR",
+                "FBFBFBFBFBFBFBFBFBFBFBFBF",
+                "DUDUDUDUDUDUDUDUDUDUDUDUD",
+                "LRLRLRLRLRLRLRLRLRLRLRLRL",
+                "BFBFBFBFBFBFBFBFBFBFBFBFB",
+            ],
+            faces(&cube)
+        );
+    }
+
+    #[test]
+    fn test_checkerboard_on_even_cube() {
+        assert!(checkerboard(4).is_err());
+    }
+
+    #[test]
+    fn test_stripes_on_4x4() {
+        let cube = apply(&stripes(4), 4);
+
+        assert_eq!(
+            vec![
+                "UDUDUDUDUDUDUDUD",
+                "RRRRRRRRRRRRRRRR",
+                "FBFBFBFBFBFBFBFB",
+                "DUDUDUDUDUDUDUDU",
+                "LLLLLLLLLLLLLLLL",
+                "FBFBFBFBFBFBFBFB",
+            ],
+            faces(&cube)
+        );
+    }
+
+    #[test]
+    fn test_frames_on_5x5() {
+        let cube = apply(&frames(5), 5);
+
+        assert_eq!("UDDDUDUUUDDUUUDDUUUDUDDDU", faces(&cube)[0]);
+        assert!(frames(2).is_empty());
+    }
+}
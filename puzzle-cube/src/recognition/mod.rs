@@ -0,0 +1,524 @@
+use std::{fmt, mem, ops::Range};
+
+use crate::{
+    cube::{cubie_face::CubieFace, face::Face, rotation::Rotation, Cube},
+    notation::parse_sequence,
+};
+
+const RECOGNITION_SIDE_LENGTH: usize = 3;
+const SIDE_FACES: [Face; 4] = [Face::Front, Face::Right, Face::Back, Face::Left];
+
+/// One of the 57 cases that can be left by the last layer of a 3x3x3 cube when its stickers are not all facing up, numbered as usual.
+///
+/// Cases that are mirror images of each other are numbered separately, but cases that only differ by a turn of the Up face (AUF) are the same case.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OllCase {
+    Oll1,
+    Oll2,
+    Oll3,
+    Oll4,
+    Oll5,
+    Oll6,
+    Oll7,
+    Oll8,
+    Oll9,
+    Oll10,
+    Oll11,
+    Oll12,
+    Oll13,
+    Oll14,
+    Oll15,
+    Oll16,
+    Oll17,
+    Oll18,
+    Oll19,
+    Oll20,
+    Oll21,
+    Oll22,
+    Oll23,
+    Oll24,
+    Oll25,
+    Oll26,
+    Oll27,
+    Oll28,
+    Oll29,
+    Oll30,
+    Oll31,
+    Oll32,
+    Oll33,
+    Oll34,
+    Oll35,
+    Oll36,
+    Oll37,
+    Oll38,
+    Oll39,
+    Oll40,
+    Oll41,
+    Oll42,
+    Oll43,
+    Oll44,
+    Oll45,
+    Oll46,
+    Oll47,
+    Oll48,
+    Oll49,
+    Oll50,
+    Oll51,
+    Oll52,
+    Oll53,
+    Oll54,
+    Oll55,
+    Oll56,
+    Oll57,
+}
+
+impl OllCase {
+    /// Every OLL case, in order of their numbers.
+    pub const ALL: [OllCase; 57] = {
+        use OllCase as O;
+        [
+            O::Oll1,
+            O::Oll2,
+            O::Oll3,
+            O::Oll4,
+            O::Oll5,
+            O::Oll6,
+            O::Oll7,
+            O::Oll8,
+            O::Oll9,
+            O::Oll10,
+            O::Oll11,
+            O::Oll12,
+            O::Oll13,
+            O::Oll14,
+            O::Oll15,
+            O::Oll16,
+            O::Oll17,
+            O::Oll18,
+            O::Oll19,
+            O::Oll20,
+            O::Oll21,
+            O::Oll22,
+            O::Oll23,
+            O::Oll24,
+            O::Oll25,
+            O::Oll26,
+            O::Oll27,
+            O::Oll28,
+            O::Oll29,
+            O::Oll30,
+            O::Oll31,
+            O::Oll32,
+            O::Oll33,
+            O::Oll34,
+            O::Oll35,
+            O::Oll36,
+            O::Oll37,
+            O::Oll38,
+            O::Oll39,
+            O::Oll40,
+            O::Oll41,
+            O::Oll42,
+            O::Oll43,
+            O::Oll44,
+            O::Oll45,
+            O::Oll46,
+            O::Oll47,
+            O::Oll48,
+            O::Oll49,
+            O::Oll50,
+            O::Oll51,
+            O::Oll52,
+            O::Oll53,
+            O::Oll54,
+            O::Oll55,
+            O::Oll56,
+            O::Oll57,
+        ]
+    };
+
+    /// Returns the number this case is known by, from 1 to 57.
+    #[must_use]
+    pub fn number(self) -> usize {
+        self as usize + 1
+    }
+
+    /// Returns an algorithm that orients the last layer in this case, written in notation.
+    #[must_use]
+    pub fn algorithm(self) -> &'static str {
+        OLL_ALGORITHMS[self as usize]
+    }
+}
+
+impl fmt::Display for OllCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OLL {}", self.number())
+    }
+}
+
+/// One of the 21 cases that can be left by the last layer of a 3x3x3 cube once its stickers all face up, named as usual.
+///
+/// Cases that only differ by a turn of the Up face (AUF), before or after the algorithm, are the same case.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PllCase {
+    Aa,
+    Ab,
+    E,
+    F,
+    Ga,
+    Gb,
+    Gc,
+    Gd,
+    H,
+    Ja,
+    Jb,
+    Na,
+    Nb,
+    Ra,
+    Rb,
+    T,
+    Ua,
+    Ub,
+    V,
+    Y,
+    Z,
+}
+
+impl PllCase {
+    /// Every PLL case, in order of their names.
+    pub const ALL: [PllCase; 21] = {
+        use PllCase as P;
+        [
+            P::Aa,
+            P::Ab,
+            P::E,
+            P::F,
+            P::Ga,
+            P::Gb,
+            P::Gc,
+            P::Gd,
+            P::H,
+            P::Ja,
+            P::Jb,
+            P::Na,
+            P::Nb,
+            P::Ra,
+            P::Rb,
+            P::T,
+            P::Ua,
+            P::Ub,
+            P::V,
+            P::Y,
+            P::Z,
+        ]
+    };
+
+    /// Returns an algorithm that solves the last layer in this case, up to a final turn of the Up face, written in notation.
+    #[must_use]
+    pub fn algorithm(self) -> &'static str {
+        PLL_ALGORITHMS[self as usize]
+    }
+}
+
+impl fmt::Display for PllCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}-perm")
+    }
+}
+
+/// Identify which OLL case the last layer of a 3x3x3 cube is in, holding the cube with the last layer on the Up face, or `None` if its stickers already all face up.
+///
+/// Colours are read relative to the centres, so any colour scheme can be recognised.
+/// ```
+/// # use rusty_puzzle_cube::{cube::Cube, notation::perform_3x3_sequence, recognition::{recognise_oll, OllCase}};
+/// let mut cube = Cube::create(3);
+/// perform_3x3_sequence("R U2 R' U' R U' R'", &mut cube).unwrap();
+/// assert_eq!(Ok(Some(OllCase::Oll27)), recognise_oll(&cube));
+/// assert_eq!(Ok(None), recognise_oll(&Cube::create(3)));
+/// ```
+/// # Errors
+/// Will return an Err variant when the cube is not 3x3x3, or its first two layers are not solved
+pub fn recognise_oll(cube: &Cube) -> Result<Option<OllCase>, String> {
+    check_first_two_layers(cube)?;
+    if is_last_layer_oriented(cube) {
+        return Ok(None);
+    }
+    let key = oll_key(cube);
+    let case = OllCase::ALL.into_iter().find(|case| {
+        up_turns(&set_up(case.algorithm()))
+            .into_iter()
+            .any(|reference| oll_key(&reference) == key)
+    });
+    Ok(case)
+}
+
+/// Identify which PLL case the last layer of a 3x3x3 cube is in, holding the cube with the last layer on the Up face, or `None` if it is already solved, allowing a turn of the Up face.
+///
+/// Colours are read relative to the centres, so any colour scheme can be recognised.
+/// ```
+/// # use rusty_puzzle_cube::{cube::Cube, notation::perform_3x3_sequence, recognition::{recognise_pll, PllCase}};
+/// let mut cube = Cube::create(3);
+/// perform_3x3_sequence("U R U R' U' R' F R2 U' R' U' R U R' F'", &mut cube).unwrap();
+/// assert_eq!(Ok(Some(PllCase::T)), recognise_pll(&cube));
+/// assert_eq!("T-perm", PllCase::T.to_string());
+/// ```
+/// # Errors
+/// Will return an Err variant when the cube is not 3x3x3, its first two layers are not solved, or its last layer is not oriented
+pub fn recognise_pll(cube: &Cube) -> Result<Option<PllCase>, String> {
+    check_first_two_layers(cube)?;
+    if !is_last_layer_oriented(cube) {
+        return Err("The last layer must be oriented to recognise its permutation".to_string());
+    }
+    let keys = whole_cube_turns(cube)
+        .iter()
+        .flat_map(up_turns)
+        .map(|cube| pll_key(&cube))
+        .collect::<Vec<_>>();
+    if keys.contains(&pll_key(&Cube::create(RECOGNITION_SIDE_LENGTH))) {
+        return Ok(None);
+    }
+    let case = PllCase::ALL
+        .into_iter()
+        .find(|case| keys.contains(&pll_key(&set_up(case.algorithm()))));
+    Ok(case)
+}
+
+fn check_first_two_layers(cube: &Cube) -> Result<(), String> {
+    let n = cube.side_length();
+    if n != RECOGNITION_SIDE_LENGTH {
+        return Err(format!(
+            "Recognition needs a 3x3x3 cube, not a {n}x{n} cube"
+        ));
+    }
+    let down = cube.oriented_side(Face::Down);
+    let is_solved = down
+        .cells()
+        .iter()
+        .all(|&cubie| same_colour(cubie, down[1][1]))
+        && SIDE_FACES.into_iter().all(|face| {
+            let side = cube.oriented_side(face);
+            side.cells()[n..]
+                .iter()
+                .all(|&cubie| same_colour(cubie, side[1][1]))
+        });
+    if is_solved {
+        Ok(())
+    } else {
+        Err("The first two layers must be solved to recognise the last layer".to_string())
+    }
+}
+
+fn is_last_layer_oriented(cube: &Cube) -> bool {
+    let up = cube.oriented_side(Face::Up);
+    up.cells().iter().all(|&cubie| same_colour(cubie, up[1][1]))
+}
+
+/// Which stickers of the last layer, on the Up face then the top rows of the sides, have the Up colour.
+fn oll_key(cube: &Cube) -> Vec<bool> {
+    let up_centre = cube.oriented_side(Face::Up)[1][1];
+    last_layer(cube)
+        .map(|cubie| same_colour(cubie, up_centre))
+        .collect()
+}
+
+/// The face whose centre has the colour of each sticker on the top rows of the sides.
+fn pll_key(cube: &Cube) -> Vec<Option<Face>> {
+    last_layer(cube)
+        .skip(RECOGNITION_SIDE_LENGTH * RECOGNITION_SIDE_LENGTH)
+        .map(|cubie| {
+            SIDE_FACES
+                .into_iter()
+                .find(|&face| same_colour(cubie, cube.oriented_side(face)[1][1]))
+        })
+        .collect()
+}
+
+fn last_layer(cube: &Cube) -> impl Iterator<Item = CubieFace> + '_ {
+    cube.oriented_side(Face::Up).cells().iter().copied().chain(
+        SIDE_FACES
+            .into_iter()
+            .flat_map(|face| cube.oriented_side(face)[0].iter().copied()),
+    )
+}
+
+fn same_colour(a: CubieFace, b: CubieFace) -> bool {
+    mem::discriminant(&a) == mem::discriminant(&b)
+}
+
+/// The cube after each of the four turns of the Up face, starting with no turn.
+fn up_turns(cube: &Cube) -> [Cube; 4] {
+    quarter_turns(cube, 0..1)
+}
+
+/// The cube after each of the four turns as a whole about the Up face, which bring the same last layer into view from each side.
+fn whole_cube_turns(cube: &Cube) -> [Cube; 4] {
+    quarter_turns(cube, 0..RECOGNITION_SIDE_LENGTH)
+}
+
+fn quarter_turns(cube: &Cube, layers: Range<usize>) -> [Cube; 4] {
+    let mut cube = cube.clone();
+    [(); 4].map(|()| {
+        let turned = cube.clone();
+        layers
+            .clone()
+            .for_each(|layer| cube.rotate(Rotation::clockwise(Face::Up).at_layer(layer)));
+        turned
+    })
+}
+
+/// A solved cube with the inverse of the algorithm applied, giving the case the algorithm solves.
+fn set_up(algorithm: &str) -> Cube {
+    let mut cube = Cube::create(RECOGNITION_SIDE_LENGTH);
+    parse_sequence(algorithm)
+        .expect("Recognition algorithms are valid notation")
+        .iter()
+        .rev()
+        .for_each(|rotation| cube.rotate(rotation.inverse()));
+    cube
+}
+
+/// Wide turns such as `r` are written as parallel turns of the outer face and middle layer, such as `R M'`.
+const OLL_ALGORITHMS: [&str; 57] = [
+    "R U2 R2 F R F' U2 R' F R F'",
+    "F R U R' U' F' F S R U R' U' F' S'",
+    "F S R U R' U' F' S' U' F R U R' U' F'",
+    "F S R U R' U' F' S' U F R U R' U' F'",
+    "R' M U2 R U R' U R M'",
+    "R M' U2 R' U' R U' R' M",
+    "R M' U R' U R U2 R' M",
+    "L' M' U' L U' L' U2 L M",
+    "R U R' U' R' F R2 U R' U' F'",
+    "R U R' U R' F R F' R U2 R'",
+    "R M' U R' U R' F R F' R U2 R' M",
+    "M' R' U' R U' R' U2 R U' M",
+    "F U R U' R2 F' R U R U' R'",
+    "R' F R U R' F' R F U' F'",
+    "R' M U' R M' R' U' R U R' M U R M'",
+    "R M' U R' M R U R' U' R M' U' R' M",
+    "R U R' U R' F R F' U2 R' F R F'",
+    "R M' U R' U R U2 R2 M2 U' R U' R' U2 R M'",
+    "M U R U R' U' M' R' F R F'",
+    "R M' U R' U' M2 U R U' R' U' M'",
+    "R U2 R' U' R U R' U' R U' R'",
+    "R U2 R2 U' R2 U' R2 U2 R",
+    "R2 D' R U2 R' D R U2 R",
+    "R M' U R' U' R' M F R F'",
+    "F' R M' U R' U' R' M F R",
+    "R U2 R' U' R U' R'",
+    "R U R' U R U2 R'",
+    "R M' U R' U' M U R U' R'",
+    "R U R' U' R U' R' F' U' F R U R'",
+    "F R' F R2 U' R' U' R U R' F2",
+    "R' U' F U R U' R' F' R",
+    "L U F' U' L' U L F L'",
+    "R U R' U' R' F R F'",
+    "R U R2 U' R' F R U R U' F'",
+    "R U2 R2 F R F' R U2 R'",
+    "L' U' L U' L' U L U L F' L' F",
+    "F R' F' R U R U' R'",
+    "R U R' U R U' R' U' R' F R F'",
+    "L F' L' U' L U F U' L'",
+    "R' F R U R' U' F' U R",
+    "R U R' U R U2 R' F R U R' U' F'",
+    "R' U' R U' R' U2 R F R U R' U' F'",
+    "F' U' L' U L F",
+    "F U R U' R' F'",
+    "F R U R' U' F'",
+    "R' U' R' F R F' U R",
+    "R' U' R' F R F' R' F R F' U R",
+    "F R U R' U' R U R' U' F'",
+    "R M' U' R2 M2 U R2 M2 U R2 M2 U' R M'",
+    "R' M U R2 M2 U' R2 M2 U' R2 M2 U R' M",
+    "F U R U' R' U R U' R' F'",
+    "R U R' U R U' B U' B' R'",
+    "L' M' U2 L U L' U' L U L' U L M",
+    "R M' U2 R' U' R U R' U' R U' R' M",
+    "R' F R U R U' R2 F' R2 U' R' U R U R'",
+    "R' M U' R M' U' R' U R U' R' U M U R M'",
+    "R U R' U' M' U R U' R' M",
+];
+
+/// Turns of the whole cube, such as `y`, are written as parallel turns of every layer, such as `U E' D'`.
+const PLL_ALGORITHMS: [&str; 21] = [
+    "R' F R' B2 R F' R' B2 R2",
+    "R2 B2 R F R' B2 R F' R",
+    "R B' R' F R B R' F' R B R' F R B' R' F'",
+    "R' U' F' R U R' U' R' F R2 U' R' U' R U R' U R",
+    "R2 U R' U R' U' R U' R2 U' D R' U R D'",
+    "R' U' R U D' R2 U R' U R U' R U' R2 D",
+    "R2 U' R U' R U R' U R2 U D' R U' R' D",
+    "R U R' U' D R2 U' R U' R' U R' U R2 D'",
+    "M2 U M2 U2 M2 U M2",
+    "L' U' L F L' U' L U L F' L2 U L",
+    "R U R' F' R U R' U' R' F R2 U' R'",
+    "R U R' U R U R' F' R U R' U' R' F R2 U' R' U2 R U' R'",
+    "R' U R U' R' F' U' F R U R' F R' F' R U' R",
+    "R U' R' U' R U R D R' U' R D' R' U2 R'",
+    "R2 F R U R U' R' F' R U2 R' U2 R",
+    "R U R' U' R' F R2 U' R' U' R U R' F'",
+    "R U' R U R U R U' R' U' R2",
+    "R2 U R U R' U' R' U' R' U R'",
+    "R' U R' U' U E' D' R' F' R2 U' R' U R' F R F",
+    "F R U' R' U' R U R' F' R U R' U' R' F R F'",
+    "M' U M2 U M2 U M' U2 M2",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::perform_3x3_sequence;
+    use pretty_assertions::assert_eq;
+
+    fn cube_after(sequence: &str) -> Cube {
+        let mut cube = Cube::create(3);
+        perform_3x3_sequence(sequence, &mut cube).unwrap();
+        cube
+    }
+
+    #[test]
+    fn test_every_oll_case_is_recognised_after_any_auf() {
+        for case in OllCase::ALL {
+            for cube in up_turns(&set_up(case.algorithm())) {
+                assert_eq!(Ok(Some(case)), recognise_oll(&cube), "{case}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_pll_case_is_recognised_after_any_auf() {
+        for case in PllCase::ALL {
+            let set_up = set_up(case.algorithm());
+            for cube in whole_cube_turns(&set_up).iter().flat_map(up_turns) {
+                assert_eq!(Ok(Some(case)), recognise_pll(&cube), "{case}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_solved_last_layer_has_no_case() {
+        assert_eq!(Ok(None), recognise_oll(&cube_after("U")));
+        assert_eq!(Ok(None), recognise_pll(&cube_after("U")));
+    }
+
+    #[test]
+    fn test_unsolved_first_two_layers() {
+        assert_eq!(
+            Err("The first two layers must be solved to recognise the last layer".to_string()),
+            recognise_oll(&cube_after("R"))
+        );
+        assert_eq!(
+            Err("Recognition needs a 3x3x3 cube, not a 4x4 cube".to_string()),
+            recognise_pll(&Cube::create(4))
+        );
+    }
+
+    #[test]
+    fn test_pll_needs_oriented_last_layer() {
+        assert_eq!(
+            Err("The last layer must be oriented to recognise its permutation".to_string()),
+            recognise_pll(&cube_after("R U R' U R U2 R'"))
+        );
+    }
+}
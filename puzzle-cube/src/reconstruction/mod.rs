@@ -0,0 +1,318 @@
+use std::{fmt::Write as _, time::Duration};
+
+use crate::{
+    cube::{rotation::Rotation, Cube},
+    notation::{format_sequence, parse_sequence},
+};
+
+const SCRAMBLE_PREFIX: &str = "Scramble:";
+const TIME_PREFIX: char = '@';
+const UNDO: &str = "undo";
+const COMMENT: &str = "//";
+
+/// Something the solver did at a point during a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    /// A move made by the solver, as the quarter turns it is made of, so a half turn is undone as a whole.
+    Move(Vec<Rotation>),
+    /// The solver undid their most recent move that had not already been undone.
+    Undo,
+}
+
+/// An [`Event`] and when it happened, measured from the start of the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimedEvent {
+    /// How long after the start of the session the event happened.
+    pub at: Duration,
+    /// What happened.
+    pub event: Event,
+}
+
+/// A record of a whole solving session that can be shared as annotated text and replayed.
+///
+/// The text format has an optional scramble line, then one line per event with its time in seconds. Blank lines are ignored, as is anything after `//` on a line.
+/// ```
+/// # use rusty_puzzle_cube::reconstruction::Reconstruction;
+/// let text = "Scramble: R U R' // a gentle start
+/// @1.200 R
+/// @1.850 U'
+/// @2.100 undo
+/// @2.400 U
+/// ";
+/// let reconstruction = Reconstruction::from_text(text).unwrap();
+/// assert_eq!(2, reconstruction.effective_moves().len());
+/// assert_eq!(Ok(reconstruction.clone()), Reconstruction::from_text(&reconstruction.to_text()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reconstruction {
+    /// The rotations applied to a solved cube before the session started.
+    pub scramble: Vec<Rotation>,
+    /// Everything the solver did, in the order it happened.
+    pub events: Vec<TimedEvent>,
+}
+
+impl Reconstruction {
+    /// Start recording a session that begins from the given scramble.
+    #[must_use]
+    pub fn new(scramble: Vec<Rotation>) -> Self {
+        Self {
+            scramble,
+            events: vec![],
+        }
+    }
+
+    /// Record a move made `at` the given time into the session.
+    pub fn record_move(&mut self, at: Duration, rotation: Rotation) {
+        self.record_rotations(at, vec![rotation]);
+    }
+
+    /// Record a move made of several quarter turns, such as a half turn, made `at` the given time into the session.
+    /// A later undo removes all of them together.
+    pub fn record_rotations(&mut self, at: Duration, rotations: Vec<Rotation>) {
+        self.events.push(TimedEvent {
+            at,
+            event: Event::Move(rotations),
+        });
+    }
+
+    /// Record an undo made `at` the given time into the session.
+    pub fn record_undo(&mut self, at: Duration) {
+        self.events.push(TimedEvent {
+            at,
+            event: Event::Undo,
+        });
+    }
+
+    /// The solver's moves that were not undone, in order.
+    #[must_use]
+    pub fn effective_moves(&self) -> Vec<Rotation> {
+        let mut moves: Vec<&[Rotation]> = vec![];
+        for timed in &self.events {
+            match &timed.event {
+                Event::Move(rotations) => moves.push(rotations),
+                Event::Undo => {
+                    moves.pop();
+                }
+            }
+        }
+        moves.concat()
+    }
+
+    /// Apply the scramble and then every move that was not undone to the given cube, reconstructing the state the session ended in.
+    pub fn replay_onto(&self, cube: &mut Cube) {
+        self.scramble
+            .iter()
+            .chain(&self.effective_moves())
+            .for_each(|&rotation| cube.rotate(rotation));
+    }
+
+    /// Write this session in the annotated text format read by [`Reconstruction::from_text`].
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        if !self.scramble.is_empty() {
+            let _ = writeln!(
+                text,
+                "{SCRAMBLE_PREFIX} {}",
+                format_sequence(&self.scramble)
+            );
+        }
+        for timed in &self.events {
+            let _ = write!(text, "{TIME_PREFIX}{:.3} ", timed.at.as_secs_f64());
+            let _ = match &timed.event {
+                Event::Move(rotations) => writeln!(text, "{}", format_sequence(rotations)),
+                Event::Undo => writeln!(text, "{UNDO}"),
+            };
+        }
+        text
+    }
+
+    /// Read a session from the annotated text format written by [`Reconstruction::to_text`].
+    /// # Errors
+    /// Will return an Err variant, naming the line, when the scramble or a move is malformed notation, when a time is missing or invalid, or when times go backwards
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let mut reconstruction = Self::default();
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, strip_comment(line).trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        for (number, line) in lines {
+            let error = |e: String| format!("Line {number}: {e}");
+            if let Some(scramble) = line.strip_prefix(SCRAMBLE_PREFIX) {
+                if !reconstruction.scramble.is_empty() || !reconstruction.events.is_empty() {
+                    return Err(error(
+                        "The scramble must come once, before any moves".to_string(),
+                    ));
+                }
+                reconstruction.scramble =
+                    parse_sequence(scramble).map_err(|e| error(e.to_string()))?;
+                continue;
+            }
+
+            let (at, event) = parse_event(line).map_err(error)?;
+            if reconstruction
+                .events
+                .last()
+                .is_some_and(|last| last.at > at)
+            {
+                return Err(error("Events must be in time order".to_string()));
+            }
+            match event {
+                None => reconstruction.record_undo(at),
+                Some(rotations) => reconstruction.record_rotations(at, rotations),
+            }
+        }
+        Ok(reconstruction)
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    line.split_once(COMMENT).map_or(line, |(before, _)| before)
+}
+
+fn parse_event(line: &str) -> Result<(Duration, Option<Vec<Rotation>>), String> {
+    let (time, event) = line
+        .strip_prefix(TIME_PREFIX)
+        .and_then(|line| line.split_once(char::is_whitespace))
+        .ok_or_else(|| {
+            format!("Expected a time such as {TIME_PREFIX}1.250 then a move: [{line}]")
+        })?;
+    let at = time
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| format!("Invalid time: [{time}]"))?;
+
+    let event = event.trim();
+    if event == UNDO {
+        Ok((at, None))
+    } else {
+        Ok((at, Some(parse_sequence(event).map_err(|e| e.to_string())?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::face::Face;
+    use pretty_assertions::assert_eq;
+
+    fn session() -> Reconstruction {
+        let mut reconstruction = Reconstruction::new(parse_sequence("R U2 F'").unwrap());
+        reconstruction.record_move(
+            Duration::from_millis(1200),
+            Rotation::clockwise(Face::Front),
+        );
+        reconstruction.record_move(Duration::from_millis(1850), Rotation::clockwise(Face::Up));
+        reconstruction.record_undo(Duration::from_millis(2100));
+        reconstruction.record_move(
+            Duration::from_millis(2400),
+            Rotation::anticlockwise(Face::Up),
+        );
+        reconstruction
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(
+            "Scramble: R U2 F'\n@1.200 F\n@1.850 U\n@2.100 undo\n@2.400 U'\n",
+            session().to_text()
+        );
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let session = session();
+
+        assert_eq!(
+            Ok(session.clone()),
+            Reconstruction::from_text(&session.to_text())
+        );
+    }
+
+    #[test]
+    fn test_effective_moves_skip_undone_moves() {
+        assert_eq!(
+            vec![
+                Rotation::clockwise(Face::Front),
+                Rotation::anticlockwise(Face::Up)
+            ],
+            session().effective_moves()
+        );
+    }
+
+    #[test]
+    fn test_replay_onto() {
+        let mut replayed = Cube::create(3);
+        let mut expected = Cube::create(3);
+
+        session().replay_onto(&mut replayed);
+        crate::notation::perform_3x3_sequence("R U2 F' F U'", &mut expected).unwrap();
+
+        assert_eq!(expected, replayed);
+    }
+
+    #[test]
+    fn test_from_text_ignores_comments_and_blank_lines() {
+        let text = "// Reconstruction of a practice solve
+
+Scramble: R U2 F' // from the timer
+@1.200 F // cross
+@1.850 U2
+";
+
+        let reconstruction = Reconstruction::from_text(text).unwrap();
+
+        assert_eq!(parse_sequence("R U2 F'").unwrap(), reconstruction.scramble);
+        assert_eq!(2, reconstruction.events.len());
+        assert_eq!(Duration::from_millis(1850), reconstruction.events[1].at);
+    }
+
+    #[test]
+    fn test_undo_removes_a_whole_half_turn() {
+        let reconstruction = Reconstruction::from_text("@1 F\n@2 R2\n@3 undo").unwrap();
+
+        assert_eq!(
+            vec![Rotation::clockwise(Face::Front)],
+            reconstruction.effective_moves()
+        );
+        assert_eq!(
+            "@1.000 F\n@2.000 R2\n@3.000 undo\n",
+            reconstruction.to_text()
+        );
+    }
+
+    #[test]
+    fn test_from_text_without_scramble() {
+        let reconstruction = Reconstruction::from_text("@0.5 R").unwrap();
+
+        assert_eq!(Vec::<Rotation>::new(), reconstruction.scramble);
+        assert_eq!(
+            vec![Rotation::clockwise(Face::Right)],
+            reconstruction.effective_moves()
+        );
+    }
+
+    #[test]
+    fn test_from_text_errors_name_the_line() {
+        assert_eq!(
+            Err("Line 2: Unsupported token in notation string: [Q]".to_string()),
+            Reconstruction::from_text("Scramble: R\n@1 Q")
+        );
+        assert_eq!(
+            Err("Line 1: Invalid time: [-1]".to_string()),
+            Reconstruction::from_text("@-1 R")
+        );
+        assert_eq!(
+            Err("Line 2: Events must be in time order".to_string()),
+            Reconstruction::from_text("@2 R\n@1 R")
+        );
+        assert!(Reconstruction::from_text("R U").is_err());
+        assert!(Reconstruction::from_text("@1 R\nScramble: R").is_err());
+    }
+}
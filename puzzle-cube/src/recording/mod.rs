@@ -0,0 +1,368 @@
+use std::io::{self, Write};
+
+use crate::{
+    cube::{rotation::Rotation, Cube},
+    notation::format_sequence,
+    robot::RobotExporter,
+};
+
+/// Something that follows along with the rotations applied to a cube, such as a log, a robot or the clients of a network session.
+pub trait MoveSink {
+    /// Called after each rotation has been applied, with the cube in its new state.
+    fn on_move(&mut self, rotation: Rotation, state_after: &Cube);
+}
+
+impl<S: MoveSink + ?Sized> MoveSink for &mut S {
+    fn on_move(&mut self, rotation: Rotation, state_after: &Cube) {
+        (**self).on_move(rotation, state_after);
+    }
+}
+
+impl MoveSink for Vec<Rotation> {
+    fn on_move(&mut self, rotation: Rotation, _state_after: &Cube) {
+        self.push(rotation);
+    }
+}
+
+/// A cube that passes every rotation applied to it on to a [`MoveSink`], so integrations can follow along without any changes to the code turning the cube.
+/// ```
+/// # use rusty_puzzle_cube::{cube::{Cube, face::Face, rotation::Rotation}, recording::{LogSink, RecordingCube}};
+/// let mut recording_cube = RecordingCube::new(Cube::create(3), LogSink::new(vec![]));
+/// recording_cube.rotate(Rotation::clockwise(Face::Right));
+/// recording_cube.rotate(Rotation::anticlockwise(Face::Up));
+/// let (_, log) = recording_cube.into_parts();
+/// assert_eq!(b"R\nU'\n", log.into_writer().as_slice());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingCube<S: MoveSink> {
+    cube: Cube,
+    sink: S,
+}
+
+impl<S: MoveSink> RecordingCube<S> {
+    /// Start passing rotations applied to the given cube on to `sink`.
+    #[must_use]
+    pub fn new(cube: Cube, sink: S) -> Self {
+        Self { cube, sink }
+    }
+
+    /// Returns the cube in its current state.
+    #[must_use]
+    pub fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// Returns the sink rotations are passed on to.
+    #[must_use]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Returns the sink rotations are passed on to mutably, such as to connect more clients to a [`BroadcastSink`].
+    pub fn sink_mut(&mut self) -> &mut S {
+        &mut self.sink
+    }
+
+    /// Stop recording and return the cube in its current state along with the sink.
+    #[must_use]
+    pub fn into_parts(self) -> (Cube, S) {
+        (self.cube, self.sink)
+    }
+
+    /// Apply the rotation to the cube, then pass it on to the sink.
+    pub fn rotate(&mut self, rotation: Rotation) {
+        self.cube.rotate(rotation);
+        self.sink.on_move(rotation, &self.cube);
+    }
+
+    /// Apply each of the rotations in order, passing each on to the sink as it is applied.
+    pub fn rotate_all(&mut self, rotations: impl IntoIterator<Item = Rotation>) {
+        rotations
+            .into_iter()
+            .for_each(|rotation| self.rotate(rotation));
+    }
+}
+
+/// Writes the notation for each rotation to a writer, one per line.
+///
+/// Once writing fails the error is kept and nothing more is written, as rotations cannot be rejected after they have been made.
+#[derive(Debug)]
+pub struct LogSink<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> LogSink<W> {
+    /// Create a sink writing to `writer`.
+    #[must_use]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Returns the error that stopped the sink writing, if there has been one.
+    #[must_use]
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Returns the writer, giving up any error that stopped the sink writing.
+    #[must_use]
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> MoveSink for LogSink<W> {
+    fn on_move(&mut self, rotation: Rotation, _state_after: &Cube) {
+        if self.error.is_none() {
+            self.error = write_line(&mut self.writer, rotation).err();
+        }
+    }
+}
+
+/// Sends the robot commands for each rotation to a writer as it is made, such as an open serial port.
+///
+/// The grip hook of the exporter is given the rotation made before, as it would be when exporting the rotations all at once.
+/// Once writing fails the error is kept and nothing more is written.
+pub struct RobotSink<W: Write> {
+    exporter: RobotExporter,
+    writer: W,
+    previous: Option<Rotation>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> RobotSink<W> {
+    /// Create a sink writing the commands made by `exporter` to `writer`.
+    #[must_use]
+    pub fn new(exporter: RobotExporter, writer: W) -> Self {
+        Self {
+            exporter,
+            writer,
+            previous: None,
+            error: None,
+        }
+    }
+
+    /// Returns the error that stopped the sink writing, if there has been one.
+    #[must_use]
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Returns the writer, giving up any error that stopped the sink writing.
+    #[must_use]
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "serial")]
+impl RobotSink<Box<dyn serialport::SerialPort>> {
+    /// Open the serial port at `port_path` and create a sink writing the commands made by `exporter` to it.
+    /// # Errors
+    /// Will return an Err variant when the port cannot be opened
+    pub fn open_serial(
+        exporter: RobotExporter,
+        port_path: &str,
+        baud_rate: u32,
+    ) -> Result<Self, String> {
+        let port = serialport::new(port_path, baud_rate)
+            .open()
+            .map_err(|e| format!("Could not open serial port {port_path}: {e}"))?;
+        Ok(Self::new(exporter, port))
+    }
+}
+
+impl<W: Write> MoveSink for RobotSink<W> {
+    fn on_move(&mut self, rotation: Rotation, _state_after: &Cube) {
+        let previous = self.previous.replace(rotation);
+        if self.error.is_none() {
+            self.error = self
+                .exporter
+                .write_commands_after(previous, rotation, &mut self.writer)
+                .err();
+        }
+    }
+}
+
+/// Writes the notation for each rotation to every connected client, such as the `TcpStream` of each spectator of a session.
+///
+/// Clients that cannot be written to are assumed to have disconnected, and are dropped.
+#[derive(Debug)]
+pub struct BroadcastSink<W: Write> {
+    clients: Vec<W>,
+}
+
+impl<W: Write> Default for BroadcastSink<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Write> BroadcastSink<W> {
+    /// Create a sink with no clients connected.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { clients: vec![] }
+    }
+
+    /// Start writing rotations to another client, from the next rotation on.
+    pub fn connect(&mut self, client: W) {
+        self.clients.push(client);
+    }
+
+    /// Returns the clients still connected.
+    #[must_use]
+    pub fn clients(&self) -> &[W] {
+        &self.clients
+    }
+}
+
+impl<W: Write> MoveSink for BroadcastSink<W> {
+    fn on_move(&mut self, rotation: Rotation, _state_after: &Cube) {
+        self.clients
+            .retain_mut(|client| write_line(client, rotation).is_ok());
+    }
+}
+
+fn write_line(writer: &mut impl Write, rotation: Rotation) -> io::Result<()> {
+    let line = format!("{}\n", format_sequence(&[rotation]));
+    writer.write_all(line.as_bytes())?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::face::Face;
+    use pretty_assertions::assert_eq;
+
+    /// A client that accepts a fixed number of writes and then fails, as if it disconnected.
+    struct FlakyClient {
+        written: Vec<u8>,
+        writes_left: usize,
+    }
+
+    impl Write for FlakyClient {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.writes_left == 0 {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+            self.writes_left -= 1;
+            self.written.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn flaky_client(writes_left: usize) -> FlakyClient {
+        FlakyClient {
+            written: vec![],
+            writes_left,
+        }
+    }
+
+    fn rotations() -> [Rotation; 3] {
+        [
+            Rotation::clockwise(Face::Right),
+            Rotation::anticlockwise(Face::Up),
+            Rotation::clockwise(Face::Front).at_layer(1),
+        ]
+    }
+
+    #[test]
+    fn test_sink_sees_state_after_each_move() {
+        struct SolvedCheck(Vec<bool>);
+        impl MoveSink for SolvedCheck {
+            fn on_move(&mut self, _rotation: Rotation, state_after: &Cube) {
+                self.0.push(state_after.is_solved());
+            }
+        }
+
+        let mut recording_cube = RecordingCube::new(Cube::create(3), SolvedCheck(vec![]));
+        let r = Rotation::clockwise(Face::Right);
+        recording_cube.rotate_all([r, r, r, r]);
+
+        assert_eq!(vec![false, false, false, true], recording_cube.sink().0);
+    }
+
+    #[test]
+    fn test_recorded_rotations_match_cube() {
+        let mut recording_cube = RecordingCube::new(Cube::create(3), vec![]);
+        recording_cube.rotate_all(rotations());
+
+        let (cube, recorded) = recording_cube.into_parts();
+        let mut expected = Cube::create(3);
+        recorded
+            .iter()
+            .for_each(|&rotation| expected.rotate(rotation));
+        assert_eq!(rotations().to_vec(), recorded);
+        assert_eq!(expected, cube);
+    }
+
+    #[test]
+    fn test_borrowed_sink() {
+        let mut recorded = vec![];
+        let mut recording_cube = RecordingCube::new(Cube::create(3), &mut recorded);
+        recording_cube.rotate_all(rotations());
+        drop(recording_cube);
+
+        assert_eq!(rotations().to_vec(), recorded);
+    }
+
+    #[test]
+    fn test_log_sink_stops_after_error() {
+        let mut log = LogSink::new(flaky_client(1));
+        rotations()
+            .into_iter()
+            .for_each(|rotation| log.on_move(rotation, &Cube::create(3)));
+
+        assert_eq!(
+            Some(io::ErrorKind::BrokenPipe),
+            log.error().map(io::Error::kind)
+        );
+        assert_eq!(b"R\n", log.into_writer().written.as_slice());
+    }
+
+    #[test]
+    fn test_robot_sink_matches_exporter() {
+        let hook = |previous: Option<Rotation>, rotation: Rotation| match previous {
+            Some(previous) if previous.relative_to != rotation.relative_to => {
+                vec!["REGRIP".to_string()]
+            }
+            _ => vec![],
+        };
+        let mut recording_cube = RecordingCube::new(
+            Cube::create(3),
+            RobotSink::new(RobotExporter::new().with_grip_hook(hook), vec![]),
+        );
+        recording_cube.rotate_all(rotations());
+
+        let (_, sink) = recording_cube.into_parts();
+        let mut exported = vec![];
+        RobotExporter::new()
+            .with_grip_hook(hook)
+            .write_commands(&rotations(), &mut exported)
+            .unwrap();
+        assert!(sink.error().is_none());
+        assert_eq!(exported, sink.into_writer());
+    }
+
+    #[test]
+    fn test_broadcast_drops_disconnected_clients() {
+        let mut broadcast = BroadcastSink::new();
+        broadcast.connect(flaky_client(usize::MAX));
+        broadcast.connect(flaky_client(1));
+        let mut recording_cube = RecordingCube::new(Cube::create(3), broadcast);
+        recording_cube.rotate_all(rotations());
+
+        let clients = recording_cube.sink().clients();
+        assert_eq!(1, clients.len());
+        assert_eq!(b"R\nU'\nS\n", clients[0].written.as_slice());
+    }
+}
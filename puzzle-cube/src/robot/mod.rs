@@ -0,0 +1,341 @@
+use std::io::{self, Write};
+
+use enum_map::{enum_map, EnumMap};
+
+use crate::cube::{
+    direction::Direction,
+    face::Face,
+    rotation::Rotation,
+    sequence::{Op, Sequence},
+};
+
+const DELAY_PLACEHOLDER: &str = "{ms}";
+
+type GripHook = Box<dyn Fn(Option<Rotation>, Rotation) -> Vec<String>>;
+
+/// The commands a robot expects for turning one face in each direction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaceCommands {
+    /// The command sent for a clockwise turn of the face.
+    pub clockwise: String,
+    /// The command sent for an anticlockwise turn of the face.
+    pub anticlockwise: String,
+}
+
+/// Turns a sequence of rotations into a stream of commands for a cube-solving robot.
+///
+/// Each rotation produces, in order, any commands returned by the grip hook, the command mapped to that rotation, and the delay command if one is configured.
+/// ```
+/// # use rusty_puzzle_cube::{notation::parse_sequence, robot::RobotExporter};
+/// let exporter = RobotExporter::new().with_delay(250, "WAIT {ms}");
+/// let rotations = parse_sequence("F R'").unwrap();
+/// assert_eq!(vec!["F", "WAIT 250", "R'", "WAIT 250"], exporter.commands(&rotations));
+/// ```
+pub struct RobotExporter {
+    face_commands: EnumMap<Face, FaceCommands>,
+    delay_command: Option<String>,
+    grip_hook: Option<GripHook>,
+    line_ending: String,
+    parallel_joiner: Option<String>,
+}
+
+impl Default for RobotExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RobotExporter {
+    /// Create an exporter that sends standard notation for each rotation (such as `F` and `F'`), one command per line, with no delays.
+    #[must_use]
+    pub fn new() -> Self {
+        let notation_commands = |letter: char| FaceCommands {
+            clockwise: letter.to_string(),
+            anticlockwise: format!("{letter}'"),
+        };
+        Self {
+            face_commands: enum_map! {
+                Face::Up => notation_commands('U'),
+                Face::Down => notation_commands('D'),
+                Face::Front => notation_commands('F'),
+                Face::Right => notation_commands('R'),
+                Face::Back => notation_commands('B'),
+                Face::Left => notation_commands('L'),
+            },
+            delay_command: None,
+            grip_hook: None,
+            parallel_joiner: None,
+            line_ending: "\n".to_string(),
+        }
+    }
+
+    /// Replace the commands sent for turning the given face.
+    #[must_use]
+    pub fn with_face_commands(
+        mut self,
+        face: Face,
+        clockwise: impl Into<String>,
+        anticlockwise: impl Into<String>,
+    ) -> Self {
+        self.face_commands[face] = FaceCommands {
+            clockwise: clockwise.into(),
+            anticlockwise: anticlockwise.into(),
+        };
+        self
+    }
+
+    /// Send a delay command after every rotation. Any `{ms}` in `command_template` is replaced with `delay_ms`.
+    #[must_use]
+    pub fn with_delay(mut self, delay_ms: u32, command_template: &str) -> Self {
+        self.delay_command =
+            Some(command_template.replace(DELAY_PLACEHOLDER, &delay_ms.to_string()));
+        self
+    }
+
+    /// Send extra commands before each rotation, such as re-gripping the cube.
+    ///
+    /// The hook is given the previous rotation (if any) and the rotation about to be made.
+    #[must_use]
+    pub fn with_grip_hook(
+        mut self,
+        hook: impl Fn(Option<Rotation>, Rotation) -> Vec<String> + 'static,
+    ) -> Self {
+        self.grip_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Replace the text written after each command by [`RobotExporter::write_commands`].
+    #[must_use]
+    pub fn with_line_ending(mut self, line_ending: impl Into<String>) -> Self {
+        self.line_ending = line_ending.into();
+        self
+    }
+
+    /// Send each parallel move in a [`Sequence`] as one command, made by joining the command for each of its rotations with `joiner`.
+    ///
+    /// Without a joiner, the rotations of a parallel move are sent one after the other.
+    #[must_use]
+    pub fn with_parallel_joiner(mut self, joiner: impl Into<String>) -> Self {
+        self.parallel_joiner = Some(joiner.into());
+        self
+    }
+
+    /// The full list of commands for performing the given rotations.
+    #[must_use]
+    pub fn commands(&self, rotations: &[Rotation]) -> Vec<String> {
+        let mut commands = vec![];
+        let mut previous = None;
+        for &rotation in rotations {
+            self.push_commands(
+                &mut commands,
+                previous,
+                rotation,
+                self.command_for(rotation),
+            );
+            previous = Some(rotation);
+        }
+        commands
+    }
+
+    /// The full list of commands for performing the given sequence, keeping parallel moves together when a joiner is set with [`RobotExporter::with_parallel_joiner`].
+    ///
+    /// The grip hook is called once for a joined parallel move, with its first rotation.
+    /// ```
+    /// # use rusty_puzzle_cube::{notation::parse_ops, robot::RobotExporter};
+    /// let exporter = RobotExporter::new().with_parallel_joiner("+");
+    /// let sequence = parse_ops("F (R, L')").unwrap();
+    /// assert_eq!(vec!["F", "R+L'"], exporter.sequence_commands(&sequence));
+    /// ```
+    #[must_use]
+    pub fn sequence_commands(&self, sequence: &Sequence) -> Vec<String> {
+        let mut commands = vec![];
+        let mut previous = None;
+        for op in sequence {
+            match (op, &self.parallel_joiner) {
+                (Op::Parallel(parallel), Some(joiner)) => {
+                    let rotations = parallel.rotations();
+                    let joined = rotations
+                        .iter()
+                        .map(|&rotation| self.command_for(rotation))
+                        .collect::<Vec<_>>()
+                        .join(joiner);
+                    self.push_commands(&mut commands, previous, rotations[0], &joined);
+                    previous = rotations.last().copied();
+                }
+                _ => {
+                    for &rotation in op.rotations() {
+                        self.push_commands(
+                            &mut commands,
+                            previous,
+                            rotation,
+                            self.command_for(rotation),
+                        );
+                        previous = Some(rotation);
+                    }
+                }
+            }
+        }
+        commands
+    }
+
+    /// Write the commands for performing the given rotations, each followed by the line ending.
+    /// # Errors
+    /// Will return an Err variant when writing to `writer` fails
+    pub fn write_commands(
+        &self,
+        rotations: &[Rotation],
+        writer: &mut impl Write,
+    ) -> io::Result<()> {
+        for command in self.commands(rotations) {
+            write!(writer, "{command}{}", self.line_ending)?;
+        }
+        writer.flush()
+    }
+
+    /// Open the serial port at `port_path` and write the commands for performing the given rotations to it.
+    /// # Errors
+    /// Will return an Err variant when the port cannot be opened or written to
+    #[cfg(feature = "serial")]
+    pub fn send_over_serial(
+        &self,
+        rotations: &[Rotation],
+        port_path: &str,
+        baud_rate: u32,
+    ) -> Result<(), String> {
+        let mut port = serialport::new(port_path, baud_rate)
+            .open()
+            .map_err(|e| format!("Could not open serial port {port_path}: {e}"))?;
+        self.write_commands(rotations, &mut port)
+            .map_err(|e| format!("Could not write to serial port {port_path}: {e}"))
+    }
+
+    /// Write the commands for a single rotation, made after `previous`, each followed by the line ending.
+    pub(crate) fn write_commands_after(
+        &self,
+        previous: Option<Rotation>,
+        rotation: Rotation,
+        writer: &mut impl Write,
+    ) -> io::Result<()> {
+        let mut commands = vec![];
+        self.push_commands(
+            &mut commands,
+            previous,
+            rotation,
+            self.command_for(rotation),
+        );
+        for command in commands {
+            write!(writer, "{command}{}", self.line_ending)?;
+        }
+        writer.flush()
+    }
+
+    fn push_commands(
+        &self,
+        commands: &mut Vec<String>,
+        previous: Option<Rotation>,
+        rotation: Rotation,
+        command: &str,
+    ) {
+        if let Some(hook) = &self.grip_hook {
+            commands.extend(hook(previous, rotation));
+        }
+        commands.push(command.to_string());
+        if let Some(delay_command) = &self.delay_command {
+            commands.push(delay_command.clone());
+        }
+    }
+
+    fn command_for(&self, rotation: Rotation) -> &str {
+        let commands = &self.face_commands[rotation.relative_to];
+        match rotation.direction {
+            Direction::Clockwise => &commands.clockwise,
+            Direction::Anticlockwise => &commands.anticlockwise,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::{parse_ops, parse_sequence};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_commands_are_notation() {
+        let rotations = parse_sequence("F R' U2").unwrap();
+
+        assert_eq!(
+            vec!["F", "R'", "U", "U"],
+            RobotExporter::new().commands(&rotations)
+        );
+    }
+
+    #[test]
+    fn test_custom_face_commands() {
+        let exporter = RobotExporter::new().with_face_commands(Face::Down, "M5+", "M5-");
+        let rotations = parse_sequence("D D' F").unwrap();
+
+        assert_eq!(vec!["M5+", "M5-", "F"], exporter.commands(&rotations));
+    }
+
+    #[test]
+    fn test_grip_hook_sees_previous_rotation() {
+        let exporter = RobotExporter::new().with_grip_hook(|previous, next| {
+            if previous.map(|p| p.relative_to) == Some(next.relative_to) {
+                vec![]
+            } else {
+                vec![format!("GRIP {:?}", next.relative_to)]
+            }
+        });
+        let rotations = parse_sequence("F F R").unwrap();
+
+        assert_eq!(
+            vec!["GRIP Front", "F", "F", "GRIP Right", "R"],
+            exporter.commands(&rotations)
+        );
+    }
+
+    #[test]
+    fn test_write_commands() {
+        let exporter = RobotExporter::new()
+            .with_delay(100, "D{ms}")
+            .with_line_ending("\r\n");
+        let rotations = parse_sequence("L B'").unwrap();
+        let mut output = vec![];
+
+        exporter.write_commands(&rotations, &mut output).unwrap();
+
+        assert_eq!(
+            "L\r\nD100\r\nB'\r\nD100\r\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_rotations_no_commands() {
+        assert_eq!(Vec::<String>::new(), RobotExporter::new().commands(&[]));
+    }
+
+    #[test]
+    fn test_parallel_moves_are_joined() {
+        let exporter = RobotExporter::new()
+            .with_parallel_joiner(" & ")
+            .with_delay(100, "WAIT {ms}");
+        let sequence = parse_ops("(R2, L') U").unwrap();
+
+        assert_eq!(
+            vec!["R & R & L'", "WAIT 100", "U", "WAIT 100"],
+            exporter.sequence_commands(&sequence)
+        );
+    }
+
+    #[test]
+    fn test_parallel_moves_without_joiner_are_sequential() {
+        let sequence = parse_ops("(R, L') U").unwrap();
+
+        assert_eq!(
+            vec!["R", "L'", "U"],
+            RobotExporter::new().sequence_commands(&sequence)
+        );
+    }
+}
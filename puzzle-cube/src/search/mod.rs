@@ -0,0 +1,327 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    mem,
+};
+
+use enum_map::Enum;
+
+use crate::{
+    budget::{Budget, Interrupted},
+    cube::{face::Face, rotation::Rotation, Cube},
+};
+
+const CORNER_TWIST_SIDE_LENGTH: usize = 3;
+
+/// An estimate of how many rotations are still needed to solve a cube, used to guide [`ida_star`].
+///
+/// For the solutions found to be as short as possible the estimate must never be more than the true number of rotations needed, using the moves being searched.
+/// Any function from `&Cube` to `usize` can be used as a heuristic.
+pub trait Heuristic {
+    /// Estimate how many rotations are needed to solve the given cube, returning 0 only if it may already be solved.
+    fn estimate(&self, cube: &Cube) -> usize;
+}
+
+impl<F: Fn(&Cube) -> usize> Heuristic for F {
+    fn estimate(&self, cube: &Cube) -> usize {
+        self(cube)
+    }
+}
+
+/// Estimates from the number of stickers that do not match the most common colour on their side.
+///
+/// This works for cubes of any side length and any moves, but is a weak guide as a single turn can move many stickers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MisplacedStickers;
+
+impl Heuristic for MisplacedStickers {
+    fn estimate(&self, cube: &Cube) -> usize {
+        let n = cube.side_length();
+        let misplaced = cube
+            .side_map()
+            .values()
+            .map(|side| {
+                let mut counts = HashMap::new();
+                for cubie in side.cells() {
+                    *counts.entry(mem::discriminant(cubie)).or_insert(0) += 1;
+                }
+                side.cells().len() - counts.into_values().max().unwrap_or(0)
+            })
+            .sum::<usize>();
+        // A turn of an outer layer moves 4n stickers of the neighbouring sides and at most n * n of its own
+        misplaced.div_ceil(4 * n + n * n)
+    }
+}
+
+/// Estimates from how the corners of a 3x3 cube are twisted, using a table of the fewest quarter turns of the outer faces that fix each combination of twists.
+///
+/// The table has an entry for every one of the 2187 ways the corners can be twisted, and is built by [`CornerTwistDatabase::build`].
+/// Cubes that are not 3x3 are always estimated as 0.
+#[derive(Debug, Clone)]
+pub struct CornerTwistDatabase {
+    distances: Vec<u8>,
+}
+
+impl CornerTwistDatabase {
+    /// Build the table by searching outwards from a solved cube.
+    #[must_use]
+    pub fn build() -> Self {
+        let mut distances = vec![u8::MAX; 3_usize.pow(7)];
+        let solved = Cube::create(CORNER_TWIST_SIDE_LENGTH);
+        let moves = outer_face_turns();
+        distances[corner_twist(&solved).expect("A solved 3x3 has corners")] = 0;
+        let mut queue = VecDeque::from([(solved, 0)]);
+        while let Some((cube, distance)) = queue.pop_front() {
+            for &rotation in &moves {
+                let mut next = cube.clone();
+                next.rotate(rotation);
+                let twist = corner_twist(&next).expect("Turning a 3x3 keeps its corners");
+                if distances[twist] == u8::MAX {
+                    distances[twist] = distance + 1;
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        Self { distances }
+    }
+}
+
+impl Heuristic for CornerTwistDatabase {
+    fn estimate(&self, cube: &Cube) -> usize {
+        if cube.side_length() != CORNER_TWIST_SIDE_LENGTH {
+            return 0;
+        }
+        corner_twist(cube).map_or(0, |twist| usize::from(self.distances[twist]))
+    }
+}
+
+/// The twists of the first seven corners as a number in base 3, as the last corner's twist follows from the others.
+fn corner_twist(cube: &Cube) -> Option<usize> {
+    let state = cube.corner_state().ok()?;
+    Some(
+        state.orientation[..7]
+            .iter()
+            .fold(0, |twist, &orientation| {
+                twist * 3 + usize::from(orientation)
+            }),
+    )
+}
+
+/// Returns quarter turns of the six outer faces in both directions, which can reach every position of a cube up to 3x3.
+#[must_use]
+pub fn outer_face_turns() -> Vec<Rotation> {
+    [
+        Face::Up,
+        Face::Down,
+        Face::Front,
+        Face::Right,
+        Face::Back,
+        Face::Left,
+    ]
+    .into_iter()
+    .flat_map(|face| [Rotation::clockwise(face), Rotation::anticlockwise(face)])
+    .collect()
+}
+
+/// Returns quarter turns of the Up, Right and Front faces in both directions, which can reach every position of a 2x2 cube while keeping its Down, Back and Left corner in place.
+///
+/// Searching a 2x2 cube with these is much quicker than with [`outer_face_turns`], as they are the only turns that are not repeats of each other up to how the cube is held.
+#[must_use]
+pub fn fixed_corner_turns() -> Vec<Rotation> {
+    [Face::Up, Face::Right, Face::Front]
+        .into_iter()
+        .flat_map(|face| [Rotation::clockwise(face), Rotation::anticlockwise(face)])
+        .collect()
+}
+
+/// Search for the shortest sequence of the given moves, of at most `max_moves`, that solves the cube, using iterative deepening A*.
+///
+/// Each depth is searched in turn, skipping any cube whose heuristic estimate shows it cannot be solved within the moves remaining, so the first solution found is as short as possible as long as the heuristic never overestimates.
+/// Sequences that undo a move straight away, turn the same layer three times in a row, or could be reordered by swapping turns of opposite outer faces are not searched.
+/// A cube is solved when every side is one colour, however it is held.
+/// ```
+/// # use rusty_puzzle_cube::{budget::Budget, cube::Cube, notation::perform_3x3_sequence};
+/// # use rusty_puzzle_cube::search::{ida_star, fixed_corner_turns, MisplacedStickers};
+/// let mut cube = Cube::create(2);
+/// perform_3x3_sequence("R U' F R", &mut cube).unwrap();
+/// let solution = ida_star(&cube, &fixed_corner_turns(), &MisplacedStickers, 6, &Budget::default())
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(4, solution.len());
+/// ```
+/// # Errors
+/// Will return an Err variant when the budget is used up before the search finishes, where each cube visited counts as one rotation
+pub fn ida_star(
+    cube: &Cube,
+    moves: &[Rotation],
+    heuristic: &impl Heuristic,
+    max_moves: usize,
+    budget: &Budget,
+) -> Result<Option<Vec<Rotation>>, Interrupted> {
+    let mut search = Search {
+        moves,
+        heuristic,
+        budget,
+        visited: 0,
+        solution: vec![],
+    };
+    for depth in 0..=max_moves {
+        if search.search(cube, depth)? {
+            return Ok(Some(search.solution));
+        }
+    }
+    Ok(None)
+}
+
+struct Search<'a, H> {
+    moves: &'a [Rotation],
+    heuristic: &'a H,
+    budget: &'a Budget,
+    visited: usize,
+    solution: Vec<Rotation>,
+}
+
+impl<H: Heuristic> Search<'_, H> {
+    fn search(&mut self, cube: &Cube, remaining: usize) -> Result<bool, Interrupted> {
+        self.budget.check(self.visited)?;
+        self.visited += 1;
+        if cube.is_solved() {
+            return Ok(true);
+        }
+        if remaining == 0 || self.heuristic.estimate(cube) > remaining {
+            return Ok(false);
+        }
+        for &rotation in self.moves {
+            if self.is_redundant(rotation) {
+                continue;
+            }
+            let mut next = cube.clone();
+            next.rotate(rotation);
+            self.solution.push(rotation);
+            if self.search(&next, remaining - 1)? {
+                return Ok(true);
+            }
+            self.solution.pop();
+        }
+        Ok(false)
+    }
+
+    fn is_redundant(&self, rotation: Rotation) -> bool {
+        match self.solution.as_slice() {
+            [.., last] if rotation == last.inverse() => true,
+            [.., second_last, last] if rotation == *last && rotation == *second_last => true,
+            [.., last] => {
+                rotation.layer == 0
+                    && last.layer == 0
+                    && rotation.relative_to == last.relative_to.opposite()
+                    && rotation.relative_to.into_usize() < last.relative_to.into_usize()
+            }
+            [] => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::perform_3x3_sequence;
+    use pretty_assertions::assert_eq;
+
+    fn solve(cube: &Cube, moves: &[Rotation], heuristic: &impl Heuristic) -> Vec<Rotation> {
+        let solution = ida_star(cube, moves, heuristic, 8, &Budget::default())
+            .unwrap()
+            .expect("A solution should be found");
+        let mut solved = cube.clone();
+        solution
+            .iter()
+            .for_each(|&rotation| solved.rotate(rotation));
+        assert!(solved.is_solved());
+        solution
+    }
+
+    #[test]
+    fn test_solved_cube_needs_no_moves() {
+        assert_eq!(
+            Some(vec![]),
+            ida_star(
+                &Cube::create(3),
+                &outer_face_turns(),
+                &MisplacedStickers,
+                0,
+                &Budget::default()
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_finds_shortest_solution_on_3x3() {
+        let mut cube = Cube::create(3);
+        perform_3x3_sequence("R U F' D", &mut cube).unwrap();
+
+        assert_eq!(
+            4,
+            solve(&cube, &outer_face_turns(), &CornerTwistDatabase::build()).len()
+        );
+    }
+
+    #[test]
+    fn test_finds_shorter_solution_than_scramble_on_2x2() {
+        let mut cube = Cube::create(2);
+        perform_3x3_sequence("R L' U D' F", &mut cube).unwrap();
+
+        assert_eq!(
+            1,
+            solve(&cube, &fixed_corner_turns(), &MisplacedStickers).len()
+        );
+    }
+
+    #[test]
+    fn test_gives_up_beyond_max_moves() {
+        let mut cube = Cube::create(3);
+        perform_3x3_sequence("R U", &mut cube).unwrap();
+
+        assert_eq!(
+            Ok(None),
+            ida_star(
+                &cube,
+                &outer_face_turns(),
+                &MisplacedStickers,
+                1,
+                &Budget::default()
+            )
+        );
+    }
+
+    #[test]
+    fn test_stops_when_budget_is_used() {
+        let mut cube = Cube::create(3);
+        perform_3x3_sequence("R U F", &mut cube).unwrap();
+        let budget = Budget::default().with_max_rotations(10);
+
+        assert!(ida_star(&cube, &outer_face_turns(), &MisplacedStickers, 3, &budget).is_err());
+    }
+
+    #[test]
+    fn test_corner_twist_database_never_overestimates() {
+        let database = CornerTwistDatabase::build();
+        let mut cube = Cube::create(3);
+        perform_3x3_sequence("R U", &mut cube).unwrap();
+
+        assert_eq!(0, database.estimate(&Cube::create(3)));
+        assert!(database.estimate(&cube) <= 2);
+        assert_eq!(0, database.estimate(&Cube::create(4)));
+        assert!(database
+            .distances
+            .iter()
+            .all(|&distance| distance != u8::MAX));
+    }
+
+    #[test]
+    fn test_closures_are_heuristics() {
+        let no_estimate = |_: &Cube| 0;
+        let mut cube = Cube::create(2);
+        perform_3x3_sequence("R", &mut cube).unwrap();
+
+        assert_eq!(1, solve(&cube, &fixed_corner_turns(), &no_estimate).len());
+    }
+}
@@ -0,0 +1,273 @@
+use std::time::Duration;
+
+use crate::{cube::rotation::Rotation, reconstruction::Reconstruction};
+
+const AO5: usize = 5;
+const AO12: usize = 12;
+
+/// The result of a solve, or of an average of solves.
+///
+/// Times are ordered before a DNF, so the best of some results is the smallest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SolveTime {
+    /// The solve was finished in this time.
+    Time(Duration),
+    /// The solve was not finished, so it counts as worse than any time.
+    Dnf,
+}
+
+/// A finished solve, with everything the solver did during it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Solve {
+    /// The scramble, and each move with its time measured from the start of the solve.
+    pub reconstruction: Reconstruction,
+    /// When the solve started, measured from the start of the session.
+    pub started_at: Duration,
+    /// When the solve stopped, measured from the start of the session.
+    pub stopped_at: Duration,
+    /// The result of the solve.
+    pub time: SolveTime,
+}
+
+/// A sitting of timed solves, keeping every solve along with the averages used to report progress.
+///
+/// Every time passed in is measured from the start of the session, using whatever clock suits the caller, and times within each solve are kept relative to when it started.
+/// ```
+/// # use std::time::Duration;
+/// # use rusty_puzzle_cube::{cube::{face::Face, rotation::Rotation}, session::{SolveSession, SolveTime}};
+/// let mut session = SolveSession::new();
+/// for (start, stop) in [(0, 11), (20, 30), (40, 52), (60, 69), (80, 100)] {
+///     session.start(Duration::from_secs(start), vec![Rotation::clockwise(Face::Right)]).unwrap();
+///     session.record_move(Duration::from_secs(start + 1), Rotation::anticlockwise(Face::Right)).unwrap();
+///     session.stop(Duration::from_secs(stop)).unwrap();
+/// }
+/// assert_eq!(Some(SolveTime::Time(Duration::from_secs(11))), session.ao5());
+/// assert_eq!(Some(SolveTime::Time(Duration::from_secs(9))), session.best());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveSession {
+    solves: Vec<Solve>,
+    current: Option<(Duration, Reconstruction)>,
+}
+
+impl SolveSession {
+    /// Create a session with no solves.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns every finished solve, from first to last.
+    #[must_use]
+    pub fn solves(&self) -> &[Solve] {
+        &self.solves
+    }
+
+    /// Returns whether a solve has been started and not yet stopped.
+    #[must_use]
+    pub fn is_solving(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// Start timing a solve of a cube scrambled with the given rotations.
+    /// # Errors
+    /// Will return an Err variant when a solve is already being timed
+    pub fn start(&mut self, at: Duration, scramble: Vec<Rotation>) -> Result<(), String> {
+        if self.current.is_some() {
+            return Err("A solve is already being timed".to_string());
+        }
+        self.current = Some((at, Reconstruction::new(scramble)));
+        Ok(())
+    }
+
+    /// Record a move made during the current solve.
+    /// # Errors
+    /// Will return an Err variant when no solve is being timed
+    pub fn record_move(&mut self, at: Duration, rotation: Rotation) -> Result<(), String> {
+        let (started_at, reconstruction) = self.current_solve()?;
+        reconstruction.record_move(at.saturating_sub(started_at), rotation);
+        Ok(())
+    }
+
+    /// Record an undo made during the current solve.
+    /// # Errors
+    /// Will return an Err variant when no solve is being timed
+    pub fn record_undo(&mut self, at: Duration) -> Result<(), String> {
+        let (started_at, reconstruction) = self.current_solve()?;
+        reconstruction.record_undo(at.saturating_sub(started_at));
+        Ok(())
+    }
+
+    /// Stop timing the current solve, as finished, returning how long it took.
+    /// # Errors
+    /// Will return an Err variant when no solve is being timed
+    pub fn stop(&mut self, at: Duration) -> Result<Duration, String> {
+        let (started_at, _) = self.current_solve()?;
+        let time = at.saturating_sub(started_at);
+        self.finish(at, SolveTime::Time(time));
+        Ok(time)
+    }
+
+    /// Stop timing the current solve, recording it as not finished.
+    /// # Errors
+    /// Will return an Err variant when no solve is being timed
+    pub fn stop_as_dnf(&mut self, at: Duration) -> Result<(), String> {
+        self.current_solve()?;
+        self.finish(at, SolveTime::Dnf);
+        Ok(())
+    }
+
+    /// Returns the best result of every finished solve, or `None` if there are none.
+    #[must_use]
+    pub fn best(&self) -> Option<SolveTime> {
+        self.solves.iter().map(|solve| solve.time).min()
+    }
+
+    /// Returns the average of the last 5 solves, or `None` if there are fewer than 5.
+    #[must_use]
+    pub fn ao5(&self) -> Option<SolveTime> {
+        self.average_of(AO5)
+    }
+
+    /// Returns the average of the last 12 solves, or `None` if there are fewer than 12.
+    #[must_use]
+    pub fn ao12(&self) -> Option<SolveTime> {
+        self.average_of(AO12)
+    }
+
+    /// Returns the average of the last `count` solves, as used in competitions, or `None` if there are fewer solves than that or `count` is less than 3.
+    ///
+    /// The best and worst 5% of the results, rounded up to at least one each, are left out, and the rest are averaged. If any result that is not left out is a DNF, so is the average.
+    #[must_use]
+    pub fn average_of(&self, count: usize) -> Option<SolveTime> {
+        if count < 3 || self.solves.len() < count {
+            return None;
+        }
+        let mut times = self.solves[self.solves.len() - count..]
+            .iter()
+            .map(|solve| solve.time)
+            .collect::<Vec<_>>();
+        times.sort_unstable();
+        let trimmed = count.div_ceil(20);
+        let counted = &times[trimmed..count - trimmed];
+
+        let mut total = Duration::ZERO;
+        for time in counted {
+            match time {
+                SolveTime::Time(time) => total += *time,
+                SolveTime::Dnf => return Some(SolveTime::Dnf),
+            }
+        }
+        let counted = u32::try_from(counted.len()).expect("Averages are of few enough solves");
+        Some(SolveTime::Time(total / counted))
+    }
+
+    fn current_solve(&mut self) -> Result<(Duration, &mut Reconstruction), String> {
+        self.current
+            .as_mut()
+            .map(|(started_at, reconstruction)| (*started_at, reconstruction))
+            .ok_or_else(|| "No solve is being timed".to_string())
+    }
+
+    fn finish(&mut self, at: Duration, time: SolveTime) {
+        if let Some((started_at, reconstruction)) = self.current.take() {
+            self.solves.push(Solve {
+                reconstruction,
+                started_at,
+                stopped_at: at,
+                time,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::face::Face;
+    use pretty_assertions::assert_eq;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    fn session_of(times: &[Option<u64>]) -> SolveSession {
+        let mut session = SolveSession::new();
+        for (i, time) in times.iter().enumerate() {
+            let start = secs(100 * i as u64);
+            session.start(start, vec![]).unwrap();
+            match time {
+                Some(time) => {
+                    session.stop(start + secs(*time)).unwrap();
+                }
+                None => session.stop_as_dnf(start + secs(30)).unwrap(),
+            }
+        }
+        session
+    }
+
+    #[test]
+    fn test_moves_are_timed_from_start_of_solve() {
+        let mut session = SolveSession::new();
+        let r = Rotation::clockwise(Face::Right);
+        session.start(secs(10), vec![r.inverse()]).unwrap();
+        session.record_move(secs(12), r).unwrap();
+        session.record_undo(secs(13)).unwrap();
+        session.record_move(secs(14), r).unwrap();
+
+        assert_eq!(Ok(secs(5)), session.stop(secs(15)));
+        let solve = &session.solves()[0];
+        assert_eq!(vec![r.inverse()], solve.reconstruction.scramble);
+        assert_eq!(secs(2), solve.reconstruction.events[0].at);
+        assert_eq!(vec![r], solve.reconstruction.effective_moves());
+        assert_eq!((secs(10), secs(15)), (solve.started_at, solve.stopped_at));
+    }
+
+    #[test]
+    fn test_solves_must_be_started_once() {
+        let mut session = SolveSession::new();
+
+        assert_eq!(
+            Err("No solve is being timed".to_string()),
+            session.stop(secs(1))
+        );
+        session.start(secs(1), vec![]).unwrap();
+        assert!(session.is_solving());
+        assert_eq!(
+            Err("A solve is already being timed".to_string()),
+            session.start(secs(2), vec![])
+        );
+    }
+
+    #[test]
+    fn test_ao5_drops_best_and_worst() {
+        let session = session_of(&[Some(10), Some(18), Some(12), None, Some(15)]);
+
+        assert_eq!(Some(SolveTime::Time(secs(15))), session.ao5());
+        assert_eq!(None, session.ao12());
+        assert_eq!(Some(SolveTime::Time(secs(10))), session.best());
+    }
+
+    #[test]
+    fn test_average_with_two_dnfs_is_dnf() {
+        let session = session_of(&[Some(10), None, Some(12), None, Some(14)]);
+
+        assert_eq!(Some(SolveTime::Dnf), session.ao5());
+    }
+
+    #[test]
+    fn test_ao12_uses_last_twelve() {
+        let mut times = vec![None; 3];
+        times.extend((1..=12).map(Some));
+        let session = session_of(&times);
+
+        assert_eq!(
+            Some(SolveTime::Time(Duration::from_millis(6500))),
+            session.ao12()
+        );
+        assert_eq!(Some(SolveTime::Time(secs(10))), session.ao5());
+    }
+}
@@ -0,0 +1,489 @@
+use crate::{
+    cube::{
+        face::Face,
+        pieces::{self, CORNER_SLOTS, EDGE_SLOTS},
+        rotation::Rotation,
+        Cube,
+    },
+    notation::parse_sequence,
+};
+
+use super::{cancel_rotations, unsolvable, SOLVABLE_SIDE_LENGTH};
+
+const SIDES: [Face; 4] = [Face::Front, Face::Right, Face::Back, Face::Left];
+
+const CROSS_EDGE_FLIPPED: &str = "U' R' F R";
+const LIFT_CORNER: &str = "R U R'";
+const TWIST_CORNER_INTO_SLOT: &str = "R U R' U'";
+const INSERT_EDGE_RIGHT: &str = "U R U' R' U' F' U F";
+const INSERT_EDGE_LEFT: &str = "U' L' U L U F U' F'";
+const ORIENT_EDGES_FROM_LINE: &str = "F R U R' U' F'";
+const ORIENT_EDGES_FROM_CORNER: &str = "F U R U' R' F'";
+const TWIST_LAST_LAYER_CORNER: &str = "R' D' R D";
+const CYCLE_CORNERS: &str = "R' F R' B2 R F' R' B2 R2";
+const CYCLE_EDGES: [&str; 2] = ["R U' R U R U R U' R' U' R2", "R2 U R U R' U' R' U' R' U R'"];
+
+/// The attempts allowed at any one step before the cube is judged unsolvable, which is more than a solvable cube ever needs.
+const MAX_ATTEMPTS: usize = 8;
+
+/// Solve a 3x3 cube with the beginner's layer by layer method, returning the rotations that solve it.
+///
+/// The Down layer is solved first as a cross and then its corners, followed by the middle layer edges, and finally the Up layer. Solutions are far from the shortest, but each step follows a well known pattern so they are easy to follow by hand.
+/// The centres decide which colour belongs on each face, so cubes in any orientation can be solved.
+/// ```
+/// # use rusty_puzzle_cube::{cube::Cube, notation::perform_3x3_sequence, solver::layer_by_layer::solve};
+/// let mut cube = Cube::create(3);
+/// perform_3x3_sequence("R U2 F' L D' B2 R'", &mut cube).unwrap();
+/// for rotation in solve(&cube).unwrap() {
+///     cube.rotate(rotation);
+/// }
+/// assert!(cube.is_solved());
+/// ```
+/// # Errors
+/// Will return an Err variant when the cube is not 3x3, or is in a state that cannot be solved, such as a twisted corner or stickers of the wrong colours
+pub fn solve(cube: &Cube) -> Result<Vec<Rotation>, String> {
+    if cube.side_length() != SOLVABLE_SIDE_LENGTH {
+        return Err(format!(
+            "Only 3x3 cubes can be solved layer by layer, not {0}x{0}",
+            cube.side_length()
+        ));
+    }
+    let mut solver = Solver {
+        cube: cube.clone(),
+        solution: vec![],
+    };
+    solver.solve_cross()?;
+    solver.solve_first_layer_corners()?;
+    solver.solve_middle_layer()?;
+    solver.orient_last_layer_edges()?;
+    solver.orient_last_layer_corners()?;
+    solver.permute_last_layer()?;
+
+    if !solver.cube.is_solved() {
+        return Err(unsolvable());
+    }
+    Ok(cancel_rotations(&solver.solution))
+}
+
+struct Solver {
+    cube: Cube,
+    solution: Vec<Rotation>,
+}
+
+impl Solver {
+    fn solve_cross(&mut self) -> Result<(), String> {
+        for side in SIDES {
+            let colours = [Face::Down, side];
+            for _ in 0..MAX_ATTEMPTS {
+                let state = self.state()?;
+                let (slot, found) = state.find_edge(colours)?;
+                let [(_, first), (_, second)] = EDGE_SLOTS[slot];
+                if found == [first, second] && [first, second] == colours {
+                    break;
+                }
+                match (first, second) {
+                    (Face::Down, bottom_side) => {
+                        self.perform(&[Rotation::clockwise(bottom_side); 2])
+                    }
+                    (Face::Up, top_side) => {
+                        if top_side != side {
+                            self.perform(&[Rotation::clockwise(Face::Up)]);
+                        } else if found[0] == Face::Down {
+                            self.perform(&[Rotation::clockwise(side); 2]);
+                        } else {
+                            self.perform_relative(CROSS_EDGE_FLIPPED, side);
+                        }
+                    }
+                    (middle_side, other_side) => {
+                        let lift = [middle_side, other_side]
+                            .into_iter()
+                            .flat_map(|face| {
+                                [Rotation::clockwise(face), Rotation::anticlockwise(face)]
+                            })
+                            .find(|&rotation| {
+                                let mut lifted = self.clone_cube();
+                                lifted.rotate(rotation);
+                                State::read(&lifted)
+                                    .and_then(|state| state.find_edge(colours))
+                                    .is_ok_and(|(slot, _)| EDGE_SLOTS[slot][0].1 == Face::Up)
+                            })
+                            .ok_or_else(unsolvable)?;
+                        self.perform(&[lift, Rotation::clockwise(Face::Up), lift.inverse()]);
+                    }
+                }
+            }
+            self.ensure(|state| state.is_edge_solved([Face::Down, side]))?;
+        }
+        Ok(())
+    }
+
+    fn solve_first_layer_corners(&mut self) -> Result<(), String> {
+        for side in SIDES {
+            let colours = [Face::Down, side, right_of(side)];
+            for _ in 0..MAX_ATTEMPTS {
+                let state = self.state()?;
+                if state.is_corner_solved(colours) {
+                    break;
+                }
+                let slot = state.find_corner(colours)?;
+                let [(_, layer), (_, a), (_, b)] = CORNER_SLOTS[slot];
+                let slot_side = if right_of(a) == b { a } else { b };
+                if layer == Face::Down {
+                    self.perform_relative(LIFT_CORNER, slot_side);
+                } else if slot_side != side {
+                    self.perform(&[Rotation::clockwise(Face::Up)]);
+                } else {
+                    for _ in 0..MAX_ATTEMPTS {
+                        if self.state()?.is_corner_solved(colours) {
+                            break;
+                        }
+                        self.perform_relative(TWIST_CORNER_INTO_SLOT, side);
+                    }
+                }
+            }
+            self.ensure(|state| state.is_corner_solved(colours))?;
+        }
+        Ok(())
+    }
+
+    fn solve_middle_layer(&mut self) -> Result<(), String> {
+        for side in SIDES {
+            let colours = [side, right_of(side)];
+            for _ in 0..MAX_ATTEMPTS {
+                let state = self.state()?;
+                if state.is_edge_solved(colours) {
+                    break;
+                }
+                let (slot, found) = state.find_edge(colours)?;
+                let [(_, first), (_, second)] = EDGE_SLOTS[slot];
+                if first != Face::Up {
+                    let slot_side = if right_of(first) == second {
+                        first
+                    } else {
+                        second
+                    };
+                    self.perform_relative(INSERT_EDGE_RIGHT, slot_side);
+                } else if found[1] != second {
+                    self.perform(&[Rotation::clockwise(Face::Up)]);
+                } else if found[0] == right_of(second) {
+                    self.perform_relative(INSERT_EDGE_RIGHT, second);
+                } else {
+                    self.perform_relative(INSERT_EDGE_LEFT, second);
+                }
+            }
+            self.ensure(|state| state.is_edge_solved(colours))?;
+        }
+        Ok(())
+    }
+
+    fn orient_last_layer_edges(&mut self) -> Result<(), String> {
+        for _ in 0..MAX_ATTEMPTS {
+            let state = self.state()?;
+            let oriented = |side: Face| state.is_up_edge_oriented(side);
+            match SIDES.map(oriented) {
+                [true, true, true, true] => return Ok(()),
+                [false, true, false, true] | [false, false, false, false] => {
+                    self.perform_relative(ORIENT_EDGES_FROM_LINE, Face::Front);
+                }
+                [false, false, true, true] => {
+                    self.perform_relative(ORIENT_EDGES_FROM_CORNER, Face::Front);
+                }
+                _ => self.perform(&[Rotation::clockwise(Face::Up)]),
+            }
+        }
+        Err(unsolvable())
+    }
+
+    fn orient_last_layer_corners(&mut self) -> Result<(), String> {
+        for _ in SIDES {
+            for _ in 0..MAX_ATTEMPTS {
+                if self.state()?.is_up_corner_oriented() {
+                    break;
+                }
+                self.perform_relative(TWIST_LAST_LAYER_CORNER, Face::Front);
+            }
+            self.perform(&[Rotation::clockwise(Face::Up)]);
+        }
+        Ok(())
+    }
+
+    fn permute_last_layer(&mut self) -> Result<(), String> {
+        let setups = [
+            vec![Rotation::clockwise(Face::Up)],
+            vec![Rotation::anticlockwise(Face::Up)],
+            vec![Rotation::clockwise(Face::Up); 2],
+        ];
+        let corner_cycles = SIDES.map(|side| relative(CYCLE_CORNERS, side));
+        let corners = setups
+            .iter()
+            .cloned()
+            .chain(corner_cycles)
+            .collect::<Vec<_>>();
+        self.search(&corners, 5, |state| state.are_up_corners_solved())?;
+
+        let edges = SIDES
+            .into_iter()
+            .flat_map(|side| CYCLE_EDGES.map(|cycle| relative(cycle, side)))
+            .collect::<Vec<_>>();
+        self.search(&edges, 2, State::is_solved)
+    }
+
+    /// Find the fewest of the given steps that reach the goal, from 0 up to `max_depth` steps, and perform them.
+    fn search(
+        &mut self,
+        steps: &[Vec<Rotation>],
+        max_depth: usize,
+        goal: impl Fn(&State) -> bool,
+    ) -> Result<(), String> {
+        fn search_from(
+            cube: &Cube,
+            steps: &[Vec<Rotation>],
+            depth: usize,
+            goal: &dyn Fn(&Cube) -> bool,
+            path: &mut Vec<usize>,
+        ) -> bool {
+            if goal(cube) {
+                return true;
+            }
+            if depth == 0 {
+                return false;
+            }
+            for (i, step) in steps.iter().enumerate() {
+                let mut next = cube.clone();
+                step.iter().for_each(|&rotation| next.rotate(rotation));
+                path.push(i);
+                if search_from(&next, steps, depth - 1, goal, path) {
+                    return true;
+                }
+                path.pop();
+            }
+            false
+        }
+
+        let goal = |cube: &Cube| State::read(cube).is_ok_and(|state| goal(&state));
+        for depth in 0..=max_depth {
+            let mut path = vec![];
+            if search_from(&self.cube, steps, depth, &goal, &mut path) {
+                path.into_iter().for_each(|i| self.perform(&steps[i]));
+                return Ok(());
+            }
+        }
+        Err(unsolvable())
+    }
+
+    fn ensure(&self, check: impl Fn(&State) -> bool) -> Result<(), String> {
+        if check(&self.state()?) {
+            Ok(())
+        } else {
+            Err(unsolvable())
+        }
+    }
+
+    fn state(&self) -> Result<State, String> {
+        State::read(&self.cube)
+    }
+
+    fn clone_cube(&self) -> Cube {
+        self.cube.clone()
+    }
+
+    fn perform(&mut self, rotations: &[Rotation]) {
+        for &rotation in rotations {
+            self.cube.rotate(rotation);
+            self.solution.push(rotation);
+        }
+    }
+
+    fn perform_relative(&mut self, algorithm: &str, front: Face) {
+        self.perform(&relative(algorithm, front));
+    }
+}
+
+/// The colour of every sticker, given as the face whose centre has that colour.
+struct State([Face; 54]);
+
+impl State {
+    fn read(cube: &Cube) -> Result<Self, String> {
+        pieces::read_stickers(cube).map(Self).ok_or_else(unsolvable)
+    }
+
+    fn edge(&self, slot: usize) -> [Face; 2] {
+        EDGE_SLOTS[slot].map(|(i, _)| self.0[i])
+    }
+
+    fn corner(&self, slot: usize) -> [Face; 3] {
+        CORNER_SLOTS[slot].map(|(i, _)| self.0[i])
+    }
+
+    /// The slot holding the edge with the given colours, and its colours in the order of that slot's stickers.
+    fn find_edge(&self, colours: [Face; 2]) -> Result<(usize, [Face; 2]), String> {
+        (0..EDGE_SLOTS.len())
+            .map(|slot| (slot, self.edge(slot)))
+            .find(|(_, found)| found == &colours || found == &[colours[1], colours[0]])
+            .ok_or_else(unsolvable)
+    }
+
+    fn find_corner(&self, colours: [Face; 3]) -> Result<usize, String> {
+        (0..CORNER_SLOTS.len())
+            .find(|&slot| {
+                let found = self.corner(slot);
+                colours.iter().all(|colour| found.contains(colour))
+            })
+            .ok_or_else(unsolvable)
+    }
+
+    fn is_edge_solved(&self, colours: [Face; 2]) -> bool {
+        EDGE_SLOTS.iter().any(|slot| {
+            let faces = slot.map(|(_, face)| face);
+            (faces == colours || faces == [colours[1], colours[0]])
+                && slot.iter().all(|&(i, face)| self.0[i] == face)
+        })
+    }
+
+    fn is_corner_solved(&self, colours: [Face; 3]) -> bool {
+        CORNER_SLOTS.iter().any(|slot| {
+            colours
+                .iter()
+                .all(|colour| slot.iter().any(|(_, face)| face == colour))
+                && slot.iter().all(|&(i, face)| self.0[i] == face)
+        })
+    }
+
+    fn is_up_edge_oriented(&self, side: Face) -> bool {
+        EDGE_SLOTS
+            .iter()
+            .find(|slot| slot[0].1 == Face::Up && slot[1].1 == side)
+            .is_some_and(|slot| self.0[slot[0].0] == Face::Up)
+    }
+
+    fn is_up_corner_oriented(&self) -> bool {
+        self.0[CORNER_SLOTS[0][0].0] == Face::Up
+    }
+
+    fn are_up_corners_solved(&self) -> bool {
+        CORNER_SLOTS[..4]
+            .iter()
+            .all(|slot| slot.iter().all(|&(i, face)| self.0[i] == face))
+    }
+
+    fn is_solved(&self) -> bool {
+        let solved = |&(i, face): &(usize, Face)| self.0[i] == face;
+        EDGE_SLOTS.iter().flatten().all(solved) && CORNER_SLOTS.iter().flatten().all(solved)
+    }
+}
+
+fn right_of(side: Face) -> Face {
+    match side {
+        Face::Front => Face::Right,
+        Face::Right => Face::Back,
+        Face::Back => Face::Left,
+        Face::Left => Face::Front,
+        Face::Up | Face::Down => side,
+    }
+}
+
+/// The rotations of an algorithm written for the Front face, performed with the given side as the front instead.
+fn relative(algorithm: &str, front: Face) -> Vec<Rotation> {
+    let turns_from_front = SIDES.iter().position(|&side| side == front).unwrap_or(0);
+    parse_sequence(algorithm)
+        .expect("Solver algorithms are valid notation")
+        .into_iter()
+        .map(|rotation| {
+            let mut face = rotation.relative_to;
+            for _ in 0..turns_from_front {
+                face = right_of(face);
+            }
+            Rotation {
+                relative_to: face,
+                ..rotation
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cube::cubie_face::CubieFace, notation::perform_3x3_sequence, test_support::scrambles,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn assert_solves(cube: &Cube) {
+        let solution = solve(cube).unwrap();
+        let mut solved = cube.clone();
+        solution
+            .iter()
+            .for_each(|&rotation| solved.rotate(rotation));
+
+        assert!(solved.is_solved(), "{cube}was not solved by {solution:?}");
+    }
+
+    #[test]
+    fn test_solved_cube_needs_no_rotations() {
+        assert_eq!(Ok(vec![]), solve(&Cube::create(3)));
+    }
+
+    #[test]
+    fn test_single_turns() {
+        for face in SIDES.into_iter().chain([Face::Up, Face::Down]) {
+            let mut cube = Cube::create(3);
+            cube.rotate(Rotation::clockwise(face));
+
+            assert_solves(&cube);
+        }
+    }
+
+    #[test]
+    fn test_scrambles() {
+        for scramble in scrambles(0x2545_f491, 40, 25) {
+            let mut cube = Cube::create(3);
+            perform_3x3_sequence(&scramble, &mut cube).unwrap();
+
+            assert_solves(&cube);
+        }
+    }
+
+    #[test]
+    fn test_recoloured_cube() {
+        let mut cube =
+            Cube::try_from_facelet_string("FFFFFFFFFUUUUUUUUURRRRRRRRRBBBBBBBBBDDDDDDDDDLLLLLLLLL")
+                .unwrap();
+        perform_3x3_sequence("R U F' L2 D B'", &mut cube).unwrap();
+
+        assert_solves(&cube);
+    }
+
+    #[test]
+    fn test_wrong_side_length() {
+        assert_eq!(
+            Err("Only 3x3 cubes can be solved layer by layer, not 4x4".to_string()),
+            solve(&Cube::create(4))
+        );
+    }
+
+    #[test]
+    fn test_unsolvable_cubes() {
+        let solved = Cube::create(3).to_facelet_string();
+        let swap = |a: usize, b: usize| {
+            let mut facelets = solved.clone().into_bytes();
+            facelets.swap(a, b);
+            Cube::try_from_facelet_string(&String::from_utf8(facelets).unwrap()).unwrap()
+        };
+
+        let flipped_edge = swap(7, 19);
+        let mut twisted_corner = swap(8, 9);
+        twisted_corner = {
+            let mut facelets = twisted_corner.to_facelet_string().into_bytes();
+            facelets.swap(9, 20);
+            Cube::try_from_facelet_string(&String::from_utf8(facelets).unwrap()).unwrap()
+        };
+        let mut side_map = Cube::create(3).side_map().clone();
+        side_map[Face::Front][0][0] = CubieFace::White(None);
+        let wrong_colours = Cube::try_from_side_map(side_map).unwrap();
+
+        for cube in [flipped_edge, twisted_corner, wrong_colours] {
+            assert_eq!(Err(unsolvable()), solve(&cube), "{cube}");
+        }
+    }
+}
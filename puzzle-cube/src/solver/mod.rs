@@ -0,0 +1,71 @@
+use crate::cube::{direction::Direction, rotation::Rotation};
+
+/// Module providing the beginner's layer by layer method for solving a 3x3 cube.
+pub mod layer_by_layer;
+
+/// Module providing Kociemba's two-phase algorithm, which finds short solutions for a 3x3 cube.
+#[cfg(feature = "two-phase")]
+pub mod two_phase;
+
+const SOLVABLE_SIDE_LENGTH: usize = 3;
+
+/// Merge neighbouring rotations of the same layer, so that turns which cancel out are dropped and three quarter turns become one.
+fn cancel_rotations(rotations: &[Rotation]) -> Vec<Rotation> {
+    let mut merged: Vec<(Rotation, usize)> = vec![];
+    for &rotation in rotations {
+        let quarter_turns = match rotation.direction {
+            Direction::Clockwise => 1,
+            Direction::Anticlockwise => 3,
+        };
+        match merged.last_mut() {
+            Some((last, turns))
+                if last.relative_to == rotation.relative_to && last.layer == rotation.layer =>
+            {
+                *turns = (*turns + quarter_turns) % 4;
+                if *turns == 0 {
+                    merged.pop();
+                }
+            }
+            _ => merged.push((
+                Rotation::clockwise(rotation.relative_to).at_layer(rotation.layer),
+                quarter_turns,
+            )),
+        }
+    }
+    merged
+        .into_iter()
+        .flat_map(|(rotation, turns)| match turns {
+            3 => vec![rotation.inverse()],
+            turns => vec![rotation; turns],
+        })
+        .collect()
+}
+
+fn unsolvable() -> String {
+    "This cube cannot be solved, as it has a twisted corner, a flipped edge, swapped pieces or stickers of the wrong colours".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cube::face::Face;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_cancel_rotations() {
+        let r = Rotation::clockwise(Face::Right);
+        let u = Rotation::clockwise(Face::Up);
+        let m = Rotation::clockwise(Face::Right).at_layer(1);
+
+        assert_eq!(vec![r.inverse()], cancel_rotations(&[r, r, r]));
+        assert_eq!(
+            vec![r, u],
+            cancel_rotations(&[r, u, u.inverse(), u, r.inverse(), r])
+        );
+        assert_eq!(
+            Vec::<Rotation>::new(),
+            cancel_rotations(&[r, u, u.inverse(), r.inverse()])
+        );
+        assert_eq!(vec![r, m, m], cancel_rotations(&[r, m, m]));
+    }
+}
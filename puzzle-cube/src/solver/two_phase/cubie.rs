@@ -0,0 +1,184 @@
+use enum_map::Enum;
+
+use crate::cube::{face::Face, rotation::Rotation, Cube};
+
+use super::super::unsolvable;
+
+const CORNERS: usize = 8;
+const EDGES: usize = 12;
+/// Edges from this index onwards belong in the slice between Up and Down.
+const FIRST_SLICE_EDGE: u8 = 8;
+
+/// A 3x3 cube described by which piece is in each slot and how it is twisted or flipped there, with slots and pieces in Kociemba's order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct CubieCube {
+    corner_permutation: [u8; CORNERS],
+    corner_orientation: [u8; CORNERS],
+    edge_permutation: [u8; EDGES],
+    edge_orientation: [u8; EDGES],
+}
+
+pub(super) const SOLVED: CubieCube = CubieCube {
+    corner_permutation: [0, 1, 2, 3, 4, 5, 6, 7],
+    corner_orientation: [0; CORNERS],
+    edge_permutation: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+    edge_orientation: [0; EDGES],
+};
+
+impl CubieCube {
+    pub(super) fn read(cube: &Cube) -> Result<Self, String> {
+        cube.check_solvable().map_err(|_| unsolvable())?;
+        let corners = cube.corner_state()?;
+        let edges = cube.edge_state()?;
+        Ok(Self {
+            corner_permutation: corners.permutation.map(|piece| to_u8(piece.into_usize())),
+            corner_orientation: corners.orientation,
+            edge_permutation: edges.permutation.map(|piece| to_u8(piece.into_usize())),
+            edge_orientation: edges.orientation,
+        })
+    }
+
+    /// The pieces as they are after turning the given face of a solved cube clockwise, read from [`Cube`] so the two always agree.
+    pub(super) fn face_turn(face: Face) -> Self {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(face));
+        Self::read(&cube).expect("A solved cube turned once is always solvable")
+    }
+
+    /// The pieces as they are after performing the moves of `other` on this cube.
+    pub(super) fn then(&self, other: &Self) -> Self {
+        let mut result = SOLVED;
+        for slot in 0..CORNERS {
+            let from = usize::from(other.corner_permutation[slot]);
+            result.corner_permutation[slot] = self.corner_permutation[from];
+            result.corner_orientation[slot] =
+                (self.corner_orientation[from] + other.corner_orientation[slot]) % 3;
+        }
+        for slot in 0..EDGES {
+            let from = usize::from(other.edge_permutation[slot]);
+            result.edge_permutation[slot] = self.edge_permutation[from];
+            result.edge_orientation[slot] =
+                (self.edge_orientation[from] + other.edge_orientation[slot]) % 2;
+        }
+        result
+    }
+
+    /// How every corner but the last is twisted, from 0 to 2186. The twist of the last corner follows from the others.
+    pub(super) fn twist(&self) -> usize {
+        self.corner_orientation[..CORNERS - 1]
+            .iter()
+            .fold(0, |twist, &orientation| {
+                3 * twist + usize::from(orientation)
+            })
+    }
+
+    /// How every edge but the last is flipped, from 0 to 2047. The flip of the last edge follows from the others.
+    pub(super) fn flip(&self) -> usize {
+        self.edge_orientation[..EDGES - 1]
+            .iter()
+            .fold(0, |flip, &orientation| 2 * flip + usize::from(orientation))
+    }
+
+    /// Which four slots hold the edges that belong between Up and Down, ignoring their order, from 0 to 494. Solved is 0.
+    pub(super) fn slice(&self) -> usize {
+        let mut slice = 0;
+        let mut found = 0;
+        for slot in (0..EDGES).rev() {
+            if self.edge_permutation[slot] >= FIRST_SLICE_EDGE {
+                slice += choose(EDGES - 1 - slot, found + 1);
+                found += 1;
+            }
+        }
+        slice
+    }
+
+    /// The order of the corners, from 0 to 40319.
+    pub(super) fn corner_permutation(&self) -> usize {
+        rank(&self.corner_permutation)
+    }
+
+    /// The order of the edges of the Up and Down layers, from 0 to 40319. Only meaningful once every slice edge is in the slice.
+    pub(super) fn up_down_edge_permutation(&self) -> usize {
+        rank(&self.edge_permutation[..usize::from(FIRST_SLICE_EDGE)])
+    }
+
+    /// The order of the edges in the slice between Up and Down, from 0 to 23. Only meaningful once every slice edge is in the slice.
+    pub(super) fn slice_permutation(&self) -> usize {
+        rank(&self.edge_permutation[usize::from(FIRST_SLICE_EDGE)..])
+    }
+}
+
+fn to_u8(index: usize) -> u8 {
+    u8::try_from(index).expect("Slot and orientation indices are always small")
+}
+
+fn choose(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |result, i| result * (n - i) / (i + 1))
+}
+
+/// The position of this ordering among every ordering of the same pieces, counting from 0 for ascending order.
+fn rank(pieces: &[u8]) -> usize {
+    pieces.iter().enumerate().fold(0, |rank, (i, piece)| {
+        let smaller_after = pieces[i + 1..]
+            .iter()
+            .filter(|&other| other < piece)
+            .count();
+        rank * (pieces.len() - i) + smaller_after
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_read_solved() {
+        assert_eq!(Ok(SOLVED), CubieCube::read(&Cube::create(3)));
+    }
+
+    #[test]
+    fn test_face_turns_match_cube() {
+        let mut cube = Cube::create(3);
+        let mut cubie_cube = SOLVED;
+        for face in [
+            Face::Right,
+            Face::Up,
+            Face::Front,
+            Face::Down,
+            Face::Left,
+            Face::Back,
+        ] {
+            cube.rotate(Rotation::clockwise(face));
+            cubie_cube = cubie_cube.then(&CubieCube::face_turn(face));
+
+            assert_eq!(Ok(cubie_cube), CubieCube::read(&cube));
+        }
+    }
+
+    #[test]
+    fn test_coordinates_of_solved_are_zero() {
+        assert_eq!(
+            [0; 6],
+            [
+                SOLVED.twist(),
+                SOLVED.flip(),
+                SOLVED.slice(),
+                SOLVED.corner_permutation(),
+                SOLVED.up_down_edge_permutation(),
+                SOLVED.slice_permutation()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank() {
+        assert_eq!(0, rank(&[0, 1, 2, 3]));
+        assert_eq!(1, rank(&[0, 1, 3, 2]));
+        assert_eq!(23, rank(&[11, 10, 9, 8]));
+        assert_eq!(40319, rank(&[7, 6, 5, 4, 3, 2, 1, 0]));
+    }
+}
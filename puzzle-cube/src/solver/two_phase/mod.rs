@@ -0,0 +1,207 @@
+use crate::cube::{rotation::Rotation, Cube};
+
+use self::{
+    cubie::CubieCube,
+    tables::{all_moves, Phase1, Phase2, Tables, FACES, MOVES, PHASE_2_MOVES},
+};
+
+use super::SOLVABLE_SIDE_LENGTH;
+
+mod cubie;
+mod tables;
+
+/// The most moves a solution may have, counting half turns as one move. Every 3x3 cube can be solved in 20, and allowing a couple more lets the search finish quickly.
+pub const MAX_SOLUTION_MOVES: usize = 22;
+
+/// Solve a 3x3 cube with Kociemba's two-phase algorithm, returning the rotations that solve it.
+///
+/// Solutions are at most [`MAX_SOLUTION_MOVES`] moves, counting half turns as one move, though each half turn is returned as two clockwise rotations.
+/// Phase 1 brings the cube into the group that can be solved with only Up and Down turns and half turns of the other faces, and phase 2 solves it from there.
+/// Both phases are guided by tables that are generated the first time this is called, which takes a moment, and are kept for the rest of the program.
+/// ```
+/// # use rusty_puzzle_cube::{cube::Cube, notation::perform_3x3_sequence, solver::two_phase::solve};
+/// let mut cube = Cube::create(3);
+/// perform_3x3_sequence("R U2 F' L D' B2 R' U F2 D L'", &mut cube).unwrap();
+/// for rotation in solve(&cube).unwrap() {
+///     cube.rotate(rotation);
+/// }
+/// assert!(cube.is_solved());
+/// ```
+/// # Errors
+/// Will return an Err variant when the cube is not 3x3, or is in a state that cannot be solved, such as a twisted corner or stickers of the wrong colours
+pub fn solve(cube: &Cube) -> Result<Vec<Rotation>, String> {
+    if cube.side_length() != SOLVABLE_SIDE_LENGTH {
+        return Err(format!(
+            "Only 3x3 cubes can be solved with the two-phase algorithm, not {0}x{0}",
+            cube.side_length()
+        ));
+    }
+    let moves = find_moves(CubieCube::read(cube)?)?;
+    Ok(moves.into_iter().flat_map(to_rotations).collect())
+}
+
+fn find_moves(start: CubieCube) -> Result<Vec<usize>, String> {
+    let mut search = Search {
+        tables: Tables::get(),
+        moves: all_moves(),
+        start,
+        solution: vec![],
+    };
+    let phase_1 = (start.twist(), start.flip(), start.slice());
+    for depth in 0..=MAX_SOLUTION_MOVES {
+        if search.phase_1(phase_1, depth) {
+            return Ok(search.solution);
+        }
+    }
+    Err(format!(
+        "No solution of at most {MAX_SOLUTION_MOVES} moves was found"
+    ))
+}
+
+struct Search {
+    tables: &'static Tables,
+    moves: [CubieCube; MOVES],
+    start: CubieCube,
+    solution: Vec<usize>,
+}
+
+impl Search {
+    fn phase_1(&mut self, coordinates: Phase1, remaining: usize) -> bool {
+        if remaining == 0 {
+            let ends_in_phase_2_move = self
+                .solution
+                .last()
+                .is_some_and(|m| PHASE_2_MOVES.contains(m));
+            // Solutions ending in a phase 2 move are found sooner by handing over to phase 2 before that move
+            return coordinates == (0, 0, 0) && !ends_in_phase_2_move && self.start_phase_2();
+        }
+        if self.tables.phase_1_distance(coordinates) > remaining {
+            return false;
+        }
+        for m in 0..MOVES {
+            if self.is_redundant(m) {
+                continue;
+            }
+            self.solution.push(m);
+            if self.phase_1(self.tables.phase_1_move(coordinates, m), remaining - 1) {
+                return true;
+            }
+            self.solution.pop();
+        }
+        false
+    }
+
+    fn start_phase_2(&mut self) -> bool {
+        let cubie_cube = self
+            .solution
+            .iter()
+            .fold(self.start, |cubie_cube, &m| cubie_cube.then(&self.moves[m]));
+        let coordinates = (
+            cubie_cube.corner_permutation(),
+            cubie_cube.up_down_edge_permutation(),
+            cubie_cube.slice_permutation(),
+        );
+        let phase_1_length = self.solution.len();
+        (0..=MAX_SOLUTION_MOVES - phase_1_length).any(|depth| self.phase_2(coordinates, depth))
+    }
+
+    fn phase_2(&mut self, coordinates: Phase2, remaining: usize) -> bool {
+        if remaining == 0 {
+            return coordinates == (0, 0, 0);
+        }
+        if self.tables.phase_2_distance(coordinates) > remaining {
+            return false;
+        }
+        for (i, &m) in PHASE_2_MOVES.iter().enumerate() {
+            if self.is_redundant(m) {
+                continue;
+            }
+            self.solution.push(m);
+            if self.phase_2(self.tables.phase_2_move(coordinates, i), remaining - 1) {
+                return true;
+            }
+            self.solution.pop();
+        }
+        false
+    }
+
+    /// Whether move `m` turns the same face as the previous move, or turns a face opposite the previous move that could have been turned first instead.
+    fn is_redundant(&self, m: usize) -> bool {
+        self.solution.last().is_some_and(|&previous| {
+            let (face, previous_face) = (m / 3, previous / 3);
+            face == previous_face || face + 3 == previous_face
+        })
+    }
+}
+
+fn to_rotations(m: usize) -> Vec<Rotation> {
+    let face = FACES[m / 3];
+    match m % 3 {
+        0 => vec![Rotation::clockwise(face)],
+        1 => vec![Rotation::clockwise(face); 2],
+        _ => vec![Rotation::anticlockwise(face)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{notation::perform_3x3_sequence, solver::unsolvable, test_support::scrambles};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_solved_cube_needs_no_rotations() {
+        assert_eq!(Ok(vec![]), solve(&Cube::create(3)));
+    }
+
+    #[test]
+    fn test_scrambles_are_solved_in_few_moves() {
+        for scramble in scrambles(0x9e37_79b9, 10, 30) {
+            let mut cube = Cube::create(3);
+            perform_3x3_sequence(&scramble, &mut cube).unwrap();
+
+            let moves = find_moves(CubieCube::read(&cube).unwrap()).unwrap();
+            assert!(moves.len() <= MAX_SOLUTION_MOVES, "{scramble}");
+
+            moves
+                .into_iter()
+                .flat_map(to_rotations)
+                .for_each(|rotation| cube.rotate(rotation));
+            assert!(cube.is_solved(), "{scramble}");
+        }
+    }
+
+    #[test]
+    fn test_single_turn_is_undone_in_one_move() {
+        let mut cube = Cube::create(3);
+        perform_3x3_sequence("R2", &mut cube).unwrap();
+
+        assert_eq!(Ok(vec![4]), find_moves(CubieCube::read(&cube).unwrap()));
+    }
+
+    #[test]
+    fn test_wrong_side_length() {
+        assert_eq!(
+            Err("Only 3x3 cubes can be solved with the two-phase algorithm, not 2x2".to_string()),
+            solve(&Cube::create(2))
+        );
+    }
+
+    #[test]
+    fn test_unsolvable_cubes() {
+        let mut flipped_edge = Cube::create(3).to_facelet_string().into_bytes();
+        flipped_edge.swap(7, 19);
+        let mut twisted_corner = Cube::create(3).to_facelet_string().into_bytes();
+        twisted_corner.swap(8, 9);
+        twisted_corner.swap(9, 20);
+        let mut swapped_edges = Cube::create(3).to_facelet_string().into_bytes();
+        swapped_edges.swap(7, 5);
+        swapped_edges.swap(19, 10);
+
+        for facelets in [flipped_edge, twisted_corner, swapped_edges] {
+            let cube =
+                Cube::try_from_facelet_string(&String::from_utf8(facelets).unwrap()).unwrap();
+            assert_eq!(Err(unsolvable()), solve(&cube), "{cube}");
+        }
+    }
+}
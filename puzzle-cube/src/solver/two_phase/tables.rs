@@ -0,0 +1,227 @@
+use std::{collections::VecDeque, sync::OnceLock};
+
+use crate::cube::face::Face;
+
+use super::cubie::{CubieCube, SOLVED};
+
+/// Faces in the order their moves are numbered, with the clockwise, half and anticlockwise turns of each face numbered in turn.
+pub(super) const FACES: [Face; 6] = [
+    Face::Up,
+    Face::Right,
+    Face::Front,
+    Face::Down,
+    Face::Left,
+    Face::Back,
+];
+pub(super) const MOVES: usize = FACES.len() * 3;
+/// The moves that keep a cube in the subgroup reached by phase 1: any turn of Up or Down, and half turns of the other faces.
+pub(super) const PHASE_2_MOVES: [usize; 10] = [0, 1, 2, 4, 7, 9, 10, 11, 13, 16];
+
+const TWISTS: usize = 2187;
+const FLIPS: usize = 2048;
+const SLICES: usize = 495;
+const CORNER_PERMUTATIONS: usize = 40320;
+const UP_DOWN_EDGE_PERMUTATIONS: usize = 40320;
+const SLICE_PERMUTATIONS: usize = 24;
+
+/// Where each move takes each coordinate, and lower bounds on the moves needed to solve each pair of coordinates.
+pub(super) struct Tables {
+    twist: MoveTable,
+    flip: MoveTable,
+    slice: MoveTable,
+    corner_permutation: MoveTable,
+    up_down_edge_permutation: MoveTable,
+    slice_permutation: MoveTable,
+    twist_slice_depth: Vec<u8>,
+    flip_slice_depth: Vec<u8>,
+    corner_slice_depth: Vec<u8>,
+    edge_slice_depth: Vec<u8>,
+}
+
+impl Tables {
+    /// The tables, generated the first time they are needed and shared from then on. Generating them takes a moment, and much longer in debug builds.
+    pub(super) fn get() -> &'static Self {
+        static TABLES: OnceLock<Tables> = OnceLock::new();
+        TABLES.get_or_init(Self::generate)
+    }
+
+    fn generate() -> Self {
+        let moves = all_moves();
+        let phase_2_moves = PHASE_2_MOVES.map(|m| moves[m]);
+
+        let twist = MoveTable::generate(TWISTS, &moves, CubieCube::twist);
+        let flip = MoveTable::generate(FLIPS, &moves, CubieCube::flip);
+        let slice = MoveTable::generate(SLICES, &moves, CubieCube::slice);
+        let corner_permutation = MoveTable::generate(
+            CORNER_PERMUTATIONS,
+            &phase_2_moves,
+            CubieCube::corner_permutation,
+        );
+        let up_down_edge_permutation = MoveTable::generate(
+            UP_DOWN_EDGE_PERMUTATIONS,
+            &phase_2_moves,
+            CubieCube::up_down_edge_permutation,
+        );
+        let slice_permutation = MoveTable::generate(
+            SLICE_PERMUTATIONS,
+            &phase_2_moves,
+            CubieCube::slice_permutation,
+        );
+
+        Self {
+            twist_slice_depth: depth_table(&twist, &slice),
+            flip_slice_depth: depth_table(&flip, &slice),
+            corner_slice_depth: depth_table(&corner_permutation, &slice_permutation),
+            edge_slice_depth: depth_table(&up_down_edge_permutation, &slice_permutation),
+            twist,
+            flip,
+            slice,
+            corner_permutation,
+            up_down_edge_permutation,
+            slice_permutation,
+        }
+    }
+
+    /// The phase 1 coordinates after performing move `m`, numbered as in [`FACES`].
+    pub(super) fn phase_1_move(&self, (twist, flip, slice): Phase1, m: usize) -> Phase1 {
+        (
+            self.twist.apply(twist, m),
+            self.flip.apply(flip, m),
+            self.slice.apply(slice, m),
+        )
+    }
+
+    /// The phase 2 coordinates after performing the move at index `m` of [`PHASE_2_MOVES`].
+    pub(super) fn phase_2_move(&self, (corners, edges, slice): Phase2, m: usize) -> Phase2 {
+        (
+            self.corner_permutation.apply(corners, m),
+            self.up_down_edge_permutation.apply(edges, m),
+            self.slice_permutation.apply(slice, m),
+        )
+    }
+
+    /// The fewest moves that could possibly finish phase 1 from these coordinates.
+    pub(super) fn phase_1_distance(&self, (twist, flip, slice): Phase1) -> usize {
+        let twist_slice = self.twist_slice_depth[twist * SLICES + slice];
+        let flip_slice = self.flip_slice_depth[flip * SLICES + slice];
+        usize::from(twist_slice.max(flip_slice))
+    }
+
+    /// The fewest phase 2 moves that could possibly solve the cube from these coordinates.
+    pub(super) fn phase_2_distance(&self, (corners, edges, slice): Phase2) -> usize {
+        let corner_slice = self.corner_slice_depth[corners * SLICE_PERMUTATIONS + slice];
+        let edge_slice = self.edge_slice_depth[edges * SLICE_PERMUTATIONS + slice];
+        usize::from(corner_slice.max(edge_slice))
+    }
+}
+
+/// Twist, flip and slice, which are all 0 once phase 1 is complete.
+pub(super) type Phase1 = (usize, usize, usize);
+/// Corner, Up and Down edge, and slice permutations, which are all 0 once the cube is solved.
+pub(super) type Phase2 = (usize, usize, usize);
+
+/// Every move as pieces, numbered as in [`FACES`].
+pub(super) fn all_moves() -> [CubieCube; MOVES] {
+    let quarter_turns = FACES.map(CubieCube::face_turn);
+    std::array::from_fn(|m| {
+        let quarter_turn = quarter_turns[m / 3];
+        (0..m % 3).fold(quarter_turn, |turned, _| turned.then(&quarter_turn))
+    })
+}
+
+struct MoveTable {
+    moves: usize,
+    next: Vec<u16>,
+}
+
+impl MoveTable {
+    /// Find where every move takes every coordinate, by exploring outwards from solved and keeping one cube for each coordinate reached.
+    fn generate(size: usize, moves: &[CubieCube], coordinate: fn(&CubieCube) -> usize) -> Self {
+        let mut next = vec![0; size * moves.len()];
+        let mut seen = vec![false; size];
+        seen[coordinate(&SOLVED)] = true;
+        let mut queue = VecDeque::from([SOLVED]);
+        while let Some(cubie_cube) = queue.pop_front() {
+            let from = coordinate(&cubie_cube);
+            for (m, move_cube) in moves.iter().enumerate() {
+                let moved = cubie_cube.then(move_cube);
+                let to = coordinate(&moved);
+                next[from * moves.len() + m] =
+                    u16::try_from(to).expect("Every coordinate fits in a u16");
+                if !seen[to] {
+                    seen[to] = true;
+                    queue.push_back(moved);
+                }
+            }
+        }
+        Self {
+            moves: moves.len(),
+            next,
+        }
+    }
+
+    fn apply(&self, coordinate: usize, m: usize) -> usize {
+        usize::from(self.next[coordinate * self.moves + m])
+    }
+
+    fn size(&self) -> usize {
+        self.next.len() / self.moves
+    }
+}
+
+/// The fewest moves needed to solve each pair of coordinates together, found by exploring outwards from solved.
+fn depth_table(first: &MoveTable, second: &MoveTable) -> Vec<u8> {
+    let second_size = second.size();
+    let mut depths = vec![u8::MAX; first.size() * second_size];
+    depths[0] = 0;
+    let mut queue = VecDeque::from([(0, 0)]);
+    while let Some((a, b)) = queue.pop_front() {
+        let next_depth = depths[a * second_size + b] + 1;
+        for m in 0..first.moves {
+            let (next_a, next_b) = (first.apply(a, m), second.apply(b, m));
+            let depth = &mut depths[next_a * second_size + next_b];
+            if *depth == u8::MAX {
+                *depth = next_depth;
+                queue.push_back((next_a, next_b));
+            }
+        }
+    }
+    depths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_coordinate_is_reached() {
+        let tables = Tables::get();
+
+        for depths in [
+            &tables.twist_slice_depth,
+            &tables.flip_slice_depth,
+            &tables.corner_slice_depth,
+            &tables.edge_slice_depth,
+        ] {
+            assert!(depths.iter().all(|&depth| depth != u8::MAX));
+        }
+    }
+
+    #[test]
+    fn test_distances_are_within_known_phase_limits() {
+        let tables = Tables::get();
+
+        assert!(tables.twist_slice_depth.iter().all(|&depth| depth <= 12));
+        assert!(tables.flip_slice_depth.iter().all(|&depth| depth <= 12));
+        assert!(tables.corner_slice_depth.iter().all(|&depth| depth <= 18));
+        assert!(tables.edge_slice_depth.iter().all(|&depth| depth <= 18));
+    }
+
+    #[test]
+    fn test_phase_2_moves_are_half_turns_or_up_and_down() {
+        for m in PHASE_2_MOVES {
+            let face = FACES[m / 3];
+            assert!(m % 3 == 1 || face == Face::Up || face == Face::Down);
+        }
+    }
+}
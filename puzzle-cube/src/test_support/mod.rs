@@ -0,0 +1,25 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Returns a random number generator that picks the same numbers every time it is created with the same seed, so tests of random input are repeatable.
+pub(crate) fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Returns `count` scrambles of `length` outer face turns each, written in notation, which are the same every time they are made with the same seed.
+pub(crate) fn scrambles(seed: u64, count: usize, length: usize) -> Vec<String> {
+    let faces = ["F", "R", "U", "L", "B", "D"];
+    let modifiers = ["", "'", "2"];
+    let mut rng = seeded_rng(seed);
+    (0..count)
+        .map(|_| {
+            (0..length)
+                .map(|_| {
+                    let face = faces[rng.gen_range(0..faces.len())];
+                    let modifier = modifiers[rng.gen_range(0..modifiers.len())];
+                    format!("{face}{modifier}")
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
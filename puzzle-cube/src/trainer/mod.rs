@@ -0,0 +1,589 @@
+use std::time::Duration;
+
+use crate::{
+    cube::{face::Face, rotation::Rotation, Cube},
+    notation::parse_sequence,
+    recognition::{OllCase, PllCase},
+};
+
+const TRAINER_SIDE_LENGTH: usize = 3;
+
+/// A named algorithm that a trainer session can drill, with any other known algorithms that solve the same case.
+///
+/// The case is set up on a solved 3x3x3 cube with the inverse of its algorithm.
+/// ```
+/// # use rusty_puzzle_cube::{notation::parse_sequence, trainer::{AlgorithmCase, Goal}};
+/// let case = AlgorithmCase::new("Sune", "R U R' U R U2 R'")
+///     .unwrap()
+///     .with_goal(Goal::OrientLastLayer)
+///     .with_alternatives(&["U L' U2 L U L' U L"])
+///     .unwrap();
+/// assert!(!Goal::OrientLastLayer.is_reached(&case.set_up()));
+/// assert!(case.is_solved_by(&case.alternatives[0]));
+/// assert!(!case.is_solved_by(&parse_sequence("R U R'").unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlgorithmCase {
+    /// The name shown for this case, such as `T-perm`.
+    pub name: String,
+    /// The rotations that solve this case, which it is usually taught with.
+    pub algorithm: Vec<Rotation>,
+    /// Other known algorithms that solve this case.
+    pub alternatives: Vec<Vec<Rotation>>,
+    /// What an attempt at this case must achieve to be solved.
+    pub goal: Goal,
+}
+
+impl AlgorithmCase {
+    /// Create a case from an algorithm written in notation, which is solved once the whole cube is solved.
+    /// # Errors
+    /// Will return an Err variant when `algorithm` is malformed notation
+    pub fn new(name: impl Into<String>, algorithm: &str) -> Result<Self, String> {
+        Ok(Self {
+            name: name.into(),
+            algorithm: parse_sequence(algorithm).map_err(|e| e.to_string())?,
+            alternatives: Vec::new(),
+            goal: Goal::Solve,
+        })
+    }
+
+    /// Use the given goal to decide when an attempt at this case is solved, instead of solving the whole cube.
+    #[must_use]
+    pub fn with_goal(self, goal: Goal) -> Self {
+        Self { goal, ..self }
+    }
+
+    /// Add other algorithms, written in notation, that are known to solve this case.
+    /// # Errors
+    /// Will return an Err variant when any of `alternatives` is malformed notation
+    pub fn with_alternatives(mut self, alternatives: &[&str]) -> Result<Self, String> {
+        for alternative in alternatives {
+            self.alternatives
+                .push(parse_sequence(alternative).map_err(|e| e.to_string())?);
+        }
+        Ok(self)
+    }
+
+    /// Returns a solved 3x3x3 cube with the inverse of this case's algorithm applied.
+    #[must_use]
+    pub fn set_up(&self) -> Cube {
+        let mut cube = Cube::create(TRAINER_SIDE_LENGTH);
+        self.algorithm
+            .iter()
+            .rev()
+            .for_each(|rotation| cube.rotate(rotation.inverse()));
+        cube
+    }
+
+    /// Returns whether the given rotations, applied to this case once set up, reach its goal.
+    #[must_use]
+    pub fn is_solved_by(&self, rotations: &[Rotation]) -> bool {
+        let mut cube = self.set_up();
+        rotations.iter().for_each(|&rotation| cube.rotate(rotation));
+        self.goal.is_reached(&cube)
+    }
+}
+
+/// Timing statistics collected for a single case.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaseStats {
+    /// How many attempts were started and then either finished or abandoned.
+    pub attempts: u32,
+    /// How many attempts solved the case.
+    pub successes: u32,
+    /// The fastest successful attempt.
+    pub best: Option<Duration>,
+    total_successful: Duration,
+}
+
+impl CaseStats {
+    /// The mean time of all successful attempts.
+    #[must_use]
+    pub fn mean(&self) -> Option<Duration> {
+        (self.successes > 0).then(|| self.total_successful / self.successes)
+    }
+}
+
+/// Where a trainer session is in its cycle of presenting a case and checking the attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrainerState {
+    /// No case is being attempted.
+    Idle,
+    /// The case at `case_index` has been set up and is waiting for the user's moves.
+    Attempting {
+        /// The index of the case being attempted.
+        case_index: usize,
+        /// How many quarter turns the user has made so far.
+        moves: usize,
+    },
+    /// The case at `case_index` has been solved, and is waiting for its time to be recorded.
+    Solved {
+        /// The index of the case that was solved.
+        case_index: usize,
+        /// How many quarter turns the user needed.
+        moves: usize,
+    },
+}
+
+/// A session drilling a set of algorithm cases on a 3x3x3 cube.
+///
+/// Each attempt starts from a solved cube with the inverse of the case's algorithm applied. The attempt is solved once the cube reaches the case's goal, such as being solved allowing any final turn of the Up face (AUF), so equivalent algorithms are accepted too.
+pub struct TrainerSession {
+    cases: Vec<AlgorithmCase>,
+    stats: Vec<CaseStats>,
+    cube: Cube,
+    state: TrainerState,
+}
+
+impl TrainerSession {
+    /// Create a session drilling the given cases.
+    /// # Errors
+    /// Will return an Err variant when `cases` is empty
+    pub fn new(cases: Vec<AlgorithmCase>) -> Result<Self, String> {
+        if cases.is_empty() {
+            return Err("A trainer session needs at least one case".to_string());
+        }
+        Ok(Self {
+            stats: vec![CaseStats::default(); cases.len()],
+            cases,
+            cube: Cube::create(TRAINER_SIDE_LENGTH),
+            state: TrainerState::Idle,
+        })
+    }
+
+    /// The cases this session drills.
+    #[must_use]
+    pub fn cases(&self) -> &[AlgorithmCase] {
+        &self.cases
+    }
+
+    /// The statistics recorded for each case, in the same order as [`TrainerSession::cases`].
+    #[must_use]
+    pub fn stats(&self) -> &[CaseStats] {
+        &self.stats
+    }
+
+    /// The cube the user is working on.
+    #[must_use]
+    pub fn cube(&self) -> &Cube {
+        &self.cube
+    }
+
+    /// The current state of the session.
+    #[must_use]
+    pub fn state(&self) -> TrainerState {
+        self.state
+    }
+
+    /// Set up the cube for the case at `case_index`. Any attempt in progress is abandoned.
+    /// # Errors
+    /// Will return an Err variant when `case_index` is out of range
+    pub fn start_case(&mut self, case_index: usize) -> Result<(), String> {
+        let cube = self
+            .cases
+            .get(case_index)
+            .ok_or_else(|| format!("No trainer case at index {case_index}"))?
+            .set_up();
+
+        self.abandon();
+        self.cube = cube;
+        self.state = TrainerState::Attempting {
+            case_index,
+            moves: 0,
+        };
+        Ok(())
+    }
+
+    /// Set up the case after the one most recently attempted, wrapping around to the first.
+    pub fn start_next_case(&mut self) {
+        let next_index = match self.state {
+            TrainerState::Idle => self
+                .stats
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, stats)| stats.attempts)
+                .map_or(0, |(index, _)| index),
+            TrainerState::Attempting { case_index, .. }
+            | TrainerState::Solved { case_index, .. } => (case_index + 1) % self.cases.len(),
+        };
+        self.start_case(next_index)
+            .expect("Next case index is always in range");
+    }
+
+    /// Apply one of the user's rotations to the cube, returning the new state.
+    /// # Errors
+    /// Will return an Err variant when no attempt is in progress
+    pub fn rotate(&mut self, rotation: Rotation) -> Result<TrainerState, String> {
+        let TrainerState::Attempting { case_index, moves } = self.state else {
+            return Err("No trainer case is being attempted".to_string());
+        };
+
+        self.cube.rotate(rotation);
+        let moves = moves + 1;
+        self.state = if self.cases[case_index].goal.is_reached(&self.cube) {
+            TrainerState::Solved { case_index, moves }
+        } else {
+            TrainerState::Attempting { case_index, moves }
+        };
+        Ok(self.state)
+    }
+
+    /// Record the time taken by a solved attempt and return to idle.
+    /// # Errors
+    /// Will return an Err variant when the current case has not been solved
+    pub fn record_time(&mut self, elapsed: Duration) -> Result<(), String> {
+        let TrainerState::Solved { case_index, .. } = self.state else {
+            return Err("Only a solved case can have its time recorded".to_string());
+        };
+
+        let stats = &mut self.stats[case_index];
+        stats.attempts += 1;
+        stats.successes += 1;
+        stats.total_successful += elapsed;
+        stats.best = Some(stats.best.map_or(elapsed, |best| best.min(elapsed)));
+        self.state = TrainerState::Idle;
+        Ok(())
+    }
+
+    /// Give up on any attempt in progress, counting it as an unsuccessful attempt, and return to idle.
+    pub fn abandon(&mut self) {
+        if let TrainerState::Attempting { case_index, .. } = self.state {
+            self.stats[case_index].attempts += 1;
+        }
+        self.state = TrainerState::Idle;
+    }
+}
+
+/// What the algorithms of an [`AlgorithmCase`] must achieve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// The first two layers must be left solved, and every sticker on the Up face must be the Up colour.
+    OrientLastLayer,
+    /// The whole cube must be solved, allowing any final turn of the Up face (AUF).
+    Solve,
+}
+
+impl Goal {
+    /// Returns whether the given 3x3x3 cube, held with white on the Up face and blue on the Front face, has reached this goal.
+    #[must_use]
+    pub fn is_reached(self, cube: &Cube) -> bool {
+        let solved = Cube::create(TRAINER_SIDE_LENGTH);
+        match self {
+            Goal::OrientLastLayer => {
+                let (sides, solved_sides) = (cube.side_map(), solved.side_map());
+                sides[Face::Up] == solved_sides[Face::Up]
+                    && sides[Face::Down] == solved_sides[Face::Down]
+                    && [Face::Front, Face::Right, Face::Back, Face::Left]
+                        .into_iter()
+                        .all(|face| {
+                            (1..TRAINER_SIDE_LENGTH)
+                                .all(|row| sides[face][row] == solved_sides[face][row])
+                        })
+            }
+            Goal::Solve => {
+                let mut cube = cube.clone();
+                (0..4).any(|_| {
+                    cube.rotate(Rotation::clockwise(Face::Up));
+                    cube == solved
+                })
+            }
+        }
+    }
+}
+
+/// Returns a starter set of cases that orient the last layer (OLL), chosen to be quick to learn.
+#[must_use]
+pub fn oll_cases() -> Vec<AlgorithmCase> {
+    OLL_CASES
+        .iter()
+        .map(|&(name, case, alternatives)| {
+            starter_case(name, Goal::OrientLastLayer, case.algorithm(), alternatives)
+        })
+        .collect()
+}
+
+/// Returns a starter set of cases that permute the last layer (PLL), once it is oriented.
+#[must_use]
+pub fn pll_cases() -> Vec<AlgorithmCase> {
+    PLL_CASES
+        .iter()
+        .map(|&(case, alternatives)| {
+            starter_case(
+                &case.to_string(),
+                Goal::Solve,
+                case.algorithm(),
+                alternatives,
+            )
+        })
+        .collect()
+}
+
+/// Each case is taught with the algorithm [`OllCase::algorithm`] gives, followed by any alternatives.
+const OLL_CASES: &[(&str, OllCase, &[&str])] = &[
+    ("Sune", OllCase::Oll27, &["U L' U2 L U L' U L"]),
+    ("Antisune", OllCase::Oll26, &["U L' U' L U' L' U2 L"]),
+    ("H", OllCase::Oll21, &["F R U R' U' R U R' U' R U R' U' F'"]),
+    ("Pi", OllCase::Oll22, &[]),
+    ("Headlights", OllCase::Oll23, &[]),
+    ("T", OllCase::Oll45, &[]),
+    ("Small T", OllCase::Oll33, &[]),
+];
+
+/// Each case is taught with the algorithm [`PllCase::algorithm`] gives, followed by any alternatives.
+const PLL_CASES: &[(PllCase, &[&str])] = &[
+    (PllCase::T, &[]),
+    (PllCase::Ua, &["M2 U M U2 M' U M2"]),
+    (PllCase::Ub, &["M2 U' M U2 M' U' M2"]),
+    (PllCase::H, &[]),
+    (PllCase::Z, &[]),
+    (PllCase::Aa, &[]),
+    (PllCase::Ab, &[]),
+    (PllCase::Ja, &[]),
+    (PllCase::Jb, &[]),
+    (PllCase::Y, &[]),
+    (PllCase::F, &[]),
+];
+
+fn starter_case(name: &str, goal: Goal, algorithm: &str, alternatives: &[&str]) -> AlgorithmCase {
+    AlgorithmCase::new(name, algorithm)
+        .and_then(|case| case.with_goal(goal).with_alternatives(alternatives))
+        .expect("Starter case algorithms are valid notation")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const T_PERM: &str = "R U R' U' R' F R2 U' R' U' R U R' F'";
+
+    fn session() -> TrainerSession {
+        TrainerSession::new(vec![
+            AlgorithmCase::new("T-perm", T_PERM).unwrap(),
+            AlgorithmCase::new("Sune", "R U R' U R U2 R'").unwrap(),
+        ])
+        .unwrap()
+    }
+
+    fn perform(session: &mut TrainerSession, sequence: &str) -> TrainerState {
+        parse_sequence(sequence)
+            .unwrap()
+            .into_iter()
+            .map(|rotation| session.rotate(rotation).unwrap())
+            .last()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new_without_cases() {
+        assert!(TrainerSession::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_start_case_sets_up_unsolved_cube() {
+        let mut session = session();
+
+        session.start_case(0).unwrap();
+
+        assert_ne!(&Cube::create(3), session.cube());
+        assert_eq!(
+            TrainerState::Attempting {
+                case_index: 0,
+                moves: 0
+            },
+            session.state()
+        );
+    }
+
+    #[test]
+    fn test_start_case_out_of_range() {
+        assert_eq!(
+            Err("No trainer case at index 2".to_string()),
+            session().start_case(2)
+        );
+    }
+
+    #[test]
+    fn test_expected_algorithm_solves_case() {
+        let mut session = session();
+        session.start_case(0).unwrap();
+
+        assert_eq!(
+            TrainerState::Solved {
+                case_index: 0,
+                moves: 15
+            },
+            perform(&mut session, T_PERM)
+        );
+    }
+
+    #[test]
+    fn test_final_auf_is_allowed() {
+        let mut session =
+            TrainerSession::new(vec![
+                AlgorithmCase::new("T-perm", &format!("{T_PERM} U")).unwrap()
+            ])
+            .unwrap();
+        session.start_case(0).unwrap();
+
+        assert_eq!(
+            TrainerState::Solved {
+                case_index: 0,
+                moves: 15
+            },
+            perform(&mut session, T_PERM)
+        );
+    }
+
+    #[test]
+    fn test_wrong_algorithm_is_still_attempting() {
+        let mut session = session();
+        session.start_case(1).unwrap();
+
+        assert_eq!(
+            TrainerState::Attempting {
+                case_index: 1,
+                moves: 3
+            },
+            perform(&mut session, "R U R'")
+        );
+    }
+
+    #[test]
+    fn test_rotate_while_idle() {
+        assert!(session().rotate(Rotation::clockwise(Face::Up)).is_err());
+    }
+
+    #[test]
+    fn test_record_time_updates_stats() {
+        let mut session = session();
+        for millis in [3000, 2000] {
+            session.start_case(0).unwrap();
+            perform(&mut session, T_PERM);
+            session.record_time(Duration::from_millis(millis)).unwrap();
+        }
+
+        let stats = &session.stats()[0];
+        assert_eq!(2, stats.attempts);
+        assert_eq!(2, stats.successes);
+        assert_eq!(Some(Duration::from_millis(2000)), stats.best);
+        assert_eq!(Some(Duration::from_millis(2500)), stats.mean());
+        assert_eq!(TrainerState::Idle, session.state());
+    }
+
+    #[test]
+    fn test_record_time_before_solved() {
+        let mut session = session();
+        session.start_case(0).unwrap();
+
+        assert!(session.record_time(Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn test_abandon_counts_unsuccessful_attempt() {
+        let mut session = session();
+        session.start_case(1).unwrap();
+
+        session.abandon();
+
+        assert_eq!(1, session.stats()[1].attempts);
+        assert_eq!(0, session.stats()[1].successes);
+        assert_eq!(None, session.stats()[1].mean());
+    }
+
+    #[test]
+    fn test_every_starter_solution_solves_its_case() {
+        for case in oll_cases().into_iter().chain(pll_cases()) {
+            assert!(!case.goal.is_reached(&case.set_up()), "{}", case.name);
+            assert!(case.is_solved_by(&case.algorithm), "{}", case.name);
+            for alternative in &case.alternatives {
+                assert!(case.is_solved_by(alternative), "{}", case.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_orient_last_layer_ignores_last_layer_sides() {
+        let mut cube = Cube::create(3);
+        cube.rotate(Rotation::clockwise(Face::Up));
+        assert!(Goal::OrientLastLayer.is_reached(&cube));
+
+        cube.rotate(Rotation::clockwise(Face::Right));
+        assert!(!Goal::OrientLastLayer.is_reached(&cube));
+    }
+
+    #[test]
+    fn test_pll_is_solved_allowing_auf() {
+        let case = &pll_cases()[0];
+        let mut solution = case.algorithm.clone();
+        solution.push(Rotation::anticlockwise(Face::Up));
+
+        assert!(case.is_solved_by(&solution));
+        assert!(!case.is_solved_by(&[]));
+    }
+
+    #[test]
+    fn test_malformed_alternative() {
+        assert!(AlgorithmCase::new("T-perm", T_PERM)
+            .unwrap()
+            .with_alternatives(&["R Q"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_session_accepts_alternative_algorithms() {
+        for name in ["Sune", "Antisune", "H"] {
+            let case = oll_cases()
+                .into_iter()
+                .find(|case| case.name == name)
+                .unwrap();
+            let alternative = case.alternatives[0].clone();
+            let mut session = TrainerSession::new(vec![case]).unwrap();
+            session.start_case(0).unwrap();
+
+            let state = alternative
+                .iter()
+                .map(|&rotation| session.rotate(rotation).unwrap())
+                .last();
+
+            assert_eq!(
+                Some(TrainerState::Solved {
+                    case_index: 0,
+                    moves: alternative.len()
+                }),
+                state,
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_session_sets_up_the_case() {
+        let case = AlgorithmCase::new("T-perm", T_PERM).unwrap();
+        let mut session = TrainerSession::new(vec![case.clone()]).unwrap();
+        session.start_case(0).unwrap();
+
+        assert_eq!(&case.set_up(), session.cube());
+    }
+
+    #[test]
+    fn test_start_next_case_cycles() {
+        let mut session = session();
+
+        session.start_next_case();
+        assert!(matches!(
+            session.state(),
+            TrainerState::Attempting { case_index: 0, .. }
+        ));
+        session.start_next_case();
+        assert!(matches!(
+            session.state(),
+            TrainerState::Attempting { case_index: 1, .. }
+        ));
+        session.start_next_case();
+        assert!(matches!(
+            session.state(),
+            TrainerState::Attempting { case_index: 0, .. }
+        ));
+    }
+}
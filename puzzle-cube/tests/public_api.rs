@@ -0,0 +1,849 @@
+//! A snapshot of the public API of this crate, checked by the compiler.
+//!
+//! Every exported item is named here with its exact signature, every public enum is matched exhaustively, and every struct with public fields is built from a literal. Removing or renaming an item, changing a signature, adding an enum variant or adding a public field will stop this file compiling.
+//!
+//! If that happens the change is breaking for users of the crate. Either rework it to be additive, or update the snapshot here in the same change so the next release can bump the version accordingly.
+
+use std::{
+    error::Error,
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use enum_map::EnumMap;
+use rand::rngs::StdRng;
+use rusty_puzzle_cube::{
+    budget::{Budget, CancellationToken, InterruptReason, Interrupted},
+    cube::{
+        colour_counts::ColourCountError,
+        colour_scheme::{self, ColourScheme, SchemedCube, JAPANESE, WESTERN},
+        cubie_face::CubieFace,
+        direction::Direction,
+        face::Face,
+        facelets::{ImportOptions, ImportOrientation},
+        grid::Grid,
+        parallel_rotation::ParallelRotation,
+        pieces::{Corner, CornerState, Edge, EdgeState},
+        render_text::{NetLayout, StickerChars, TextRenderOptions},
+        rotation::Rotation,
+        sequence::{Op, Sequence},
+        sticker_origin::StickerOrigin,
+        Cube, SideMap, StickerOriginMap, StickerRotationMap,
+    },
+    history::HistoryCube,
+    known_transforms,
+    metrics::{MoveCounter, MoveCountingCube, MoveCounts},
+    notation::{
+        self,
+        grammar::{self, Grammar, Modifier},
+        ParseError, ParseOptions, TokenSpan,
+    },
+    patterns,
+    recognition::{recognise_oll, recognise_pll, OllCase, PllCase},
+    reconstruction::{Event, Reconstruction, TimedEvent},
+    recording::{BroadcastSink, LogSink, MoveSink, RecordingCube, RobotSink},
+    robot::{FaceCommands, RobotExporter},
+    search::{
+        fixed_corner_turns, ida_star, outer_face_turns, CornerTwistDatabase, Heuristic,
+        MisplacedStickers,
+    },
+    session::{Solve, SolveSession, SolveTime},
+    solver::layer_by_layer,
+    trainer::{oll_cases, pll_cases, AlgorithmCase, CaseStats, Goal, TrainerSession, TrainerState},
+};
+
+fn value_type<T: Debug + Clone + PartialEq>() {}
+fn copy_type<T: Debug + Copy + Eq>() {}
+fn hash_type<T: Eq + Hash>() {}
+
+#[test]
+fn budget_api() {
+    let _: fn() -> CancellationToken = CancellationToken::new;
+    let _: fn(&CancellationToken) = CancellationToken::cancel;
+    let _: fn(&CancellationToken) -> bool = CancellationToken::is_cancelled;
+    let _ = <CancellationToken as Clone>::clone;
+    let _ = <CancellationToken as Default>::default;
+
+    let _: fn(Budget, CancellationToken) -> Budget = Budget::with_cancellation;
+    let _: fn(Budget, Instant) -> Budget = Budget::with_deadline;
+    let _: fn(Budget, usize) -> Budget = Budget::with_max_rotations;
+    let _: fn(&Budget, usize) -> Result<(), Interrupted> = Budget::check;
+    let _ = <Budget as Default>::default;
+
+    copy_type::<InterruptReason>();
+    match InterruptReason::Cancelled {
+        InterruptReason::Cancelled
+        | InterruptReason::DeadlineExceeded
+        | InterruptReason::RotationLimitReached => {}
+    }
+
+    copy_type::<Interrupted>();
+    let interrupted = Interrupted {
+        reason: InterruptReason::Cancelled,
+        completed_rotations: 0,
+    };
+    let _: &dyn Error = &interrupted;
+    let _: &dyn Display = &interrupted;
+}
+
+#[test]
+fn cube_api() {
+    let _: fn(usize) -> Cube = Cube::create;
+    let _: fn(usize) -> Cube = Cube::create_with_unique_characters;
+    let _: fn(usize) -> Cube = Cube::create_with_sticker_origins;
+    let _: fn(usize) -> Cube = Cube::create_supercube;
+    let _: fn(SideMap) -> Result<Cube, String> = Cube::try_from_side_map;
+    type Rows = Vec<Vec<CubieFace>>;
+    let _: fn(Rows, Rows, Rows, Rows, Rows, Rows) -> Result<Cube, String> = Cube::try_from_sides;
+    let _: fn(&Cube) -> usize = Cube::side_length;
+    let _: fn(&Cube) -> &SideMap = Cube::side_map;
+    let _: fn(&Cube, Face) -> &Grid<CubieFace> = Cube::oriented_side;
+    #[cfg(feature = "instrumentation")]
+    {
+        use rusty_puzzle_cube::cube::instrumentation::RotationCounters;
+
+        let _: fn() -> RotationCounters = Cube::rotation_counters;
+        let _: fn() = Cube::reset_rotation_counters;
+        copy_type::<RotationCounters>();
+        let _ = RotationCounters {
+            rotations: 0,
+            slice_copies: 0,
+            element_swaps: 0,
+            allocations: 0,
+        };
+    }
+    let _: fn(&Cube) -> Option<&StickerOriginMap> = Cube::sticker_origin_map;
+    let _: fn(&Cube) -> Option<bool> = Cube::is_every_sticker_at_origin;
+    let _: fn(&Cube) -> Option<&StickerRotationMap> = Cube::sticker_rotation_map;
+    let _: fn(&Cube) -> Option<bool> = Cube::is_every_sticker_upright;
+    let _: fn(&Cube) -> EnumMap<Face, bool> = Cube::solved_faces;
+    let _: fn(&Cube) -> bool = Cube::is_solved;
+    let _: fn(&Cube) -> Cube = Cube::normalised_orientation;
+    let _: fn(&Cube, &Cube) -> bool = Cube::eq_up_to_orientation;
+    let _: fn(&Cube) -> Cube = Cube::canonicalise;
+    let _: fn(&Cube) -> String = Cube::to_facelet_string;
+    let _: fn(&Cube, usize) -> String = Cube::to_compact_string;
+    let _: fn(&Cube) -> usize = Cube::approx_memory_bytes;
+    let _: fn(usize) -> usize = Cube::approx_memory_bytes_for;
+    let _: fn(&str) -> Result<Cube, String> = Cube::try_from_facelet_string;
+    let _: fn(&str, &ImportOptions) -> Result<Cube, String> = Cube::try_from_facelet_string_with;
+    let _: fn(&Cube) -> Vec<u8> = Cube::to_bytes;
+    let _: fn(&[u8]) -> Result<Cube, String> = Cube::from_bytes;
+    let _: fn(&mut Cube, Face) = Cube::rotate_face_90_degrees_clockwise;
+    let _: fn(&mut Cube, Face) = Cube::rotate_face_90_degrees_anticlockwise;
+    let _: fn(&mut Cube, Rotation) = Cube::rotate;
+    let _: fn(&mut Cube, Rotation) -> Result<(), String> = Cube::try_rotate;
+    let _: fn(&mut Cube, usize) -> Vec<Rotation> = Cube::shuffle;
+    let _: fn(&mut Cube, usize, &mut StdRng) -> Vec<Rotation> = Cube::shuffle_with_rng;
+    let _: fn(&mut Cube, Face, usize) = Cube::lock_layer;
+    let _: fn(&mut Cube, Face, usize) = Cube::unlock_layer;
+    let _: fn(&mut Cube) = Cube::unlock_all;
+    let _: fn(&Cube, Face, usize) -> bool = Cube::is_layer_locked;
+    let _: fn(&Cube) -> &[(Face, usize)] = Cube::locked_layers;
+    let _: fn(&Cube, Rotation) -> Result<(), String> = Cube::check_unlocked;
+    let _: fn(&mut Cube, &[Rotation]) = Cube::rotate_batch;
+    let _: fn(&mut Cube, &[Rotation], &Budget) -> Result<(), Interrupted> =
+        Cube::rotate_within_budget;
+    value_type::<Cube>();
+    hash_type::<Cube>();
+    let _ = <Cube as Default>::default;
+    let _: &dyn Display = &Cube::create(1);
+
+    let _: fn(CubieFace) -> colored::ColoredString = CubieFace::get_coloured_display_char;
+    value_type::<CubieFace>();
+    hash_type::<CubieFace>();
+    let _ = <CubieFace as Ord>::cmp;
+    match CubieFace::Blue(None) {
+        CubieFace::Blue(_)
+        | CubieFace::Green(_)
+        | CubieFace::Orange(_)
+        | CubieFace::Red(_)
+        | CubieFace::White(_)
+        | CubieFace::Yellow(_) => {}
+    }
+
+    let _: fn(Face) -> [Face; 4] = Face::neighbours;
+    let _: fn(Face) -> Face = Face::opposite;
+    copy_type::<Face>();
+    hash_type::<Face>();
+    match Face::Up {
+        Face::Up | Face::Down | Face::Front | Face::Right | Face::Back | Face::Left => {}
+    }
+
+    let _: fn(Direction) -> Direction = Direction::opposite;
+    copy_type::<Direction>();
+    match Direction::Clockwise {
+        Direction::Clockwise | Direction::Anticlockwise => {}
+    }
+
+    let _: fn(Face) -> Rotation = Rotation::clockwise;
+    let _: fn(Face) -> Rotation = Rotation::anticlockwise;
+    let _: fn(Rotation, usize) -> Rotation = Rotation::at_layer;
+    let _: fn(Rotation) -> Rotation = Rotation::inverse;
+    let _: fn(usize) -> Rotation = Rotation::random;
+    let _: fn(usize, &mut StdRng) -> Rotation = Rotation::random_with_rng;
+    copy_type::<Rotation>();
+    let _ = Rotation {
+        relative_to: Face::Up,
+        direction: Direction::Clockwise,
+        layer: 0,
+    };
+
+    let _: fn(Vec<Vec<Rotation>>) -> Result<ParallelRotation, String> = ParallelRotation::new;
+    let _: fn(&ParallelRotation) -> &[Rotation] = ParallelRotation::rotations;
+    let _: fn(&ParallelRotation) -> ParallelRotation = ParallelRotation::inverse;
+    let _: fn(&ParallelRotation, &mut Cube) = ParallelRotation::apply_to;
+    value_type::<ParallelRotation>();
+    let parallel = ParallelRotation::new(vec![
+        vec![Rotation::clockwise(Face::Up)],
+        vec![Rotation::clockwise(Face::Down)],
+    ]);
+    let _: Option<&[Rotation]> = parallel.as_ref().ok().and_then(|p| p.members().next());
+
+    let _: fn(StickerOrigin) -> Face = StickerOrigin::face;
+    let _: fn(StickerOrigin) -> usize = StickerOrigin::row;
+    let _: fn(StickerOrigin) -> usize = StickerOrigin::col;
+    let _: fn(StickerOrigin, usize) -> usize = StickerOrigin::id;
+    let _: fn(StickerOrigin, Face, usize, usize) -> bool = StickerOrigin::is_at;
+    copy_type::<StickerOrigin>();
+    hash_type::<StickerOrigin>();
+}
+
+#[test]
+fn colour_counts_api() {
+    let _: fn(&Cube) -> [(CubieFace, usize); 6] = Cube::colour_counts;
+    let _: fn(&Cube) -> Result<(), ColourCountError> = Cube::check_colour_counts;
+    let error = ColourCountError {
+        colour: CubieFace::Blue(None),
+        count: 0,
+        expected: 0,
+    };
+    let _: &dyn Error = &error;
+    value_type::<ColourCountError>();
+}
+
+#[test]
+fn colour_scheme_api() {
+    let _: EnumMap<Face, CubieFace> = WESTERN;
+    let _: [ColourScheme; 2] = [WESTERN, JAPANESE];
+    let _: fn(&ColourScheme) -> Result<(), String> = colour_scheme::check;
+    let _: fn(CubieFace, &ColourScheme) -> CubieFace = colour_scheme::recolour;
+    let _: fn(&Cube, &ColourScheme) -> Result<Cube, String> = Cube::with_colour_scheme;
+    let cube = Cube::create(1);
+    let schemed: SchemedCube<'_> = cube.display_with_colour_scheme(&WESTERN);
+    let _: &dyn Display = &schemed;
+}
+
+#[test]
+fn facelets_api() {
+    copy_type::<ImportOptions>();
+    copy_type::<ImportOrientation>();
+    let options = ImportOptions {
+        scheme: WESTERN,
+        orientation: ImportOrientation::AsWritten,
+    };
+    let _: ImportOptions = ImportOptions::default();
+    match options.orientation {
+        ImportOrientation::AsWritten | ImportOrientation::Normalised => {}
+    }
+}
+
+#[test]
+fn render_text_api() {
+    hash_type::<NetLayout>();
+    hash_type::<StickerChars>();
+    copy_type::<TextRenderOptions>();
+    let _: NetLayout = NetLayout::default();
+    let _: StickerChars = StickerChars::default();
+    let options = TextRenderOptions {
+        layout: NetLayout::Cross,
+        spacing: true,
+        ansi_colour: true,
+        sticker_chars: StickerChars::Display,
+        colour_scheme: WESTERN,
+        isometric: false,
+    };
+    let _: [TextRenderOptions; 3] = [
+        TextRenderOptions::default(),
+        TextRenderOptions::plain(),
+        TextRenderOptions::compact(),
+    ];
+    let _: fn(&Cube, &TextRenderOptions) -> String = Cube::format_with;
+    match options.layout {
+        NetLayout::Cross | NetLayout::T | NetLayout::Strip => {}
+    }
+    match options.sticker_chars {
+        StickerChars::Display | StickerChars::ColourInitials | StickerChars::FaceLetters => {}
+    }
+
+    #[cfg(feature = "svg")]
+    {
+        use rusty_puzzle_cube::cube::render_svg::{SvgRenderOptions, SvgView};
+
+        hash_type::<SvgView>();
+        copy_type::<SvgRenderOptions>();
+        let svg_options = SvgRenderOptions {
+            view: SvgView::Net(NetLayout::T),
+            sticker_size: 20,
+            colour_scheme: WESTERN,
+        };
+        let _: SvgRenderOptions = SvgRenderOptions::default();
+        let _: fn(&Cube, &SvgRenderOptions) -> String = Cube::to_svg;
+        match svg_options.view {
+            SvgView::Net(_) | SvgView::Isometric => {}
+        }
+    }
+}
+
+#[test]
+fn grid_api() {
+    value_type::<Grid<CubieFace>>();
+    let _: Grid<u8> = Grid::default();
+    let _: Grid<usize> = Grid::from_fn(1, |row: usize, col: usize| row + col);
+    let _: fn(usize, u8) -> Grid<u8> = Grid::filled;
+    let _: Result<Grid<u8>, String> = Grid::try_from(Vec::<Vec<u8>>::new());
+    let _: fn(&Grid<u8>) -> usize = Grid::len;
+    let _: fn(&Grid<u8>) -> bool = Grid::is_empty;
+    let _: fn(&Grid<u8>) -> &[u8] = Grid::cells;
+    let _: fn(&mut Grid<u8>) -> &mut [u8] = Grid::cells_mut;
+    let _: fn(&Grid<u8>) -> std::slice::ChunksExact<'_, u8> = Grid::rows;
+    let _: fn(&mut Grid<u8>) -> std::slice::ChunksExactMut<'_, u8> = Grid::rows_mut;
+    let _: fn(&Grid<u8>, usize, usize) -> usize = Grid::index_of;
+    let _: fn(&Grid<u8>) -> Vec<Vec<u8>> = Grid::to_rows;
+    let mut grid = Grid::filled(1, 0_u8);
+    let _: &[u8] = &grid[0];
+    let _: &mut [u8] = &mut grid[0];
+    for _row in &grid {}
+}
+
+#[test]
+fn pieces_api() {
+    let _: fn(&Cube) -> Result<CornerState, String> = Cube::corner_state;
+    let _: fn(&Cube) -> Result<EdgeState, String> = Cube::edge_state;
+    let _: fn(&Cube) -> Result<(), String> = Cube::check_solvable;
+
+    let _: [Corner; 8] = Corner::ALL;
+    let _: fn(Corner) -> [Face; 3] = Corner::faces;
+    copy_type::<Corner>();
+    match Corner::UpRightFront {
+        Corner::UpRightFront
+        | Corner::UpFrontLeft
+        | Corner::UpLeftBack
+        | Corner::UpBackRight
+        | Corner::DownFrontRight
+        | Corner::DownLeftFront
+        | Corner::DownBackLeft
+        | Corner::DownRightBack => {}
+    }
+
+    let _: [Edge; 12] = Edge::ALL;
+    let _: fn(Edge) -> [Face; 2] = Edge::faces;
+    copy_type::<Edge>();
+    match Edge::UpRight {
+        Edge::UpRight
+        | Edge::UpFront
+        | Edge::UpLeft
+        | Edge::UpBack
+        | Edge::DownRight
+        | Edge::DownFront
+        | Edge::DownLeft
+        | Edge::DownBack
+        | Edge::FrontRight
+        | Edge::FrontLeft
+        | Edge::BackLeft
+        | Edge::BackRight => {}
+    }
+
+    copy_type::<CornerState>();
+    let _ = CornerState {
+        permutation: Corner::ALL,
+        orientation: [0; 8],
+    };
+    let _: fn(&CornerState) -> bool = CornerState::is_odd_permutation;
+    let _: fn(&CornerState) -> u8 = CornerState::total_twist;
+
+    copy_type::<EdgeState>();
+    let _ = EdgeState {
+        permutation: Edge::ALL,
+        orientation: [0; 12],
+    };
+    let _: fn(&EdgeState) -> bool = EdgeState::is_odd_permutation;
+    let _: fn(&EdgeState) -> u8 = EdgeState::total_flip;
+}
+
+#[test]
+fn sequence_api() {
+    let _: fn(&Op) -> &[Rotation] = Op::rotations;
+    let _: fn(&Op) -> Option<&str> = Op::label;
+    let _: fn(&Op) -> Op = Op::inverted;
+    let _: fn(&Op, &mut Cube) = Op::apply_to;
+    let _: fn(Rotation) -> Op = Op::from;
+    let _: fn(ParallelRotation) -> Op = Op::from;
+    value_type::<Op>();
+    match Op::Single(Rotation::clockwise(Face::Up)) {
+        Op::Single(_) | Op::Group(_, _) | Op::Parallel(_) => {}
+    }
+
+    let _: fn() -> Sequence = Sequence::new;
+    let _: fn(&Sequence) -> &[Op] = Sequence::ops;
+    let _: fn(&Sequence) -> usize = Sequence::len;
+    let _: fn(&Sequence) -> bool = Sequence::is_empty;
+    let _: fn(&Sequence) -> Sequence = Sequence::inverted;
+    let _: fn(&Sequence, &mut Cube) = Sequence::apply_to;
+    let _: Sequence = Sequence::new().filter(|_: Rotation| true);
+    let _: fn(Vec<Rotation>) -> Sequence = Sequence::from;
+    value_type::<Sequence>();
+    let _ = <Sequence as Default>::default;
+
+    use rusty_puzzle_cube::cube::sequence::RotationFilter;
+    let _: fn(RotationFilter, Rotation, usize) -> bool = RotationFilter::keeps;
+    copy_type::<RotationFilter>();
+    match RotationFilter::SkipUpFace {
+        RotationFilter::SkipUpFace | RotationFilter::CornerMovesOnly => {}
+    }
+
+    let mut sequence = Sequence::from_iter([Op::Single(Rotation::clockwise(Face::Up))]);
+    sequence.push(Rotation::clockwise(Face::Up));
+    sequence.push(Op::Single(Rotation::clockwise(Face::Up)));
+    sequence.push_group("label", Vec::new());
+    sequence.push_group(String::new(), Vec::new());
+    let _: Option<Rotation> = sequence.rotations().next_back();
+    let _: Option<Rotation> = sequence.inverse_rotations().next();
+    let _: Option<&Op> = (&sequence).into_iter().next();
+}
+
+#[test]
+fn history_api() {
+    let _: fn(Cube) -> HistoryCube = HistoryCube::new;
+    let _: fn(HistoryCube, usize) -> HistoryCube = HistoryCube::with_capacity;
+    let _: fn(&HistoryCube) -> &Cube = HistoryCube::cube;
+    let _: fn(HistoryCube) -> Cube = HistoryCube::into_cube;
+    let _: fn(&mut HistoryCube, Rotation) = HistoryCube::rotate;
+    let _: fn(&mut HistoryCube) -> Option<Rotation> = HistoryCube::undo;
+    let _: fn(&mut HistoryCube) -> Option<Rotation> = HistoryCube::redo;
+    let _: fn(&HistoryCube) -> bool = HistoryCube::can_undo;
+    let _: fn(&HistoryCube) -> bool = HistoryCube::can_redo;
+    let _: fn(&HistoryCube) -> Vec<Rotation> = HistoryCube::history;
+    let _: fn(&HistoryCube, &mut Cube) = HistoryCube::replay_onto;
+    let _: fn(&mut HistoryCube) = HistoryCube::clear_history;
+    value_type::<HistoryCube>();
+}
+
+#[test]
+fn patterns_api() {
+    let _: fn(usize) -> Result<Vec<Rotation>, String> = patterns::checkerboard;
+    let _: fn(usize) -> Vec<Rotation> = patterns::stripes;
+    let _: fn(usize) -> Vec<Rotation> = patterns::frames;
+}
+
+#[test]
+fn known_transforms_api() {
+    let _: fn(&mut Cube) = known_transforms::checkerboard_corners;
+    let _: fn(&mut Cube) = known_transforms::cube_in_cube_in_cube;
+    let _: fn(&mut Cube) = known_transforms::checkerboard;
+    let _: fn(&mut Cube) = known_transforms::side_stripes;
+    let _: fn(&mut Cube) = known_transforms::stripes;
+    let _: fn(&mut Cube) = known_transforms::frames;
+
+    use known_transforms::{KnownTransform, SideLengths};
+    let _: [KnownTransform; 6] = KnownTransform::ALL;
+    let _: fn(KnownTransform) -> &'static str = KnownTransform::name;
+    let _: fn(KnownTransform) -> SideLengths = KnownTransform::side_lengths;
+    let _: Option<KnownTransform> = KnownTransform::for_side_length(3).next();
+    let _: fn(KnownTransform, &mut Cube) -> Result<(), String> = KnownTransform::apply_to;
+    copy_type::<KnownTransform>();
+    match KnownTransform::Checkerboard {
+        KnownTransform::Checkerboard
+        | KnownTransform::CheckerboardCorners
+        | KnownTransform::CubeInCubeInCube
+        | KnownTransform::Frames
+        | KnownTransform::SideStripes
+        | KnownTransform::Stripes => {}
+    }
+
+    let _: fn(SideLengths, usize) -> bool = SideLengths::contains;
+    copy_type::<SideLengths>();
+    match SideLengths::Odd {
+        SideLengths::Only(_) | SideLengths::AtLeast(_) | SideLengths::Odd => {}
+    }
+}
+
+#[test]
+fn metrics_api() {
+    let _: fn(&[Rotation], usize) -> Result<MoveCounts, String> = MoveCounts::of;
+    copy_type::<MoveCounts>();
+    let counts = MoveCounts {
+        htm: 0,
+        qtm: 0,
+        stm: 0,
+    };
+    let _: MoveCounts = counts + counts;
+    let _: &dyn Display = &counts;
+    let _ = <MoveCounts as Default>::default;
+
+    let _: fn() -> MoveCounter = MoveCounter::new;
+    let _: fn(&MoveCounter) -> MoveCounts = MoveCounter::counts;
+    value_type::<MoveCounter>();
+    let _ = <MoveCounter as Default>::default;
+    let _: &dyn MoveSink = &MoveCounter::new();
+    let _: fn(Cube, MoveCounter) -> MoveCountingCube = MoveCountingCube::new;
+}
+
+#[test]
+fn notation_api() {
+    let _: fn(&str, &mut Cube) -> Result<(), String> = notation::perform_3x3_sequence;
+    let _: fn(&str) -> Result<Vec<Rotation>, ParseError> = notation::parse_sequence;
+    let _: fn(&str) -> Result<Sequence, ParseError> = notation::parse_ops;
+    let _: fn(&str, &mut Cube, ParseOptions) -> Result<(), String> =
+        notation::perform_3x3_sequence_with;
+    let _: fn(&str, ParseOptions) -> Result<Vec<Rotation>, ParseError> =
+        notation::parse_sequence_with;
+    copy_type::<ParseOptions>();
+    value_type::<ParseError>();
+    let parse_error = ParseError {
+        token_index: 0,
+        span: Range { start: 0, end: 0 },
+        message: String::new(),
+    };
+    let _: &dyn Error = &parse_error;
+    let _: [ParseOptions; 3] = [
+        ParseOptions::default(),
+        ParseOptions::strict(),
+        ParseOptions {
+            lowercase_wide_moves: true,
+        },
+    ];
+    let _: fn(&str) -> Vec<TokenSpan> = notation::tokenise;
+    let _: fn(&[Rotation]) -> String = notation::format_sequence;
+    let _: fn(&[Rotation]) -> String = notation::to_string;
+    value_type::<TokenSpan>();
+    let _ = TokenSpan {
+        range: Range { start: 0, end: 0 },
+        error: None,
+    };
+
+    let _: fn() -> Grammar = grammar::grammar;
+    let _: fn(&Grammar, char) -> Option<Face> = Grammar::face_for;
+    let _: fn(&Grammar, char) -> Option<Face> = Grammar::wide_face_for;
+    let _: fn(&Grammar, char) -> Option<Face> = Grammar::slice_for;
+    let _: fn(&Grammar, char) -> Option<Modifier> = Grammar::modifier_for;
+    let _: fn(&Grammar) -> String = Grammar::to_ebnf;
+    value_type::<Grammar>();
+    let _ = Grammar {
+        separator: ' ',
+        faces: &[],
+        wide_marker: 'w',
+        wide_faces: &[],
+        slices: &[],
+        modifiers: &[],
+        parallel_open: '(',
+        parallel_separator: ',',
+        parallel_close: ')',
+        bracket_open: '[',
+        commutator_separator: ',',
+        conjugate_separator: ':',
+        bracket_close: ']',
+    };
+
+    copy_type::<Modifier>();
+    match Modifier::Anticlockwise {
+        Modifier::Anticlockwise | Modifier::TurnTwice => {}
+    }
+}
+
+#[test]
+fn recognition_api() {
+    copy_type::<OllCase>();
+    hash_type::<OllCase>();
+    let _: [OllCase; 57] = OllCase::ALL;
+    let _ = [OllCase::Oll1, OllCase::Oll27, OllCase::Oll57];
+    let _: fn(OllCase) -> usize = OllCase::number;
+    let _: fn(OllCase) -> &'static str = OllCase::algorithm;
+    let _: &dyn Display = &OllCase::Oll1;
+
+    copy_type::<PllCase>();
+    hash_type::<PllCase>();
+    let _: [PllCase; 21] = PllCase::ALL;
+    let _ = [
+        PllCase::Aa,
+        PllCase::Ab,
+        PllCase::E,
+        PllCase::F,
+        PllCase::Ga,
+        PllCase::Gb,
+        PllCase::Gc,
+        PllCase::Gd,
+        PllCase::H,
+        PllCase::Ja,
+        PllCase::Jb,
+        PllCase::Na,
+        PllCase::Nb,
+        PllCase::Ra,
+        PllCase::Rb,
+        PllCase::T,
+        PllCase::Ua,
+        PllCase::Ub,
+        PllCase::V,
+        PllCase::Y,
+        PllCase::Z,
+    ];
+    let _: fn(PllCase) -> &'static str = PllCase::algorithm;
+    let _: &dyn Display = &PllCase::T;
+
+    let _: fn(&Cube) -> Result<Option<OllCase>, String> = recognise_oll;
+    let _: fn(&Cube) -> Result<Option<PllCase>, String> = recognise_pll;
+}
+
+#[test]
+fn reconstruction_api() {
+    value_type::<Event>();
+    match Event::Undo {
+        Event::Move(_) | Event::Undo => {}
+    }
+
+    value_type::<TimedEvent>();
+    let _ = TimedEvent {
+        at: Duration::ZERO,
+        event: Event::Undo,
+    };
+
+    let _: fn(Vec<Rotation>) -> Reconstruction = Reconstruction::new;
+    let _: fn(&mut Reconstruction, Duration, Rotation) = Reconstruction::record_move;
+    let _: fn(&mut Reconstruction, Duration, Vec<Rotation>) = Reconstruction::record_rotations;
+    let _: fn(&mut Reconstruction, Duration) = Reconstruction::record_undo;
+    let _: fn(&Reconstruction) -> Vec<Rotation> = Reconstruction::effective_moves;
+    let _: fn(&Reconstruction, &mut Cube) = Reconstruction::replay_onto;
+    let _: fn(&Reconstruction) -> String = Reconstruction::to_text;
+    let _: fn(&str) -> Result<Reconstruction, String> = Reconstruction::from_text;
+    value_type::<Reconstruction>();
+    let _ = <Reconstruction as Default>::default;
+    let _ = Reconstruction {
+        scramble: Vec::new(),
+        events: Vec::new(),
+    };
+}
+
+#[test]
+fn recording_api() {
+    type Recording = RecordingCube<Vec<Rotation>>;
+    let _: fn(Cube, Vec<Rotation>) -> Recording = RecordingCube::new;
+    let _: fn(&Recording) -> &Cube = RecordingCube::cube;
+    let _: fn(&Recording) -> &Vec<Rotation> = RecordingCube::sink;
+    let _: fn(&mut Recording) -> &mut Vec<Rotation> = RecordingCube::sink_mut;
+    let _: fn(Recording) -> (Cube, Vec<Rotation>) = RecordingCube::into_parts;
+    let _: fn(&mut Recording, Rotation) = RecordingCube::rotate;
+    let _: fn(&mut Recording, [Rotation; 1]) = RecordingCube::rotate_all;
+    value_type::<Recording>();
+
+    fn move_sink<S: MoveSink>() {}
+    move_sink::<Vec<Rotation>>();
+    move_sink::<&mut dyn MoveSink>();
+    move_sink::<LogSink<Vec<u8>>>();
+    move_sink::<RobotSink<Vec<u8>>>();
+    move_sink::<BroadcastSink<Vec<u8>>>();
+    let _: fn(&mut Vec<Rotation>, Rotation, &Cube) = MoveSink::on_move;
+
+    let _: fn(Vec<u8>) -> LogSink<Vec<u8>> = LogSink::new;
+    let _: fn(&LogSink<Vec<u8>>) -> Option<&std::io::Error> = LogSink::error;
+    let _: fn(LogSink<Vec<u8>>) -> Vec<u8> = LogSink::into_writer;
+
+    let _: fn(RobotExporter, Vec<u8>) -> RobotSink<Vec<u8>> = RobotSink::new;
+    let _: fn(&RobotSink<Vec<u8>>) -> Option<&std::io::Error> = RobotSink::error;
+    let _: fn(RobotSink<Vec<u8>>) -> Vec<u8> = RobotSink::into_writer;
+    #[cfg(feature = "serial")]
+    {
+        type SerialSink = RobotSink<Box<dyn serialport::SerialPort>>;
+        let _: fn(RobotExporter, &str, u32) -> Result<SerialSink, String> = RobotSink::open_serial;
+    }
+
+    let _: fn() -> BroadcastSink<Vec<u8>> = BroadcastSink::new;
+    let _ = <BroadcastSink<Vec<u8>> as Default>::default;
+    let _: fn(&mut BroadcastSink<Vec<u8>>, Vec<u8>) = BroadcastSink::connect;
+    let _: fn(&BroadcastSink<Vec<u8>>) -> &[Vec<u8>] = BroadcastSink::clients;
+}
+
+#[test]
+fn robot_api() {
+    value_type::<FaceCommands>();
+    let _ = FaceCommands {
+        clockwise: String::new(),
+        anticlockwise: String::new(),
+    };
+
+    let _: fn() -> RobotExporter = RobotExporter::new;
+    let _ = <RobotExporter as Default>::default;
+    let _: fn(RobotExporter, u32, &str) -> RobotExporter = RobotExporter::with_delay;
+    let _: fn(&RobotExporter, &[Rotation]) -> Vec<String> = RobotExporter::commands;
+    let _: fn(&RobotExporter, &Sequence) -> Vec<String> = RobotExporter::sequence_commands;
+    let exporter = RobotExporter::new()
+        .with_face_commands(Face::Up, "U", String::new())
+        .with_line_ending("\n")
+        .with_line_ending(String::new())
+        .with_parallel_joiner("+")
+        .with_parallel_joiner(String::new())
+        .with_grip_hook(|_: Option<Rotation>, _: Rotation| Vec::<String>::new());
+    let _: std::io::Result<()> = exporter.write_commands(&[], &mut Vec::<u8>::new());
+    #[cfg(feature = "serial")]
+    let _: fn(&RobotExporter, &[Rotation], &str, u32) -> Result<(), String> =
+        RobotExporter::send_over_serial;
+}
+
+#[test]
+fn search_api() {
+    fn heuristic<H: Heuristic>() {}
+
+    type IdaStar<H> =
+        fn(&Cube, &[Rotation], &H, usize, &Budget) -> Result<Option<Vec<Rotation>>, Interrupted>;
+    let _: IdaStar<MisplacedStickers> = ida_star;
+    let _: fn() -> Vec<Rotation> = outer_face_turns;
+    let _: fn() -> Vec<Rotation> = fixed_corner_turns;
+    let _: fn(&MisplacedStickers, &Cube) -> usize = MisplacedStickers::estimate;
+
+    heuristic::<MisplacedStickers>();
+    let _ = MisplacedStickers;
+    let _ = <MisplacedStickers as Clone>::clone;
+    let _ = <MisplacedStickers as Default>::default;
+
+    heuristic::<CornerTwistDatabase>();
+    let _: fn() -> CornerTwistDatabase = CornerTwistDatabase::build;
+    let _ = <CornerTwistDatabase as Clone>::clone;
+
+    heuristic::<fn(&Cube) -> usize>();
+}
+
+#[test]
+fn session_api() {
+    copy_type::<SolveTime>();
+    let _ = SolveTime::Time(Duration::ZERO);
+    let _ = SolveTime::Dnf;
+    let _ = <SolveTime as Ord>::cmp;
+
+    value_type::<Solve>();
+    let _ = Solve {
+        reconstruction: Reconstruction::default(),
+        started_at: Duration::ZERO,
+        stopped_at: Duration::ZERO,
+        time: SolveTime::Dnf,
+    };
+
+    value_type::<SolveSession>();
+    let _ = <SolveSession as Default>::default;
+    let _: fn() -> SolveSession = SolveSession::new;
+    let _: fn(&SolveSession) -> &[Solve] = SolveSession::solves;
+    let _: fn(&SolveSession) -> bool = SolveSession::is_solving;
+    let _: fn(&mut SolveSession, Duration, Vec<Rotation>) -> Result<(), String> =
+        SolveSession::start;
+    let _: fn(&mut SolveSession, Duration, Rotation) -> Result<(), String> =
+        SolveSession::record_move;
+    let _: fn(&mut SolveSession, Duration) -> Result<(), String> = SolveSession::record_undo;
+    let _: fn(&mut SolveSession, Duration) -> Result<Duration, String> = SolveSession::stop;
+    let _: fn(&mut SolveSession, Duration) -> Result<(), String> = SolveSession::stop_as_dnf;
+    let _: fn(&SolveSession) -> Option<SolveTime> = SolveSession::best;
+    let _: fn(&SolveSession) -> Option<SolveTime> = SolveSession::ao5;
+    let _: fn(&SolveSession) -> Option<SolveTime> = SolveSession::ao12;
+    let _: fn(&SolveSession, usize) -> Option<SolveTime> = SolveSession::average_of;
+}
+
+#[test]
+fn solver_api() {
+    let _: fn(&Cube) -> Result<Vec<Rotation>, String> = layer_by_layer::solve;
+    #[cfg(feature = "two-phase")]
+    {
+        use rusty_puzzle_cube::solver::two_phase;
+
+        let _: fn(&Cube) -> Result<Vec<Rotation>, String> = two_phase::solve;
+        let _: usize = two_phase::MAX_SOLUTION_MOVES;
+    }
+}
+
+#[test]
+fn trainer_api() {
+    let _: Result<AlgorithmCase, String> = AlgorithmCase::new("name", "");
+    let _: Result<AlgorithmCase, String> = AlgorithmCase::new(String::new(), "");
+    value_type::<AlgorithmCase>();
+    let _ = AlgorithmCase {
+        name: String::new(),
+        algorithm: Vec::new(),
+        alternatives: Vec::new(),
+        goal: Goal::Solve,
+    };
+    let _: fn(AlgorithmCase, Goal) -> AlgorithmCase = AlgorithmCase::with_goal;
+    let _: fn(AlgorithmCase, &[&str]) -> Result<AlgorithmCase, String> =
+        AlgorithmCase::with_alternatives;
+    let _: fn(&AlgorithmCase) -> Cube = AlgorithmCase::set_up;
+    let _: fn(&AlgorithmCase, &[Rotation]) -> bool = AlgorithmCase::is_solved_by;
+    let _: fn() -> Vec<AlgorithmCase> = oll_cases;
+    let _: fn() -> Vec<AlgorithmCase> = pll_cases;
+
+    copy_type::<Goal>();
+    let _ = [Goal::OrientLastLayer, Goal::Solve];
+    let _: fn(Goal, &Cube) -> bool = Goal::is_reached;
+
+    let _: fn(&CaseStats) -> Option<Duration> = CaseStats::mean;
+    value_type::<CaseStats>();
+    let stats = CaseStats::default();
+    let _: (u32, u32, Option<Duration>) = (stats.attempts, stats.successes, stats.best);
+
+    copy_type::<TrainerState>();
+    match TrainerState::Idle {
+        TrainerState::Idle
+        | TrainerState::Attempting {
+            case_index: _,
+            moves: _,
+        }
+        | TrainerState::Solved {
+            case_index: _,
+            moves: _,
+        } => {}
+    }
+
+    let _: fn(Vec<AlgorithmCase>) -> Result<TrainerSession, String> = TrainerSession::new;
+    let _: fn(&TrainerSession) -> &[AlgorithmCase] = TrainerSession::cases;
+    let _: fn(&TrainerSession) -> &[CaseStats] = TrainerSession::stats;
+    let _: fn(&TrainerSession) -> &Cube = TrainerSession::cube;
+    let _: fn(&TrainerSession) -> TrainerState = TrainerSession::state;
+    let _: fn(&mut TrainerSession, usize) -> Result<(), String> = TrainerSession::start_case;
+    let _: fn(&mut TrainerSession) = TrainerSession::start_next_case;
+    let _: fn(&mut TrainerSession, Rotation) -> Result<TrainerState, String> =
+        TrainerSession::rotate;
+    let _: fn(&mut TrainerSession, Duration) -> Result<(), String> = TrainerSession::record_time;
+    let _: fn(&mut TrainerSession) = TrainerSession::abandon;
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_api() {
+    fn round_trips<T>(value: T)
+    where
+        for<'de> T: Debug + PartialEq + serde::Serialize + serde::Deserialize<'de>,
+    {
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(value, serde_json::from_str(&json).unwrap());
+    }
+
+    round_trips(Cube::create(2));
+    round_trips(CubieFace::Blue(Some('x')));
+    round_trips(Face::Up);
+    round_trips(Direction::Clockwise);
+    round_trips(Rotation::clockwise(Face::Up));
+    round_trips(Cube::create(3).corner_state().unwrap());
+    round_trips(Cube::create(3).edge_state().unwrap());
+    round_trips(
+        Cube::create_with_sticker_origins(1)
+            .sticker_origin_map()
+            .unwrap()[Face::Up][0][0],
+    );
+    round_trips(Reconstruction::from_text("Scramble: R U\n@1.500 U'\n@2.000 undo").unwrap());
+    let mut session = SolveSession::new();
+    session
+        .start(Duration::ZERO, vec![Rotation::clockwise(Face::Up)])
+        .unwrap();
+    session
+        .record_move(Duration::from_secs(1), Rotation::anticlockwise(Face::Up))
+        .unwrap();
+    session.stop(Duration::from_secs(2)).unwrap();
+    session.start(Duration::from_secs(3), vec![]).unwrap();
+    round_trips(session);
+}
@@ -0,0 +1,67 @@
+//! Golden tests for the text rendering of cubes.
+//!
+//! Each case renders a cube with colour disabled and compares it against a fixture file in `tests/fixtures/text_render`.
+//! Run with `UPDATE_GOLDEN=1` to rewrite the fixtures after an intentional change to the output.
+
+use std::{env, fs, path::PathBuf};
+
+use pretty_assertions::assert_eq;
+use rusty_puzzle_cube::{
+    cube::Cube,
+    known_transforms::{checkerboard_corners, cube_in_cube_in_cube},
+    notation::perform_3x3_sequence,
+};
+
+fn assert_golden(name: &str, cube: &Cube) {
+    colored::control::set_override(false);
+    let rendered = cube.to_string();
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/text_render")
+        .join(format!("{name}.txt"));
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, &rendered).expect("Fixture should be writable");
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "Could not read fixture {}, run with UPDATE_GOLDEN=1 to create it: {e}",
+            path.display()
+        )
+    });
+    assert_eq!(expected, rendered, "Rendering of {name} has changed");
+}
+
+macro_rules! golden_tests {
+    ($($name:ident: $cube:expr),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_golden(stringify!($name), &$cube);
+            }
+        )*
+    }
+}
+
+fn after(sequence: &str, mut cube: Cube) -> Cube {
+    perform_3x3_sequence(sequence, &mut cube).expect("Sequence in test should be valid");
+    cube
+}
+
+fn with(transform: fn(&mut Cube), mut cube: Cube) -> Cube {
+    transform(&mut cube);
+    cube
+}
+
+golden_tests!(
+    solved_1x1: Cube::create(1),
+    solved_3x3: Cube::create(3),
+    unique_1x1: Cube::create_with_unique_characters(1),
+    unique_2x2: Cube::create_with_unique_characters(2),
+    unique_3x3: Cube::create_with_unique_characters(3),
+    unique_5x5: Cube::create_with_unique_characters(5),
+    unique_3x3_every_face_turned: after("F R U L B D", Cube::create_with_unique_characters(3)),
+    unique_4x4_scrambled: after("F R' U2 L B' D2 F'", Cube::create_with_unique_characters(4)),
+    unique_3x3_checkerboard_corners: with(checkerboard_corners, Cube::create_with_unique_characters(3)),
+    unique_3x3_cube_in_cube_in_cube: with(cube_in_cube_in_cube, Cube::create_with_unique_characters(3)),
+);